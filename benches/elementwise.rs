@@ -0,0 +1,87 @@
+//! Compares the SIMD element-wise kernels against the generic [tensor_op()] and [map_tensor()]
+//! paths they replace. Run with `cargo bench --bench elementwise`.
+//!
+//! Both sides run on one thread with the buffer pool off, so the baseline is the scalar loop as it
+//! was before the worker pool and the buffer pool, and the speedup measures the kernels alone.
+
+use std::hint::black_box;
+use std::time::{ Duration, Instant };
+use tensorium::Tensor;
+use tensorium::memory::{ set_pool_config, PoolConfig };
+use tensorium::parallel::{ set_parallel_config, ParallelConfig };
+use tensorium::tensor_ops::{
+    add_tensors,
+    build_tensor,
+    map_tensor,
+    multiply_add_tensors,
+    multiply_tensors,
+    relu,
+    simd_level,
+    sqrt_tensor,
+    tensor_op
+};
+
+/// Runs `f` repeatedly for about half a second and returns the mean time per run.
+fn time(mut f: impl FnMut() -> Tensor) -> Duration {
+    black_box(f());
+
+    let start = Instant::now();
+    let mut runs = 0;
+    while start.elapsed() < Duration::from_millis(500) {
+        black_box(f());
+        runs += 1;
+    }
+    start.elapsed() / runs
+}
+
+fn report(name: &str, baseline: Duration, simd: Duration) {
+    println!(
+        "{name:<24} baseline {:>10.1?}   simd {:>10.1?}   speedup {:.2}x",
+        baseline,
+        simd,
+        baseline.as_secs_f64() / simd.as_secs_f64()
+    );
+}
+
+fn main() {
+    set_parallel_config(ParallelConfig { threads: 1, ..Default::default() });
+    set_pool_config(PoolConfig { enabled: false, ..Default::default() });
+    println!("SIMD level: {:?}, on one thread without the buffer pool", simd_level());
+
+    for &(rows, columns) in &[(1, 1 << 10), (1, 1 << 16), (1, 1 << 20), (1024, 1024)] {
+        let count = rows * columns;
+        let a: Vec<f64> = (0..count).map(|i| (i % 1000) as f64 * 0.5 + 1.0).collect();
+        let b: Vec<f64> = (0..count).map(|i| (i % 777) as f64 * 0.25 + 2.0).collect();
+        let a = build_tensor(&a, &[rows, columns]);
+        let b = build_tensor(&b, &[rows, columns]);
+
+        println!("\nshape [{rows}, {columns}]");
+        report(
+            "add",
+            time(|| tensor_op(&a, &b, |x, y| x + y)),
+            time(|| add_tensors(&a, &b))
+        );
+        report(
+            "multiply",
+            time(|| tensor_op(&a, &b, |x, y| x * y)),
+            time(|| multiply_tensors(&a, &b))
+        );
+        report(
+            "multiply-add",
+            time(|| tensor_op(&tensor_op(&a, &b, |x, y| x * y), &a, |x, y| x + y)),
+            time(|| multiply_add_tensors(&a, &b, &a))
+        );
+        // The closures given to map_tensor are already vectorized by the compiler, and sqrt is
+        // bound by the divider, so unary kernels gain little over them
+        report(
+            "relu",
+            time(|| map_tensor(&b, |x| if x > 0.0 || x.is_nan() { x } else { 0.0 })),
+            time(|| relu(&b))
+        );
+        report(
+            "sqrt",
+            time(|| map_tensor(&a, f64::sqrt)),
+            time(|| sqrt_tensor(&a))
+        );
+    }
+}
@@ -0,0 +1,63 @@
+//! # Data
+//!
+//! Loading training data in batches:
+//!
+//! - [Dataset] is anything with a length whose samples can be read by index, each sample being a
+//!   list of Tensors such as features and a label. [TensorDataset] holds its samples in memory.
+//! - [DataLoader] iterates over a dataset in batches, shuffling it with a seed every epoch and
+//!   optionally loading batches ahead on background threads. Batches are built from their
+//!   samples by [default_collate()] or a custom [CollateFn].
+//! - [random_split()] and [train_val_split()] divide a dataset into [Subset]s at random.
+//!
+//! ## Example
+//!
+//! ```
+//! use tensorium::Tensor;
+//! use tensorium::tensor_ops::{ build_tensor, get_dimension };
+//! use tensorium::data::{ train_val_split, DataLoader, DataLoaderOptions, Dataset, TensorDataset };
+//!
+//! // 100 points with 3 features each and a label
+//! let features = build_tensor(&vec![0.5; 300], &[100, 3]);
+//! let labels = Tensor::Element(vec![1.0; 100]);
+//! let dataset = TensorDataset::new(vec![features, labels]);
+//!
+//! let (train, val) = train_val_split(dataset, 0.1, 42);
+//! assert_eq!((train.len(), val.len()), (90, 10));
+//!
+//! let options = DataLoaderOptions {
+//!     batch_size: 32,
+//!     shuffle: true,
+//!     drop_last: true,
+//!     workers: 2,
+//!     ..Default::default()
+//! };
+//! let mut loader = DataLoader::new(train, options);
+//! for _epoch in 0..2 {
+//!     for batch in &mut loader {
+//!         assert_eq!(get_dimension(&batch[0]), [32, 3]);
+//!         assert_eq!(get_dimension(&batch[1]), [32, 1]);
+//!     }
+//! }
+//! ```
+
+mod dataset;
+pub use dataset::{
+    Dataset,
+    TensorDataset,
+    Subset
+};
+
+mod loader;
+pub use loader::{
+    CollateFn,
+    DataLoaderOptions,
+    DataLoader,
+    Batches,
+    default_collate
+};
+
+mod split;
+pub use split::{
+    random_split,
+    train_val_split
+};
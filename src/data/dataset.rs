@@ -0,0 +1,142 @@
+use std::sync::Arc;
+use crate::{ Tensor, TensorIndexResult };
+use crate::tensor_ops::get_dimension;
+
+/// A collection of samples that can be read by index, in any order and from any thread. Each
+/// sample is a list of Tensors, such as the features and the label of one example, which a
+/// [crate::data::DataLoader] stacks field by field into batches.
+pub trait Dataset: Send + Sync {
+    /// The number of samples.
+    fn len(&self) -> usize;
+
+    /// The fields of sample `index`, which is below [Dataset::len()].
+    fn get(&self, index: usize) -> Vec<Tensor>;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<D: Dataset + ?Sized> Dataset for Arc<D> {
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn get(&self, index: usize) -> Vec<Tensor> {
+        (**self).get(index)
+    }
+}
+
+/// A dataset held in memory as Tensors whose first axis indexes the samples, so sample `i` has
+/// element `i` of the first axis of every Tensor as its fields. The samples of a 1-D Tensor, such
+/// as a vector of labels, are Tensors of shape \[1\].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::data::{ Dataset, TensorDataset };
+/// use tensorium::tensor_ops::build_tensor;
+///
+/// let features = build_tensor(&[0.0, 0.1, 1.0, 1.1, 2.0, 2.1], &[3, 2]);
+/// let labels = Tensor::Element(vec![0.0, 1.0, 0.0]);
+/// let dataset = TensorDataset::new(vec![features, labels]);
+///
+/// assert_eq!(dataset.len(), 3);
+/// assert_eq!(dataset.get(1), [Tensor::Element(vec![1.0, 1.1]), Tensor::Element(vec![1.0])]);
+/// ```
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct TensorDataset {
+    tensors: Vec<Tensor>,
+    len: usize,
+}
+
+impl TensorDataset {
+    /// # Panics
+    ///
+    /// This function will panic if there are no Tensors or their first axes differ in length.
+    pub fn new(tensors: Vec<Tensor>) -> TensorDataset {
+        let Some(first) = tensors.first() else {
+            panic!("A TensorDataset needs at least one Tensor!");
+        };
+        let len = get_dimension(first)[0];
+        for tensor in &tensors {
+            let other = get_dimension(tensor)[0];
+            if other != len {
+                panic!(
+                    "TensorDataset Tensors have {len} and {other} samples along their first axis!"
+                );
+            }
+        }
+
+        TensorDataset { tensors, len }
+    }
+
+    pub fn tensors(&self) -> &[Tensor] {
+        &self.tensors
+    }
+}
+
+impl Dataset for TensorDataset {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    /// # Panics
+    ///
+    /// This function will panic if the index is out of range.
+    fn get(&self, index: usize) -> Vec<Tensor> {
+        if index >= self.len {
+            panic!("Sample {index} is out of range for a dataset of {} samples!", self.len);
+        }
+
+        self.tensors.iter()
+            .map(|tensor| match tensor.index(index) {
+                Some(TensorIndexResult::Tensor(sample)) => sample,
+                Some(TensorIndexResult::Value(value)) => Tensor::Element(vec![value]),
+                None => unreachable!(),
+            })
+            .collect()
+    }
+}
+
+/// The samples of another dataset at a list of indices, in that order. Created by
+/// [crate::data::random_split()] and [crate::data::train_val_split()].
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Subset<D: Dataset> {
+    dataset: Arc<D>,
+    indices: Vec<usize>,
+}
+
+impl<D: Dataset> Subset<D> {
+    /// # Panics
+    ///
+    /// This function will panic if any index is out of range for the dataset.
+    pub fn new(dataset: Arc<D>, indices: Vec<usize>) -> Subset<D> {
+        if let Some(index) = indices.iter().find(|&&i| i >= dataset.len()) {
+            panic!("Sample {index} is out of range for a dataset of {} samples!", dataset.len());
+        }
+        Subset { dataset, indices }
+    }
+
+    pub fn dataset(&self) -> &Arc<D> {
+        &self.dataset
+    }
+
+    /// The indices of the samples in the underlying dataset.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+}
+
+impl<D: Dataset> Dataset for Subset<D> {
+    fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    fn get(&self, index: usize) -> Vec<Tensor> {
+        self.dataset.get(self.indices[index])
+    }
+}
@@ -0,0 +1,329 @@
+use std::panic::{ self, AssertUnwindSafe };
+use std::sync::Arc;
+use std::sync::mpsc::{ self, Receiver };
+use std::thread::{ self, JoinHandle };
+use crate::Tensor;
+use crate::data::Dataset;
+use crate::data::split::permutation;
+use crate::random::Rng;
+use crate::tensor_ops::get_dimension;
+
+/// Turns the samples of a batch into the batch's fields, like [default_collate()].
+pub type CollateFn = Arc<dyn Fn(Vec<Vec<Tensor>>) -> Vec<Tensor> + Send + Sync>;
+
+/// A collated batch, or the panic that stopped a worker from collating it.
+type BatchResult = thread::Result<Vec<Tensor>>;
+
+/// Loads and collates the samples at a batch's indices.
+type LoadFn = Box<dyn Fn(&[usize]) -> Vec<Tensor> + Send>;
+
+/// Options for a [DataLoader].
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct DataLoaderOptions {
+    /// The number of samples in a batch.
+    pub batch_size: usize,
+    /// Whether to visit the samples in a new random order every epoch.
+    pub shuffle: bool,
+    /// The seed of the shuffled orders. Each epoch is shuffled differently, and loaders with the
+    /// same seed visit the samples in the same sequence of orders, epoch after epoch.
+    pub seed: u64,
+    /// Whether to leave out the last batch if it has fewer than `batch_size` samples.
+    pub drop_last: bool,
+    /// The number of background threads loading batches, with 0 loading them on the thread
+    /// iterating over the loader.
+    pub workers: usize,
+    /// The number of finished batches each worker may hold before waiting to be read.
+    pub prefetch: usize,
+}
+
+impl Default for DataLoaderOptions {
+    fn default() -> DataLoaderOptions {
+        DataLoaderOptions {
+            batch_size: 1,
+            shuffle: false,
+            seed: 0,
+            drop_last: false,
+            workers: 0,
+            prefetch: 2,
+        }
+    }
+}
+
+/// Iterates over a [Dataset] in batches, one epoch per call to [DataLoader::iter()]. Each batch
+/// is built by collating the batch's samples, by default with [default_collate()], which stacks
+/// every field into a Tensor with a new leading axis of length `batch_size`.
+///
+/// With `workers` set, the batches are loaded ahead on background threads and still arrive in the
+/// same order as loading them on the calling thread would give.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ build_tensor, get_dimension };
+/// use tensorium::data::{ DataLoader, DataLoaderOptions, TensorDataset };
+///
+/// let features = build_tensor(&[0.0; 20], &[10, 2]);
+/// let labels = Tensor::Element(vec![0.0; 10]);
+/// let options = DataLoaderOptions { batch_size: 4, shuffle: true, ..Default::default() };
+/// let mut loader = DataLoader::new(TensorDataset::new(vec![features, labels]), options);
+///
+/// assert_eq!(loader.len(), 3);
+/// let shapes: Vec<Vec<usize>> = loader.iter().map(|batch| get_dimension(&batch[0])).collect();
+/// assert_eq!(shapes, [vec![4, 2], vec![4, 2], vec![2, 2]]);
+/// ```
+pub struct DataLoader<D: Dataset + 'static> {
+    dataset: Arc<D>,
+    options: DataLoaderOptions,
+    collate: CollateFn,
+    rng: Rng,
+}
+
+impl<D: Dataset + 'static> DataLoader<D> {
+    /// # Panics
+    ///
+    /// This function will panic if `batch_size` or `prefetch` is 0.
+    pub fn new(dataset: D, options: DataLoaderOptions) -> DataLoader<D> {
+        if options.batch_size == 0 {
+            panic!("A DataLoader needs a batch size of at least 1!");
+        }
+        if options.prefetch == 0 {
+            panic!("A DataLoader needs to prefetch at least 1 batch per worker!");
+        }
+
+        DataLoader {
+            dataset: Arc::new(dataset),
+            rng: Rng::new(options.seed),
+            options,
+            collate: Arc::new(default_collate),
+        }
+    }
+
+    /// Replaces [default_collate()] with a custom function building a batch from its samples.
+    pub fn with_collate<F>(mut self, collate: F) -> DataLoader<D>
+    where
+        F: Fn(Vec<Vec<Tensor>>) -> Vec<Tensor> + Send + Sync + 'static
+    {
+        self.collate = Arc::new(collate);
+        self
+    }
+
+    pub fn dataset(&self) -> &Arc<D> {
+        &self.dataset
+    }
+
+    pub fn options(&self) -> &DataLoaderOptions {
+        &self.options
+    }
+
+    /// The number of batches in an epoch.
+    pub fn len(&self) -> usize {
+        let samples = self.dataset.len();
+        if self.options.drop_last {
+            samples / self.options.batch_size
+        } else {
+            samples.div_ceil(self.options.batch_size)
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Starts a new epoch, reshuffling the samples if `shuffle` is set.
+    pub fn iter(&mut self) -> Batches {
+        let samples = self.dataset.len();
+        let order = if self.options.shuffle {
+            permutation(samples, &mut self.rng)
+        } else {
+            (0..samples).collect()
+        };
+
+        let batches: Vec<Vec<usize>> = order.chunks(self.options.batch_size)
+            .take(self.len())
+            .map(|batch| batch.to_vec())
+            .collect();
+        let count = batches.len();
+
+        let workers = self.options.workers.min(count);
+        let source = if workers == 0 {
+            let dataset = Arc::clone(&self.dataset);
+            let collate = Arc::clone(&self.collate);
+            let load: LoadFn = Box::new(move |batch| {
+                collate(batch.iter().map(|&i| dataset.get(i)).collect())
+            });
+            Source::Inline { load, batches }
+        } else {
+            self.spawn_workers(workers, batches)
+        };
+
+        Batches { source, next: 0, count }
+    }
+
+    /// Starts `workers` threads, with worker `w` loading batches `w`, `w + workers`, and so on.
+    fn spawn_workers(&self, workers: usize, batches: Vec<Vec<usize>>) -> Source {
+        let batches = Arc::new(batches);
+        let mut receivers = Vec::with_capacity(workers);
+        let mut handles = Vec::with_capacity(workers);
+
+        for index in 0..workers {
+            let (sender, receiver) = mpsc::sync_channel::<BatchResult>(self.options.prefetch);
+            let dataset = Arc::clone(&self.dataset);
+            let collate = Arc::clone(&self.collate);
+            let batches = Arc::clone(&batches);
+
+            let handle = thread::Builder::new()
+                .name(format!("tensorium-loader-{index}"))
+                .spawn(move || {
+                    for batch in batches.iter().skip(index).step_by(workers) {
+                        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                            collate(batch.iter().map(|&i| dataset.get(i)).collect())
+                        }));
+                        let failed = result.is_err();
+                        // Sending fails once the batches are dropped, and nothing is left to do
+                        if sender.send(result).is_err() || failed {
+                            break;
+                        }
+                    }
+                })
+                .expect("Failed to spawn a tensorium loader thread!");
+
+            receivers.push(receiver);
+            handles.push(handle);
+        }
+
+        Source::Workers { receivers, handles }
+    }
+}
+
+impl<D: Dataset + 'static> IntoIterator for &mut DataLoader<D> {
+    type Item = Vec<Tensor>;
+    type IntoIter = Batches;
+
+    fn into_iter(self) -> Batches {
+        self.iter()
+    }
+}
+
+/// Where the batches of an epoch come from.
+enum Source {
+    Inline {
+        load: LoadFn,
+        batches: Vec<Vec<usize>>,
+    },
+    Workers {
+        receivers: Vec<Receiver<BatchResult>>,
+        handles: Vec<JoinHandle<()>>,
+    },
+}
+
+/// The batches of one epoch of a [DataLoader], each holding one Tensor per field of the samples.
+/// A panic while loading a batch on a worker is raised again when that batch is reached.
+pub struct Batches {
+    source: Source,
+    next: usize,
+    count: usize,
+}
+
+impl Iterator for Batches {
+    type Item = Vec<Tensor>;
+
+    fn next(&mut self) -> Option<Vec<Tensor>> {
+        if self.next == self.count {
+            return None;
+        }
+        let index = self.next;
+        self.next += 1;
+
+        match &self.source {
+            Source::Inline { load, batches } => Some(load(&batches[index])),
+            Source::Workers { receivers, .. } => {
+                match receivers[index % receivers.len()].recv() {
+                    Ok(Ok(batch)) => Some(batch),
+                    Ok(Err(payload)) => {
+                        self.count = self.next;
+                        panic::resume_unwind(payload)
+                    },
+                    // Workers only stop early after sending a panic
+                    Err(_) => unreachable!(),
+                }
+            },
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.count - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Batches {}
+
+impl Drop for Batches {
+    fn drop(&mut self) {
+        if let Source::Workers { receivers, handles } = &mut self.source {
+            // Closing the channels wakes any worker waiting to send a batch nobody will read
+            receivers.clear();
+            for handle in handles.drain(..) {
+                let _ = handle.join();
+            }
+        }
+    }
+}
+
+/// Builds a batch by stacking each field of the samples into a Tensor with a new leading axis, so
+/// samples of shape `s` give a field of shape `[samples, ...s]`.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::data::default_collate;
+/// use tensorium::tensor_ops::{ build_tensor, get_dimension };
+///
+/// let samples = vec![
+///     vec![Tensor::Element(vec![1.0, 2.0]), Tensor::Element(vec![0.0])],
+///     vec![Tensor::Element(vec![3.0, 4.0]), Tensor::Element(vec![1.0])],
+/// ];
+/// let batch = default_collate(samples);
+///
+/// assert_eq!(batch[0], build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]));
+/// assert_eq!(get_dimension(&batch[1]), [2, 1]);
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if there are no samples, or the samples differ in their number of
+/// fields or in the shape of a field.
+pub fn default_collate(samples: Vec<Vec<Tensor>>) -> Vec<Tensor> {
+    let Some(first) = samples.first() else {
+        panic!("Cannot collate an empty batch!");
+    };
+    let width = first.len();
+    let mut fields: Vec<Vec<Tensor>> = (0..width)
+        .map(|_| Vec::with_capacity(samples.len()))
+        .collect();
+
+    for sample in samples {
+        if sample.len() != width {
+            panic!("Cannot collate samples with {width} and {} fields!", sample.len());
+        }
+        for (field, tensor) in fields.iter_mut().zip(sample) {
+            field.push(tensor);
+        }
+    }
+
+    fields.into_iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let shape = get_dimension(&field[0]);
+            if let Some(other) = field.iter().map(get_dimension).find(|other| *other != shape) {
+                panic!(
+                    "Cannot collate field {index} with samples of shapes {shape:?} and {other:?}!"
+                );
+            }
+            Tensor::Array(field)
+        })
+        .collect()
+}
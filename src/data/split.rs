@@ -0,0 +1,90 @@
+use std::sync::Arc;
+use crate::data::{ Dataset, Subset };
+use crate::random::Rng;
+
+/// Splits a dataset into non-overlapping [Subset]s of the given lengths, with the samples
+/// assigned at random. The same seed always gives the same split. The subsets share the dataset
+/// rather than copying it.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::data::{ random_split, Dataset, TensorDataset };
+///
+/// let values: Vec<f64> = (0..10).map(|x| x as f64).collect();
+/// let dataset = TensorDataset::new(vec![build_tensor(&values, &[10])]);
+///
+/// let parts = random_split(dataset, &[6, 3, 1], 7);
+/// assert_eq!(parts.iter().map(|part| part.len()).collect::<Vec<_>>(), [6, 3, 1]);
+///
+/// let mut indices: Vec<usize> = parts.iter().flat_map(|part| part.indices().to_vec()).collect();
+/// indices.sort();
+/// assert_eq!(indices, (0..10).collect::<Vec<_>>());
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the lengths do not add up to the length of the dataset.
+pub fn random_split<D: Dataset>(dataset: D, lengths: &[usize], seed: u64) -> Vec<Subset<D>> {
+    let total: usize = lengths.iter().sum();
+    if total != dataset.len() {
+        panic!("Split lengths add up to {total}, but the dataset has {} samples!", dataset.len());
+    }
+
+    let order = permutation(total, &mut Rng::new(seed));
+    let dataset = Arc::new(dataset);
+    let mut start = 0;
+    lengths.iter()
+        .map(|&len| {
+            let subset = Subset::new(Arc::clone(&dataset), order[start..start + len].to_vec());
+            start += len;
+            subset
+        })
+        .collect()
+}
+
+/// Splits a dataset at random into training and validation [Subset]s, with `val_fraction` of the
+/// samples, rounded to the nearest sample, going to validation. See [random_split()].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::data::{ train_val_split, Dataset, TensorDataset };
+///
+/// let dataset = TensorDataset::new(vec![build_tensor(&[0.0; 50], &[25, 2])]);
+/// let (train, val) = train_val_split(dataset, 0.2, 42);
+///
+/// assert_eq!((train.len(), val.len()), (20, 5));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if `val_fraction` is not between 0 and 1.
+pub fn train_val_split<D: Dataset>(
+    dataset: D,
+    val_fraction: f64,
+    seed: u64
+) -> (Subset<D>, Subset<D>) {
+    if !(0.0..=1.0).contains(&val_fraction) {
+        panic!("Validation fraction {val_fraction} must be between 0 and 1!");
+    }
+
+    let val_len = (dataset.len() as f64 * val_fraction).round() as usize;
+    let train_len = dataset.len() - val_len;
+    let mut parts = random_split(dataset, &[train_len, val_len], seed).into_iter();
+    match (parts.next(), parts.next()) {
+        (Some(train), Some(val)) => (train, val),
+        _ => unreachable!(),
+    }
+}
+
+/// A random permutation of `0..len`, by a Fisher-Yates shuffle.
+pub(crate) fn permutation(len: usize, rng: &mut Rng) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..len).collect();
+    for i in (1..len).rev() {
+        order.swap(i, rng.below(i + 1));
+    }
+    order
+}
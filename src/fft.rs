@@ -0,0 +1,62 @@
+//! # FFT
+//!
+//! Discrete Fourier transforms of [crate::ComplexTensor]s along any axis, following the functions
+//! and arguments of __numpy__'s `fft` module:
+//!
+//! - One axis: [fft()] and [ifft()], and [rfft()] and [irfft()] for real signals.
+//! - Several axes: [fft2()] and [ifft2()] over the last two axes, and [fftn()] and [ifftn()].
+//! - Helpers: [fftfreq()] and [rfftfreq()] for the frequency of each output, and [fftshift()] and
+//!   [ifftshift()] to center the zero frequency.
+//!
+//! Every length is supported in `O(n log n)` time. Lengths made of small prime factors use a
+//! mixed-radix transform, and lengths with a large prime factor use Bluestein's algorithm, which
+//! computes the transform as a convolution of a longer, mixed-radix length. The twiddle factors
+//! for each length are computed once and cached, keeping the 16 most recently used lengths.
+//!
+//! The scaling of each direction is chosen with [FftNorm], matching the `backward`, `ortho` and
+//! `forward` modes of __numpy__.
+//!
+//! ## Example
+//!
+//! ```
+//! use tensorium::Tensor;
+//! use tensorium::fft::{ rfft, rfftfreq, FftNorm };
+//! use tensorium::tensor_ops::flatten_tensor;
+//!
+//! // One second of a 3 Hz cosine, sampled at 16 Hz
+//! let rate = 16;
+//! let signal: Vec<f64> = (0..rate)
+//!     .map(|t| (2.0 * std::f64::consts::PI * 3.0 * t as f64 / rate as f64).cos())
+//!     .collect();
+//!
+//! let spectrum = rfft(&Tensor::Element(signal), None, 0, FftNorm::Backward);
+//! let magnitudes = flatten_tensor(&spectrum.abs());
+//! let frequencies = flatten_tensor(&rfftfreq(rate, 1.0 / rate as f64));
+//!
+//! let peak = (0..magnitudes.len())
+//!     .max_by(|&a, &b| magnitudes[a].total_cmp(&magnitudes[b]))
+//!     .unwrap();
+//! assert_eq!(frequencies[peak], 3.0);
+//! ```
+
+mod plan;
+#[cfg(test)]
+pub(crate) use plan::cached_lengths;
+
+mod transforms;
+pub use transforms::{
+    FftNorm,
+    FftShift,
+    fft,
+    ifft,
+    rfft,
+    irfft,
+    fft2,
+    ifft2,
+    fftn,
+    ifftn,
+    fftfreq,
+    rfftfreq,
+    fftshift,
+    ifftshift
+};
@@ -0,0 +1,257 @@
+use std::f64::consts::PI;
+use std::sync::{ Arc, Mutex, MutexGuard };
+use crate::Complex;
+
+type C64 = Complex<f64>;
+
+/// How a plan computes its transform.
+enum Algorithm {
+    /// Recursive mixed-radix decimation in time over the prime factors of the length.
+    MixedRadix { factors: Vec<usize> },
+    /// Bluestein's algorithm, which turns the transform into a convolution computed with a
+    /// mixed-radix transform of a longer length. Used when the length has a large prime factor.
+    Bluestein {
+        inner: Arc<FftPlan>,
+        /// `exp(-πi k² / n)` for every `k` below `n`.
+        chirp: Vec<C64>,
+        /// The transform of the conjugate chirp, wrapped around to the inner length and already
+        /// divided by that length for the inverse transform.
+        kernel: Vec<C64>,
+    },
+}
+
+/// A forward discrete Fourier transform of one length, with its twiddle factors computed once.
+pub(crate) struct FftPlan {
+    len: usize,
+    /// `exp(-2πi k / len)` for every `k` below `len`.
+    twiddles: Vec<C64>,
+    algorithm: Algorithm,
+}
+
+/// The most plans kept in the cache, the same as __numpy__'s pocketfft. Each plan holds `O(n)`
+/// values, so workloads with ever-changing lengths would otherwise keep all of them.
+const MAX_CACHED_PLANS: usize = 16;
+
+/// Plans are cached by length, as transforms usually repeat over many lanes and calls. The least
+/// recently used plan comes first and is dropped when the cache is full.
+static PLANS: Mutex<Vec<Arc<FftPlan>>> = Mutex::new(Vec::new());
+
+fn lock() -> MutexGuard<'static, Vec<Arc<FftPlan>>> {
+    PLANS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Moves the cached plan for `len` to the back of the cache and returns it.
+fn touch(plans: &mut Vec<Arc<FftPlan>>, len: usize) -> Option<Arc<FftPlan>> {
+    let index = plans.iter().position(|plan| plan.len == len)?;
+    let plan = plans.remove(index);
+    plans.push(Arc::clone(&plan));
+    Some(plan)
+}
+
+/// The plan for transforms of length `len`, which must be at least 1.
+pub(crate) fn plan_for(len: usize) -> Arc<FftPlan> {
+    if let Some(plan) = touch(&mut lock(), len) {
+        return plan;
+    }
+
+    // Built without the lock, as a Bluestein plan needs the plan of another length
+    let plan = Arc::new(FftPlan::new(len));
+    let mut plans = lock();
+    if let Some(plan) = touch(&mut plans, len) {
+        return plan;
+    }
+    if plans.len() == MAX_CACHED_PLANS {
+        plans.remove(0);
+    }
+    plans.push(Arc::clone(&plan));
+    plan
+}
+
+/// The lengths of the cached plans, least recently used first.
+#[cfg(test)]
+pub(crate) fn cached_lengths() -> Vec<usize> {
+    lock().iter().map(|plan| plan.len).collect()
+}
+
+/// The prime factors of `n` in increasing order, with pairs of 2s merged into 4s first so more of
+/// the work uses the cheaper radix-4 butterfly.
+fn factorize(mut n: usize) -> Vec<usize> {
+    let mut factors = Vec::new();
+    while n.is_multiple_of(4) {
+        factors.push(4);
+        n /= 4;
+    }
+    let mut p = 2;
+    while p * p <= n {
+        while n.is_multiple_of(p) {
+            factors.push(p);
+            n /= p;
+        }
+        p += 1;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+    factors
+}
+
+/// A rough count of the operations in a mixed-radix transform, where each stage of radix `p` costs
+/// about `p` operations per element.
+fn mixed_radix_cost(len: usize) -> usize {
+    len * factorize(len).iter().map(|&p| if p <= 5 { p } else { p * 2 }).sum::<usize>()
+}
+
+/// The smallest length of at least `n` whose only prime factors are 2, 3 and 5.
+fn good_size(n: usize) -> usize {
+    let mut best = n.next_power_of_two();
+    let mut f5 = 1;
+    while f5 < best {
+        let mut f35 = f5;
+        while f35 < best {
+            let mut candidate = f35;
+            while candidate < n {
+                candidate *= 2;
+            }
+            best = best.min(candidate);
+            f35 *= 3;
+        }
+        f5 *= 5;
+    }
+    best
+}
+
+fn unit_root(numerator: usize, denominator: usize) -> C64 {
+    C64::from_polar(1.0, -2.0 * PI * numerator as f64 / denominator as f64)
+}
+
+impl FftPlan {
+    fn new(len: usize) -> FftPlan {
+        let twiddles = (0..len).map(|k| unit_root(k, len)).collect();
+
+        // Bluestein costs two transforms of about twice the length, plus some overhead
+        let inner_len = good_size(2 * len - 1);
+        let use_bluestein = len > 5 && mixed_radix_cost(len) > 3 * mixed_radix_cost(inner_len);
+        let algorithm = if use_bluestein {
+            let inner = plan_for(inner_len);
+            // k² is reduced modulo 2n before scaling so the angle stays accurate for large k
+            let chirp: Vec<C64> = (0..len)
+                .map(|k| unit_root((k * k) % (2 * len), 2 * len))
+                .collect();
+
+            let mut kernel = vec![C64::default(); inner_len];
+            kernel[0] = chirp[0].conj();
+            for k in 1..len {
+                kernel[k] = chirp[k].conj();
+                kernel[inner_len - k] = chirp[k].conj();
+            }
+            inner.forward(&mut kernel);
+            let scale = 1.0 / inner_len as f64;
+            for value in kernel.iter_mut() {
+                *value = C64::new(value.re * scale, value.im * scale);
+            }
+
+            Algorithm::Bluestein { inner, chirp, kernel }
+        } else {
+            Algorithm::MixedRadix { factors: factorize(len) }
+        };
+
+        FftPlan { len, twiddles, algorithm }
+    }
+
+    /// Replaces `data`, of the plan's length, with its forward transform `Σ x[j] exp(-2πi jk/n)`.
+    pub(crate) fn forward(&self, data: &mut [C64]) {
+        debug_assert_eq!(data.len(), self.len);
+        match &self.algorithm {
+            Algorithm::MixedRadix { factors } => {
+                let input = data.to_vec();
+                let mut scratch = Vec::new();
+                self.mixed_radix(&input, 1, data, factors, 1, &mut scratch);
+            },
+            Algorithm::Bluestein { inner, chirp, kernel } => {
+                let mut work = vec![C64::default(); kernel.len()];
+                for ((w, &x), &c) in work.iter_mut().zip(data.iter()).zip(chirp) {
+                    *w = x * c;
+                }
+                inner.forward(&mut work);
+                for (w, &k) in work.iter_mut().zip(kernel) {
+                    *w = *w * k;
+                }
+                // The inverse transform as the conjugate of the forward transform of the conjugate
+                for w in work.iter_mut() {
+                    *w = w.conj();
+                }
+                inner.forward(&mut work);
+                for ((x, w), &c) in data.iter_mut().zip(&work).zip(chirp) {
+                    *x = w.conj() * c;
+                }
+            },
+        }
+    }
+
+    /// Transforms the `out.len()` inputs `input[0], input[stride], ...` into `out`, where the
+    /// roots of unity for this length are every `twiddle_stride`-th twiddle of the plan.
+    fn mixed_radix(
+        &self,
+        input: &[C64],
+        stride: usize,
+        out: &mut [C64],
+        factors: &[usize],
+        twiddle_stride: usize,
+        scratch: &mut Vec<C64>
+    ) {
+        let n = out.len();
+        let Some((&radix, rest)) = factors.split_first() else {
+            out[0] = input[0];
+            return;
+        };
+        let m = n / radix;
+
+        // Transform each of the `radix` interleaved subsequences into its own block of `out`
+        for j in 0..radix {
+            self.mixed_radix(
+                &input[j * stride..],
+                stride * radix,
+                &mut out[j * m..(j + 1) * m],
+                rest,
+                twiddle_stride * radix,
+                scratch
+            );
+        }
+
+        // Combine the blocks with one butterfly of size `radix` per output index below `m`
+        let root = |exponent: usize| self.twiddles[(exponent % n) * twiddle_stride];
+        scratch.resize(radix, C64::default());
+        for k in 0..m {
+            for j in 0..radix {
+                scratch[j] = if j == 0 { out[k] } else { out[j * m + k] * root(j * k) };
+            }
+            match radix {
+                2 => {
+                    let (a, b) = (scratch[0], scratch[1]);
+                    out[k] = a + b;
+                    out[m + k] = a - b;
+                },
+                4 => {
+                    let (a, b, c, d) = (scratch[0], scratch[1], scratch[2], scratch[3]);
+                    let (sum_ac, diff_ac) = (a + c, a - c);
+                    let (sum_bd, diff_bd) = (b + d, b - d);
+                    // Multiplying by -i
+                    let rotated = C64::new(diff_bd.im, -diff_bd.re);
+                    out[k] = sum_ac + sum_bd;
+                    out[m + k] = diff_ac + rotated;
+                    out[2 * m + k] = sum_ac - sum_bd;
+                    out[3 * m + k] = diff_ac - rotated;
+                },
+                _ => {
+                    for q in 0..radix {
+                        let mut total = scratch[0];
+                        for (j, &value) in scratch.iter().enumerate().skip(1) {
+                            total = total + value * root((j * q % radix) * m);
+                        }
+                        out[q * m + k] = total;
+                    }
+                },
+            }
+        }
+    }
+}
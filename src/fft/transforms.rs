@@ -0,0 +1,470 @@
+use crate::{ Complex, ComplexTensor, Tensor };
+use crate::fft::plan::plan_for;
+use crate::parallel;
+use crate::tensor_ops::{ build_tensor, flatten_tensor, get_dimension };
+use crate::tensor_ops::utilities::axis_layout;
+
+type C64 = Complex<f64>;
+
+/// How a transform and its inverse are scaled, following __numpy__'s `norm` argument. `n` is the
+/// length of the transform.
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+pub enum FftNorm {
+    /// The forward transform is not scaled and the inverse is scaled by `1/n`.
+    #[default]
+    Backward,
+    /// Both directions are scaled by `1/sqrt(n)`, which makes the transform unitary.
+    Ortho,
+    /// The forward transform is scaled by `1/n` and the inverse is not scaled.
+    Forward,
+}
+
+impl FftNorm {
+    fn scale(self, n: usize, inverse: bool) -> f64 {
+        match (self, inverse) {
+            (FftNorm::Backward, false) | (FftNorm::Forward, true) => 1.0,
+            (FftNorm::Ortho, _) => 1.0 / (n as f64).sqrt(),
+            (FftNorm::Backward, true) | (FftNorm::Forward, false) => 1.0 / n as f64,
+        }
+    }
+}
+
+/// Transforms every lane along `axis` of the row-major `values`, first cropping or zero-padding it
+/// to `n`. Returns the new values and shape.
+fn transform_axis(
+    values: &[C64],
+    shape: &[usize],
+    axis: usize,
+    n: usize,
+    inverse: bool,
+    norm: FftNorm
+) -> (Vec<C64>, Vec<usize>) {
+    if n == 0 {
+        panic!("Invalid number of FFT data points (0) specified!");
+    }
+    let (outer, axis_len, inner) = axis_layout(shape, axis);
+    let plan = plan_for(n);
+    let scale = norm.scale(n, inverse);
+
+    // The inverse is the conjugate of the forward transform of the conjugate
+    let cost = n * (usize::BITS - n.leading_zeros()) as usize;
+    let lanes = parallel::map_collect(outer * inner, cost, |lane| {
+        let (o, i) = (lane / inner, lane % inner);
+        let mut data = vec![C64::default(); n];
+        for (k, value) in data.iter_mut().enumerate().take(axis_len) {
+            let x = values[o * axis_len * inner + k * inner + i];
+            *value = if inverse { x.conj() } else { x };
+        }
+        plan.forward(&mut data);
+        for value in data.iter_mut() {
+            let x = if inverse { value.conj() } else { *value };
+            *value = C64::new(x.re * scale, x.im * scale);
+        }
+        data
+    });
+
+    let mut out = vec![C64::default(); outer * n * inner];
+    for (lane, data) in lanes.iter().enumerate() {
+        let (o, i) = (lane / inner, lane % inner);
+        for (k, &value) in data.iter().enumerate() {
+            out[o * n * inner + k * inner + i] = value;
+        }
+    }
+
+    let mut shape = shape.to_vec();
+    shape[axis] = n;
+    (out, shape)
+}
+
+fn transform(
+    tensor: &ComplexTensor,
+    n: Option<usize>,
+    axis: usize,
+    inverse: bool,
+    norm: FftNorm
+) -> ComplexTensor {
+    let shape = tensor.shape();
+    let n = n.unwrap_or_else(|| shape.get(axis).copied().unwrap_or(0).max(1));
+    let (values, shape) = transform_axis(tensor.values(), shape, axis, n, inverse, norm);
+    ComplexTensor::from_values(values, &shape)
+}
+
+/// The one-dimensional discrete Fourier transform along `axis`, computed with a fast Fourier
+/// transform for any length. With `n`, each lane is cropped or padded with zeros to `n` values
+/// first, like __numpy__'s `fft.fft`.
+///
+/// Lengths whose prime factors are small use a mixed-radix transform, and lengths with large prime
+/// factors use Bluestein's algorithm, so every length takes `O(n log n)` time.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::{ Complex, ComplexTensor, Tensor };
+/// use tensorium::fft::{ fft, FftNorm };
+///
+/// let x = ComplexTensor::from_real(&Tensor::Element(vec![1.0, 2.0, 3.0, 4.0]));
+/// let spectrum = fft(&x, None, 0, FftNorm::Backward);
+///
+/// assert_eq!(spectrum.values(), &[
+///     Complex::new(10.0, 0.0),
+///     Complex::new(-2.0, 2.0),
+///     Complex::new(-2.0, 0.0),
+///     Complex::new(-2.0, -2.0)
+/// ]);
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range or if `n` is 0.
+pub fn fft(tensor: &ComplexTensor, n: Option<usize>, axis: usize, norm: FftNorm) -> ComplexTensor {
+    transform(tensor, n, axis, false, norm)
+}
+
+/// The inverse of [fft()], so that `ifft(fft(x))` is `x` for the same `norm`.
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range or if `n` is 0.
+pub fn ifft(tensor: &ComplexTensor, n: Option<usize>, axis: usize, norm: FftNorm) -> ComplexTensor {
+    transform(tensor, n, axis, true, norm)
+}
+
+/// The transform of a real Tensor along `axis`, keeping only the `n / 2 + 1` non-negative
+/// frequencies, as the others are their complex conjugates. `n` defaults to the length of the
+/// axis.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::{ Complex, Tensor };
+/// use tensorium::fft::{ irfft, rfft, FftNorm };
+///
+/// let x = Tensor::Element(vec![0.0, 1.0, 0.0, -1.0]);
+/// let spectrum = rfft(&x, None, 0, FftNorm::Backward);
+/// assert_eq!(spectrum.values(), &[
+///     Complex::new(0.0, 0.0),
+///     Complex::new(0.0, -2.0),
+///     Complex::new(0.0, 0.0)
+/// ]);
+///
+/// assert_eq!(irfft(&spectrum, Some(4), 0, FftNorm::Backward), x);
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range or if `n` is 0.
+pub fn rfft(tensor: &Tensor, n: Option<usize>, axis: usize, norm: FftNorm) -> ComplexTensor {
+    let full = fft(&ComplexTensor::from_real(tensor), n, axis, norm);
+    let shape = full.shape().to_vec();
+    let n = shape[axis];
+    let kept = n / 2 + 1;
+
+    let (outer, _, inner) = axis_layout(&shape, axis);
+    let mut values = Vec::with_capacity(outer * kept * inner);
+    for o in 0..outer {
+        values.extend_from_slice(&full.values()[o * n * inner..(o * n + kept) * inner]);
+    }
+
+    let mut out_shape = shape;
+    out_shape[axis] = kept;
+    ComplexTensor::from_values(values, &out_shape)
+}
+
+/// The inverse of [rfft()], giving a real Tensor of `n` values along `axis`. `n` defaults to
+/// `2 * (m - 1)` for `m` values along the axis, so an odd length has to be given explicitly. The
+/// input is treated as the non-negative half of a Hermitian spectrum, and the imaginary parts of
+/// the zero and, for even `n`, the `n / 2` frequencies are ignored, like __numpy__'s `fft.irfft`.
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range or if `n` is 0.
+pub fn irfft(tensor: &ComplexTensor, n: Option<usize>, axis: usize, norm: FftNorm) -> Tensor {
+    let shape = tensor.shape();
+    let (outer, axis_len, inner) = axis_layout(shape, axis);
+    let n = n.unwrap_or(2 * axis_len.saturating_sub(1));
+    if n == 0 {
+        panic!("Invalid number of FFT data points (0) specified!");
+    }
+
+    // Rebuild the full spectrum from the conjugate symmetry, cropping or padding the half first
+    let half = n / 2 + 1;
+    let mut full = vec![C64::default(); outer * n * inner];
+    for o in 0..outer {
+        for i in 0..inner {
+            let at = |k: usize| tensor.values()[o * axis_len * inner + k * inner + i];
+            for k in 0..half.min(axis_len) {
+                full[o * n * inner + k * inner + i] = at(k);
+                if k > 0 && n - k >= half {
+                    full[o * n * inner + (n - k) * inner + i] = at(k).conj();
+                }
+            }
+        }
+    }
+
+    let mut full_shape = shape.to_vec();
+    full_shape[axis] = n;
+    let (values, out_shape) = transform_axis(&full, &full_shape, axis, n, true, norm);
+    let real: Vec<f64> = values.iter().map(|z| z.re).collect();
+    build_tensor(&real, &out_shape)
+}
+
+/// Resolves the lengths and axes of a multi-dimensional transform like __numpy__: the axes
+/// default to the last `s.len()` axes if `s` is given and to every axis otherwise, and each
+/// length defaults to the size of its axis.
+fn lengths_and_axes(
+    shape: &[usize],
+    s: Option<&[usize]>,
+    axes: Option<&[usize]>
+) -> Vec<(usize, usize)> {
+    let axes: Vec<usize> = match (axes, s) {
+        (Some(axes), _) => axes.to_vec(),
+        (None, Some(s)) => {
+            if s.len() > shape.len() {
+                let (count, ndim) = (s.len(), shape.len());
+                panic!("Cannot transform {count} axes of a Tensor with {ndim} dimensions!");
+            }
+            (shape.len() - s.len()..shape.len()).collect()
+        },
+        (None, None) => (0..shape.len()).collect(),
+    };
+    if let Some(s) = s
+        && s.len() != axes.len()
+    {
+        panic!("The lengths and axes of an FFT must have the same number of entries!");
+    }
+
+    axes.iter()
+        .enumerate()
+        .map(|(i, &axis)| {
+            axis_layout(shape, axis);
+            (s.map(|s| s[i]).unwrap_or(shape[axis]), axis)
+        })
+        .collect()
+}
+
+fn transform_n(
+    tensor: &ComplexTensor,
+    s: Option<&[usize]>,
+    axes: Option<&[usize]>,
+    inverse: bool,
+    norm: FftNorm
+) -> ComplexTensor {
+    let mut values = tensor.values().to_vec();
+    let mut shape = tensor.shape().to_vec();
+    for (n, axis) in lengths_and_axes(&shape, s, axes) {
+        (values, shape) = transform_axis(&values, &shape, axis, n, inverse, norm);
+    }
+    ComplexTensor::from_values(values, &shape)
+}
+
+/// The discrete Fourier transform over several axes, one axis after another. `axes` defaults to
+/// the last `s.len()` axes if `s` is given and to every axis otherwise, and `s` gives the length
+/// to crop or pad each axis to, like __numpy__'s `fft.fftn`.
+///
+/// # Panics
+///
+/// This function will panic if an axis is out of range, if a length is 0, or if `s` and `axes`
+/// have different lengths.
+pub fn fftn(
+    tensor: &ComplexTensor,
+    s: Option<&[usize]>,
+    axes: Option<&[usize]>,
+    norm: FftNorm
+) -> ComplexTensor {
+    transform_n(tensor, s, axes, false, norm)
+}
+
+/// The inverse of [fftn()].
+///
+/// # Panics
+///
+/// This function will panic if an axis is out of range, if a length is 0, or if `s` and `axes`
+/// have different lengths.
+pub fn ifftn(
+    tensor: &ComplexTensor,
+    s: Option<&[usize]>,
+    axes: Option<&[usize]>,
+    norm: FftNorm
+) -> ComplexTensor {
+    transform_n(tensor, s, axes, true, norm)
+}
+
+fn last_two_axes(tensor: &ComplexTensor) -> [usize; 2] {
+    let ndim = tensor.shape().len();
+    if ndim < 2 {
+        panic!("A two-dimensional FFT needs a Tensor with at least 2 dimensions!");
+    }
+    [ndim - 2, ndim - 1]
+}
+
+/// The two-dimensional transform over the last two axes, see [fftn()].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::{ Complex, ComplexTensor };
+/// use tensorium::fft::{ fft2, FftNorm };
+/// use tensorium::tensor_ops::build_tensor;
+///
+/// let image = ComplexTensor::from_real(&build_tensor(&[1.0, 1.0, 1.0, 1.0], &[2, 2]));
+/// let spectrum = fft2(&image, None, FftNorm::Backward);
+///
+/// // A constant image only has a zero-frequency component
+/// assert_eq!(spectrum.values()[0], Complex::new(4.0, 0.0));
+/// assert!(spectrum.values()[1..].iter().all(|z| z.abs() == 0.0));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the Tensor has fewer than 2 dimensions or if a length is 0.
+pub fn fft2(tensor: &ComplexTensor, s: Option<&[usize]>, norm: FftNorm) -> ComplexTensor {
+    fftn(tensor, s, Some(&last_two_axes(tensor)), norm)
+}
+
+/// The inverse of [fft2()].
+///
+/// # Panics
+///
+/// This function will panic if the Tensor has fewer than 2 dimensions or if a length is 0.
+pub fn ifft2(tensor: &ComplexTensor, s: Option<&[usize]>, norm: FftNorm) -> ComplexTensor {
+    ifftn(tensor, s, Some(&last_two_axes(tensor)), norm)
+}
+
+/// The frequencies, in cycles per unit of `d`, of the outputs of [fft()] for `n` samples spaced
+/// `d` apart: `[0, 1, ..., (n - 1) / 2, -(n / 2), ..., -1] / (d * n)`.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::fft::fftfreq;
+///
+/// assert_eq!(fftfreq(4, 0.5), Tensor::Element(vec![0.0, 0.5, -1.0, -0.5]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if `n` is 0.
+pub fn fftfreq(n: usize, d: f64) -> Tensor {
+    if n == 0 {
+        panic!("Invalid number of FFT data points (0) specified!");
+    }
+    let scale = 1.0 / (d * n as f64);
+    let positive = n.div_ceil(2);
+    let values: Vec<f64> = (0..n)
+        .map(|k| if k < positive { k as f64 } else { k as f64 - n as f64 })
+        .map(|k| k * scale)
+        .collect();
+    Tensor::Element(values)
+}
+
+/// The frequencies of the outputs of [rfft()] for `n` samples spaced `d` apart:
+/// `[0, 1, ..., n / 2] / (d * n)`.
+///
+/// # Panics
+///
+/// This function will panic if `n` is 0.
+pub fn rfftfreq(n: usize, d: f64) -> Tensor {
+    if n == 0 {
+        panic!("Invalid number of FFT data points (0) specified!");
+    }
+    let scale = 1.0 / (d * n as f64);
+    Tensor::Element((0..=n / 2).map(|k| k as f64 * scale).collect())
+}
+
+/// The Tensors [fftshift()] and [ifftshift()] work on: [Tensor] for frequencies and real data,
+/// and [ComplexTensor] for the spectra of the transforms.
+pub trait FftShift: Sized {
+    /// Rolls every axis in `axes`, or every axis if `None`, by `shift(len)` positions.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if an axis is out of range.
+    fn roll_axes(&self, axes: Option<&[usize]>, shift: impl Fn(usize) -> usize) -> Self;
+}
+
+impl FftShift for Tensor {
+    fn roll_axes(&self, axes: Option<&[usize]>, shift: impl Fn(usize) -> usize) -> Tensor {
+        let shape = get_dimension(self);
+        build_tensor(&roll_axes(flatten_tensor(self), &shape, axes, shift), &shape)
+    }
+}
+
+impl FftShift for ComplexTensor {
+    fn roll_axes(&self, axes: Option<&[usize]>, shift: impl Fn(usize) -> usize) -> ComplexTensor {
+        let values = roll_axes(self.values().to_vec(), self.shape(), axes, shift);
+        ComplexTensor::from_values(values, self.shape())
+    }
+}
+
+/// Rolls every axis in `axes` of the row-major `values`, or every axis if `None`, by `shift(len)`
+/// positions.
+fn roll_axes<T: Copy + Default>(
+    mut values: Vec<T>,
+    shape: &[usize],
+    axes: Option<&[usize]>,
+    shift: impl Fn(usize) -> usize
+) -> Vec<T> {
+    let axes: Vec<usize> = match axes {
+        Some(axes) => axes.to_vec(),
+        None => (0..shape.len()).collect(),
+    };
+
+    for axis in axes {
+        let (outer, len, inner) = axis_layout(shape, axis);
+        let amount = shift(len) % len.max(1);
+        if amount == 0 {
+            continue;
+        }
+        let mut rolled = vec![T::default(); values.len()];
+        for o in 0..outer {
+            for k in 0..len {
+                let from = o * len * inner + k * inner;
+                let to = o * len * inner + (k + amount) % len * inner;
+                rolled[to..to + inner].copy_from_slice(&values[from..from + inner]);
+            }
+        }
+        values = rolled;
+    }
+
+    values
+}
+
+/// Moves the zero-frequency values of a spectrum to the middle of each axis in `axes`, or of
+/// every axis if `None`, by rolling the axis by half its length. It works on real Tensors, such as
+/// the frequencies from [fftfreq()], and on the [ComplexTensor]s of the transforms.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::{ Complex, ComplexTensor, Tensor };
+/// use tensorium::fft::{ fft, fftfreq, fftshift, ifftshift, FftNorm };
+///
+/// let shifted = fftshift(&fftfreq(5, 1.0), None);
+/// assert_eq!(shifted, Tensor::Element(vec![-0.4, -0.2, 0.0, 0.2, 0.4]));
+/// assert_eq!(ifftshift(&shifted, None), fftfreq(5, 1.0));
+///
+/// // The constant signal's spectrum is all at frequency 0, which moves to the middle
+/// let signal = ComplexTensor::from_real(&Tensor::Element(vec![1.0; 4]));
+/// let spectrum = fftshift(&fft(&signal, None, 0, FftNorm::Backward), None);
+/// assert_eq!(spectrum.values()[2], Complex::new(4.0, 0.0));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if an axis is out of range.
+pub fn fftshift<T: FftShift>(tensor: &T, axes: Option<&[usize]>) -> T {
+    tensor.roll_axes(axes, |len| len / 2)
+}
+
+/// The inverse of [fftshift()], which differs from it for odd lengths.
+///
+/// # Panics
+///
+/// This function will panic if an axis is out of range.
+pub fn ifftshift<T: FftShift>(tensor: &T, axes: Option<&[usize]>) -> T {
+    tensor.roll_axes(axes, |len| len - len / 2)
+}
@@ -0,0 +1,471 @@
+//! # Init
+//!
+//! Standard weight initialization schemes for neural network layers. Every function in this module
+//! operates in place on an existing Tensor, overwriting its values while keeping its shape. The fan
+//! of a Tensor is computed from the shape reported by [crate::tensor_ops::get_dimension()] using
+//! the usual convention for layer weights:
+//!
+//! - `fan_in` is `shape[1]` multiplied by the receptive field size
+//! - `fan_out` is `shape[0]` multiplied by the receptive field size
+//!
+//! where the receptive field size is the product of every dimension after the first two. A linear
+//! layer weight of shape \[out, in\] therefore has `fan_in = in`, and a convolution weight of shape
+//! \[out, in, kh, kw\] has `fan_in = in * kh * kw`.
+//!
+//! ## Example
+//!
+//! ```
+//! use tensorium::tensor_ops::{ zero_tensor, flatten_tensor };
+//! use tensorium::random::Rng;
+//! use tensorium::init;
+//!
+//! let mut rng = Rng::new(0);
+//! let mut weight = zero_tensor(vec![64, 32]);
+//!
+//! init::kaiming_uniform(&mut weight, init::FanMode::FanIn, init::Nonlinearity::Relu, &mut rng);
+//!
+//! // Kaiming uniform draws from [-bound, bound] with bound = gain * sqrt(3 / fan_in)
+//! let bound = 2.0_f64.sqrt() * (3.0 / 32.0_f64).sqrt();
+//! assert!(flatten_tensor(&weight).iter().all(|x| x.abs() <= bound));
+//! ```
+
+use crate::Tensor;
+use crate::random::Rng;
+use crate::tensor_ops::get_dimension;
+use crate::tensor_ops::activations::erfc;
+
+/// Which fan Kaiming initialization should preserve the variance of. `FanIn` preserves the
+/// magnitude of activations in the forward pass and `FanOut` preserves the magnitude of gradients
+/// in the backward pass.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum FanMode {
+    FanIn,
+    FanOut,
+}
+
+/// The nonlinearity that follows a layer, used to pick the recommended gain.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum Nonlinearity {
+    Linear,
+    Conv,
+    Sigmoid,
+    Tanh,
+    Relu,
+    /// A leaky ReLU with the given negative slope.
+    LeakyRelu(f64),
+    Selu,
+}
+
+/// Returns the recommended gain for the given nonlinearity.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::init::{ calculate_gain, Nonlinearity };
+///
+/// assert_eq!(calculate_gain(Nonlinearity::Linear), 1.0);
+/// assert_eq!(calculate_gain(Nonlinearity::Relu), 2.0_f64.sqrt());
+/// assert_eq!(calculate_gain(Nonlinearity::LeakyRelu(0.0)), 2.0_f64.sqrt());
+/// ```
+pub fn calculate_gain(nonlinearity: Nonlinearity) -> f64 {
+    match nonlinearity {
+        Nonlinearity::Linear | Nonlinearity::Conv | Nonlinearity::Sigmoid => 1.0,
+        Nonlinearity::Tanh => 5.0 / 3.0,
+        Nonlinearity::Relu => 2.0_f64.sqrt(),
+        Nonlinearity::LeakyRelu(slope) => (2.0 / (1.0 + slope * slope)).sqrt(),
+        Nonlinearity::Selu => 3.0 / 4.0,
+    }
+}
+
+/// Computes `(fan_in, fan_out)` for a Tensor shape.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::init::calculate_fan_in_and_fan_out;
+///
+/// // A linear layer mapping 3 features to 5 features
+/// assert_eq!(calculate_fan_in_and_fan_out(&[5, 3]), (3, 5));
+///
+/// // A convolution with 16 output channels, 8 input channels and a 3x3 kernel
+/// assert_eq!(calculate_fan_in_and_fan_out(&[16, 8, 3, 3]), (72, 144));
+/// ```
+///
+/// # Panics
+///
+/// The fan is undefined for Tensors with fewer than two dimensions.
+///
+/// ```should_panic
+/// use tensorium::init::calculate_fan_in_and_fan_out;
+///
+/// calculate_fan_in_and_fan_out(&[5]);
+/// ```
+pub fn calculate_fan_in_and_fan_out(shape: &[usize]) -> (usize, usize) {
+    if shape.len() < 2 {
+        panic!("Fan in and fan out require a Tensor with at least 2 dimensions!");
+    }
+
+    let receptive_field_size: usize = shape[2..].iter().product();
+
+    (shape[1] * receptive_field_size, shape[0] * receptive_field_size)
+}
+
+/// Fills the Tensor with values drawn from `U(-a, a)` where
+/// `a = gain * sqrt(6 / (fan_in + fan_out))`.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::{ zero_tensor, flatten_tensor };
+/// use tensorium::random::Rng;
+/// use tensorium::init::xavier_uniform;
+///
+/// let mut rng = Rng::new(7);
+/// let mut t = zero_tensor(vec![4, 2]);
+/// xavier_uniform(&mut t, 1.0, &mut rng);
+///
+/// assert!(flatten_tensor(&t).iter().all(|x| x.abs() <= 1.0));
+/// ```
+pub fn xavier_uniform(tensor: &mut Tensor, gain: f64, rng: &mut Rng) {
+    let (fan_in, fan_out) = calculate_fan_in_and_fan_out(&get_dimension(tensor));
+    let bound = gain * (6.0 / (fan_in + fan_out) as f64).sqrt();
+
+    fill_with(tensor, &mut || rng.uniform(-bound, bound));
+}
+
+/// Fills the Tensor with values drawn from `N(0, std^2)` where
+/// `std = gain * sqrt(2 / (fan_in + fan_out))`.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::{ zero_tensor, get_dimension };
+/// use tensorium::random::Rng;
+/// use tensorium::init::xavier_normal;
+///
+/// let mut rng = Rng::new(7);
+/// let mut t = zero_tensor(vec![4, 2]);
+/// xavier_normal(&mut t, 1.0, &mut rng);
+///
+/// assert_eq!(get_dimension(&t), vec![4, 2]);
+/// ```
+pub fn xavier_normal(tensor: &mut Tensor, gain: f64, rng: &mut Rng) {
+    let (fan_in, fan_out) = calculate_fan_in_and_fan_out(&get_dimension(tensor));
+    let std = gain * (2.0 / (fan_in + fan_out) as f64).sqrt();
+
+    fill_with(tensor, &mut || rng.normal(0.0, std));
+}
+
+/// Fills the Tensor with values drawn from `U(-bound, bound)` where
+/// `bound = gain * sqrt(3 / fan)`. `fan` is picked by `mode` and `gain` by `nonlinearity`.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::{ zero_tensor, flatten_tensor };
+/// use tensorium::random::Rng;
+/// use tensorium::init::{ kaiming_uniform, FanMode, Nonlinearity };
+///
+/// let mut rng = Rng::new(7);
+/// let mut t = zero_tensor(vec![8, 6]);
+/// kaiming_uniform(&mut t, FanMode::FanIn, Nonlinearity::Relu, &mut rng);
+///
+/// assert!(flatten_tensor(&t).iter().all(|x| x.abs() <= 1.0));
+/// ```
+pub fn kaiming_uniform(tensor: &mut Tensor, mode: FanMode, nonlinearity: Nonlinearity, rng: &mut Rng) {
+    let std = kaiming_std(tensor, mode, nonlinearity);
+    let bound = 3.0_f64.sqrt() * std;
+
+    fill_with(tensor, &mut || rng.uniform(-bound, bound));
+}
+
+/// Fills the Tensor with values drawn from `N(0, std^2)` where `std = gain / sqrt(fan)`. `fan` is
+/// picked by `mode` and `gain` by `nonlinearity`.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::{ zero_tensor, get_dimension };
+/// use tensorium::random::Rng;
+/// use tensorium::init::{ kaiming_normal, FanMode, Nonlinearity };
+///
+/// let mut rng = Rng::new(7);
+/// let mut t = zero_tensor(vec![8, 3, 3, 3]);
+/// kaiming_normal(&mut t, FanMode::FanOut, Nonlinearity::Relu, &mut rng);
+///
+/// assert_eq!(get_dimension(&t), vec![8, 3, 3, 3]);
+/// ```
+pub fn kaiming_normal(tensor: &mut Tensor, mode: FanMode, nonlinearity: Nonlinearity, rng: &mut Rng) {
+    let std = kaiming_std(tensor, mode, nonlinearity);
+
+    fill_with(tensor, &mut || rng.normal(0.0, std));
+}
+
+/// Fills the Tensor with values drawn from `N(mean, std^2)` truncated to `[a, b]`. Values are
+/// drawn by inverting the CDF of the truncated distribution, so ranges far out in the tails are
+/// sampled as quickly as ranges around the mean.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::{ zero_tensor, flatten_tensor };
+/// use tensorium::random::Rng;
+/// use tensorium::init::trunc_normal;
+///
+/// let mut rng = Rng::new(7);
+/// let mut t = zero_tensor(vec![10, 10]);
+/// trunc_normal(&mut t, 0.0, 1.0, -0.5, 0.5, &mut rng);
+///
+/// assert!(flatten_tensor(&t).iter().all(|x| (-0.5..=0.5).contains(x)));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if `std` is not positive and finite, if `mean`, `a` or `b` is not
+/// finite, or if `a` is not smaller than `b`.
+pub fn trunc_normal(tensor: &mut Tensor, mean: f64, std: f64, a: f64, b: f64, rng: &mut Rng) {
+    if !(std > 0.0 && std.is_finite()) {
+        panic!("Truncated normal standard deviation must be positive and finite, not {std}!");
+    }
+    if !(mean.is_finite() && a.is_finite() && b.is_finite()) {
+        panic!("Truncated normal mean and range bounds must be finite!");
+    }
+    if a >= b {
+        panic!("Truncation range lower bound must be smaller than its upper bound!");
+    }
+
+    // Sampling the half of the range nearer the upper tail keeps the tail probabilities small
+    // numbers rather than differences from 1, which would round away
+    let (low, high) = ((a - mean) / std, (b - mean) / std);
+    let flip = low + high < 0.0;
+    let (low, high) = if flip { (-high, -low) } else { (low, high) };
+
+    fill_with(tensor, &mut || {
+        let z = standard_trunc_normal(low, high, rng.next_f64());
+        let value = mean + std * if flip { -z } else { z };
+        value.clamp(a, b)
+    });
+}
+
+/// Inverts the CDF of the standard normal truncated to `[low, high]`, where `low + high >= 0`, at
+/// `u` in `[0, 1)`.
+fn standard_trunc_normal(low: f64, high: f64, u: f64) -> f64 {
+    // Past 30 the tail probabilities approach the smallest f64, but the density beyond `low` is
+    // then exponential with rate `low` to within a factor of exp(-1 / (2 * low^2))
+    if low > 30.0 {
+        return low - (u * (-low * (high - low)).exp_m1()).ln_1p() / low;
+    }
+
+    let upper = normal_tail(low);
+    let lower = normal_tail(high);
+    -normal_quantile(upper - u * (upper - lower))
+}
+
+/// The probability that a standard normal value is above `x`.
+fn normal_tail(x: f64) -> f64 {
+    0.5 * erfc(x / std::f64::consts::SQRT_2)
+}
+
+/// The value a standard normal value is below with probability `p`. Acklam's rational
+/// approximation is refined with one step of Halley's method, giving close to full precision.
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e+01, 2.209460984245205e+02, -2.759285104469687e+02,
+        1.38357751867269e+02, -3.066479806614716e+01, 2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01, 1.615858368580409e+02, -1.556989798598866e+02,
+        6.680131188771972e+01, -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03, -3.223964580411365e-01, -2.400758277161838e+00,
+        -2.549732539343734e+00, 4.374664141464968e+00, 2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03, 3.224671290700398e-01, 2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+
+    let polynomial = |coefficients: &[f64], x: f64| {
+        coefficients.iter().fold(0.0, |acc, c| acc * x + c)
+    };
+    let tail = |q: f64| {
+        let r = (-2.0 * q.ln()).sqrt();
+        polynomial(&C, r) / (polynomial(&D, r) * r + 1.0)
+    };
+    let x = if p < 0.02425 {
+        tail(p)
+    } else if p > 1.0 - 0.02425 {
+        -tail(1.0 - p)
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        polynomial(&A, r) * q / (polynomial(&B, r) * r + 1.0)
+    };
+
+    // Halley's method on Φ(x) - p, with the error measured through the tail so it stays precise
+    let error = normal_tail(-x) - p;
+    let step = error * (2.0 * std::f64::consts::PI).sqrt() * (x * x / 2.0).exp();
+    x - step / (1.0 + x * step / 2.0)
+}
+
+/// Fills the Tensor with a (semi) orthogonal matrix scaled by `gain`. The Tensor is viewed as a
+/// matrix with `shape[0]` rows and every remaining dimension flattened into the columns. If there
+/// are at least as many rows as columns the columns are orthonormal, otherwise the rows are.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::{ zero_tensor, flatten_tensor };
+/// use tensorium::random::Rng;
+/// use tensorium::init::orthogonal;
+///
+/// let mut rng = Rng::new(7);
+/// let mut t = zero_tensor(vec![2, 3]);
+/// orthogonal(&mut t, 1.0, &mut rng);
+///
+/// // The two rows are orthonormal
+/// let v = flatten_tensor(&t);
+/// let dot = v[0] * v[3] + v[1] * v[4] + v[2] * v[5];
+/// assert!(dot.abs() < 1e-12);
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the Tensor has fewer than two dimensions.
+pub fn orthogonal(tensor: &mut Tensor, gain: f64, rng: &mut Rng) {
+    let shape = get_dimension(tensor);
+    if shape.len() < 2 {
+        panic!("Orthogonal initialization requires a Tensor with at least 2 dimensions!");
+    }
+
+    let rows = shape[0];
+    let cols: usize = shape[1..].iter().product();
+
+    // Orthonormalize the columns of a tall (n >= m) random matrix. A wide Tensor is handled by
+    // building its transpose and transposing back afterwards.
+    let transposed = rows < cols;
+    let (n, m) = if transposed { (cols, rows) } else { (rows, cols) };
+    let mut a: Vec<f64> = (0..n * m).map(|_| rng.normal(0.0, 1.0)).collect();
+
+    // Modified Gram-Schmidt. Each diagonal entry of the implied R factor is a positive norm, which
+    // makes Q uniformly distributed over the orthogonal matrices.
+    for j in 0..m {
+        for k in 0..j {
+            let dot: f64 = (0..n).map(|i| a[i * m + j] * a[i * m + k]).sum();
+            for i in 0..n {
+                a[i * m + j] -= dot * a[i * m + k];
+            }
+        }
+
+        let norm: f64 = (0..n).map(|i| a[i * m + j] * a[i * m + j]).sum::<f64>().sqrt();
+        for i in 0..n {
+            a[i * m + j] /= norm;
+        }
+    }
+
+    let mut values = Vec::with_capacity(rows * cols);
+    for r in 0..rows {
+        for c in 0..cols {
+            let q = if transposed { a[c * m + r] } else { a[r * m + c] };
+            values.push(gain * q);
+        }
+    }
+
+    let mut values = values.into_iter();
+    fill_with(tensor, &mut || values.next().unwrap());
+}
+
+/// Fills the Tensor with zeros.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::init::zeros_;
+///
+/// let mut t = Tensor::Element(vec![1.0, 2.0]);
+/// zeros_(&mut t);
+/// assert_eq!(t, Tensor::Element(vec![0.0, 0.0]));
+/// ```
+pub fn zeros_(tensor: &mut Tensor) {
+    constant_(tensor, 0.0);
+}
+
+/// Fills the Tensor with ones.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::init::ones_;
+///
+/// let mut t = Tensor::Element(vec![3.0, 2.0]);
+/// ones_(&mut t);
+/// assert_eq!(t, Tensor::Element(vec![1.0, 1.0]));
+/// ```
+pub fn ones_(tensor: &mut Tensor) {
+    constant_(tensor, 1.0);
+}
+
+/// Fills the Tensor with `value`.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::init::constant_;
+///
+/// let mut t = Tensor::Array(Vec::from([
+///     Tensor::Element(vec![1.0, 2.0]),
+///     Tensor::Element(vec![3.0, 4.0])
+/// ]));
+/// constant_(&mut t, 0.5);
+/// assert_eq!(
+///     t,
+///     Tensor::Array(Vec::from([
+///         Tensor::Element(vec![0.5, 0.5]),
+///         Tensor::Element(vec![0.5, 0.5])
+///     ]))
+/// );
+/// ```
+pub fn constant_(tensor: &mut Tensor, value: f64) {
+    fill_with(tensor, &mut || value);
+}
+
+fn kaiming_std(tensor: &Tensor, mode: FanMode, nonlinearity: Nonlinearity) -> f64 {
+    let (fan_in, fan_out) = calculate_fan_in_and_fan_out(&get_dimension(tensor));
+    let fan = match mode {
+        FanMode::FanIn => fan_in,
+        FanMode::FanOut => fan_out,
+    };
+
+    calculate_gain(nonlinearity) / (fan as f64).sqrt()
+}
+
+/// Overwrites every value of the Tensor, in row-major order, with the output of `func`.
+fn fill_with(tensor: &mut Tensor, func: &mut impl FnMut() -> f64) {
+    match tensor {
+        Tensor::Element(x) => {
+            for value in x.iter_mut() {
+                *value = func();
+            }
+        },
+        Tensor::Array(x) => {
+            for t in x.iter_mut() {
+                fill_with(t, func);
+            }
+        }
+    }
+}
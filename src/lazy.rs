@@ -0,0 +1,37 @@
+//! # Lazy
+//!
+//! Every eager operation on a [crate::Tensor] builds its full result before the next one starts, so
+//! an expression like `a * b + c - d` writes and reads back two intermediate Tensors. A
+//! [LazyTensor] instead records the expression as a graph, and [LazyTensor::eval()] runs it.
+//!
+//! Before running, the graph is scheduled into a plan. Consecutive element-wise operations,
+//! including broadcasts, are fused into a single pass that computes each output element from the
+//! inputs without storing anything in between. Operations that are not element-wise, such as
+//! [LazyTensor::matmul()] and [LazyTensor::sum()], end a fused pass and run on their own.
+//! Subexpressions used more than once are computed once. Very long chains of operations are split
+//! into passes of at most 256 operations, which keeps planning them from overflowing the stack.
+//! [LazyTensor::explain()] shows the plan.
+//!
+//! Fused passes compute exactly the same values as the eager functions, and are split across
+//! threads like them, see [crate::parallel].
+//!
+//! ## Example
+//!
+//! ```
+//! use tensorium::tensor_ops::build_tensor;
+//!
+//! let a = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]).lazy();
+//! let b = build_tensor(&[10.0, 20.0], &[2]).lazy();
+//! let c = build_tensor(&[0.5, 0.5, 0.5, 0.5], &[2, 2]).lazy();
+//!
+//! // b is broadcast across the rows of a, all inside one pass
+//! let expr = (&a * &b + &c).relu() - 1.0;
+//! println!("{}", expr.explain());
+//!
+//! assert_eq!(expr.eval(), build_tensor(&[9.5, 39.5, 29.5, 79.5], &[2, 2]));
+//! ```
+
+mod lazy_tensor;
+pub use lazy_tensor::LazyTensor;
+
+mod plan;
@@ -0,0 +1,492 @@
+use std::fmt;
+use std::ops::{ Add, Div, Mul, Neg, Rem, Sub };
+use std::sync::Arc;
+use crate::Tensor;
+use crate::lazy::plan::Plan;
+use crate::tensor_ops::{ broadcast_shape, flatten_tensor, get_dimension, is_broadcastable };
+use crate::tensor_ops::activations::stable_sigmoid;
+use crate::tensor_ops::utilities::axis_layout;
+
+/// An element-wise operation on one value.
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+pub(crate) enum UnaryFn {
+    Neg,
+    Abs,
+    Sqrt,
+    Square,
+    Exp,
+    Ln,
+    Tanh,
+    Sigmoid,
+    Relu,
+    Map(fn(f64) -> f64),
+}
+
+impl UnaryFn {
+    pub(crate) fn apply(self, x: f64) -> f64 {
+        match self {
+            UnaryFn::Neg => -x,
+            UnaryFn::Abs => x.abs(),
+            UnaryFn::Sqrt => x.sqrt(),
+            UnaryFn::Square => x * x,
+            UnaryFn::Exp => x.exp(),
+            UnaryFn::Ln => x.ln(),
+            UnaryFn::Tanh => x.tanh(),
+            UnaryFn::Sigmoid => stable_sigmoid(x),
+            UnaryFn::Relu => if x > 0.0 || x.is_nan() { x } else { 0.0 },
+            UnaryFn::Map(func) => func(x),
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            UnaryFn::Neg => "neg",
+            UnaryFn::Abs => "abs",
+            UnaryFn::Sqrt => "sqrt",
+            UnaryFn::Square => "square",
+            UnaryFn::Exp => "exp",
+            UnaryFn::Ln => "ln",
+            UnaryFn::Tanh => "tanh",
+            UnaryFn::Sigmoid => "sigmoid",
+            UnaryFn::Relu => "relu",
+            UnaryFn::Map(_) => "map",
+        }
+    }
+}
+
+/// An element-wise operation on two values.
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+pub(crate) enum BinaryFn {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+    Maximum,
+    Minimum,
+}
+
+impl BinaryFn {
+    pub(crate) fn apply(self, x: f64, y: f64) -> f64 {
+        match self {
+            BinaryFn::Add => x + y,
+            BinaryFn::Sub => x - y,
+            BinaryFn::Mul => x * y,
+            BinaryFn::Div => x / y,
+            BinaryFn::Rem => x % y,
+            BinaryFn::Pow => x.powf(y),
+            BinaryFn::Maximum => if x.is_nan() || y.is_nan() { f64::NAN } else { x.max(y) },
+            BinaryFn::Minimum => if x.is_nan() || y.is_nan() { f64::NAN } else { x.min(y) },
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            BinaryFn::Add => "add",
+            BinaryFn::Sub => "sub",
+            BinaryFn::Mul => "mul",
+            BinaryFn::Div => "div",
+            BinaryFn::Rem => "rem",
+            BinaryFn::Pow => "pow",
+            BinaryFn::Maximum => "maximum",
+            BinaryFn::Minimum => "minimum",
+        }
+    }
+}
+
+/// A node of the expression graph.
+pub(crate) enum Node {
+    /// Values taken from a Tensor, in row-major order.
+    Input(Arc<Vec<f64>>),
+    /// A single value, broadcast like a Tensor of shape \[1\].
+    Scalar(f64),
+    Unary(UnaryFn, Arc<Expr>),
+    Binary(BinaryFn, Arc<Expr>, Arc<Expr>),
+    BroadcastTo(Arc<Expr>),
+    Matmul(Arc<Expr>, Arc<Expr>),
+    Sum(Arc<Expr>, Option<usize>),
+}
+
+impl Node {
+    /// The expressions the node reads.
+    pub(crate) fn operands(&self) -> Vec<&Arc<Expr>> {
+        match self {
+            Node::Input(_) | Node::Scalar(_) => Vec::new(),
+            Node::Unary(_, input) | Node::BroadcastTo(input) | Node::Sum(input, _) => vec![input],
+            Node::Binary(_, left, right) | Node::Matmul(left, right) => vec![left, right],
+        }
+    }
+
+    /// Moves the operands out of the node, leaving a scalar in its place.
+    fn take_operands(&mut self) -> Vec<Arc<Expr>> {
+        match std::mem::replace(self, Node::Scalar(0.0)) {
+            Node::Input(_) | Node::Scalar(_) => Vec::new(),
+            Node::Unary(_, input) | Node::BroadcastTo(input) | Node::Sum(input, _) => vec![input],
+            Node::Binary(_, left, right) | Node::Matmul(left, right) => vec![left, right],
+        }
+    }
+}
+
+/// A node together with the shape of its result, which is checked when the node is built.
+pub(crate) struct Expr {
+    pub(crate) node: Node,
+    pub(crate) shape: Vec<usize>,
+}
+
+impl Drop for Expr {
+    /// Frees the nodes only this expression holds with a loop rather than by recursion, so a chain
+    /// of many operations cannot overflow the stack.
+    fn drop(&mut self) {
+        let mut pending = self.node.take_operands();
+        while let Some(operand) = pending.pop() {
+            if let Some(mut expr) = Arc::into_inner(operand) {
+                pending.extend(expr.node.take_operands());
+            }
+        }
+    }
+}
+
+/// A Tensor expression that is only computed when [LazyTensor::eval()] is called. Lazy Tensors are
+/// built with [Tensor::lazy()] and combined with the arithmetic operators, with each other or with
+/// `f64` values, and the methods below. Shapes are checked as the expression is built, so a
+/// mismatch panics at the operation at fault rather than in `eval()`.
+///
+/// Cloning a LazyTensor is cheap and shares the expression, and an expression used in several
+/// places is computed once per evaluation. See [crate::lazy] for how expressions are run.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+///
+/// let x = Tensor::Element(vec![-1.0, 0.0, 1.0]).lazy();
+/// let y = (x.clone() * 2.0).exp() + x;
+///
+/// assert_eq!(y.shape(), &[3]);
+/// assert_eq!(
+///     y.eval(),
+///     Tensor::Element(vec![(-2.0f64).exp() - 1.0, 1.0, 2.0f64.exp() + 1.0])
+/// );
+/// ```
+#[derive(Clone)]
+pub struct LazyTensor {
+    pub(crate) expr: Arc<Expr>,
+}
+
+impl LazyTensor {
+    fn from_node(node: Node, shape: Vec<usize>) -> LazyTensor {
+        LazyTensor { expr: Arc::new(Expr { node, shape }) }
+    }
+
+    /// Starts a lazy expression from a copy of a Tensor's values. See also [Tensor::lazy()].
+    pub fn new(tensor: &Tensor) -> LazyTensor {
+        LazyTensor::from_node(Node::Input(Arc::new(flatten_tensor(tensor))), get_dimension(tensor))
+    }
+
+    /// A single value, which broadcasts against any shape.
+    pub fn scalar(value: f64) -> LazyTensor {
+        LazyTensor::from_node(Node::Scalar(value), vec![1])
+    }
+
+    /// The shape the expression will have when evaluated.
+    pub fn shape(&self) -> &[usize] {
+        &self.expr.shape
+    }
+
+    /// Runs the expression and returns the result.
+    pub fn eval(&self) -> Tensor {
+        Plan::new(&self.expr).execute()
+    }
+
+    /// Describes the plan [LazyTensor::eval()] would run, for debugging. Each line defines a
+    /// buffer, `$n`, as an input, a fused pass or an unfused operation. The instructions of a
+    /// fused pass, `%n`, run once per output element, and loads marked `broadcast` read a smaller
+    /// buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tensorium::tensor_ops::build_tensor;
+    ///
+    /// let a = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]).lazy();
+    /// let b = build_tensor(&[1.0, 2.0], &[2]).lazy();
+    ///
+    /// let plan = (a.matmul(&a) * &b + 1.0).explain();
+    /// assert_eq!(plan, "\
+    /// $0 = input [2, 2]
+    /// $1 = matmul $0 $0 -> [2, 2]
+    /// $2 = input [2]
+    /// $3 = fused [2, 2]
+    ///     %0 = load $1
+    ///     %1 = load $2 broadcast
+    ///     %2 = mul %0 %1
+    ///     %3 = const 1
+    ///     %4 = add %2 %3
+    /// result $3
+    /// ");
+    /// ```
+    pub fn explain(&self) -> String {
+        Plan::new(&self.expr).to_string()
+    }
+
+    fn unary(&self, func: UnaryFn) -> LazyTensor {
+        LazyTensor::from_node(Node::Unary(func, Arc::clone(&self.expr)), self.expr.shape.clone())
+    }
+
+    fn binary(&self, func: BinaryFn, other: &LazyTensor) -> LazyTensor {
+        let (lshape, rshape) = (&self.expr.shape, &other.expr.shape);
+        if !is_broadcastable(lshape, rshape) {
+            panic!("Shapes {lshape:?} and {rshape:?} are not broadcastable!");
+        }
+        let shape = broadcast_shape(lshape, rshape);
+        let node = Node::Binary(func, Arc::clone(&self.expr), Arc::clone(&other.expr));
+        LazyTensor::from_node(node, shape)
+    }
+
+    /// The absolute value of every element.
+    pub fn abs(&self) -> LazyTensor {
+        self.unary(UnaryFn::Abs)
+    }
+
+    /// The square root of every element.
+    pub fn sqrt(&self) -> LazyTensor {
+        self.unary(UnaryFn::Sqrt)
+    }
+
+    /// The square of every element.
+    pub fn square(&self) -> LazyTensor {
+        self.unary(UnaryFn::Square)
+    }
+
+    /// `e` raised to every element.
+    pub fn exp(&self) -> LazyTensor {
+        self.unary(UnaryFn::Exp)
+    }
+
+    /// The natural logarithm of every element.
+    pub fn ln(&self) -> LazyTensor {
+        self.unary(UnaryFn::Ln)
+    }
+
+    /// The hyperbolic tangent of every element.
+    pub fn tanh(&self) -> LazyTensor {
+        self.unary(UnaryFn::Tanh)
+    }
+
+    /// The logistic sigmoid of every element, like [crate::tensor_ops::sigmoid()].
+    pub fn sigmoid(&self) -> LazyTensor {
+        self.unary(UnaryFn::Sigmoid)
+    }
+
+    /// The rectified linear unit of every element, like [crate::tensor_ops::relu()].
+    pub fn relu(&self) -> LazyTensor {
+        self.unary(UnaryFn::Relu)
+    }
+
+    /// Applies a function to every element, like [crate::tensor_ops::map_tensor()]. It takes a
+    /// function pointer, so closures must not capture anything.
+    pub fn map(&self, func: fn(f64) -> f64) -> LazyTensor {
+        self.unary(UnaryFn::Map(func))
+    }
+
+    /// Raises every element to the power of the matching element of `exponent`, broadcasting.
+    pub fn pow(&self, exponent: &LazyTensor) -> LazyTensor {
+        self.binary(BinaryFn::Pow, exponent)
+    }
+
+    /// The larger of the matching elements, broadcasting. NaN is propagated.
+    pub fn maximum(&self, other: &LazyTensor) -> LazyTensor {
+        self.binary(BinaryFn::Maximum, other)
+    }
+
+    /// The smaller of the matching elements, broadcasting. NaN is propagated.
+    pub fn minimum(&self, other: &LazyTensor) -> LazyTensor {
+        self.binary(BinaryFn::Minimum, other)
+    }
+
+    /// Broadcasts the expression to a larger shape, following the rules in [crate::tensor_ops].
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the expression cannot be broadcast to `shape`.
+    pub fn broadcast_to(&self, shape: &[usize]) -> LazyTensor {
+        let target = shape.to_vec();
+        let source = &self.expr.shape;
+        if !is_broadcastable(source, &target) || broadcast_shape(source, &target) != target {
+            panic!("Cannot broadcast shape {:?} to {target:?}!", self.expr.shape);
+        }
+        LazyTensor::from_node(Node::BroadcastTo(Arc::clone(&self.expr)), target)
+    }
+
+    /// A matrix product, following the rules of [crate::tensor_ops::matmul()]. It is not fused, so
+    /// its operands are evaluated first.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the inner dimensions do not match or if the batch dimensions are
+    /// not broadcastable.
+    pub fn matmul(&self, other: &LazyTensor) -> LazyTensor {
+        let shape = matmul_shape(&self.expr.shape, &other.expr.shape);
+        LazyTensor::from_node(Node::Matmul(Arc::clone(&self.expr), Arc::clone(&other.expr)), shape)
+    }
+
+    /// A sum over one axis or the whole expression, like [crate::tensor_ops::sum()]. It is not
+    /// fused, so its operand is evaluated first.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the axis is out of range.
+    pub fn sum(&self, axis: Option<usize>) -> LazyTensor {
+        let mut shape = vec![1];
+        if let Some(axis) = axis {
+            axis_layout(&self.expr.shape, axis);
+            shape = self.expr.shape.clone();
+            shape.remove(axis);
+            if shape.is_empty() {
+                shape.push(1);
+            }
+        }
+        LazyTensor::from_node(Node::Sum(Arc::clone(&self.expr), axis), shape)
+    }
+}
+
+/// The shape of a matrix product, with the same checks as [crate::tensor_ops::matmul()].
+fn matmul_shape(lshape: &[usize], rshape: &[usize]) -> Vec<usize> {
+    let mut lshape = lshape.to_vec();
+    let mut rshape = rshape.to_vec();
+    let l_vector = lshape.len() == 1;
+    let r_vector = rshape.len() == 1;
+    if l_vector {
+        lshape.insert(0, 1);
+    }
+    if r_vector {
+        rshape.push(1);
+    }
+
+    let (m, k) = (lshape[lshape.len() - 2], lshape[lshape.len() - 1]);
+    let (k2, n) = (rshape[rshape.len() - 2], rshape[rshape.len() - 1]);
+    if k != k2 {
+        panic!("Inner dimensions do not match for matmul! ({k} vs {k2})");
+    }
+
+    let lbatch = lshape[..lshape.len() - 2].to_vec();
+    let rbatch = rshape[..rshape.len() - 2].to_vec();
+    let mut shape = broadcast_shape(&lbatch, &rbatch);
+    if !l_vector {
+        shape.push(m);
+    }
+    if !r_vector {
+        shape.push(n);
+    }
+    if shape.is_empty() {
+        shape.push(1);
+    }
+    shape
+}
+
+impl fmt::Debug for LazyTensor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyTensor").field("shape", &self.expr.shape).finish_non_exhaustive()
+    }
+}
+
+impl From<&Tensor> for LazyTensor {
+    fn from(tensor: &Tensor) -> Self {
+        LazyTensor::new(tensor)
+    }
+}
+
+impl Neg for LazyTensor {
+    type Output = LazyTensor;
+
+    fn neg(self) -> Self::Output {
+        self.unary(UnaryFn::Neg)
+    }
+}
+
+impl Neg for &LazyTensor {
+    type Output = LazyTensor;
+
+    fn neg(self) -> Self::Output {
+        self.unary(UnaryFn::Neg)
+    }
+}
+
+/// Implements an operator between lazy Tensors, owned or borrowed, and between a lazy Tensor and
+/// an `f64` on either side.
+macro_rules! lazy_operator {
+    ($trait:ident, $method:ident, $func:expr) => {
+        impl $trait for LazyTensor {
+            type Output = LazyTensor;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                self.binary($func, &rhs)
+            }
+        }
+
+        impl $trait<&LazyTensor> for LazyTensor {
+            type Output = LazyTensor;
+
+            fn $method(self, rhs: &LazyTensor) -> Self::Output {
+                self.binary($func, rhs)
+            }
+        }
+
+        impl $trait for &LazyTensor {
+            type Output = LazyTensor;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                self.binary($func, rhs)
+            }
+        }
+
+        impl $trait<LazyTensor> for &LazyTensor {
+            type Output = LazyTensor;
+
+            fn $method(self, rhs: LazyTensor) -> Self::Output {
+                self.binary($func, &rhs)
+            }
+        }
+
+        impl $trait<f64> for LazyTensor {
+            type Output = LazyTensor;
+
+            fn $method(self, rhs: f64) -> Self::Output {
+                self.binary($func, &LazyTensor::scalar(rhs))
+            }
+        }
+
+        impl $trait<f64> for &LazyTensor {
+            type Output = LazyTensor;
+
+            fn $method(self, rhs: f64) -> Self::Output {
+                self.binary($func, &LazyTensor::scalar(rhs))
+            }
+        }
+
+        impl $trait<LazyTensor> for f64 {
+            type Output = LazyTensor;
+
+            fn $method(self, rhs: LazyTensor) -> Self::Output {
+                LazyTensor::scalar(self).binary($func, &rhs)
+            }
+        }
+
+        impl $trait<&LazyTensor> for f64 {
+            type Output = LazyTensor;
+
+            fn $method(self, rhs: &LazyTensor) -> Self::Output {
+                LazyTensor::scalar(self).binary($func, rhs)
+            }
+        }
+    };
+}
+
+lazy_operator!(Add, add, BinaryFn::Add);
+lazy_operator!(Sub, sub, BinaryFn::Sub);
+lazy_operator!(Mul, mul, BinaryFn::Mul);
+lazy_operator!(Div, div, BinaryFn::Div);
+lazy_operator!(Rem, rem, BinaryFn::Rem);
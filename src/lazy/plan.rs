@@ -0,0 +1,444 @@
+use std::collections::{ HashMap, HashSet };
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+use crate::Tensor;
+use crate::lazy::lazy_tensor::{ BinaryFn, Expr, Node, UnaryFn };
+use crate::memory::PoolBuffer;
+use crate::parallel;
+use crate::tensor_ops::{ build_tensor, flatten_tensor, matmul, sum };
+use crate::tensor_ops::simd::fill;
+
+/// The number of elements a fused pass computes at a time. Each instruction keeps a block of
+/// values, which stays in cache between instructions.
+const BLOCK: usize = 256;
+
+/// The longest chain of operands scheduled in one go. Scheduling recurses along operands, so
+/// nodes this deep are computed into buffers first to bound the stack it needs.
+const MAX_DEPTH: usize = 256;
+
+/// One instruction of a fused pass. Operands are the indices of earlier instructions.
+enum Instr {
+    /// Reads element-wise from a source of the pass, broadcasting it to the output shape.
+    Load(usize),
+    Const(f64),
+    Unary(UnaryFn, usize),
+    Binary(BinaryFn, usize, usize),
+}
+
+/// A step of the plan, writing the buffer `output`.
+enum Stage {
+    /// Computes every element of `output` with `program`, reading the buffers in `sources`. The
+    /// last instruction gives the result.
+    Fused { output: usize, sources: Vec<usize>, program: Vec<Instr> },
+    Matmul { output: usize, left: usize, right: usize },
+    Sum { output: usize, input: usize, axis: Option<usize> },
+}
+
+struct Buffer {
+    shape: Vec<usize>,
+    /// The values of an input, or `None` for a buffer written by a stage.
+    input: Option<Arc<Vec<f64>>>,
+}
+
+/// The order in which an expression graph is computed. Buffers are numbered in the order they are
+/// first needed, and stages run in order.
+pub(crate) struct Plan {
+    buffers: Vec<Buffer>,
+    stages: Vec<Stage>,
+    result: usize,
+}
+
+/// The instructions of a fused pass being built.
+struct FusedBuilder {
+    sources: Vec<usize>,
+    program: Vec<Instr>,
+    /// The instruction computing each node already in the pass.
+    registers: HashMap<*const Expr, usize>,
+}
+
+impl Plan {
+    pub(crate) fn new(root: &Arc<Expr>) -> Plan {
+        let mut plan = Plan { buffers: Vec::new(), stages: Vec::new(), result: 0 };
+        let mut memo = HashMap::new();
+
+        // Working up from the inputs, every node MAX_DEPTH operands above the last computed ones
+        // is computed first, so no call below recurses further than that before reaching a buffer
+        let mut depths: HashMap<*const Expr, usize> = HashMap::new();
+        for expr in post_order(root) {
+            let depth = expr.node.operands().iter()
+                .map(|operand| depths[&Arc::as_ptr(operand)] + 1)
+                .max()
+                .unwrap_or(0);
+            let depth = if depth >= MAX_DEPTH {
+                plan.buffer_for(expr, &mut memo);
+                0
+            } else {
+                depth
+            };
+            depths.insert(Arc::as_ptr(expr), depth);
+        }
+
+        plan.result = plan.buffer_for(root, &mut memo);
+        plan
+    }
+
+    fn new_buffer(&mut self, shape: &[usize], input: Option<Arc<Vec<f64>>>) -> usize {
+        self.buffers.push(Buffer { shape: shape.to_vec(), input });
+        self.buffers.len() - 1
+    }
+
+    /// The buffer holding the value of `expr`, scheduling the stages that compute it.
+    fn buffer_for(&mut self, expr: &Arc<Expr>, memo: &mut HashMap<*const Expr, usize>) -> usize {
+        if let Some(&buffer) = memo.get(&Arc::as_ptr(expr)) {
+            return buffer;
+        }
+
+        let buffer = match &expr.node {
+            Node::Input(values) => self.new_buffer(&expr.shape, Some(Arc::clone(values))),
+            Node::Matmul(left, right) => {
+                let left = self.buffer_for(left, memo);
+                let right = self.buffer_for(right, memo);
+                let output = self.new_buffer(&expr.shape, None);
+                self.stages.push(Stage::Matmul { output, left, right });
+                output
+            },
+            Node::Sum(input, axis) => {
+                let input = self.buffer_for(input, memo);
+                let output = self.new_buffer(&expr.shape, None);
+                self.stages.push(Stage::Sum { output, input, axis: *axis });
+                output
+            },
+            _ => {
+                // Operands of matmuls and sums inside the pass are computed first, so an
+                // element-wise node the pass shares with them is read back instead of inlined again
+                self.schedule_unfused(expr, memo, &mut HashSet::new());
+                let mut fused = FusedBuilder {
+                    sources: Vec::new(),
+                    program: Vec::new(),
+                    registers: HashMap::new(),
+                };
+                self.emit(expr, &mut fused, memo);
+                let output = self.new_buffer(&expr.shape, None);
+                let FusedBuilder { sources, program, .. } = fused;
+                self.stages.push(Stage::Fused { output, sources, program });
+                output
+            },
+        };
+
+        memo.insert(Arc::as_ptr(expr), buffer);
+        buffer
+    }
+
+    /// Schedules every matmul and sum a fused pass computing `expr` would read, walking the nodes
+    /// the pass inlines in the order [Plan::emit()] visits them.
+    fn schedule_unfused(
+        &mut self,
+        expr: &Arc<Expr>,
+        memo: &mut HashMap<*const Expr, usize>,
+        visited: &mut HashSet<*const Expr>
+    ) {
+        if memo.contains_key(&Arc::as_ptr(expr)) || !visited.insert(Arc::as_ptr(expr)) {
+            return;
+        }
+
+        match &expr.node {
+            Node::Input(_) | Node::Scalar(_) => {},
+            Node::Matmul(..) | Node::Sum(..) => {
+                self.buffer_for(expr, memo);
+            },
+            Node::BroadcastTo(input) | Node::Unary(_, input) => {
+                self.schedule_unfused(input, memo, visited);
+            },
+            Node::Binary(_, left, right) => {
+                self.schedule_unfused(left, memo, visited);
+                self.schedule_unfused(right, memo, visited);
+            },
+        }
+    }
+
+    /// Adds the instructions computing `expr` to a fused pass, returning the instruction with its
+    /// value. Nodes that cannot be fused become sources of the pass.
+    fn emit(
+        &mut self,
+        expr: &Arc<Expr>,
+        fused: &mut FusedBuilder,
+        memo: &mut HashMap<*const Expr, usize>
+    ) -> usize {
+        if let Some(&register) = fused.registers.get(&Arc::as_ptr(expr)) {
+            return register;
+        }
+
+        // A node already computed into a buffer, such as the operand of a matmul, is read back
+        let computed = memo.contains_key(&Arc::as_ptr(expr));
+        let instr = match &expr.node {
+            _ if computed => Instr::Load(self.source_for(expr, fused, memo)),
+            Node::Input(_) | Node::Matmul(..) | Node::Sum(..) => {
+                Instr::Load(self.source_for(expr, fused, memo))
+            },
+            Node::Scalar(value) => Instr::Const(*value),
+            // Loads broadcast to the shape of the whole pass, so a broadcast needs no instruction
+            Node::BroadcastTo(input) => {
+                let register = self.emit(input, fused, memo);
+                fused.registers.insert(Arc::as_ptr(expr), register);
+                return register;
+            },
+            Node::Unary(func, input) => Instr::Unary(*func, self.emit(input, fused, memo)),
+            Node::Binary(func, left, right) => {
+                let left = self.emit(left, fused, memo);
+                let right = self.emit(right, fused, memo);
+                Instr::Binary(*func, left, right)
+            },
+        };
+
+        fused.program.push(instr);
+        let register = fused.program.len() - 1;
+        fused.registers.insert(Arc::as_ptr(expr), register);
+        register
+    }
+
+    /// The index of the source of a fused pass that reads the buffer of `expr`.
+    fn source_for(
+        &mut self,
+        expr: &Arc<Expr>,
+        fused: &mut FusedBuilder,
+        memo: &mut HashMap<*const Expr, usize>
+    ) -> usize {
+        let buffer = self.buffer_for(expr, memo);
+        match fused.sources.iter().position(|&s| s == buffer) {
+            Some(source) => source,
+            None => {
+                fused.sources.push(buffer);
+                fused.sources.len() - 1
+            },
+        }
+    }
+
+    /// Runs every stage and returns the result buffer as a Tensor.
+    pub(crate) fn execute(&self) -> Tensor {
+        // Every stage reads only buffers written before it, so they are always filled
+        let mut values: Vec<Option<Values>> =
+            self.buffers.iter().map(|b| b.input.clone().map(Values::Input)).collect();
+        let tensor = |values: &[Option<Values>], buffer: usize| {
+            build_tensor(values[buffer].as_ref().unwrap(), &self.buffers[buffer].shape)
+        };
+
+        for stage in &self.stages {
+            let (output, result) = match stage {
+                Stage::Fused { output, sources, program } => {
+                    let shape = &self.buffers[*output].shape;
+                    let loader = |s: usize| {
+                        Loader::new(values[s].as_ref().unwrap(), &self.buffers[s].shape, shape)
+                    };
+                    let loaders: Vec<Loader> = sources.iter().map(|&s| loader(s)).collect();
+                    (*output, Values::Pooled(run_fused(shape, &loaders, program)))
+                },
+                Stage::Matmul { output, left, right } => {
+                    let product = matmul(&tensor(&values, *left), &tensor(&values, *right));
+                    (*output, Values::Owned(flatten_tensor(&product)))
+                },
+                Stage::Sum { output, input, axis } => {
+                    let total = sum(&tensor(&values, *input), *axis);
+                    (*output, Values::Owned(flatten_tensor(&total)))
+                },
+            };
+            values[output] = Some(result);
+        }
+
+        // Dropping the values hands the pooled intermediates back
+        tensor(&values, self.result)
+    }
+}
+
+/// The values of a buffer while a plan runs.
+enum Values {
+    Input(Arc<Vec<f64>>),
+    /// Written by a fused pass into a buffer from the pool, see [crate::memory].
+    Pooled(PoolBuffer),
+    Owned(Vec<f64>),
+}
+
+impl Deref for Values {
+    type Target = [f64];
+
+    fn deref(&self) -> &[f64] {
+        match self {
+            Values::Input(values) => values,
+            Values::Pooled(values) => values,
+            Values::Owned(values) => values,
+        }
+    }
+}
+
+/// Every node of the graph under `root` once, each after its operands, found without recursion.
+fn post_order(root: &Arc<Expr>) -> Vec<&Arc<Expr>> {
+    let mut order = Vec::new();
+    let mut visited = HashSet::new();
+    // Each node is pushed once to visit its operands and again, below them, to be added after them
+    let mut stack = vec![(root, false)];
+    while let Some((expr, operands_done)) = stack.pop() {
+        if operands_done {
+            order.push(expr);
+        } else if visited.insert(Arc::as_ptr(expr)) {
+            stack.push((expr, true));
+            for operand in expr.node.operands().into_iter().rev() {
+                stack.push((operand, false));
+            }
+        }
+    }
+    order
+}
+
+/// Reads a source buffer at the positions of the output elements, following the broadcasting rules.
+struct Loader<'a> {
+    values: &'a [f64],
+    /// Whether the source has the output shape, so it can be read directly.
+    contiguous: bool,
+    output_shape: &'a [usize],
+    /// The step in `values` for each output dimension, 0 along broadcast dimensions.
+    strides: Vec<usize>,
+}
+
+impl<'a> Loader<'a> {
+    fn new(values: &'a [f64], shape: &[usize], output_shape: &'a [usize]) -> Loader<'a> {
+        let pad = output_shape.len() - shape.len();
+        let mut strides = vec![0; output_shape.len()];
+        let mut stride = 1;
+        for d in (pad..output_shape.len()).rev() {
+            if shape[d - pad] != 1 {
+                strides[d] = stride;
+            }
+            stride *= shape[d - pad];
+        }
+
+        Loader { values, contiguous: shape == output_shape, output_shape, strides }
+    }
+
+    /// Fills `out` with the values for the output elements starting at `start`.
+    fn load(&self, start: usize, out: &mut [f64]) {
+        if self.contiguous {
+            out.copy_from_slice(&self.values[start..start + out.len()]);
+            return;
+        }
+
+        let ndim = self.output_shape.len();
+        let mut index = vec![0; ndim];
+        let mut remaining = start;
+        for d in (0..ndim).rev() {
+            index[d] = remaining % self.output_shape[d];
+            remaining /= self.output_shape[d];
+        }
+        let mut offset: usize = index.iter().zip(&self.strides).map(|(i, s)| i * s).sum();
+
+        for value in out.iter_mut() {
+            *value = self.values[offset];
+            for d in (0..ndim).rev() {
+                index[d] += 1;
+                offset += self.strides[d];
+                if index[d] < self.output_shape[d] {
+                    break;
+                }
+                offset -= index[d] * self.strides[d];
+                index[d] = 0;
+            }
+        }
+    }
+}
+
+/// Computes every element of a fused pass, a block of elements and one instruction at a time.
+fn run_fused(shape: &[usize], loaders: &[Loader], program: &[Instr]) -> PoolBuffer {
+    let len: usize = shape.iter().product();
+    let chunk_len = parallel::items_per_chunk(program.len()).next_multiple_of(BLOCK);
+
+    let mut values = PoolBuffer::with_capacity(len);
+    fill(&mut values, len, |out| parallel::for_each_chunk_mut(out, chunk_len, |start, piece| {
+        let mut registers = vec![0.0; program.len() * BLOCK];
+        for (block, out_block) in piece.chunks_mut(BLOCK).enumerate() {
+            let n = out_block.len();
+            for (i, instr) in program.iter().enumerate() {
+                let (done, rest) = registers.split_at_mut(i * BLOCK);
+                let target = &mut rest[..n];
+                let register = |r: usize| &done[r * BLOCK..r * BLOCK + n];
+                match *instr {
+                    Instr::Load(source) => loaders[source].load(start + block * BLOCK, target),
+                    Instr::Const(value) => target.fill(value),
+                    Instr::Unary(func, a) => {
+                        for (t, &x) in target.iter_mut().zip(register(a)) {
+                            *t = func.apply(x);
+                        }
+                    },
+                    Instr::Binary(func, a, b) => {
+                        for ((t, &x), &y) in target.iter_mut().zip(register(a)).zip(register(b)) {
+                            *t = func.apply(x, y);
+                        }
+                    },
+                }
+            }
+
+            let result = &registers[(program.len() - 1) * BLOCK..];
+            for (slot, &value) in out_block.iter_mut().zip(result) {
+                slot.write(value);
+            }
+        }
+    }));
+    values
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut shown = vec![false; self.buffers.len()];
+        let mut show_inputs = |f: &mut fmt::Formatter<'_>, buffers: &[usize]| -> fmt::Result {
+            for &b in buffers {
+                if self.buffers[b].input.is_some() && !shown[b] {
+                    shown[b] = true;
+                    writeln!(f, "${b} = input {:?}", self.buffers[b].shape)?;
+                }
+            }
+            Ok(())
+        };
+
+        for stage in &self.stages {
+            match stage {
+                Stage::Fused { output, sources, program } => {
+                    show_inputs(f, sources)?;
+                    let shape = &self.buffers[*output].shape;
+                    writeln!(f, "${output} = fused {shape:?}")?;
+                    for (i, instr) in program.iter().enumerate() {
+                        write!(f, "    %{i} = ")?;
+                        match instr {
+                            Instr::Load(s) => {
+                                let source = sources[*s];
+                                let mark = if self.buffers[source].shape != *shape {
+                                    " broadcast"
+                                } else {
+                                    ""
+                                };
+                                writeln!(f, "load ${source}{mark}")?
+                            },
+                            Instr::Const(value) => writeln!(f, "const {value}")?,
+                            Instr::Unary(func, a) => writeln!(f, "{} %{a}", func.name())?,
+                            Instr::Binary(func, a, b) => writeln!(f, "{} %{a} %{b}", func.name())?,
+                        }
+                    }
+                },
+                Stage::Matmul { output, left, right } => {
+                    show_inputs(f, &[*left, *right])?;
+                    let shape = &self.buffers[*output].shape;
+                    writeln!(f, "${output} = matmul ${left} ${right} -> {shape:?}")?;
+                },
+                Stage::Sum { output, input, axis } => {
+                    show_inputs(f, &[*input])?;
+                    let axis = match axis {
+                        Some(axis) => format!("axis {axis}"),
+                        None => String::from("all"),
+                    };
+                    let shape = &self.buffers[*output].shape;
+                    writeln!(f, "${output} = sum ${input} {axis} -> {shape:?}")?;
+                },
+            }
+        }
+
+        show_inputs(f, &[self.result])?;
+        writeln!(f, "result ${}", self.result)
+    }
+}
@@ -1,10 +1,28 @@
 pub mod tensor_objects;
 pub mod tensor_ops;
+pub mod random;
+pub mod init;
+pub mod linalg;
+pub mod parallel;
+pub mod lazy;
+pub mod memory;
+pub mod sparse;
+pub mod fft;
+pub mod quantization;
+pub mod stats;
+pub mod data;
+pub mod tensor_io;
 
 #[cfg(test)]
 mod tests;
 
 pub use tensor_objects::{
     Tensor,
-    TensorIndexResult
-};
\ No newline at end of file
+    TensorIndexResult,
+    MmapTensor,
+    Complex,
+    ComplexTensor,
+    HalfTensor,
+    f16,
+    bf16
+};
@@ -0,0 +1,59 @@
+//! # Linalg
+//!
+//! Linear algebra on the last two dimensions of a Tensor. Every function treats a Tensor of shape
+//! \[..., m, n\] as a batch of m x n matrices, so the same call works on a single matrix or on a
+//! stack of them. Results that reduce a matrix to a single number, such as [det()] or [trace()],
+//! keep the batch shape, and since tensorium has no 0-D Tensors a single matrix gives a Tensor of
+//! shape \[1\].
+//!
+//! The module provides:
+//!
+//! - Decompositions: [lu()] with partial pivoting, [qr()] by Householder reflections,
+//!   [cholesky()], the symmetric eigendecomposition [eigh()] and the singular value decomposition
+//!   [svd()].
+//! - Solvers and inverses: [det()], [inv()], [solve()], [lstsq()] and [pinv()].
+//! - Matrix properties: [matrix_rank()], [norm()] and [trace()].
+//!
+//! ## Example
+//!
+//! ```
+//! use tensorium::Tensor;
+//! use tensorium::tensor_ops::{ build_tensor, flatten_tensor };
+//! use tensorium::linalg;
+//!
+//! // A batch of two 2x2 systems solved at once
+//! let a = build_tensor(&[2.0, 0.0, 0.0, 4.0, 1.0, 1.0, 0.0, 1.0], &[2, 2, 2]);
+//! let b = build_tensor(&[2.0, 4.0, 3.0, 1.0], &[2, 2, 1]);
+//!
+//! let x = linalg::solve(&a, &b);
+//! assert_eq!(flatten_tensor(&x), vec![1.0, 1.0, 2.0, 1.0]);
+//! assert_eq!(linalg::det(&a), Tensor::Element(vec![8.0, 1.0]));
+//! ```
+
+pub(crate) mod matrix;
+
+mod decompositions;
+pub use decompositions::{
+    lu,
+    qr,
+    cholesky,
+    eigh,
+    svd
+};
+
+mod solvers;
+pub use solvers::{
+    det,
+    inv,
+    solve,
+    lstsq,
+    pinv,
+    matrix_rank
+};
+
+mod norms;
+pub use norms::{
+    NormOrder,
+    norm,
+    trace
+};
@@ -0,0 +1,531 @@
+use crate::Tensor;
+use crate::linalg::matrix::{ Matrix, expect_square, from_batch_values, from_matrices, to_matrices };
+
+/// The maximum number of Jacobi sweeps before giving up on convergence. Jacobi methods converge
+/// quadratically, so well conditioned inputs finish in well under ten sweeps.
+const MAX_SWEEPS: usize = 100;
+
+/// The combined LU factors of a square matrix, with the row permutation used for pivoting.
+pub(crate) struct LuFactors {
+    /// L below the diagonal (its unit diagonal is implied) and U on and above it.
+    pub lu: Matrix,
+    /// Row `i` of the factored matrix is row `perm[i]` of the input.
+    pub perm: Vec<usize>,
+    /// The sign of the permutation, `+1` or `-1`.
+    pub sign: f64,
+    /// Whether a zero pivot was encountered.
+    pub singular: bool,
+}
+
+/// LU decomposition with partial pivoting. At every step the remaining row with the largest
+/// magnitude in the pivot column is swapped into place.
+pub(crate) fn lu_factor(a: &Matrix) -> LuFactors {
+    let n = a.rows;
+    let mut lu = a.clone();
+    let mut perm: Vec<usize> = (0..n).collect();
+    let mut sign = 1.0;
+    let mut singular = false;
+
+    for k in 0..n {
+        let pivot = (k..n)
+            .max_by(|&i, &j| lu.get(i, k).abs().total_cmp(&lu.get(j, k).abs()))
+            .unwrap();
+
+        if lu.get(pivot, k) == 0.0 {
+            singular = true;
+            continue;
+        }
+
+        if pivot != k {
+            for c in 0..n {
+                lu.data.swap(k * n + c, pivot * n + c);
+            }
+            perm.swap(k, pivot);
+            sign = -sign;
+        }
+
+        let diag = lu.get(k, k);
+        for i in k + 1..n {
+            let factor = lu.get(i, k) / diag;
+            lu.set(i, k, factor);
+            for j in k + 1..n {
+                lu.set(i, j, lu.get(i, j) - factor * lu.get(k, j));
+            }
+        }
+    }
+
+    LuFactors { lu, perm, sign, singular }
+}
+
+/// Reduced QR decomposition by Householder reflections. Returns `Q` (m x k) and `R` (k x n) where
+/// `k = min(m, n)`.
+pub(crate) fn householder_qr(a: &Matrix) -> (Matrix, Matrix) {
+    let (m, n) = (a.rows, a.cols);
+    let k = m.min(n);
+    let mut r = a.clone();
+    let mut q = Matrix::identity(m);
+
+    for j in 0..k {
+        // Build the reflector that maps R[j.., j] onto a multiple of the first basis vector. The
+        // sign of alpha is chosen opposite to x[0] to avoid cancellation.
+        let mut v: Vec<f64> = (j..m).map(|i| r.get(i, j)).collect();
+        let norm_x = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+        if norm_x == 0.0 {
+            continue;
+        }
+        let alpha = if v[0] >= 0.0 { -norm_x } else { norm_x };
+        v[0] -= alpha;
+        let norm_v = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+        if norm_v == 0.0 {
+            continue;
+        }
+        for x in v.iter_mut() {
+            *x /= norm_v;
+        }
+
+        // R <- (I - 2vv^T) R
+        for c in 0..n {
+            let dot: f64 = (j..m).map(|i| v[i - j] * r.get(i, c)).sum();
+            for i in j..m {
+                r.set(i, c, r.get(i, c) - 2.0 * v[i - j] * dot);
+            }
+        }
+
+        // Q <- Q (I - 2vv^T)
+        for row in 0..m {
+            let dot: f64 = (j..m).map(|i| q.get(row, i) * v[i - j]).sum();
+            for i in j..m {
+                q.set(row, i, q.get(row, i) - 2.0 * dot * v[i - j]);
+            }
+        }
+    }
+
+    let mut q_reduced = Matrix::zeros(m, k);
+    for row in 0..m {
+        for c in 0..k {
+            q_reduced.set(row, c, q.get(row, c));
+        }
+    }
+
+    let mut r_reduced = Matrix::zeros(k, n);
+    for row in 0..k {
+        for c in row..n {
+            r_reduced.set(row, c, r.get(row, c));
+        }
+    }
+
+    (q_reduced, r_reduced)
+}
+
+/// Cholesky factor of a symmetric positive definite matrix. Returns `None` when the matrix is not
+/// positive definite.
+pub(crate) fn cholesky_factor(a: &Matrix) -> Option<Matrix> {
+    let n = a.rows;
+    let mut l = Matrix::zeros(n, n);
+
+    for j in 0..n {
+        let diag = a.get(j, j) - (0..j).map(|k| l.get(j, k) * l.get(j, k)).sum::<f64>();
+        if diag <= 0.0 || diag.is_nan() {
+            return None;
+        }
+        let diag = diag.sqrt();
+        l.set(j, j, diag);
+
+        for i in j + 1..n {
+            let sum: f64 = (0..j).map(|k| l.get(i, k) * l.get(j, k)).sum();
+            l.set(i, j, (a.get(i, j) - sum) / diag);
+        }
+    }
+
+    Some(l)
+}
+
+/// Eigendecomposition of a symmetric matrix by the cyclic Jacobi method. Returns the eigenvalues
+/// in ascending order and the matching eigenvectors as the columns of a matrix.
+pub(crate) fn jacobi_eigh(a: &Matrix) -> (Vec<f64>, Matrix) {
+    let n = a.rows;
+    let mut a = a.clone();
+    let mut v = Matrix::identity(n);
+
+    let scale = a.data.iter().map(|x| x * x).sum::<f64>().sqrt();
+    for _ in 0..MAX_SWEEPS {
+        let off: f64 = (0..n)
+            .flat_map(|p| (0..n).filter(move |&q| q != p).map(move |q| (p, q)))
+            .map(|(p, q)| a.get(p, q) * a.get(p, q))
+            .sum::<f64>()
+            .sqrt();
+        if off <= f64::EPSILON * scale {
+            break;
+        }
+
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = a.get(p, q);
+                if apq == 0.0 {
+                    continue;
+                }
+
+                // Choose the rotation that zeroes a[p][q], taking the smaller root for stability.
+                let theta = (a.get(q, q) - a.get(p, p)) / (2.0 * apq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+
+                for k in 0..n {
+                    let (akp, akq) = (a.get(k, p), a.get(k, q));
+                    a.set(k, p, c * akp - s * akq);
+                    a.set(k, q, s * akp + c * akq);
+                }
+                for k in 0..n {
+                    let (apk, aqk) = (a.get(p, k), a.get(q, k));
+                    a.set(p, k, c * apk - s * aqk);
+                    a.set(q, k, s * apk + c * aqk);
+                }
+                for k in 0..n {
+                    let (vkp, vkq) = (v.get(k, p), v.get(k, q));
+                    v.set(k, p, c * vkp - s * vkq);
+                    v.set(k, q, s * vkp + c * vkq);
+                }
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| a.get(i, i).total_cmp(&a.get(j, j)));
+
+    let values = order.iter().map(|&i| a.get(i, i)).collect();
+    let mut vectors = Matrix::zeros(n, n);
+    for (new_col, &old_col) in order.iter().enumerate() {
+        for r in 0..n {
+            vectors.set(r, new_col, v.get(r, old_col));
+        }
+    }
+
+    (values, vectors)
+}
+
+/// Reduced singular value decomposition by one-sided Jacobi rotations. Returns `U` (m x k), the
+/// singular values in descending order and `V` (n x k) where `k = min(m, n)`.
+pub(crate) fn jacobi_svd(a: &Matrix) -> (Matrix, Vec<f64>, Matrix) {
+    // One-sided Jacobi orthogonalizes columns, so work on the tall orientation.
+    if a.rows < a.cols {
+        let (u, s, v) = jacobi_svd(&a.transpose());
+        return (v, s, u);
+    }
+
+    let (m, n) = (a.rows, a.cols);
+    let mut u = a.clone();
+    let mut v = Matrix::identity(n);
+
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+
+        for p in 0..n {
+            for q in p + 1..n {
+                let mut alpha = 0.0;
+                let mut beta = 0.0;
+                let mut gamma = 0.0;
+                for i in 0..m {
+                    let (up, uq) = (u.get(i, p), u.get(i, q));
+                    alpha += up * up;
+                    beta += uq * uq;
+                    gamma += up * uq;
+                }
+
+                if gamma.abs() <= f64::EPSILON * (alpha * beta).sqrt() || gamma == 0.0 {
+                    continue;
+                }
+                rotated = true;
+
+                let zeta = (beta - alpha) / (2.0 * gamma);
+                let t = zeta.signum() / (zeta.abs() + (1.0 + zeta * zeta).sqrt());
+                let c = 1.0 / (1.0 + t * t).sqrt();
+                let s = c * t;
+
+                for i in 0..m {
+                    let (up, uq) = (u.get(i, p), u.get(i, q));
+                    u.set(i, p, c * up - s * uq);
+                    u.set(i, q, s * up + c * uq);
+                }
+                for i in 0..n {
+                    let (vp, vq) = (v.get(i, p), v.get(i, q));
+                    v.set(i, p, c * vp - s * vq);
+                    v.set(i, q, s * vp + c * vq);
+                }
+            }
+        }
+
+        if !rotated {
+            break;
+        }
+    }
+
+    // The column norms are the singular values. Normalizing the columns gives U.
+    let mut singular: Vec<f64> = (0..n)
+        .map(|c| u.column(c).iter().map(|x| x * x).sum::<f64>().sqrt())
+        .collect();
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| singular[j].total_cmp(&singular[i]));
+
+    let largest = singular.iter().copied().fold(0.0, f64::max);
+    let mut u_sorted = Matrix::zeros(m, n);
+    let mut v_sorted = Matrix::zeros(n, n);
+    let mut zero_columns = Vec::new();
+    for (new_col, &old_col) in order.iter().enumerate() {
+        let sigma = singular[old_col];
+        for r in 0..n {
+            v_sorted.set(r, new_col, v.get(r, old_col));
+        }
+        if sigma > largest * f64::EPSILON * m as f64 && sigma > 0.0 {
+            for r in 0..m {
+                u_sorted.set(r, new_col, u.get(r, old_col) / sigma);
+            }
+        } else {
+            zero_columns.push(new_col);
+        }
+    }
+    singular = order.iter().map(|&i| singular[i]).collect();
+
+    // Columns of U belonging to (numerically) zero singular values are undetermined. Fill them with
+    // an orthonormal completion so U always has orthonormal columns.
+    for &col in zero_columns.iter() {
+        complete_column(&mut u_sorted, col);
+    }
+
+    (u_sorted, singular, v_sorted)
+}
+
+/// Replaces column `col` of `u` with a unit vector orthogonal to every other column by
+/// Gram-Schmidt on the standard basis vectors.
+fn complete_column(u: &mut Matrix, col: usize) {
+    let m = u.rows;
+    for basis in 0..m {
+        let mut candidate = vec![0.0; m];
+        candidate[basis] = 1.0;
+
+        for other in 0..u.cols {
+            if other == col {
+                continue;
+            }
+            let dot: f64 = (0..m).map(|r| u.get(r, other) * candidate[r]).sum();
+            for (r, value) in candidate.iter_mut().enumerate() {
+                *value -= dot * u.get(r, other);
+            }
+        }
+
+        let norm = candidate.iter().map(|x| x * x).sum::<f64>().sqrt();
+        if norm > 1e-6 {
+            for (r, value) in candidate.iter().enumerate() {
+                u.set(r, col, value / norm);
+            }
+            return;
+        }
+    }
+}
+
+/// Computes the LU decomposition with partial pivoting of square matrices, returning `(P, L, U)`
+/// such that `A = P @ L @ U`. `P` is a permutation matrix, `L` is unit lower triangular and `U` is
+/// upper triangular. Leading dimensions are treated as a batch.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::{ build_tensor, matmul };
+/// use tensorium::linalg::lu;
+///
+/// let a = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]);
+/// let (p, l, u) = lu(&a);
+///
+/// // The row with the larger leading entry was pivoted to the top
+/// assert_eq!(p, build_tensor(&[0.0, 1.0, 1.0, 0.0], &[2, 2]));
+/// assert_eq!(matmul(&p, &matmul(&l, &u)), a);
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the Tensor has fewer than 2 dimensions or its matrices are not
+/// square.
+pub fn lu(tensor: &Tensor) -> (Tensor, Tensor, Tensor) {
+    let (batch, matrices) = to_matrices(tensor, "lu");
+
+    let mut ps = Vec::new();
+    let mut ls = Vec::new();
+    let mut us = Vec::new();
+    for a in matrices.iter() {
+        expect_square(a, "lu");
+        let n = a.rows;
+        let factors = lu_factor(a);
+
+        let mut p = Matrix::zeros(n, n);
+        let mut l = Matrix::identity(n);
+        let mut u = Matrix::zeros(n, n);
+        for i in 0..n {
+            p.set(factors.perm[i], i, 1.0);
+            for j in 0..n {
+                if j < i {
+                    l.set(i, j, factors.lu.get(i, j));
+                } else {
+                    u.set(i, j, factors.lu.get(i, j));
+                }
+            }
+        }
+
+        ps.push(p);
+        ls.push(l);
+        us.push(u);
+    }
+
+    (from_matrices(&batch, &ps), from_matrices(&batch, &ls), from_matrices(&batch, &us))
+}
+
+/// Computes the reduced QR decomposition using Householder reflections, returning `(Q, R)` such
+/// that `A = Q @ R`. For an m x n matrix `Q` is m x k with orthonormal columns and `R` is k x n and
+/// upper triangular, where `k = min(m, n)`. Leading dimensions are treated as a batch.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::{ build_tensor, flatten_tensor, get_dimension, matmul };
+/// use tensorium::linalg::qr;
+///
+/// let a = build_tensor(&[3.0, 1.0, 4.0, 2.0, 0.0, 5.0], &[3, 2]);
+/// let (q, r) = qr(&a);
+///
+/// assert_eq!(get_dimension(&q), vec![3, 2]);
+/// assert_eq!(get_dimension(&r), vec![2, 2]);
+///
+/// let rebuilt = flatten_tensor(&matmul(&q, &r));
+/// for (x, y) in rebuilt.iter().zip(flatten_tensor(&a)) {
+///     assert!((x - y).abs() < 1e-12);
+/// }
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the Tensor has fewer than 2 dimensions.
+pub fn qr(tensor: &Tensor) -> (Tensor, Tensor) {
+    let (batch, matrices) = to_matrices(tensor, "qr");
+
+    let (qs, rs): (Vec<Matrix>, Vec<Matrix>) = matrices.iter().map(householder_qr).unzip();
+
+    (from_matrices(&batch, &qs), from_matrices(&batch, &rs))
+}
+
+/// Computes the lower triangular Cholesky factor `L` of symmetric positive definite matrices such
+/// that `A = L @ L^T`. Only the lower triangle of the input is read. Leading dimensions are treated
+/// as a batch.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::linalg::cholesky;
+///
+/// let a = build_tensor(&[4.0, 2.0, 2.0, 5.0], &[2, 2]);
+/// assert_eq!(cholesky(&a), build_tensor(&[2.0, 0.0, 1.0, 2.0], &[2, 2]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if a matrix is not square or not positive definite.
+///
+/// ```should_panic
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::linalg::cholesky;
+///
+/// let a = build_tensor(&[1.0, 2.0, 2.0, 1.0], &[2, 2]);
+/// let l = cholesky(&a);
+/// ```
+pub fn cholesky(tensor: &Tensor) -> Tensor {
+    let (batch, matrices) = to_matrices(tensor, "cholesky");
+
+    let factors: Vec<Matrix> = matrices.iter()
+        .map(|a| {
+            expect_square(a, "cholesky");
+            match cholesky_factor(a) {
+                Some(l) => l,
+                None => panic!("Matrix is not positive definite!")
+            }
+        })
+        .collect();
+
+    from_matrices(&batch, &factors)
+}
+
+/// Computes the eigendecomposition of symmetric matrices, returning `(eigenvalues, eigenvectors)`.
+/// The eigenvalues are sorted in ascending order and column `i` of the eigenvector matrix belongs
+/// to eigenvalue `i`. Leading dimensions are treated as a batch.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::{ build_tensor, flatten_tensor };
+/// use tensorium::linalg::eigh;
+///
+/// let a = build_tensor(&[2.0, 1.0, 1.0, 2.0], &[2, 2]);
+/// let (values, vectors) = eigh(&a);
+///
+/// let values = flatten_tensor(&values);
+/// assert!((values[0] - 1.0).abs() < 1e-12);
+/// assert!((values[1] - 3.0).abs() < 1e-12);
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the Tensor has fewer than 2 dimensions or its matrices are not
+/// square.
+pub fn eigh(tensor: &Tensor) -> (Tensor, Tensor) {
+    let (batch, matrices) = to_matrices(tensor, "eigh");
+
+    let mut values = Vec::new();
+    let mut vectors = Vec::new();
+    for a in matrices.iter() {
+        expect_square(a, "eigh");
+        let (w, v) = jacobi_eigh(a);
+        values.extend(w);
+        vectors.push(v);
+    }
+
+    let n = matrices.first().map_or(0, |a| a.rows);
+    (from_batch_values(&batch, &[n], &values), from_matrices(&batch, &vectors))
+}
+
+/// Computes the reduced singular value decomposition, returning `(U, S, Vh)` such that
+/// `A = U @ diag(S) @ Vh`. For an m x n matrix `U` is m x k, `S` has the k singular values in
+/// descending order and `Vh` is k x n, where `k = min(m, n)`. Leading dimensions are treated as a
+/// batch.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::{ build_tensor, flatten_tensor };
+/// use tensorium::linalg::svd;
+///
+/// let a = build_tensor(&[3.0, 0.0, 0.0, 0.0, -2.0, 0.0], &[2, 3]);
+/// let (u, s, vh) = svd(&a);
+///
+/// let s = flatten_tensor(&s);
+/// assert!((s[0] - 3.0).abs() < 1e-12);
+/// assert!((s[1] - 2.0).abs() < 1e-12);
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the Tensor has fewer than 2 dimensions.
+pub fn svd(tensor: &Tensor) -> (Tensor, Tensor, Tensor) {
+    let (batch, matrices) = to_matrices(tensor, "svd");
+
+    let mut us = Vec::new();
+    let mut ss = Vec::new();
+    let mut vhs = Vec::new();
+    for a in matrices.iter() {
+        let (u, s, v) = jacobi_svd(a);
+        us.push(u);
+        ss.extend(s);
+        vhs.push(v.transpose());
+    }
+
+    let k = matrices.first().map_or(0, |a| a.rows.min(a.cols));
+    (from_matrices(&batch, &us), from_batch_values(&batch, &[k], &ss), from_matrices(&batch, &vhs))
+}
@@ -0,0 +1,116 @@
+use crate::Tensor;
+use crate::tensor_ops::{ build_tensor, flatten_tensor, get_dimension };
+
+/// A dense, row-major matrix used internally by the linear algebra routines. The nested Tensor is
+/// unpacked into one of these per batch entry so the algorithms can index freely.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub(crate) struct Matrix {
+    pub rows: usize,
+    pub cols: usize,
+    pub data: Vec<f64>,
+}
+
+impl Matrix {
+    pub fn zeros(rows: usize, cols: usize) -> Matrix {
+        Matrix { rows, cols, data: vec![0.0; rows * cols] }
+    }
+
+    pub fn identity(n: usize) -> Matrix {
+        let mut m = Matrix::zeros(n, n);
+        for i in 0..n {
+            m.set(i, i, 1.0);
+        }
+        m
+    }
+
+    pub fn get(&self, r: usize, c: usize) -> f64 {
+        self.data[r * self.cols + c]
+    }
+
+    pub fn set(&mut self, r: usize, c: usize, value: f64) {
+        self.data[r * self.cols + c] = value;
+    }
+
+    pub fn transpose(&self) -> Matrix {
+        let mut t = Matrix::zeros(self.cols, self.rows);
+        for r in 0..self.rows {
+            for c in 0..self.cols {
+                t.set(c, r, self.get(r, c));
+            }
+        }
+        t
+    }
+
+    pub fn matmul(&self, other: &Matrix) -> Matrix {
+        Matrix {
+            rows: self.rows,
+            cols: other.cols,
+            data: crate::tensor_ops::matmul::matmul_kernel(
+                &self.data,
+                &other.data,
+                self.rows,
+                self.cols,
+                other.cols
+            ),
+        }
+    }
+
+    /// Copies out column `c` as a `Vec`.
+    pub fn column(&self, c: usize) -> Vec<f64> {
+        (0..self.rows).map(|r| self.get(r, c)).collect()
+    }
+}
+
+/// Splits a Tensor of shape \[..., rows, cols\] into its batch shape and one Matrix per batch
+/// entry.
+pub(crate) fn to_matrices(tensor: &Tensor, name: &str) -> (Vec<usize>, Vec<Matrix>) {
+    let shape = get_dimension(tensor);
+    if shape.len() < 2 {
+        panic!("{name} requires a Tensor with at least 2 dimensions but received shape {shape:?}!");
+    }
+
+    let (rows, cols) = (shape[shape.len() - 2], shape[shape.len() - 1]);
+    let batch = shape[..shape.len() - 2].to_vec();
+    let values = flatten_tensor(tensor);
+
+    let matrices = if rows * cols == 0 {
+        vec![Matrix::zeros(rows, cols); batch.iter().product()]
+    } else {
+        values.chunks(rows * cols)
+            .map(|chunk| Matrix { rows, cols, data: chunk.to_vec() })
+            .collect()
+    };
+
+    (batch, matrices)
+}
+
+/// Builds a Tensor of shape `batch + [rows, cols]` from one Matrix per batch entry.
+pub(crate) fn from_matrices(batch: &[usize], matrices: &[Matrix]) -> Tensor {
+    let (rows, cols) = matrices.first().map_or((0, 0), |m| (m.rows, m.cols));
+    let mut shape = batch.to_vec();
+    shape.push(rows);
+    shape.push(cols);
+
+    let values: Vec<f64> = matrices.iter().flat_map(|m| m.data.iter().copied()).collect();
+    build_tensor(&values, &shape)
+}
+
+/// Builds a Tensor of shape `batch + trailing` from flat values. tensorium has no 0-D Tensors, so
+/// a result with no dimensions at all has shape \[1\].
+pub(crate) fn from_batch_values(batch: &[usize], trailing: &[usize], values: &[f64]) -> Tensor {
+    let mut shape = batch.to_vec();
+    shape.extend_from_slice(trailing);
+    if shape.is_empty() {
+        shape.push(1);
+    }
+
+    build_tensor(values, &shape)
+}
+
+pub(crate) fn expect_square(matrix: &Matrix, name: &str) {
+    if matrix.rows != matrix.cols {
+        panic!("{name} requires square matrices but received {}x{}!", matrix.rows, matrix.cols);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::Tensor;
+use crate::linalg::decompositions::jacobi_svd;
+use crate::linalg::matrix::{ Matrix, from_batch_values, to_matrices };
+use crate::tensor_ops::{ flatten_tensor, get_dimension };
+
+/// The order of the norm computed by [crate::linalg::norm()]. The same order means slightly
+/// different things for vectors and matrices, following __numpy__'s `linalg.norm`.
+///
+/// | Order       | Vector norm               | Matrix norm              |
+/// |-------------|---------------------------|--------------------------|
+/// | `Frobenius` | 2-norm                    | Frobenius norm           |
+/// | `Nuclear`   | not defined               | sum of singular values   |
+/// | `Inf`       | `max(abs(x))`             | max absolute row sum     |
+/// | `NegInf`    | `min(abs(x))`             | min absolute row sum     |
+/// | `P(0)`      | number of non-zero values | not defined              |
+/// | `P(1)`      | `sum(abs(x))`             | max absolute column sum  |
+/// | `P(-1)`     | `sum(abs(x)^-1)^-1`       | min absolute column sum  |
+/// | `P(2)`      | 2-norm                    | largest singular value   |
+/// | `P(-2)`     | `sum(abs(x)^-2)^-1/2`     | smallest singular value  |
+/// | `P(p)`      | `sum(abs(x)^p)^(1/p)`     | not defined              |
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum NormOrder {
+    Frobenius,
+    Nuclear,
+    Inf,
+    NegInf,
+    P(f64),
+}
+
+/// Computes a vector norm of a 1-D Tensor or a matrix norm over the last two dimensions of a Tensor
+/// with 2 or more dimensions. Leading dimensions are treated as a batch and a single vector or
+/// matrix gives a Tensor of shape \[1\]. See [NormOrder] for the supported orders.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::linalg::{ norm, NormOrder };
+///
+/// let v = Tensor::Element(vec![3.0, -4.0]);
+/// assert_eq!(norm(&v, NormOrder::Frobenius), Tensor::Element(vec![5.0]));
+/// assert_eq!(norm(&v, NormOrder::P(1.0)), Tensor::Element(vec![7.0]));
+/// assert_eq!(norm(&v, NormOrder::Inf), Tensor::Element(vec![4.0]));
+///
+/// let m = build_tensor(&[1.0, -2.0, 3.0, 4.0], &[2, 2]);
+/// assert_eq!(norm(&m, NormOrder::P(1.0)), Tensor::Element(vec![6.0]));
+/// assert_eq!(norm(&m, NormOrder::Inf), Tensor::Element(vec![7.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic for `Nuclear` on a vector, or for a matrix with a `P` order other than
+/// 1, -1, 2 or -2.
+pub fn norm(tensor: &Tensor, ord: NormOrder) -> Tensor {
+    if get_dimension(tensor).len() == 1 {
+        let value = vector_norm(&flatten_tensor(tensor), ord);
+        return Tensor::Element(vec![value]);
+    }
+
+    let (batch, matrices) = to_matrices(tensor, "norm");
+    let values: Vec<f64> = matrices.iter().map(|a| matrix_norm(a, ord)).collect();
+
+    from_batch_values(&batch, &[], &values)
+}
+
+/// Computes the sum of the main diagonal over the last two dimensions. Non-square matrices sum the
+/// diagonal up to the shorter side. Leading dimensions are treated as a batch and a single matrix
+/// gives a Tensor of shape \[1\].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::linalg::trace;
+///
+/// let a = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]);
+/// assert_eq!(trace(&a), Tensor::Element(vec![6.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the Tensor has fewer than 2 dimensions.
+pub fn trace(tensor: &Tensor) -> Tensor {
+    let (batch, matrices) = to_matrices(tensor, "trace");
+
+    let values: Vec<f64> = matrices.iter()
+        .map(|a| (0..a.rows.min(a.cols)).map(|i| a.get(i, i)).sum())
+        .collect();
+
+    from_batch_values(&batch, &[], &values)
+}
+
+fn vector_norm(values: &[f64], ord: NormOrder) -> f64 {
+    let abs = values.iter().map(|x| x.abs());
+    match ord {
+        NormOrder::Frobenius => abs.map(|x| x * x).sum::<f64>().sqrt(),
+        NormOrder::Inf => abs.fold(0.0, f64::max),
+        NormOrder::NegInf => abs.fold(f64::INFINITY, f64::min),
+        NormOrder::Nuclear => panic!("The nuclear norm is not defined for vectors!"),
+        NormOrder::P(0.0) => values.iter().filter(|&&x| x != 0.0).count() as f64,
+        NormOrder::P(1.0) => abs.sum(),
+        NormOrder::P(2.0) => abs.map(|x| x * x).sum::<f64>().sqrt(),
+        NormOrder::P(p) => abs.map(|x| x.powf(p)).sum::<f64>().powf(1.0 / p),
+    }
+}
+
+fn matrix_norm(a: &Matrix, ord: NormOrder) -> f64 {
+    let row_sums = || (0..a.rows).map(|r| (0..a.cols).map(|c| a.get(r, c).abs()).sum::<f64>());
+    let col_sums = || (0..a.cols).map(|c| (0..a.rows).map(|r| a.get(r, c).abs()).sum::<f64>());
+    let singular_values = || jacobi_svd(a).1;
+
+    match ord {
+        NormOrder::Frobenius => a.data.iter().map(|x| x * x).sum::<f64>().sqrt(),
+        NormOrder::Nuclear => singular_values().iter().sum(),
+        NormOrder::Inf => row_sums().fold(0.0, f64::max),
+        NormOrder::NegInf => row_sums().fold(f64::INFINITY, f64::min),
+        NormOrder::P(1.0) => col_sums().fold(0.0, f64::max),
+        NormOrder::P(-1.0) => col_sums().fold(f64::INFINITY, f64::min),
+        NormOrder::P(2.0) => singular_values().first().copied().unwrap_or(0.0),
+        NormOrder::P(-2.0) => singular_values().last().copied().unwrap_or(0.0),
+        NormOrder::P(p) => panic!("Matrix norm of order {p} is not supported!"),
+    }
+}
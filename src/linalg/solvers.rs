@@ -0,0 +1,295 @@
+use crate::Tensor;
+use crate::linalg::decompositions::{ LuFactors, jacobi_svd, lu_factor };
+use crate::linalg::matrix::{ Matrix, expect_square, from_batch_values, from_matrices, to_matrices };
+use crate::tensor_ops::{ broadcast_shape, flatten_tensor, get_dimension };
+use crate::tensor_ops::matmul::broadcast_offset;
+
+/// Computes the determinant of square matrices. Leading dimensions are treated as a batch and an
+/// unbatched input gives a Tensor of shape \[1\].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::linalg::det;
+///
+/// let a = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]);
+/// assert_eq!(det(&a), Tensor::Element(vec![-2.0]));
+///
+/// // A batch of two matrices gives two determinants
+/// let b = build_tensor(&[2.0, 0.0, 0.0, 2.0, 1.0, 1.0, 1.0, 1.0], &[2, 2, 2]);
+/// assert_eq!(det(&b), Tensor::Element(vec![4.0, 0.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the Tensor has fewer than 2 dimensions or its matrices are not
+/// square.
+pub fn det(tensor: &Tensor) -> Tensor {
+    let (batch, matrices) = to_matrices(tensor, "det");
+
+    let values: Vec<f64> = matrices.iter()
+        .map(|a| {
+            expect_square(a, "det");
+            let factors = lu_factor(a);
+            if factors.singular {
+                return 0.0;
+            }
+            (0..a.rows).map(|i| factors.lu.get(i, i)).product::<f64>() * factors.sign
+        })
+        .collect();
+
+    from_batch_values(&batch, &[], &values)
+}
+
+/// Computes the inverse of square matrices. Leading dimensions are treated as a batch.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::linalg::inv;
+///
+/// let a = build_tensor(&[2.0, 0.0, 0.0, 4.0], &[2, 2]);
+/// assert_eq!(inv(&a), build_tensor(&[0.5, 0.0, 0.0, 0.25], &[2, 2]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if a matrix is not square or is singular.
+///
+/// ```should_panic
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::linalg::inv;
+///
+/// let a = build_tensor(&[1.0, 2.0, 2.0, 4.0], &[2, 2]);
+/// let a_inv = inv(&a);
+/// ```
+pub fn inv(tensor: &Tensor) -> Tensor {
+    let (batch, matrices) = to_matrices(tensor, "inv");
+
+    let inverses: Vec<Matrix> = matrices.iter()
+        .map(|a| {
+            expect_square(a, "inv");
+            lu_solve(&checked_lu(a), &Matrix::identity(a.rows))
+        })
+        .collect();
+
+    from_matrices(&batch, &inverses)
+}
+
+/// Solves `A @ X = B` for `X`, where `A` holds square matrices. `B` is either a matrix with the
+/// same number of rows as `A` or a 1-D vector, in which case the result is also a vector. The
+/// leading batch dimensions of `A` and `B` are broadcast against each other.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::linalg::solve;
+///
+/// // 3x + y = 9, x + 2y = 8
+/// let a = build_tensor(&[3.0, 1.0, 1.0, 2.0], &[2, 2]);
+/// let b = Tensor::Element(vec![9.0, 8.0]);
+///
+/// assert_eq!(solve(&a, &b), Tensor::Element(vec![2.0, 3.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if a matrix of `A` is not square or is singular, if the rows of `B` do
+/// not match, or if the batch dimensions are not broadcastable.
+pub fn solve(a: &Tensor, b: &Tensor) -> Tensor {
+    paired_solve(a, b, "solve", |a, b| {
+        expect_square(a, "solve");
+        lu_solve(&checked_lu(a), b)
+    })
+}
+
+/// Computes the least squares solution to `A @ X = B`, minimizing `||A @ X - B||`. When the
+/// solution is not unique the one with the smallest norm is returned. `A` may be any shape, and
+/// `B` follows the same rules as in [crate::linalg::solve()].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ build_tensor, flatten_tensor };
+/// use tensorium::linalg::lstsq;
+///
+/// // Fit y = m * x + c through (0, 1), (1, 3) and (2, 5)
+/// let a = build_tensor(&[0.0, 1.0, 1.0, 1.0, 2.0, 1.0], &[3, 2]);
+/// let b = Tensor::Element(vec![1.0, 3.0, 5.0]);
+///
+/// let x = flatten_tensor(&lstsq(&a, &b));
+/// assert!((x[0] - 2.0).abs() < 1e-12);
+/// assert!((x[1] - 1.0).abs() < 1e-12);
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the rows of `B` do not match the rows of `A` or if the batch
+/// dimensions are not broadcastable.
+pub fn lstsq(a: &Tensor, b: &Tensor) -> Tensor {
+    paired_solve(a, b, "lstsq", |a, b| pinv_matrix(a).matmul(b))
+}
+
+/// Computes the Moore-Penrose pseudo-inverse from the singular value decomposition. Singular values
+/// below `max(m, n) * eps * largest_singular_value` are treated as zero. Leading dimensions are
+/// treated as a batch.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::{ build_tensor, flatten_tensor };
+/// use tensorium::linalg::pinv;
+///
+/// let a = build_tensor(&[1.0, 0.0, 0.0, 0.0], &[2, 2]);
+/// let a_pinv = flatten_tensor(&pinv(&a));
+///
+/// assert!((a_pinv[0] - 1.0).abs() < 1e-12);
+/// assert!(a_pinv[1..].iter().all(|x| x.abs() < 1e-12));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the Tensor has fewer than 2 dimensions.
+pub fn pinv(tensor: &Tensor) -> Tensor {
+    let (batch, matrices) = to_matrices(tensor, "pinv");
+
+    let inverses: Vec<Matrix> = matrices.iter().map(pinv_matrix).collect();
+
+    from_matrices(&batch, &inverses)
+}
+
+/// Computes the rank of matrices as the number of singular values above
+/// `max(m, n) * eps * largest_singular_value`. Leading dimensions are treated as a batch and an
+/// unbatched input gives a Tensor of shape \[1\].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::linalg::matrix_rank;
+///
+/// let a = build_tensor(&[1.0, 2.0, 2.0, 4.0, 3.0, 6.0], &[3, 2]);
+/// assert_eq!(matrix_rank(&a), Tensor::Element(vec![1.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the Tensor has fewer than 2 dimensions.
+pub fn matrix_rank(tensor: &Tensor) -> Tensor {
+    let (batch, matrices) = to_matrices(tensor, "matrix_rank");
+
+    let ranks: Vec<f64> = matrices.iter()
+        .map(|a| {
+            let (_, s, _) = jacobi_svd(a);
+            let tol = rank_tolerance(a, &s);
+            s.iter().filter(|&&x| x > tol).count() as f64
+        })
+        .collect();
+
+    from_batch_values(&batch, &[], &ranks)
+}
+
+/// LU factors of a matrix that must be invertible.
+fn checked_lu(a: &Matrix) -> LuFactors {
+    let factors = lu_factor(a);
+    if factors.singular {
+        panic!("Matrix is singular!");
+    }
+    factors
+}
+
+/// Solves `A @ X = B` given the LU factors of `A` by forward then backward substitution.
+fn lu_solve(factors: &LuFactors, b: &Matrix) -> Matrix {
+    let n = factors.lu.rows;
+    let lu = &factors.lu;
+    let mut x = Matrix::zeros(n, b.cols);
+
+    for c in 0..b.cols {
+        // L y = P^T b
+        let mut y: Vec<f64> = factors.perm.iter().map(|&p| b.get(p, c)).collect();
+        for i in 0..n {
+            for j in 0..i {
+                y[i] -= lu.get(i, j) * y[j];
+            }
+        }
+
+        // U x = y
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                y[i] -= lu.get(i, j) * y[j];
+            }
+            y[i] /= lu.get(i, i);
+        }
+
+        for (i, value) in y.iter().enumerate() {
+            x.set(i, c, *value);
+        }
+    }
+
+    x
+}
+
+fn pinv_matrix(a: &Matrix) -> Matrix {
+    let (u, s, v) = jacobi_svd(a);
+    let tol = rank_tolerance(a, &s);
+
+    // pinv(A) = V diag(1 / s) U^T, dropping the singular values below the tolerance.
+    let mut v_scaled = v.clone();
+    for (c, sigma) in s.iter().enumerate() {
+        let scale = if *sigma > tol { 1.0 / sigma } else { 0.0 };
+        for r in 0..v_scaled.rows {
+            v_scaled.set(r, c, v.get(r, c) * scale);
+        }
+    }
+
+    v_scaled.matmul(&u.transpose())
+}
+
+fn rank_tolerance(a: &Matrix, singular_values: &[f64]) -> f64 {
+    let largest = singular_values.first().copied().unwrap_or(0.0);
+    a.rows.max(a.cols) as f64 * f64::EPSILON * largest
+}
+
+/// Broadcasts the batches of `a` and `b` against each other and runs `func` on every pair of
+/// matrices. A 1-D `b` is treated as a single column and the column is dropped from the result.
+fn paired_solve(a: &Tensor, b: &Tensor, name: &str, func: impl Fn(&Matrix, &Matrix) -> Matrix) -> Tensor {
+    let (a_batch, a_matrices) = to_matrices(a, name);
+    let rows = a_matrices.first().map_or(0, |m| m.rows);
+
+    let b_vector = get_dimension(b).len() == 1;
+    let (b_batch, b_matrices) = if b_vector {
+        let values = flatten_tensor(b);
+        (Vec::new(), vec![Matrix { rows: values.len(), cols: 1, data: values }])
+    } else {
+        to_matrices(b, name)
+    };
+
+    if b_matrices.first().is_some_and(|m| m.rows != rows) {
+        panic!("{name} requires B to have {rows} rows!");
+    }
+
+    let batch = broadcast_shape(&a_batch, &b_batch);
+    let size: usize = batch.iter().product();
+
+    let results: Vec<Matrix> = (0..size)
+        .map(|i| func(
+            &a_matrices[broadcast_offset(i, &batch, &a_batch)],
+            &b_matrices[broadcast_offset(i, &batch, &b_batch)]
+        ))
+        .collect();
+
+    if b_vector {
+        let values: Vec<f64> = results.iter().flat_map(|m| m.data.iter().copied()).collect();
+        let cols = results.first().map_or(0, |m| m.rows);
+        from_batch_values(&batch, &[cols], &values)
+    } else {
+        from_matrices(&batch, &results)
+    }
+}
@@ -0,0 +1,51 @@
+//! # Memory
+//!
+//! Training loops run the same operations on Tensors of the same shapes, one step after another,
+//! and the temporary buffers inside those operations are handed to the allocator and asked for
+//! again a moment later. To avoid that, tensorium keeps a pool of buffers for its temporaries. The
+//! intermediate results of a [crate::lazy] evaluation and the columns built by
+//! [crate::tensor_ops::conv2d()] are drawn from the pool and handed back once the operation is
+//! done. The values of the Tensors an operation returns are allocated as usual and belong to the
+//! caller.
+//!
+//! Buffers are grouped by size class. Requests are rounded up to the next class, with four classes
+//! between consecutive powers of two, and served by any cached buffer of that class. Buffers of
+//! fewer than 64 elements are left to the allocator.
+//!
+//! The pool is on by default and caches up to 32 MiB. [set_pool_config()] changes the limit or
+//! turns the pool off, [trim_pool()] releases the cache, and [pool_stats()] reports how much
+//! memory is in use and how often the cache was hit.
+//!
+//! ## Example
+//!
+//! ```
+//! use tensorium::memory::pool_stats;
+//! use tensorium::tensor_ops::build_tensor;
+//!
+//! let t = build_tensor(&vec![0.5; 4096], &[64, 64]).lazy();
+//! for _ in 0..10 {
+//!     // Each step's intermediates reuse the buffers freed by the step before
+//!     let loss = (&t * &t - 0.25).square().sum(None).eval();
+//!     drop(loss);
+//! }
+//!
+//! let stats = pool_stats();
+//! println!("{} bytes live, hit rate {:.2}", stats.bytes_live, stats.hit_rate());
+//! ```
+
+mod pool;
+pub use pool::{
+    PoolConfig,
+    PoolStats,
+    pool_config,
+    set_pool_config,
+    pool_stats,
+    reset_pool_stats,
+    trim_pool
+};
+pub(crate) use pool::PoolBuffer;
+#[cfg(test)]
+pub(crate) use pool::{
+    Pool,
+    size_class
+};
@@ -0,0 +1,318 @@
+use std::collections::BTreeMap;
+use std::ops::{ Deref, DerefMut };
+use std::sync::{ Mutex, MutexGuard };
+use std::sync::atomic::{ AtomicBool, Ordering };
+
+/// Buffers shorter than this many elements are allocated exactly and never pooled, as the
+/// allocator serves them quickly and rounding them up to a size class would waste most of them.
+const MIN_POOLED_LEN: usize = 64;
+
+const DEFAULT_MAX_CACHED_BYTES: usize = 32 << 20;
+
+/// How the buffer pool caches memory. The defaults enable the pool and cache up to 32 MiB.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct PoolConfig {
+    /// Whether temporary buffers are drawn from and returned to the pool. When off, every buffer is
+    /// allocated and freed directly.
+    pub enabled: bool,
+    /// The most memory, in bytes, held by buffers waiting in the pool. Returned buffers that do not
+    /// fit are freed.
+    pub max_cached_bytes: usize,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        PoolConfig {
+            enabled: true,
+            max_cached_bytes: DEFAULT_MAX_CACHED_BYTES,
+        }
+    }
+}
+
+/// A snapshot of the buffer pool's counters, from [pool_stats()].
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct PoolStats {
+    /// Bytes in buffers drawn from the pool that are still in use.
+    pub bytes_live: usize,
+    /// The highest `bytes_live` since the pool was created or [reset_pool_stats()] was called.
+    pub peak_bytes_live: usize,
+    /// Bytes in buffers waiting in the pool to be reused.
+    pub bytes_cached: usize,
+    /// Requests served by a cached buffer.
+    pub hits: u64,
+    /// Requests that had to allocate a new buffer.
+    pub misses: u64,
+}
+
+impl PoolStats {
+    /// The share of requests served by a cached buffer, or 0 if there were none.
+    pub fn hit_rate(&self) -> f64 {
+        let requests = self.hits + self.misses;
+        if requests == 0 {
+            0.0
+        } else {
+            self.hits as f64 / requests as f64
+        }
+    }
+}
+
+/// A set of cached buffers with its counters. The functions of this module use one global pool.
+pub(crate) struct Pool {
+    config: PoolConfig,
+    /// Cached buffers by capacity. Every capacity is a size class.
+    free: BTreeMap<usize, Vec<Vec<f64>>>,
+    bytes_live: usize,
+    peak_bytes_live: usize,
+    bytes_cached: usize,
+    hits: u64,
+    misses: u64,
+}
+
+static POOL: Mutex<Pool> = Mutex::new(Pool::new(PoolConfig {
+    enabled: true,
+    max_cached_bytes: DEFAULT_MAX_CACHED_BYTES,
+}));
+
+/// A copy of `config.enabled` for the global pool, so buffers can skip the lock while it is
+/// disabled.
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+fn lock() -> MutexGuard<'static, Pool> {
+    POOL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn bytes(capacity: usize) -> usize {
+    capacity * size_of::<f64>()
+}
+
+/// The capacity of the buffers serving a request for `len` elements. There are four classes
+/// between consecutive powers of two, so at most a fifth of a buffer goes unused.
+pub(crate) fn size_class(len: usize) -> usize {
+    let magnitude = usize::BITS - 1 - len.leading_zeros();
+    len.next_multiple_of(1 << magnitude.saturating_sub(2))
+}
+
+impl Pool {
+    pub(crate) const fn new(config: PoolConfig) -> Pool {
+        Pool {
+            config,
+            free: BTreeMap::new(),
+            bytes_live: 0,
+            peak_bytes_live: 0,
+            bytes_cached: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub(crate) fn set_config(&mut self, config: PoolConfig) {
+        self.trim_to(if config.enabled { config.max_cached_bytes } else { 0 });
+        self.config = config;
+    }
+
+    /// Frees cached buffers, largest first, until at most `limit` bytes are cached, and returns
+    /// the number of bytes freed.
+    pub(crate) fn trim_to(&mut self, limit: usize) -> usize {
+        let before = self.bytes_cached;
+        while self.bytes_cached > limit {
+            let Some(mut entry) = self.free.last_entry() else { break };
+            let capacity = *entry.key();
+            entry.get_mut().pop();
+            if entry.get().is_empty() {
+                entry.remove();
+            }
+            self.bytes_cached -= bytes(capacity);
+        }
+        before - self.bytes_cached
+    }
+
+    pub(crate) fn stats(&self) -> PoolStats {
+        PoolStats {
+            bytes_live: self.bytes_live,
+            peak_bytes_live: self.peak_bytes_live,
+            bytes_cached: self.bytes_cached,
+            hits: self.hits,
+            misses: self.misses,
+        }
+    }
+
+    pub(crate) fn reset_stats(&mut self) {
+        self.peak_bytes_live = self.bytes_live;
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    /// An empty buffer with room for at least `len` elements, reused if possible, and the size
+    /// class it was drawn as. The class is 0 for buffers allocated outside the pool.
+    pub(crate) fn take(&mut self, len: usize) -> (Vec<f64>, usize) {
+        if len < MIN_POOLED_LEN || !self.config.enabled {
+            return (Vec::with_capacity(len), 0);
+        }
+
+        let class = size_class(len);
+        let buffer = match self.free.get_mut(&class).and_then(Vec::pop) {
+            Some(buffer) => {
+                if self.free.get(&class).is_some_and(Vec::is_empty) {
+                    self.free.remove(&class);
+                }
+                self.bytes_cached -= bytes(class);
+                self.hits += 1;
+                buffer
+            },
+            None => {
+                self.misses += 1;
+                Vec::with_capacity(class)
+            },
+        };
+
+        self.bytes_live += bytes(class);
+        self.peak_bytes_live = self.peak_bytes_live.max(self.bytes_live);
+        (buffer, class)
+    }
+
+    /// Takes back a buffer drawn by [Pool::take()] as `class`, keeping it for reuse if it still
+    /// has the capacity of its class and fits under the cache limit. Returns the buffer if it was
+    /// not kept, so the caller can free it.
+    pub(crate) fn give(&mut self, mut buffer: Vec<f64>, class: usize) -> Option<Vec<f64>> {
+        if class == 0 {
+            return Some(buffer);
+        }
+        self.bytes_live -= bytes(class);
+
+        let fits = self.bytes_cached + bytes(class) <= self.config.max_cached_bytes;
+        if !self.config.enabled || !fits || buffer.capacity() != class {
+            return Some(buffer);
+        }
+
+        buffer.clear();
+        self.free.entry(class).or_default().push(buffer);
+        self.bytes_cached += bytes(class);
+        None
+    }
+}
+
+/// The configuration of the buffer pool.
+pub fn pool_config() -> PoolConfig {
+    lock().config.clone()
+}
+
+/// Sets the configuration of the buffer pool. Cached buffers over the new limit are freed, so
+/// disabling the pool, or setting `max_cached_bytes` to 0, releases everything it holds.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::memory::{ pool_config, set_pool_config, PoolConfig };
+///
+/// let previous = pool_config();
+/// set_pool_config(PoolConfig { enabled: false, ..previous.clone() });
+/// assert!(!pool_config().enabled);
+/// set_pool_config(previous);
+/// ```
+pub fn set_pool_config(config: PoolConfig) {
+    let mut pool = lock();
+    ENABLED.store(config.enabled, Ordering::Relaxed);
+    pool.set_config(config);
+}
+
+/// Frees every buffer cached by the pool and returns the number of bytes released. Buffers in use
+/// are not affected and still return to the pool when dropped.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::memory::{ pool_stats, trim_pool };
+///
+/// trim_pool();
+/// assert_eq!(pool_stats().bytes_cached, 0);
+/// ```
+pub fn trim_pool() -> usize {
+    lock().trim_to(0)
+}
+
+/// The current counters of the buffer pool.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::memory::pool_stats;
+/// use tensorium::tensor_ops::flatten_tensor;
+///
+/// // The sum reads the intermediate `x + 1`, whose buffer is drawn from the pool
+/// let x = Tensor::Element(vec![1.0; 1000]).lazy();
+/// let y = ((&x + 1.0).sum(None) * 2.0).eval();
+/// assert_eq!(flatten_tensor(&y), [4000.0]);
+///
+/// let stats = pool_stats();
+/// assert!(stats.peak_bytes_live >= 8000);
+/// assert!(stats.hit_rate() <= 1.0);
+/// ```
+pub fn pool_stats() -> PoolStats {
+    lock().stats()
+}
+
+/// Resets the hit and miss counts, and the peak to the bytes currently live.
+pub fn reset_pool_stats() {
+    lock().reset_stats();
+}
+
+/// A temporary buffer drawn from the global pool, tagged with the size class it was drawn as, and
+/// handed back when dropped. Only buffers the pool handed out are ever taken back, so Vecs made
+/// elsewhere never enter the cache.
+pub(crate) struct PoolBuffer {
+    values: Vec<f64>,
+    /// The size class the buffer was drawn as, or 0 if it was allocated outside the pool.
+    class: usize,
+}
+
+impl PoolBuffer {
+    /// An empty buffer with room for at least `len` elements, reused from the pool if possible.
+    pub(crate) fn with_capacity(len: usize) -> PoolBuffer {
+        if len < MIN_POOLED_LEN || !ENABLED.load(Ordering::Relaxed) {
+            return PoolBuffer { values: Vec::with_capacity(len), class: 0 };
+        }
+
+        let (values, class) = lock().take(len);
+        PoolBuffer { values, class }
+    }
+
+    /// A buffer of `len` zeros.
+    pub(crate) fn zeros(len: usize) -> PoolBuffer {
+        let mut buffer = PoolBuffer::with_capacity(len);
+        buffer.resize(len, 0.0);
+        buffer
+    }
+}
+
+impl Deref for PoolBuffer {
+    type Target = Vec<f64>;
+
+    fn deref(&self) -> &Vec<f64> {
+        &self.values
+    }
+}
+
+impl DerefMut for PoolBuffer {
+    fn deref_mut(&mut self) -> &mut Vec<f64> {
+        &mut self.values
+    }
+}
+
+impl Drop for PoolBuffer {
+    fn drop(&mut self) {
+        if self.class == 0 {
+            return;
+        }
+
+        let mut pool = lock();
+        let rejected = pool.give(std::mem::take(&mut self.values), self.class);
+        // Free a rejected buffer without holding the lock
+        drop(pool);
+        drop(rejected);
+    }
+}
@@ -0,0 +1,54 @@
+//! # Parallel
+//!
+//! Large element-wise operations, broadcasts, reductions and matrix products are split into chunks
+//! that run on a shared pool of worker threads. Work smaller than the grain size of
+//! [ParallelConfig] stays on the calling thread, so small Tensors pay no synchronization cost.
+//!
+//! The configuration is global and set with [set_parallel_config()], or overridden for a single
+//! thread with [with_parallel_config()]. The pool is created on first use and grows to the
+//! largest thread count asked for, with each operation using only as many of its workers as the
+//! configuration allows.
+//!
+//! ## Determinism
+//!
+//! Element-wise operations, reductions along an axis and matrix products compute every output
+//! value on a single thread, so their results never depend on the configuration. Reducing a whole
+//! Tensor combines partial results from every chunk. With [ParallelConfig::deterministic] on, the
+//! chunks are a fixed size and combined in order, so the result is bit-for-bit the same for any
+//! thread count. With it off, there is one chunk per thread, which is slightly faster but rounds
+//! differently as the thread count changes.
+//!
+//! ## Example
+//!
+//! ```
+//! use tensorium::parallel::{ with_parallel_config, ParallelConfig };
+//! use tensorium::tensor_ops::{ build_tensor, sum };
+//!
+//! let values: Vec<f64> = (0..100_000).map(|x| 1.0 / (x as f64 + 1.0)).collect();
+//! let t = build_tensor(&values, &[100_000]);
+//!
+//! let serial = ParallelConfig { threads: 1, ..Default::default() };
+//! let parallel = ParallelConfig { threads: 4, ..Default::default() };
+//!
+//! // Deterministic reductions agree exactly whatever the thread count
+//! assert_eq!(
+//!     with_parallel_config(serial, || sum(&t, None)),
+//!     with_parallel_config(parallel, || sum(&t, None))
+//! );
+//! ```
+
+mod config;
+pub use config::{
+    ParallelConfig,
+    parallel_config,
+    set_parallel_config,
+    with_parallel_config
+};
+
+mod pool;
+pub(crate) use pool::{
+    items_per_chunk,
+    for_each_chunk_mut,
+    map_collect,
+    reduce
+};
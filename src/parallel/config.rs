@@ -0,0 +1,100 @@
+use std::cell::RefCell;
+use std::sync::{ OnceLock, RwLock };
+use std::thread;
+
+/// How tensorium splits work across threads. The defaults use every available core, a grain size
+/// of 32768 and deterministic reductions.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct ParallelConfig {
+    /// The number of threads working on an operation, including the calling thread. 1 runs
+    /// everything on the calling thread.
+    pub threads: usize,
+    /// The amount of work, roughly in multiply-adds, below which an operation stays on the
+    /// calling thread. It is also the size of the chunks that larger operations are split into.
+    pub grain_size: usize,
+    /// Whether reductions over a whole Tensor give the same result for any thread count. See the
+    /// module documentation.
+    pub deterministic: bool,
+}
+
+impl Default for ParallelConfig {
+    fn default() -> Self {
+        ParallelConfig {
+            threads: available_threads(),
+            grain_size: 1 << 15,
+            deterministic: true,
+        }
+    }
+}
+
+/// The number of threads the system can run at once. Finding it can read files under `/proc` and
+/// `/sys`, so it is only done once.
+fn available_threads() -> usize {
+    static THREADS: OnceLock<usize> = OnceLock::new();
+    *THREADS.get_or_init(|| thread::available_parallelism().map(|n| n.get()).unwrap_or(1))
+}
+
+static GLOBAL: RwLock<Option<ParallelConfig>> = RwLock::new(None);
+
+thread_local! {
+    static OVERRIDE: RefCell<Option<ParallelConfig>> = const { RefCell::new(None) };
+}
+
+/// The configuration used by operations started on the current thread: the override from
+/// [with_parallel_config()] if there is one, otherwise the global configuration.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::parallel::{ parallel_config, with_parallel_config, ParallelConfig };
+///
+/// let config = ParallelConfig { threads: 2, ..Default::default() };
+/// with_parallel_config(config.clone(), || assert_eq!(parallel_config(), config));
+/// ```
+pub fn parallel_config() -> ParallelConfig {
+    if let Some(config) = OVERRIDE.with(|o| o.borrow().clone()) {
+        return config;
+    }
+    GLOBAL.read().unwrap_or_else(|e| e.into_inner()).clone().unwrap_or_default()
+}
+
+/// Sets the configuration for every thread without an override.
+///
+/// # Panics
+///
+/// This function will panic if `threads` or `grain_size` is 0.
+pub fn set_parallel_config(config: ParallelConfig) {
+    validate(&config);
+    *GLOBAL.write().unwrap_or_else(|e| e.into_inner()) = Some(config);
+}
+
+/// Runs `f` with `config` overriding the global configuration on the current thread, restoring the
+/// previous configuration afterwards, even if `f` panics.
+///
+/// # Panics
+///
+/// This function will panic if `threads` or `grain_size` is 0.
+pub fn with_parallel_config<T>(config: ParallelConfig, f: impl FnOnce() -> T) -> T {
+    validate(&config);
+
+    struct Restore(Option<ParallelConfig>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            OVERRIDE.with(|o| *o.borrow_mut() = self.0.take());
+        }
+    }
+
+    let _restore = Restore(OVERRIDE.with(|o| o.borrow_mut().replace(config)));
+    f()
+}
+
+fn validate(config: &ParallelConfig) {
+    if config.threads == 0 {
+        panic!("A ParallelConfig needs at least one thread!");
+    }
+    if config.grain_size == 0 {
+        panic!("A ParallelConfig needs a grain size of at least 1!");
+    }
+}
@@ -0,0 +1,233 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::ops::Range;
+use std::panic::{ self, AssertUnwindSafe };
+use std::sync::atomic::{ AtomicUsize, Ordering };
+use std::sync::mpsc::{ self, Receiver, Sender };
+use std::sync::{ Arc, Condvar, Mutex };
+use std::thread;
+use crate::parallel::parallel_config;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A set of worker threads taking jobs from a shared queue. Dropping the pool closes the queue and
+/// the workers exit once it is empty.
+struct Pool {
+    sender: Mutex<Sender<Job>>,
+    workers: usize,
+}
+
+impl Pool {
+    fn new(workers: usize) -> Pool {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        for index in 0..workers {
+            let receiver = Arc::clone(&receiver);
+            thread::Builder::new()
+                .name(format!("tensorium-worker-{index}"))
+                .spawn(move || worker(&receiver))
+                .expect("Failed to spawn a tensorium worker thread!");
+        }
+
+        Pool { sender: Mutex::new(sender), workers }
+    }
+
+    fn submit(&self, job: Job) {
+        // The workers only exit once the sender is dropped, so sending cannot fail
+        let _ = self.sender.lock().unwrap_or_else(|e| e.into_inner()).send(job);
+    }
+}
+
+fn worker(receiver: &Mutex<Receiver<Job>>) {
+    IN_WORKER.with(|w| w.set(true));
+    loop {
+        let job = receiver.lock().unwrap_or_else(|e| e.into_inner()).recv();
+        match job {
+            Ok(job) => job(),
+            Err(_) => break,
+        }
+    }
+}
+
+thread_local! {
+    /// Whether this thread is a pool worker. Work started from a worker runs serially, as the
+    /// operation that started it is already spread across the pool.
+    static IN_WORKER: Cell<bool> = const { Cell::new(false) };
+}
+
+static POOL: Mutex<Option<Arc<Pool>>> = Mutex::new(None);
+
+/// A pool with at least `workers` worker threads. The pool only grows, so switching between thread
+/// counts keeps the largest pool and callers use as many of its workers as they need.
+fn pool(workers: usize) -> Arc<Pool> {
+    let mut pool = POOL.lock().unwrap_or_else(|e| e.into_inner());
+    match pool.as_ref() {
+        Some(current) if current.workers >= workers => Arc::clone(current),
+        _ => {
+            let new = Arc::new(Pool::new(workers));
+            *pool = Some(Arc::clone(&new));
+            new
+        },
+    }
+}
+
+/// A chunked task shared between the calling thread and the jobs helping with it.
+struct Task {
+    func: *const (dyn Fn(usize) + Sync),
+    chunks: usize,
+    next: AtomicUsize,
+    done: Mutex<usize>,
+    finished: Condvar,
+    panic: Mutex<Option<Box<dyn Any + Send>>>,
+}
+
+// SAFETY: `func` points to a `Sync` closure, and is only dereferenced while the call to
+// `run_chunks` that owns the closure is waiting for the task to finish.
+unsafe impl Send for Task {}
+unsafe impl Sync for Task {}
+
+impl Task {
+    /// Claims and runs chunks until none are left.
+    fn work(&self) {
+        loop {
+            let chunk = self.next.fetch_add(1, Ordering::Relaxed);
+            if chunk >= self.chunks {
+                return;
+            }
+
+            // SAFETY: a chunk was claimed, so run_chunks is still waiting for it to be done and the
+            // closure is alive.
+            let func = unsafe { &*self.func };
+            if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| func(chunk))) {
+                self.panic.lock().unwrap_or_else(|e| e.into_inner()).get_or_insert(payload);
+            }
+
+            let mut done = self.done.lock().unwrap_or_else(|e| e.into_inner());
+            *done += 1;
+            if *done == self.chunks {
+                self.finished.notify_all();
+            }
+        }
+    }
+}
+
+/// Runs `func(0)` to `func(chunks - 1)` across the pool, with the calling thread taking part, and
+/// returns once all have finished. A panic in any chunk is resumed on the calling thread after the
+/// others finish.
+fn run_chunks(chunks: usize, func: &(dyn Fn(usize) + Sync)) {
+    let threads = parallel_config().threads;
+    if chunks <= 1 || threads <= 1 || IN_WORKER.with(|w| w.get()) {
+        (0..chunks).for_each(func);
+        return;
+    }
+
+    // SAFETY: only erases the lifetime. The pointer is dereferenced only for claimed chunks, and
+    // this function does not return until every claimed chunk has finished.
+    let func: *const (dyn Fn(usize) + Sync + 'static) = unsafe { std::mem::transmute(func) };
+    let task = Arc::new(Task {
+        func,
+        chunks,
+        next: AtomicUsize::new(0),
+        done: Mutex::new(0),
+        finished: Condvar::new(),
+        panic: Mutex::new(None),
+    });
+
+    // Helpers that start after every chunk is claimed return without touching the closure
+    let helpers = (threads - 1).min(chunks - 1);
+    let pool = pool(threads - 1);
+    for _ in 0..helpers {
+        let task = Arc::clone(&task);
+        pool.submit(Box::new(move || task.work()));
+    }
+    task.work();
+
+    let mut done = task.done.lock().unwrap_or_else(|e| e.into_inner());
+    while *done < chunks {
+        done = task.finished.wait(done).unwrap_or_else(|e| e.into_inner());
+    }
+    drop(done);
+
+    if let Some(payload) = task.panic.lock().unwrap_or_else(|e| e.into_inner()).take() {
+        panic::resume_unwind(payload);
+    }
+}
+
+/// The number of items to put in a chunk when each item costs `cost_per_item`, so that every
+/// chunk holds about one grain of work.
+pub(crate) fn items_per_chunk(cost_per_item: usize) -> usize {
+    (parallel_config().grain_size / cost_per_item.max(1)).max(1)
+}
+
+/// A pointer that can be shared between the threads working on disjoint parts of a slice.
+struct SlicePtr<T>(*mut T);
+
+// SAFETY: every chunk accesses a disjoint range of the slice, and T is Send.
+unsafe impl<T: Send> Sync for SlicePtr<T> {}
+
+impl<T> SlicePtr<T> {
+    /// A method rather than a field access, so closures capture the whole `Sync` wrapper.
+    fn get(&self) -> *mut T {
+        self.0
+    }
+}
+
+/// Splits `out` into pieces of `chunk_len` and runs `func(start, piece)` on each, where `start`
+/// is the index of the first element of the piece.
+pub(crate) fn for_each_chunk_mut<T: Send>(
+    out: &mut [T],
+    chunk_len: usize,
+    func: impl Fn(usize, &mut [T]) + Sync
+) {
+    let len = out.len();
+    let chunk_len = chunk_len.max(1);
+    let ptr = SlicePtr(out.as_mut_ptr());
+
+    run_chunks(len.div_ceil(chunk_len), &|chunk| {
+        let start = chunk * chunk_len;
+        let piece_len = chunk_len.min(len - start);
+        // SAFETY: chunks cover disjoint ranges inside `out`, which is borrowed mutably until
+        // run_chunks returns.
+        let piece = unsafe { std::slice::from_raw_parts_mut(ptr.get().add(start), piece_len) };
+        func(start, piece);
+    });
+}
+
+/// Computes `func(i)` for every `i` below `count`, where each call costs about `cost_per_item`.
+pub(crate) fn map_collect<T: Send>(
+    count: usize,
+    cost_per_item: usize,
+    func: impl Fn(usize) -> T + Sync
+) -> Vec<T> {
+    let mut out = Vec::with_capacity(count);
+    let slots = &mut out.spare_capacity_mut()[..count];
+    for_each_chunk_mut(slots, items_per_chunk(cost_per_item), |start, piece| {
+        for (offset, slot) in piece.iter_mut().enumerate() {
+            slot.write(func(start + offset));
+        }
+    });
+    // SAFETY: every slot was written, or a panic was resumed above and the values leak.
+    unsafe { out.set_len(count) };
+    out
+}
+
+/// Reduces `0..len` by mapping ranges to partial results with `map` and folding them in order with
+/// `combine`. Returns `None` if `len` is 0. The ranges follow [crate::parallel::ParallelConfig].
+pub(crate) fn reduce<T: Send>(
+    len: usize,
+    map: impl Fn(Range<usize>) -> T + Sync,
+    combine: impl Fn(T, T) -> T
+) -> Option<T> {
+    let config = parallel_config();
+    let chunk_len = if config.deterministic {
+        config.grain_size
+    } else {
+        config.grain_size.max(len.div_ceil(config.threads))
+    };
+
+    let partials = map_collect(len.div_ceil(chunk_len), config.grain_size, |chunk| {
+        map(chunk * chunk_len..len.min((chunk + 1) * chunk_len))
+    });
+    partials.into_iter().reduce(combine)
+}
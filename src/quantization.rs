@@ -0,0 +1,62 @@
+//! # Quantization
+//!
+//! Storing Tensors as 8-bit integers, a quarter of the size of `f32`, for inference on small CPUs.
+//! A real value `x` is stored as the integer `round(x / scale) + zero_point` and read back as
+//! `(q - zero_point) * scale`, the affine scheme used by most inference runtimes.
+//!
+//! [QuantParams] hold the scale and zero point, either one pair for a whole Tensor or one per
+//! channel along an axis. They can be given directly, computed from a range with
+//! [QuantParams::from_range()], or calibrated from a sample of Tensors with a [Calibrator] using
+//! the minimum and maximum or percentiles of the values. [quantize()] and [dequantize()] convert
+//! between Tensors and [QuantizedTensor]s of `i8` or `u8`, and [QuantizedTensor::matmul()]
+//! multiplies quantized matrices with `i32` accumulation.
+//!
+//! ## Example
+//!
+//! ```
+//! use tensorium::tensor_ops::{ build_tensor, matmul };
+//! use tensorium::quantization::{ calibrate, quantize, CalibrationMethod, QuantScheme };
+//!
+//! let activations = build_tensor(&[0.1, 0.7, 0.3, 0.9, 0.0, 0.4], &[2, 3]);
+//! let weights = build_tensor(&[0.5, -0.2, -0.3, 0.8, 0.1, 0.05], &[3, 2]);
+//!
+//! // u8 activations with one scale, i8 weights with a scale per output column
+//! let activation_params = calibrate::<u8>(
+//!     &[activations.clone()], CalibrationMethod::MinMax, None, QuantScheme::Affine
+//! );
+//! let weight_params = calibrate::<i8>(
+//!     &[weights.clone()], CalibrationMethod::MinMax, Some(1), QuantScheme::Symmetric
+//! );
+//!
+//! let qa = quantize::<u8>(&activations, &activation_params);
+//! let qw = quantize::<i8>(&weights, &weight_params);
+//!
+//! let exact = tensorium::tensor_ops::flatten_tensor(&matmul(&activations, &weights));
+//! let approx = tensorium::tensor_ops::flatten_tensor(&qa.matmul(&qw));
+//! for (x, y) in exact.iter().zip(&approx) {
+//!     assert!((x - y).abs() < 0.01);
+//! }
+//! ```
+
+mod params;
+pub use params::{
+    QuantParams,
+    QuantScheme
+};
+
+mod quantized_tensor;
+pub use quantized_tensor::{
+    QuantInt,
+    QuantizedTensor,
+    quantize,
+    dequantize
+};
+
+mod calibration;
+pub use calibration::{
+    CalibrationMethod,
+    Calibrator,
+    calibrate
+};
+
+mod matmul;
@@ -0,0 +1,186 @@
+use crate::Tensor;
+use crate::quantization::{ QuantInt, QuantParams, QuantScheme };
+use crate::tensor_ops::{ flatten_tensor, get_dimension };
+use crate::tensor_ops::utilities::axis_layout;
+
+/// How a [Calibrator] turns the observed values into a range.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+pub enum CalibrationMethod {
+    /// The smallest and largest values seen.
+    #[default]
+    MinMax,
+    /// The given percentile, between 50 and 100, and its mirror, so `Percentile(99.9)` gives the
+    /// range from the 0.1th to the 99.9th percentile. Clipping rare outliers leaves a finer scale
+    /// for the other values. Every observed value is kept until the range is computed.
+    Percentile(f64),
+}
+
+/// Collects the range of the values in a sample of Tensors, such as the activations of a layer
+/// over a few batches, to choose [QuantParams] for them. With an axis, a range is kept for each
+/// index along it, for per-channel parameters. NaN is ignored.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::quantization::{ Calibrator, CalibrationMethod, QuantScheme };
+///
+/// let mut calibrator = Calibrator::new(CalibrationMethod::MinMax, None);
+/// calibrator.observe(&Tensor::Element(vec![0.5, -1.0, 2.0]));
+/// calibrator.observe(&Tensor::Element(vec![3.0, 0.0]));
+/// assert_eq!(calibrator.ranges(), [(-1.0, 3.0)]);
+///
+/// let params = calibrator.params::<u8>(QuantScheme::Affine);
+/// assert_eq!(params.zero_points(), &[64]);
+/// ```
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Calibrator {
+    method: CalibrationMethod,
+    axis: Option<usize>,
+    /// The smallest and largest value of each channel.
+    extremes: Vec<(f64, f64)>,
+    /// The values of each channel, kept for percentiles only.
+    samples: Vec<Vec<f64>>,
+}
+
+impl Calibrator {
+    /// # Panics
+    ///
+    /// This function will panic if a percentile is not between 50 and 100.
+    pub fn new(method: CalibrationMethod, axis: Option<usize>) -> Calibrator {
+        if let CalibrationMethod::Percentile(p) = method
+            && !(50.0..=100.0).contains(&p)
+        {
+            panic!("Calibration percentile {p} must be between 50 and 100!");
+        }
+        Calibrator { method, axis, extremes: Vec::new(), samples: Vec::new() }
+    }
+
+    /// Adds the values of a Tensor to the sample.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the axis is out of range, or if the length of the axis differs
+    /// from the Tensors observed before.
+    pub fn observe(&mut self, tensor: &Tensor) {
+        let shape = get_dimension(tensor);
+        let (_, channels, inner) = match self.axis {
+            Some(axis) => axis_layout(&shape, axis),
+            None => (1, 1, shape.iter().product()),
+        };
+
+        if self.extremes.is_empty() {
+            self.extremes = vec![(f64::INFINITY, f64::NEG_INFINITY); channels];
+            if let CalibrationMethod::Percentile(_) = self.method {
+                self.samples = vec![Vec::new(); channels];
+            }
+        } else if self.extremes.len() != channels {
+            panic!(
+                "The calibrator has seen {} channels, but this Tensor has {channels} on axis {}!",
+                self.extremes.len(),
+                self.axis.unwrap_or(0)
+            );
+        }
+
+        for (i, value) in flatten_tensor(tensor).into_iter().enumerate() {
+            if value.is_nan() {
+                continue;
+            }
+            let channel = (i / inner) % channels;
+            let (min, max) = &mut self.extremes[channel];
+            *min = min.min(value);
+            *max = max.max(value);
+            if let Some(sample) = self.samples.get_mut(channel) {
+                sample.push(value);
+            }
+        }
+    }
+
+    /// The `(min, max)` range of every channel, or of the whole sample without an axis. A channel
+    /// with no values other than NaN has the range `(0, 0)`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if no Tensor has been observed.
+    pub fn ranges(&self) -> Vec<(f64, f64)> {
+        if self.extremes.is_empty() {
+            panic!("The calibrator has not observed any Tensors!");
+        }
+
+        match self.method {
+            CalibrationMethod::MinMax => self.extremes.iter()
+                .map(|&(min, max)| if min > max { (0.0, 0.0) } else { (min, max) })
+                .collect(),
+            CalibrationMethod::Percentile(p) => self.samples.iter()
+                .map(|sample| {
+                    let mut sorted = sample.clone();
+                    sorted.sort_by(f64::total_cmp);
+                    (percentile(&sorted, 100.0 - p), percentile(&sorted, p))
+                })
+                .collect(),
+        }
+    }
+
+    /// The parameters covering the ranges with the integers of `T`, per channel along the axis if
+    /// there is one, see [QuantParams::from_range()].
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if no Tensor has been observed.
+    pub fn params<T: QuantInt>(&self, scheme: QuantScheme) -> QuantParams {
+        let ranges = self.ranges();
+        match self.axis {
+            Some(axis) => QuantParams::from_ranges::<T>(&ranges, axis, scheme),
+            None => QuantParams::from_range::<T>(ranges[0].0, ranges[0].1, scheme),
+        }
+    }
+}
+
+/// Observes every Tensor of a sample with a [Calibrator] and returns the resulting parameters.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::quantization::{ calibrate, CalibrationMethod, QuantScheme };
+///
+/// // Per-channel parameters for the two columns of a weight matrix
+/// let weights = build_tensor(&[0.5, -20.0, -0.25, 10.0], &[2, 2]);
+/// let method = CalibrationMethod::MinMax;
+/// let params = calibrate::<i8>(&[weights], method, Some(1), QuantScheme::Symmetric);
+/// assert_eq!(params.scales(), &[0.5 / 127.0, 20.0 / 127.0]);
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the sample is empty, or for the reasons [Calibrator::observe()]
+/// does.
+pub fn calibrate<T: QuantInt>(
+    sample: &[Tensor],
+    method: CalibrationMethod,
+    axis: Option<usize>,
+    scheme: QuantScheme
+) -> QuantParams {
+    let mut calibrator = Calibrator::new(method, axis);
+    for tensor in sample {
+        calibrator.observe(tensor);
+    }
+    calibrator.params::<T>(scheme)
+}
+
+/// The `p`th percentile of sorted values, interpolating linearly between neighbours. Empty input
+/// gives 0.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let Some(last) = sorted.len().checked_sub(1) else {
+        return 0.0;
+    };
+    let position = p / 100.0 * last as f64;
+    let below = position.floor() as usize;
+    let above = (below + 1).min(last);
+    let fraction = position - below as f64;
+    sorted[below] + (sorted[above] - sorted[below]) * fraction
+}
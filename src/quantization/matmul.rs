@@ -0,0 +1,86 @@
+use crate::Tensor;
+use crate::parallel;
+use crate::quantization::{ QuantInt, QuantParams, QuantizedTensor };
+use crate::tensor_ops::build_tensor;
+
+impl<T: QuantInt> QuantizedTensor<T> {
+    /// Multiplies two quantized matrices, \[m, k\] by \[k, n\]. The integer products, less the zero
+    /// points, are summed in `i32` and each sum is scaled once, so the result equals the product of
+    /// the dequantized matrices up to the rounding of that last multiplication.
+    ///
+    /// The left matrix may have per-channel parameters for its rows (axis 0) and the right matrix
+    /// for its columns (axis 1), the usual layout of activations times per-output-channel weights.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tensorium::tensor_ops::build_tensor;
+    /// use tensorium::quantization::{ quantize, QuantParams };
+    ///
+    /// let a = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]);
+    /// let b = build_tensor(&[1.0, -1.0, 0.5, 2.0], &[2, 2]);
+    ///
+    /// let qa = quantize::<u8>(&a, &QuantParams::per_tensor(0.5, 10));
+    /// let qb = quantize::<i8>(&b, &QuantParams::per_tensor(0.25, 0));
+    /// assert_eq!(qa.matmul(&qb), build_tensor(&[2.0, 3.0, 5.0, 5.0], &[2, 2]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if either Tensor is not 2-D, if the inner dimensions do not match,
+    /// if per-channel parameters are along another axis, or if the inner dimension is so long that
+    /// the `i32` sums could overflow.
+    pub fn matmul<U: QuantInt>(&self, other: &QuantizedTensor<U>) -> Tensor {
+        let (m, k, n) = match (self.shape(), other.shape()) {
+            (&[m, k], &[k2, n]) if k == k2 => (m, k, n),
+            (left, right) => panic!(
+                "Quantized matmul needs shapes [m, k] and [k, n], got {left:?} and {right:?}!"
+            ),
+        };
+        let other_axis = |params: &QuantParams, axis| params.axis().is_some_and(|a| a != axis);
+        if other_axis(self.params(), 0) || other_axis(other.params(), 1) {
+            panic!("Quantized matmul needs per-channel parameters along axis 0 of the left matrix \
+                and axis 1 of the right matrix!");
+        }
+
+        let bound = max_offset(self) as i64 * max_offset(other) as i64 * k as i64;
+        if bound > i32::MAX as i64 {
+            panic!("An inner dimension of {k} could overflow the i32 sums of a quantized matmul!");
+        }
+
+        let a = centered(self, k);
+        // The right matrix is stored by column, so both operands of each sum are contiguous
+        let b_rows = centered(other, 1);
+        let b: Vec<i32> = (0..n * k).map(|i| b_rows[(i % k) * n + i / k]).collect();
+
+        let (a_scales, b_scales) = (self.params().scales(), other.params().scales());
+        let out = parallel::map_collect(m * n, k, |index| {
+            let (row, column) = (index / n, index % n);
+            let sum: i32 = a[row * k..(row + 1) * k].iter()
+                .zip(&b[column * k..(column + 1) * k])
+                .map(|(x, y)| x * y)
+                .sum();
+            sum as f64 * a_scales[row % a_scales.len()] * b_scales[column % b_scales.len()]
+        });
+
+        build_tensor(&out, &[m, n])
+    }
+}
+
+/// The values less their zero points, where element `i` belongs to channel `i / inner`, modulo
+/// the number of channels.
+fn centered<T: QuantInt>(tensor: &QuantizedTensor<T>, inner: usize) -> Vec<i32> {
+    let zero_points = tensor.params().zero_points();
+    tensor.values().iter()
+        .enumerate()
+        .map(|(i, q)| q.to_i32() - zero_points[(i / inner) % zero_points.len()])
+        .collect()
+}
+
+/// The largest distance of any storable integer from a zero point.
+fn max_offset<T: QuantInt>(tensor: &QuantizedTensor<T>) -> i32 {
+    tensor.params().zero_points().iter()
+        .map(|&zero_point| (T::MAX - zero_point).max(zero_point - T::MIN))
+        .max()
+        .unwrap_or(0)
+}
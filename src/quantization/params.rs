@@ -0,0 +1,161 @@
+use crate::quantization::QuantInt;
+
+/// How a range of real values is mapped onto the integers of a quantized type.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+pub enum QuantScheme {
+    /// The whole integer range covers `[min, max]`, with the zero point wherever 0 falls.
+    #[default]
+    Affine,
+    /// The range is made symmetric around 0, which then sits in the middle of the integer range:
+    /// zero point 0 for `i8` and 128 for `u8`. Symmetric weights make integer products cheaper.
+    Symmetric,
+}
+
+/// The scales and zero points of an affine quantization, where a real value `x` is stored as
+/// `q = round(x / scale) + zero_point`, clamped to the integer type, and read back as
+/// `(q - zero_point) * scale`.
+///
+/// Per-tensor parameters have one scale and zero point. Per-channel parameters have one for each
+/// index along an axis, so channels with very different ranges, such as the output channels of a
+/// weight matrix, each keep their precision.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::quantization::{ QuantParams, QuantScheme };
+///
+/// let params = QuantParams::from_range::<u8>(-1.0, 3.0, QuantScheme::Affine);
+/// assert_eq!(params.scales(), &[4.0 / 255.0]);
+/// assert_eq!(params.zero_points(), &[64]);
+///
+/// let params = QuantParams::from_range::<i8>(-1.0, 3.0, QuantScheme::Symmetric);
+/// assert_eq!(params.scales(), &[3.0 / 127.0]);
+/// assert_eq!(params.zero_points(), &[0]);
+/// ```
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct QuantParams {
+    scales: Vec<f64>,
+    zero_points: Vec<i32>,
+    axis: Option<usize>,
+}
+
+impl QuantParams {
+    /// # Panics
+    ///
+    /// This function will panic if the scale is not positive and finite.
+    pub fn per_tensor(scale: f64, zero_point: i32) -> QuantParams {
+        QuantParams::checked(vec![scale], vec![zero_point], None)
+    }
+
+    /// Parameters for each index along `axis`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if there are no scales, if the numbers of scales and zero points
+    /// differ, or if any scale is not positive and finite.
+    pub fn per_channel(scales: Vec<f64>, zero_points: Vec<i32>, axis: usize) -> QuantParams {
+        if scales.is_empty() || scales.len() != zero_points.len() {
+            panic!(
+                "Per-channel quantization needs as many zero points as scales, got {} and {}!",
+                scales.len(),
+                zero_points.len()
+            );
+        }
+        QuantParams::checked(scales, zero_points, Some(axis))
+    }
+
+    /// The per-tensor parameters that cover `[min, max]` with the integers of `T`. The range is
+    /// first widened to include 0, so that 0 is stored exactly.
+    pub fn from_range<T: QuantInt>(min: f64, max: f64, scheme: QuantScheme) -> QuantParams {
+        let (scale, zero_point) = range_params::<T>(min, max, scheme);
+        QuantParams::per_tensor(scale, zero_point)
+    }
+
+    /// The per-channel parameters covering one `(min, max)` range per index along `axis`, see
+    /// [QuantParams::from_range()].
+    pub fn from_ranges<T: QuantInt>(
+        ranges: &[(f64, f64)],
+        axis: usize,
+        scheme: QuantScheme
+    ) -> QuantParams {
+        let (scales, zero_points) = ranges.iter()
+            .map(|&(min, max)| range_params::<T>(min, max, scheme))
+            .unzip();
+        QuantParams::per_channel(scales, zero_points, axis)
+    }
+
+    pub fn scales(&self) -> &[f64] {
+        &self.scales
+    }
+
+    pub fn zero_points(&self) -> &[i32] {
+        &self.zero_points
+    }
+
+    /// The axis of per-channel parameters, or `None` for per-tensor parameters.
+    pub fn axis(&self) -> Option<usize> {
+        self.axis
+    }
+
+    fn checked(scales: Vec<f64>, zero_points: Vec<i32>, axis: Option<usize>) -> QuantParams {
+        if let Some(scale) = scales.iter().find(|s| !(s.is_finite() && **s > 0.0)) {
+            panic!("Quantization scale {scale} must be positive and finite!");
+        }
+        QuantParams { scales, zero_points, axis }
+    }
+
+    /// Checks the parameters against the shape of a Tensor and the integer type, and returns the
+    /// `(outer, channels, inner)` layout of the channel axis.
+    pub(crate) fn layout<T: QuantInt>(&self, shape: &[usize]) -> (usize, usize, usize) {
+        let in_range = |zero_point: &&i32| (T::MIN..=T::MAX).contains(*zero_point);
+        if let Some(zero_point) = self.zero_points.iter().find(|z| !in_range(z)) {
+            panic!("Zero point {zero_point} is out of range for {}!", T::NAME);
+        }
+
+        let Some(axis) = self.axis else {
+            return (shape.iter().product(), 1, 1);
+        };
+        if axis >= shape.len() {
+            panic!("Axis {axis} is out of range for a Tensor with {} dimensions!", shape.len());
+        }
+        if shape[axis] != self.scales.len() {
+            panic!(
+                "Quantization parameters have {} channels but axis {axis} has length {}!",
+                self.scales.len(),
+                shape[axis]
+            );
+        }
+
+        (shape[..axis].iter().product(), shape[axis], shape[axis + 1..].iter().product())
+    }
+}
+
+/// The scale and zero point covering `[min, max]`, see [QuantParams::from_range()].
+fn range_params<T: QuantInt>(min: f64, max: f64, scheme: QuantScheme) -> (f64, i32) {
+    let (min, max) = (min.min(0.0), max.max(0.0));
+    let (qmin, qmax) = (T::MIN as f64, T::MAX as f64);
+
+    match scheme {
+        QuantScheme::Affine => {
+            let scale = (max - min) / (qmax - qmin);
+            if !(scale.is_finite() && scale > 0.0) {
+                return (1.0, T::MIN.max(0));
+            }
+            let zero_point = (qmin - (min / scale).round_ties_even()).clamp(qmin, qmax);
+            (scale, zero_point as i32)
+        },
+        QuantScheme::Symmetric => {
+            let zero_point = (T::MIN + T::MAX + 1) / 2;
+            let scale = max.max(-min) / (T::MAX - zero_point) as f64;
+            if !(scale.is_finite() && scale > 0.0) {
+                return (1.0, zero_point);
+            }
+            (scale, zero_point)
+        },
+    }
+}
@@ -0,0 +1,148 @@
+use crate::Tensor;
+use crate::parallel;
+use crate::quantization::QuantParams;
+use crate::tensor_ops::{ build_tensor, flatten_tensor, get_dimension };
+
+/// The integer types a Tensor can be quantized to: `i8` and `u8`.
+pub trait QuantInt: Copy + Default + PartialEq + std::fmt::Debug + Send + Sync + 'static {
+    const MIN: i32;
+    const MAX: i32;
+    /// The name of the type, for error messages.
+    const NAME: &'static str;
+
+    /// Converts a value already clamped to `MIN..=MAX`.
+    fn from_i32(value: i32) -> Self;
+
+    fn to_i32(self) -> i32;
+}
+
+/// Implements [QuantInt] for a primitive integer type.
+macro_rules! impl_quant_int {
+    ($int:ty) => {
+        impl QuantInt for $int {
+            const MIN: i32 = <$int>::MIN as i32;
+            const MAX: i32 = <$int>::MAX as i32;
+            const NAME: &'static str = stringify!($int);
+
+            fn from_i32(value: i32) -> Self {
+                value as $int
+            }
+
+            fn to_i32(self) -> i32 {
+                self as i32
+            }
+        }
+    };
+}
+
+impl_quant_int!(i8);
+impl_quant_int!(u8);
+
+/// A Tensor quantized to 8-bit integers, with the [QuantParams] to read it back. Like
+/// [crate::ComplexTensor], it stores its values in row-major order with a shape. It is created by
+/// [quantize()] and read back by [dequantize()] or [QuantizedTensor::dequantize()].
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct QuantizedTensor<T: QuantInt> {
+    values: Vec<T>,
+    shape: Vec<usize>,
+    params: QuantParams,
+}
+
+impl<T: QuantInt> QuantizedTensor<T> {
+    /// A Tensor with the given integers in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the number of values does not match the shape, or if the
+    /// parameters do not fit the shape, as for [quantize()].
+    pub fn from_values(
+        values: Vec<T>,
+        shape: &[usize],
+        params: QuantParams
+    ) -> QuantizedTensor<T> {
+        if values.len() != shape.iter().product::<usize>() {
+            panic!("{} values cannot fill a Tensor of shape {shape:?}!", values.len());
+        }
+        params.layout::<T>(shape);
+        QuantizedTensor { values, shape: shape.to_vec(), params }
+    }
+
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// The integers in row-major order.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    pub fn params(&self) -> &QuantParams {
+        &self.params
+    }
+
+    /// See [dequantize()].
+    pub fn dequantize(&self) -> Tensor {
+        dequantize(self)
+    }
+}
+
+/// Quantizes every value of a Tensor to the integer type `T`: `round(x / scale) + zero_point`,
+/// with ties rounded to even and the result clamped to the range of `T`. NaN is stored as the
+/// zero point.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::quantization::{ quantize, dequantize, QuantParams };
+///
+/// let params = QuantParams::per_tensor(0.5, 10);
+/// let q = quantize::<u8>(&Tensor::Element(vec![-1.0, 0.0, 0.74, 200.0]), &params);
+/// assert_eq!(q.values(), &[8, 10, 11, 255]);
+///
+/// // 200 was clamped to the largest value u8 can represent
+/// assert_eq!(dequantize(&q), Tensor::Element(vec![-1.0, 0.0, 0.5, 122.5]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if a zero point is out of range for `T`, or, for per-channel
+/// parameters, if the axis is out of range or the number of channels does not match its length.
+pub fn quantize<T: QuantInt>(tensor: &Tensor, params: &QuantParams) -> QuantizedTensor<T> {
+    let shape = get_dimension(tensor);
+    let data = flatten_tensor(tensor);
+
+    let values = map_with_params::<T, T>(&shape, params, |i, scale, zero_point| {
+        let q = (data[i] / scale).round_ties_even() + zero_point as f64;
+        // NaN casts to 0, which the zero point offsets back to the zero point
+        let q = if q.is_nan() { zero_point as f64 } else { q };
+        T::from_i32(q.clamp(T::MIN as f64, T::MAX as f64) as i32)
+    });
+
+    QuantizedTensor { values, shape, params: params.clone() }
+}
+
+/// Reads back the real values of a quantized Tensor, `(q - zero_point) * scale`.
+pub fn dequantize<T: QuantInt>(tensor: &QuantizedTensor<T>) -> Tensor {
+    let data = map_with_params::<T, f64>(&tensor.shape, &tensor.params, |i, scale, zero_point| {
+        (tensor.values[i].to_i32() - zero_point) as f64 * scale
+    });
+    build_tensor(&data, &tensor.shape)
+}
+
+/// Computes `func(i, scale, zero_point)` for every element of a Tensor of the given shape, in
+/// row-major order, with the parameters of the element's channel.
+fn map_with_params<T: QuantInt, U: Send>(
+    shape: &[usize],
+    params: &QuantParams,
+    func: impl Fn(usize, f64, i32) -> U + Sync
+) -> Vec<U> {
+    let (outer, channels, inner) = params.layout::<T>(shape);
+    let (scales, zero_points) = (params.scales(), params.zero_points());
+    parallel::map_collect(outer * channels * inner, 2, |i| {
+        let channel = (i / inner) % channels;
+        func(i, scales[channel], zero_points[channel])
+    })
+}
@@ -0,0 +1,120 @@
+//! # Random
+//!
+//! A small, seedable pseudo-random number generator used by the parts of tensorium that need
+//! randomness, such as weight initialization. The generator is xoshiro256** seeded through
+//! SplitMix64, so two generators built from the same seed always produce the same sequence.
+//!
+//! ## Example
+//!
+//! ```
+//! use tensorium::random::Rng;
+//!
+//! let mut rng1 = Rng::new(42);
+//! let mut rng2 = Rng::new(42);
+//!
+//! // The same seed gives the same values
+//! assert_eq!(rng1.next_f64(), rng2.next_f64());
+//!
+//! // Uniform values always land inside the requested range
+//! let x = rng1.uniform(-1.0, 1.0);
+//! assert!((-1.0..1.0).contains(&x));
+//! ```
+
+use std::f64::consts::PI;
+
+/// A seedable xoshiro256** pseudo-random number generator.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct Rng {
+    state: [u64; 4],
+}
+
+impl Rng {
+    /// Creates a new generator from a seed. The four words of internal state are filled using
+    /// SplitMix64 so that even small or similar seeds produce well mixed states.
+    pub fn new(seed: u64) -> Rng {
+        let mut sm = seed;
+        let mut state = [0u64; 4];
+        for word in state.iter_mut() {
+            sm = sm.wrapping_add(0x9E3779B97F4A7C15);
+            let mut z = sm;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            *word = z ^ (z >> 31);
+        }
+
+        Rng { state }
+    }
+
+    /// Returns the raw internal state of the generator. Together with [Rng::from_state] this
+    /// allows a generator to be saved and resumed exactly where it left off.
+    pub fn state(&self) -> [u64; 4] {
+        self.state
+    }
+
+    /// Rebuilds a generator from a state previously returned by [Rng::state].
+    ///
+    /// # Panics
+    ///
+    /// xoshiro256** cannot leave the all-zero state, so this function will panic if every word
+    /// of the state is zero.
+    pub fn from_state(state: [u64; 4]) -> Rng {
+        if state.iter().all(|&word| word == 0) {
+            panic!("Rng state cannot be all zeros!");
+        }
+
+        Rng { state }
+    }
+
+    /// Returns the next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.state[1] << 17;
+
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+
+        result
+    }
+
+    /// Returns a uniformly distributed value in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        // The top 53 bits fill the mantissa of an f64 exactly.
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Returns a uniformly distributed value in `[low, high)`.
+    pub fn uniform(&mut self, low: f64, high: f64) -> f64 {
+        low + (high - low) * self.next_f64()
+    }
+
+    /// Returns a normally distributed value with the given mean and standard deviation using the
+    /// Box-Muller transform.
+    pub fn normal(&mut self, mean: f64, std: f64) -> f64 {
+        // 1 - u keeps the logarithm away from zero.
+        let u1 = 1.0 - self.next_f64();
+        let u2 = self.next_f64();
+        let z = (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos();
+
+        mean + std * z
+    }
+
+    /// Returns a uniformly distributed index in `[0, n)`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `n` is zero.
+    pub fn below(&mut self, n: usize) -> usize {
+        if n == 0 {
+            panic!("Cannot sample an index below zero!");
+        }
+
+        // Lemire's multiply-shift keeps the bias negligible without a division.
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+}
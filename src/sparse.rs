@@ -0,0 +1,47 @@
+//! # Sparse
+//!
+//! Matrices that are mostly zeros, stored as their non-zero entries only. Three formats are
+//! provided:
+//!
+//! - [CooMatrix], a list of (row, column, value) entries, for building a matrix.
+//! - [CsrMatrix], compressed sparse rows, for products with dense Tensors and arithmetic.
+//! - [CscMatrix], compressed sparse columns, the column-wise mirror of CSR.
+//!
+//! Every format converts to and from dense 2-D Tensors and to the other formats. The compressed
+//! formats support sparse-dense and sparse-sparse matrix products, element-wise addition,
+//! subtraction and multiplication, scaling, sums along an axis and transposes, all of which keep
+//! the result sparse and drop entries that come out exactly zero. Products with a dense Tensor and
+//! sums give dense Tensors.
+//!
+//! ## Example
+//!
+//! ```
+//! use tensorium::Tensor;
+//! use tensorium::sparse::CooMatrix;
+//!
+//! // A 3x4 matrix with three non-zero entries
+//! let mut coo = CooMatrix::new(3, 4);
+//! coo.push(0, 1, 2.0);
+//! coo.push(2, 0, -1.0);
+//! coo.push(2, 3, 4.0);
+//! let m = coo.to_csr();
+//!
+//! let x = Tensor::Element(vec![1.0, 2.0, 3.0, 4.0]);
+//! assert_eq!(m.matmul_dense(&x), Tensor::Element(vec![4.0, 0.0, 15.0]));
+//! assert_eq!(m.sum(Some(1)), Tensor::Element(vec![2.0, 0.0, 3.0]));
+//!
+//! // The Gram matrix of the rows stays sparse
+//! let gram = m.matmul(&m.transpose());
+//! assert_eq!(gram.nnz(), 2);
+//! ```
+
+mod compressed;
+
+mod coo;
+pub use coo::CooMatrix;
+
+mod csr;
+pub use csr::CsrMatrix;
+
+mod csc;
+pub use csc::CscMatrix;
@@ -0,0 +1,382 @@
+use crate::Tensor;
+use crate::tensor_ops::{ flatten_tensor, get_dimension };
+
+/// The storage shared by CSR and CSC matrices. Lines are rows for CSR and columns for CSC, and
+/// positions index along a line. The entries of line `i` are `indptr[i]..indptr[i + 1]`, with
+/// positions strictly increasing.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub(crate) struct Compressed {
+    pub lines: usize,
+    pub positions: usize,
+    pub indptr: Vec<usize>,
+    pub indices: Vec<usize>,
+    pub values: Vec<f64>,
+}
+
+impl Compressed {
+    /// Checks the arrays describe a valid matrix. `name` is used in panic messages.
+    pub fn new(
+        lines: usize,
+        positions: usize,
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        values: Vec<f64>,
+        name: &str
+    ) -> Compressed {
+        if indptr.len() != lines + 1 || indptr[0] != 0 || indptr[lines] != indices.len() {
+            panic!("The index pointers of a {name} matrix must run from 0 to its entry count!");
+        }
+        if indices.len() != values.len() {
+            panic!("A {name} matrix needs as many values as indices!");
+        }
+        // Starting at 0 and ending at the entry count, pointers that never decrease stay in bounds
+        if indptr.windows(2).any(|w| w[0] > w[1]) {
+            panic!("The index pointers of a {name} matrix must not decrease!");
+        }
+        for line in 0..lines {
+            let line_indices = &indices[indptr[line]..indptr[line + 1]];
+            if line_indices.windows(2).any(|w| w[0] >= w[1]) {
+                panic!("The indices of each line of a {name} matrix must be strictly increasing!");
+            }
+            if line_indices.last().is_some_and(|&i| i >= positions) {
+                panic!("Index out of bounds for a {name} matrix!");
+            }
+        }
+
+        Compressed { lines, positions, indptr, indices, values }
+    }
+
+    /// Builds the matrix from entries in any order, summing duplicates.
+    pub fn from_entries(
+        lines: usize,
+        positions: usize,
+        entries: &[(usize, usize, f64)]
+    ) -> Compressed {
+        let mut counts = vec![0; lines + 1];
+        for &(line, _, _) in entries {
+            counts[line + 1] += 1;
+        }
+        for line in 0..lines {
+            counts[line + 1] += counts[line];
+        }
+
+        // Bucket the entries by line, then sort and merge each line
+        let mut next = counts.clone();
+        let mut bucketed = vec![(0, 0.0); entries.len()];
+        for &(line, position, value) in entries {
+            bucketed[next[line]] = (position, value);
+            next[line] += 1;
+        }
+
+        let mut indptr = Vec::with_capacity(lines + 1);
+        let mut indices = Vec::with_capacity(entries.len());
+        let mut values = Vec::with_capacity(entries.len());
+        indptr.push(0);
+        for line in 0..lines {
+            let bucket = &mut bucketed[counts[line]..counts[line + 1]];
+            bucket.sort_by_key(|&(position, _)| position);
+            for &(position, value) in bucket.iter() {
+                if indices.len() > indptr[line] && indices.last() == Some(&position) {
+                    *values.last_mut().unwrap() += value;
+                } else {
+                    indices.push(position);
+                    values.push(value);
+                }
+            }
+            indptr.push(indices.len());
+        }
+
+        Compressed { lines, positions, indptr, indices, values }
+    }
+
+    /// Builds the matrix from the non-zero values of a 2-D Tensor, with rows as lines, or with
+    /// columns as lines if `by_column` is set.
+    pub fn from_dense(tensor: &Tensor, by_column: bool, name: &str) -> Compressed {
+        let shape = get_dimension(tensor);
+        if shape.len() != 2 {
+            panic!("A {name} matrix can only be built from a 2-D Tensor!");
+        }
+        let (rows, cols) = (shape[0], shape[1]);
+        let data = flatten_tensor(tensor);
+        let (lines, positions) = if by_column { (cols, rows) } else { (rows, cols) };
+        let at = |line: usize, position: usize| {
+            if by_column { data[position * cols + line] } else { data[line * cols + position] }
+        };
+
+        let mut indptr = Vec::with_capacity(lines + 1);
+        let mut indices = Vec::new();
+        let mut values = Vec::new();
+        indptr.push(0);
+        for line in 0..lines {
+            for position in 0..positions {
+                let value = at(line, position);
+                if value != 0.0 {
+                    indices.push(position);
+                    values.push(value);
+                }
+            }
+            indptr.push(indices.len());
+        }
+
+        Compressed { lines, positions, indptr, indices, values }
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// The positions and values stored in a line.
+    pub fn line(&self, line: usize) -> (&[usize], &[f64]) {
+        let range = self.indptr[line]..self.indptr[line + 1];
+        (&self.indices[range.clone()], &self.values[range])
+    }
+
+    pub fn get(&self, line: usize, position: usize) -> f64 {
+        let (indices, values) = self.line(line);
+        match indices.binary_search(&position) {
+            Ok(i) => values[i],
+            Err(_) => 0.0,
+        }
+    }
+
+    /// The values in row-major order, with rows as lines, or with columns as lines if
+    /// `by_column` is set.
+    pub fn to_dense(&self, by_column: bool) -> Vec<f64> {
+        let mut data = vec![0.0; self.lines * self.positions];
+        for line in 0..self.lines {
+            let (indices, values) = self.line(line);
+            for (&position, &value) in indices.iter().zip(values) {
+                let offset = if by_column {
+                    position * self.lines + line
+                } else {
+                    line * self.positions + position
+                };
+                data[offset] = value;
+            }
+        }
+        data
+    }
+
+    /// The same matrix with lines and positions swapped, so a CSR matrix becomes the CSC matrix of
+    /// the same values, or the CSR matrix of the transpose.
+    pub fn swap_axes(&self) -> Compressed {
+        let mut indptr = vec![0; self.positions + 1];
+        for &position in &self.indices {
+            indptr[position + 1] += 1;
+        }
+        for position in 0..self.positions {
+            indptr[position + 1] += indptr[position];
+        }
+
+        // Walking the lines in order keeps the new positions increasing
+        let mut next = indptr.clone();
+        let mut indices = vec![0; self.nnz()];
+        let mut values = vec![0.0; self.nnz()];
+        for line in 0..self.lines {
+            let (line_indices, line_values) = self.line(line);
+            for (&position, &value) in line_indices.iter().zip(line_values) {
+                indices[next[position]] = line;
+                values[next[position]] = value;
+                next[position] += 1;
+            }
+        }
+
+        Compressed { lines: self.positions, positions: self.lines, indptr, indices, values }
+    }
+
+    /// The product of two matrices stored by line, `self` being `lines x positions` and `other`
+    /// being `positions x n`. Exact zeros in the result are dropped.
+    pub fn matmul(&self, other: &Compressed) -> Compressed {
+        if self.positions != other.lines {
+            panic!(
+                "Inner dimensions do not match for matmul! ({} vs {})",
+                self.positions,
+                other.lines
+            );
+        }
+
+        // Gustavson's algorithm: each result line is a sum of lines of `other`, gathered in a
+        // dense accumulator that remembers which positions it touched
+        let mut accumulator = vec![0.0; other.positions];
+        let mut touched = vec![false; other.positions];
+        let mut pattern = Vec::new();
+
+        let mut indptr = Vec::with_capacity(self.lines + 1);
+        let mut indices = Vec::new();
+        let mut values = Vec::new();
+        indptr.push(0);
+        for line in 0..self.lines {
+            let (left_indices, left_values) = self.line(line);
+            for (&k, &a) in left_indices.iter().zip(left_values) {
+                let (right_indices, right_values) = other.line(k);
+                for (&j, &b) in right_indices.iter().zip(right_values) {
+                    if !touched[j] {
+                        touched[j] = true;
+                        pattern.push(j);
+                    }
+                    accumulator[j] += a * b;
+                }
+            }
+
+            pattern.sort_unstable();
+            for &j in &pattern {
+                if accumulator[j] != 0.0 {
+                    indices.push(j);
+                    values.push(accumulator[j]);
+                }
+                accumulator[j] = 0.0;
+                touched[j] = false;
+            }
+            pattern.clear();
+            indptr.push(indices.len());
+        }
+
+        Compressed { lines: self.lines, positions: other.positions, indptr, indices, values }
+    }
+
+    /// Combines two matrices of the same shape entry by entry. With `union`, positions stored in
+    /// either matrix are combined, with the missing value taken as 0, and otherwise only positions
+    /// stored in both. Exact zeros in the result are dropped.
+    pub fn zip(
+        &self,
+        other: &Compressed,
+        union: bool,
+        func: impl Fn(f64, f64) -> f64
+    ) -> Compressed {
+        let mut indptr = Vec::with_capacity(self.lines + 1);
+        let mut indices = Vec::new();
+        let mut values = Vec::new();
+        indptr.push(0);
+
+        for line in 0..self.lines {
+            let (left_indices, left_values) = self.line(line);
+            let (right_indices, right_values) = other.line(line);
+            let (mut l, mut r) = (0, 0);
+            while l < left_indices.len() || r < right_indices.len() {
+                let left = left_indices.get(l).copied().unwrap_or(usize::MAX);
+                let right = right_indices.get(r).copied().unwrap_or(usize::MAX);
+                let entry = if left == right {
+                    l += 1;
+                    r += 1;
+                    Some((left, func(left_values[l - 1], right_values[r - 1])))
+                } else if left < right {
+                    l += 1;
+                    union.then(|| (left, func(left_values[l - 1], 0.0)))
+                } else {
+                    r += 1;
+                    union.then(|| (right, func(0.0, right_values[r - 1])))
+                };
+
+                if let Some((position, value)) = entry && value != 0.0 {
+                    indices.push(position);
+                    values.push(value);
+                }
+            }
+            indptr.push(indices.len());
+        }
+
+        Compressed { lines: self.lines, positions: self.positions, indptr, indices, values }
+    }
+
+    /// Applies `func` to every stored value, dropping results that are exactly zero.
+    pub fn map_values(&self, func: impl Fn(f64) -> f64) -> Compressed {
+        let mut indptr = Vec::with_capacity(self.lines + 1);
+        let mut indices = Vec::with_capacity(self.nnz());
+        let mut values = Vec::with_capacity(self.nnz());
+        indptr.push(0);
+        for line in 0..self.lines {
+            let (line_indices, line_values) = self.line(line);
+            for (&position, &value) in line_indices.iter().zip(line_values) {
+                let value = func(value);
+                if value != 0.0 {
+                    indices.push(position);
+                    values.push(value);
+                }
+            }
+            indptr.push(indices.len());
+        }
+
+        Compressed { lines: self.lines, positions: self.positions, indptr, indices, values }
+    }
+
+    /// The sum of each line.
+    pub fn line_sums(&self) -> Vec<f64> {
+        (0..self.lines).map(|line| self.line(line).1.iter().sum()).collect()
+    }
+
+    /// The sum at each position, across all lines.
+    pub fn position_sums(&self) -> Vec<f64> {
+        let mut sums = vec![0.0; self.positions];
+        for (&position, &value) in self.indices.iter().zip(&self.values) {
+            sums[position] += value;
+        }
+        sums
+    }
+
+    /// Multiplies the matrix by the dense `positions x n` matrix `dense`, with the result having a
+    /// row for every line.
+    pub fn matmul_lines(&self, dense: &[f64], n: usize) -> Vec<f64> {
+        let mut out = vec![0.0; self.lines * n];
+        for line in 0..self.lines {
+            let row = &mut out[line * n..(line + 1) * n];
+            let (indices, values) = self.line(line);
+            for (&k, &a) in indices.iter().zip(values) {
+                for (o, &b) in row.iter_mut().zip(&dense[k * n..(k + 1) * n]) {
+                    *o += a * b;
+                }
+            }
+        }
+        out
+    }
+
+    /// Multiplies the transpose of the matrix by the dense `lines x n` matrix `dense`, with the
+    /// result having a row for every position.
+    pub fn matmul_positions(&self, dense: &[f64], n: usize) -> Vec<f64> {
+        let mut out = vec![0.0; self.positions * n];
+        for line in 0..self.lines {
+            let source = &dense[line * n..(line + 1) * n];
+            let (indices, values) = self.line(line);
+            for (&i, &a) in indices.iter().zip(values) {
+                for (o, &b) in out[i * n..(i + 1) * n].iter_mut().zip(source) {
+                    *o += a * b;
+                }
+            }
+        }
+        out
+    }
+}
+
+/// The values of the dense operand of a sparse-dense product as a `rows x n` matrix, with `n` of 1
+/// for a 1-D Tensor, and whether it was 1-D.
+pub(crate) fn dense_operand(tensor: &Tensor, rows: usize) -> (Vec<f64>, usize, bool) {
+    let shape = get_dimension(tensor);
+    let (k, n, vector) = match *shape.as_slice() {
+        [k] => (k, 1, true),
+        [k, n] => (k, n, false),
+        _ => panic!("Sparse matrices can only be multiplied by 1-D or 2-D Tensors!"),
+    };
+    if k != rows {
+        panic!("Inner dimensions do not match for matmul! ({rows} vs {k})");
+    }
+    (flatten_tensor(tensor), n, vector)
+}
+
+/// Checks two sparse matrices have the same shape before an element-wise operation.
+pub(crate) fn expect_same_shape(left: [usize; 2], right: [usize; 2]) {
+    if left != right {
+        panic!("Sparse matrix shapes {left:?} and {right:?} do not match!");
+    }
+}
+
+/// Sums a matrix over one axis or all of it, following [crate::tensor_ops::sum()]. Lines are
+/// columns if `by_column` is set.
+pub(crate) fn sum_matrix(matrix: &Compressed, axis: Option<usize>, by_column: bool) -> Tensor {
+    match (axis, by_column) {
+        (None, _) => Tensor::Element(vec![matrix.values.iter().sum()]),
+        (Some(0), false) | (Some(1), true) => Tensor::Element(matrix.position_sums()),
+        (Some(1), false) | (Some(0), true) => Tensor::Element(matrix.line_sums()),
+        (Some(axis), _) => panic!("Axis {axis} is out of range for a Tensor with 2 dimensions!"),
+    }
+}
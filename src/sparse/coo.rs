@@ -0,0 +1,171 @@
+use crate::Tensor;
+use crate::sparse::{ CscMatrix, CsrMatrix };
+use crate::sparse::compressed::Compressed;
+use crate::tensor_ops::{ build_tensor, flatten_tensor, get_dimension };
+
+/// A sparse matrix in coordinate format: a list of entries, each a row, a column and a value, in
+/// any order. Entries at the same position add up. COO is the format for building a matrix entry
+/// by entry, before converting it to [CsrMatrix] or [CscMatrix] for computation.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::sparse::CooMatrix;
+/// use tensorium::tensor_ops::build_tensor;
+///
+/// let mut m = CooMatrix::new(2, 3);
+/// m.push(1, 2, 3.0);
+/// m.push(0, 0, 1.0);
+/// m.push(1, 2, 0.5);
+///
+/// assert_eq!(m.nnz(), 3);
+/// assert_eq!(m.to_dense(), build_tensor(&[1.0, 0.0, 0.0, 0.0, 0.0, 3.5], &[2, 3]));
+///
+/// // Converting merges the entries at [1, 2]
+/// let csr = m.to_csr();
+/// assert_eq!(csr.nnz(), 2);
+/// assert_eq!(csr.get(1, 2), 3.5);
+/// ```
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct CooMatrix {
+    rows: usize,
+    cols: usize,
+    row_indices: Vec<usize>,
+    col_indices: Vec<usize>,
+    values: Vec<f64>,
+}
+
+impl CooMatrix {
+    /// An empty matrix of the given shape.
+    pub fn new(rows: usize, cols: usize) -> CooMatrix {
+        CooMatrix {
+            rows,
+            cols,
+            row_indices: Vec::new(),
+            col_indices: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Builds a matrix from parallel lists of rows, columns and values.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the lists differ in length or if an entry is out of bounds.
+    pub fn from_triplets(
+        rows: usize,
+        cols: usize,
+        row_indices: Vec<usize>,
+        col_indices: Vec<usize>,
+        values: Vec<f64>
+    ) -> CooMatrix {
+        if row_indices.len() != values.len() || col_indices.len() != values.len() {
+            panic!("A COO matrix needs as many rows and columns as values!");
+        }
+        for (&row, &col) in row_indices.iter().zip(&col_indices) {
+            check_bounds(row, col, rows, cols);
+        }
+        CooMatrix { rows, cols, row_indices, col_indices, values }
+    }
+
+    /// Stores the non-zero values of a 2-D Tensor, in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the Tensor is not 2-D.
+    pub fn from_dense(tensor: &Tensor) -> CooMatrix {
+        let shape = get_dimension(tensor);
+        if shape.len() != 2 {
+            panic!("A COO matrix can only be built from a 2-D Tensor!");
+        }
+
+        let mut coo = CooMatrix::new(shape[0], shape[1]);
+        for (i, &value) in flatten_tensor(tensor).iter().enumerate() {
+            if value != 0.0 {
+                coo.push(i / shape[1], i % shape[1], value);
+            }
+        }
+        coo
+    }
+
+    /// Adds an entry. An entry at a position that already has one adds to it.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the position is out of bounds.
+    pub fn push(&mut self, row: usize, col: usize, value: f64) {
+        check_bounds(row, col, self.rows, self.cols);
+        self.row_indices.push(row);
+        self.col_indices.push(col);
+        self.values.push(value);
+    }
+
+    /// The dense 2-D Tensor with the same values.
+    pub fn to_dense(&self) -> Tensor {
+        let mut data = vec![0.0; self.rows * self.cols];
+        for i in 0..self.nnz() {
+            data[self.row_indices[i] * self.cols + self.col_indices[i]] += self.values[i];
+        }
+        build_tensor(&data, &self.shape())
+    }
+
+    /// The number of rows and columns.
+    pub fn shape(&self) -> [usize; 2] {
+        [self.rows, self.cols]
+    }
+
+    /// The number of entries, counting each entry at a repeated position.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// The row of every entry.
+    pub fn row_indices(&self) -> &[usize] {
+        &self.row_indices
+    }
+
+    /// The column of every entry.
+    pub fn col_indices(&self) -> &[usize] {
+        &self.col_indices
+    }
+
+    /// The value of every entry.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// The same matrix in compressed sparse row format, with repeated positions summed.
+    pub fn to_csr(&self) -> CsrMatrix {
+        let entries: Vec<(usize, usize, f64)> = (0..self.nnz())
+            .map(|i| (self.row_indices[i], self.col_indices[i], self.values[i]))
+            .collect();
+        CsrMatrix { data: Compressed::from_entries(self.rows, self.cols, &entries) }
+    }
+
+    /// The same matrix in compressed sparse column format, with repeated positions summed.
+    pub fn to_csc(&self) -> CscMatrix {
+        let entries: Vec<(usize, usize, f64)> = (0..self.nnz())
+            .map(|i| (self.col_indices[i], self.row_indices[i], self.values[i]))
+            .collect();
+        CscMatrix { data: Compressed::from_entries(self.cols, self.rows, &entries) }
+    }
+
+    /// The transpose, made by swapping the rows and columns of the entries.
+    pub fn transpose(&self) -> CooMatrix {
+        CooMatrix {
+            rows: self.cols,
+            cols: self.rows,
+            row_indices: self.col_indices.clone(),
+            col_indices: self.row_indices.clone(),
+            values: self.values.clone(),
+        }
+    }
+}
+
+fn check_bounds(row: usize, col: usize, rows: usize, cols: usize) {
+    if row >= rows || col >= cols {
+        panic!("Index [{row}, {col}] is out of bounds for shape {:?}!", [rows, cols]);
+    }
+}
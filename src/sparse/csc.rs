@@ -0,0 +1,207 @@
+use crate::Tensor;
+use crate::sparse::{ CooMatrix, CsrMatrix };
+use crate::sparse::compressed::{ dense_operand, expect_same_shape, sum_matrix, Compressed };
+use crate::tensor_ops::build_tensor;
+
+/// A sparse matrix in compressed sparse column format, the column-wise mirror of [CsrMatrix]. The
+/// entries of column `j` are stored at `indptr[j]..indptr[j + 1]` of `indices`, which holds their
+/// rows in increasing order, and `values`. Columns can be read directly, which suits algorithms
+/// that work a column at a time.
+///
+/// Operations that produce a new matrix drop entries that come out exactly zero.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::sparse::CscMatrix;
+/// use tensorium::tensor_ops::build_tensor;
+///
+/// // [[1, 0, 2],
+/// //  [0, 0, 3]]
+/// let m = CscMatrix::new(2, 3, vec![0, 1, 1, 3], vec![0, 0, 1], vec![1.0, 2.0, 3.0]);
+/// assert_eq!(m.to_dense(), build_tensor(&[1.0, 0.0, 2.0, 0.0, 0.0, 3.0], &[2, 3]));
+/// assert_eq!(m.to_csr().indices(), &[0, 2, 2]);
+/// ```
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct CscMatrix {
+    pub(crate) data: Compressed,
+}
+
+impl CscMatrix {
+    /// Builds a matrix from its arrays, which are checked.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `indptr` does not have `cols + 1` increasing entries from 0 to
+    /// the number of entries, if `indices` and `values` differ in length, or if the rows of a
+    /// column are out of bounds or not strictly increasing.
+    pub fn new(
+        rows: usize,
+        cols: usize,
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        values: Vec<f64>
+    ) -> CscMatrix {
+        CscMatrix { data: Compressed::new(cols, rows, indptr, indices, values, "CSC") }
+    }
+
+    /// Stores the non-zero values of a 2-D Tensor.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the Tensor is not 2-D.
+    pub fn from_dense(tensor: &Tensor) -> CscMatrix {
+        CscMatrix { data: Compressed::from_dense(tensor, true, "CSC") }
+    }
+
+    /// The dense 2-D Tensor with the same values.
+    pub fn to_dense(&self) -> Tensor {
+        build_tensor(&self.data.to_dense(true), &self.shape())
+    }
+
+    /// The number of rows and columns.
+    pub fn shape(&self) -> [usize; 2] {
+        [self.data.positions, self.data.lines]
+    }
+
+    /// The number of stored entries.
+    pub fn nnz(&self) -> usize {
+        self.data.nnz()
+    }
+
+    /// Where each column starts in [CscMatrix::indices()] and [CscMatrix::values()], followed by
+    /// the number of entries.
+    pub fn indptr(&self) -> &[usize] {
+        &self.data.indptr
+    }
+
+    /// The row of every entry.
+    pub fn indices(&self) -> &[usize] {
+        &self.data.indices
+    }
+
+    /// The value of every entry.
+    pub fn values(&self) -> &[f64] {
+        &self.data.values
+    }
+
+    /// The value at a row and column, 0 if no entry is stored there.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the row or column is out of bounds.
+    pub fn get(&self, row: usize, col: usize) -> f64 {
+        if row >= self.data.positions || col >= self.data.lines {
+            panic!("Index [{row}, {col}] is out of bounds for shape {:?}!", self.shape());
+        }
+        self.data.get(col, row)
+    }
+
+    /// The same matrix in coordinate format.
+    pub fn to_coo(&self) -> CooMatrix {
+        let mut coo = CooMatrix::new(self.data.positions, self.data.lines);
+        for col in 0..self.data.lines {
+            let (indices, values) = self.data.line(col);
+            for (&row, &value) in indices.iter().zip(values) {
+                coo.push(row, col, value);
+            }
+        }
+        coo
+    }
+
+    /// The same matrix in compressed sparse row format.
+    pub fn to_csr(&self) -> CsrMatrix {
+        CsrMatrix { data: self.data.swap_axes() }
+    }
+
+    /// The transpose, also in CSC format. It takes one pass over the entries.
+    pub fn transpose(&self) -> CscMatrix {
+        CscMatrix { data: self.data.swap_axes() }
+    }
+
+    /// Multiplies by a dense 1-D or 2-D Tensor, following [crate::tensor_ops::matmul()]. The result
+    /// is dense.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the Tensor is not 1-D or 2-D, or if the inner dimensions do not
+    /// match.
+    pub fn matmul_dense(&self, tensor: &Tensor) -> Tensor {
+        let (dense, n, vector) = dense_operand(tensor, self.data.lines);
+        let out = self.data.matmul_positions(&dense, n);
+        if vector {
+            Tensor::Element(out)
+        } else {
+            build_tensor(&out, &[self.data.positions, n])
+        }
+    }
+
+    /// Multiplies two sparse matrices, keeping the result sparse.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the inner dimensions do not match.
+    pub fn matmul(&self, other: &CscMatrix) -> CscMatrix {
+        let [_, k] = self.shape();
+        let [k2, _] = other.shape();
+        if k != k2 {
+            panic!("Inner dimensions do not match for matmul! ({k} vs {k2})");
+        }
+        // Column storage of a matrix is row storage of its transpose, and (AB)^T = B^T A^T
+        CscMatrix { data: other.data.matmul(&self.data) }
+    }
+
+    /// Adds two matrices of the same shape.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the shapes differ.
+    pub fn add(&self, other: &CscMatrix) -> CscMatrix {
+        expect_same_shape(self.shape(), other.shape());
+        CscMatrix { data: self.data.zip(&other.data, true, |a, b| a + b) }
+    }
+
+    /// Subtracts `other` from this matrix.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the shapes differ.
+    pub fn subtract(&self, other: &CscMatrix) -> CscMatrix {
+        expect_same_shape(self.shape(), other.shape());
+        CscMatrix { data: self.data.zip(&other.data, true, |a, b| a - b) }
+    }
+
+    /// Multiplies two matrices of the same shape element by element. Only entries stored in both
+    /// can be non-zero.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the shapes differ.
+    pub fn multiply(&self, other: &CscMatrix) -> CscMatrix {
+        expect_same_shape(self.shape(), other.shape());
+        CscMatrix { data: self.data.zip(&other.data, false, |a, b| a * b) }
+    }
+
+    /// Multiplies every entry by a scalar.
+    pub fn scale(&self, factor: f64) -> CscMatrix {
+        self.map_values(|x| x * factor)
+    }
+
+    /// Applies a function to every stored entry. Entries that are not stored stay 0, so the
+    /// function should map 0 to 0 for the result to match the dense equivalent.
+    pub fn map_values(&self, func: impl Fn(f64) -> f64) -> CscMatrix {
+        CscMatrix { data: self.data.map_values(func) }
+    }
+
+    /// Sums over one axis or the whole matrix, like [crate::tensor_ops::sum()]. The result is
+    /// dense.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the axis is not 0 or 1.
+    pub fn sum(&self, axis: Option<usize>) -> Tensor {
+        sum_matrix(&self.data, axis, true)
+    }
+}
@@ -0,0 +1,217 @@
+use crate::Tensor;
+use crate::sparse::{ CooMatrix, CscMatrix };
+use crate::sparse::compressed::{ dense_operand, expect_same_shape, sum_matrix, Compressed };
+use crate::tensor_ops::build_tensor;
+
+/// A sparse matrix in compressed sparse row format. The entries of row `i` are stored at
+/// `indptr[i]..indptr[i + 1]` of `indices`, which holds their columns in increasing order, and
+/// `values`. Rows can be read directly, which makes CSR the format for products with dense
+/// Tensors and for most arithmetic.
+///
+/// Operations that produce a new matrix drop entries that come out exactly zero.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::sparse::CsrMatrix;
+/// use tensorium::tensor_ops::build_tensor;
+///
+/// // [[1, 0, 2],
+/// //  [0, 0, 3]]
+/// let m = CsrMatrix::new(2, 3, vec![0, 2, 3], vec![0, 2, 2], vec![1.0, 2.0, 3.0]);
+/// assert_eq!(m.to_dense(), build_tensor(&[1.0, 0.0, 2.0, 0.0, 0.0, 3.0], &[2, 3]));
+///
+/// let v = Tensor::Element(vec![1.0, 1.0, 1.0]);
+/// assert_eq!(m.matmul_dense(&v), Tensor::Element(vec![3.0, 3.0]));
+/// ```
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct CsrMatrix {
+    pub(crate) data: Compressed,
+}
+
+impl CsrMatrix {
+    /// Builds a matrix from its arrays, which are checked.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `indptr` does not have `rows + 1` increasing entries from 0 to
+    /// the number of entries, if `indices` and `values` differ in length, or if the columns of a
+    /// row are out of bounds or not strictly increasing.
+    pub fn new(
+        rows: usize,
+        cols: usize,
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        values: Vec<f64>
+    ) -> CsrMatrix {
+        CsrMatrix { data: Compressed::new(rows, cols, indptr, indices, values, "CSR") }
+    }
+
+    /// Stores the non-zero values of a 2-D Tensor.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the Tensor is not 2-D.
+    pub fn from_dense(tensor: &Tensor) -> CsrMatrix {
+        CsrMatrix { data: Compressed::from_dense(tensor, false, "CSR") }
+    }
+
+    /// The dense 2-D Tensor with the same values.
+    pub fn to_dense(&self) -> Tensor {
+        build_tensor(&self.data.to_dense(false), &self.shape())
+    }
+
+    /// The number of rows and columns.
+    pub fn shape(&self) -> [usize; 2] {
+        [self.data.lines, self.data.positions]
+    }
+
+    /// The number of stored entries.
+    pub fn nnz(&self) -> usize {
+        self.data.nnz()
+    }
+
+    /// Where each row starts in [CsrMatrix::indices()] and [CsrMatrix::values()], followed by the
+    /// number of entries.
+    pub fn indptr(&self) -> &[usize] {
+        &self.data.indptr
+    }
+
+    /// The column of every entry.
+    pub fn indices(&self) -> &[usize] {
+        &self.data.indices
+    }
+
+    /// The value of every entry.
+    pub fn values(&self) -> &[f64] {
+        &self.data.values
+    }
+
+    /// The value at a row and column, 0 if no entry is stored there.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the row or column is out of bounds.
+    pub fn get(&self, row: usize, col: usize) -> f64 {
+        if row >= self.data.lines || col >= self.data.positions {
+            panic!("Index [{row}, {col}] is out of bounds for shape {:?}!", self.shape());
+        }
+        self.data.get(row, col)
+    }
+
+    /// The same matrix in coordinate format.
+    pub fn to_coo(&self) -> CooMatrix {
+        let mut coo = CooMatrix::new(self.data.lines, self.data.positions);
+        for row in 0..self.data.lines {
+            let (indices, values) = self.data.line(row);
+            for (&col, &value) in indices.iter().zip(values) {
+                coo.push(row, col, value);
+            }
+        }
+        coo
+    }
+
+    /// The same matrix in compressed sparse column format.
+    pub fn to_csc(&self) -> CscMatrix {
+        CscMatrix { data: self.data.swap_axes() }
+    }
+
+    /// The transpose, also in CSR format. It takes one pass over the entries.
+    pub fn transpose(&self) -> CsrMatrix {
+        CsrMatrix { data: self.data.swap_axes() }
+    }
+
+    /// Multiplies by a dense 1-D or 2-D Tensor, following [crate::tensor_ops::matmul()]. The result
+    /// is dense.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the Tensor is not 1-D or 2-D, or if the inner dimensions do not
+    /// match.
+    pub fn matmul_dense(&self, tensor: &Tensor) -> Tensor {
+        let (dense, n, vector) = dense_operand(tensor, self.data.positions);
+        let out = self.data.matmul_lines(&dense, n);
+        if vector {
+            Tensor::Element(out)
+        } else {
+            build_tensor(&out, &[self.data.lines, n])
+        }
+    }
+
+    /// Multiplies two sparse matrices, keeping the result sparse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tensorium::sparse::CsrMatrix;
+    ///
+    /// let a = CsrMatrix::new(2, 2, vec![0, 1, 2], vec![1, 0], vec![2.0, 3.0]);
+    /// let product = a.matmul(&a);
+    ///
+    /// // a swaps and scales the two coordinates, so its square is diagonal
+    /// assert_eq!(product.indices(), &[0, 1]);
+    /// assert_eq!(product.values(), &[6.0, 6.0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the inner dimensions do not match.
+    pub fn matmul(&self, other: &CsrMatrix) -> CsrMatrix {
+        CsrMatrix { data: self.data.matmul(&other.data) }
+    }
+
+    /// Adds two matrices of the same shape.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the shapes differ.
+    pub fn add(&self, other: &CsrMatrix) -> CsrMatrix {
+        expect_same_shape(self.shape(), other.shape());
+        CsrMatrix { data: self.data.zip(&other.data, true, |a, b| a + b) }
+    }
+
+    /// Subtracts `other` from this matrix.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the shapes differ.
+    pub fn subtract(&self, other: &CsrMatrix) -> CsrMatrix {
+        expect_same_shape(self.shape(), other.shape());
+        CsrMatrix { data: self.data.zip(&other.data, true, |a, b| a - b) }
+    }
+
+    /// Multiplies two matrices of the same shape element by element. Only entries stored in both
+    /// can be non-zero.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the shapes differ.
+    pub fn multiply(&self, other: &CsrMatrix) -> CsrMatrix {
+        expect_same_shape(self.shape(), other.shape());
+        CsrMatrix { data: self.data.zip(&other.data, false, |a, b| a * b) }
+    }
+
+    /// Multiplies every entry by a scalar.
+    pub fn scale(&self, factor: f64) -> CsrMatrix {
+        self.map_values(|x| x * factor)
+    }
+
+    /// Applies a function to every stored entry. Entries that are not stored stay 0, so the
+    /// function should map 0 to 0 for the result to match the dense equivalent.
+    pub fn map_values(&self, func: impl Fn(f64) -> f64) -> CsrMatrix {
+        CsrMatrix { data: self.data.map_values(func) }
+    }
+
+    /// Sums over one axis or the whole matrix, like [crate::tensor_ops::sum()]. The result is
+    /// dense.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the axis is not 0 or 1.
+    pub fn sum(&self, axis: Option<usize>) -> Tensor {
+        sum_matrix(&self.data, axis, false)
+    }
+}
@@ -0,0 +1,75 @@
+//! # Statistics
+//!
+//! Descriptive statistics of Tensors for data analysis, following the functions and arguments of
+//! __numpy__:
+//!
+//! - Order statistics: [median()], [quantile()] and [percentile()], with the [QuantileMethod] used
+//!   between two elements.
+//! - Histograms: [histogram()] and [histogram_bin_edges()], with fixed or estimated [Bins], and
+//!   [bincount()] for integer labels.
+//! - Correlation: [cov()] and [corrcoef()] of the variables of a matrix.
+//! - Running values: [cumsum()], [cumprod()] and [diff()].
+//! - Reductions that skip NaN: [nansum()], [nanmean()], [nanmax()] and [nanmin()].
+//!
+//! Reductions take an axis like [crate::tensor_ops::sum()]: with one, that axis is reduced and
+//! removed from the shape, and without one the whole Tensor is reduced to shape \[1\].
+//!
+//! ## Example
+//!
+//! ```
+//! use tensorium::Tensor;
+//! use tensorium::tensor_ops::build_tensor;
+//! use tensorium::stats::{ median, nanmean, percentile, QuantileMethod };
+//!
+//! // Response times of two servers, the first with a missing reading and a slow outlier
+//! let times = build_tensor(&[
+//!     12.0, 15.0, f64::NAN, 11.0, 90.0,
+//!     30.0, 25.0, 35.0, 28.0, 32.0,
+//! ], &[2, 5]);
+//! assert_eq!(nanmean(&times, Some(1)), Tensor::Element(vec![32.0, 30.0]));
+//!
+//! // Unlike the mean, the median of the first server's readings shrugs off the outlier
+//! let readings = Tensor::Element(vec![12.0, 15.0, 11.0, 90.0]);
+//! assert_eq!(median(&readings, None), Tensor::Element(vec![13.5]));
+//!
+//! let lower = percentile(&readings, 50.0, None, QuantileMethod::Lower);
+//! assert_eq!(lower, Tensor::Element(vec![12.0]));
+//! ```
+
+mod quantiles;
+pub use quantiles::{
+    QuantileMethod,
+    quantile,
+    percentile,
+    median
+};
+
+mod histogram;
+pub use histogram::{
+    Bins,
+    HistogramOptions,
+    histogram,
+    histogram_bin_edges,
+    bincount
+};
+
+mod correlation;
+pub use correlation::{
+    cov,
+    corrcoef
+};
+
+mod cumulative;
+pub use cumulative::{
+    cumsum,
+    cumprod,
+    diff
+};
+
+mod nan_reductions;
+pub use nan_reductions::{
+    nansum,
+    nanmean,
+    nanmax,
+    nanmin
+};
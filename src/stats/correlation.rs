@@ -0,0 +1,111 @@
+use crate::Tensor;
+use crate::parallel;
+use crate::tensor_ops::{ build_tensor, flatten_tensor, get_dimension };
+use crate::tensor_ops::utilities::axis_layout;
+
+/// The covariance matrix of the variables of a 1-D or 2-D Tensor, with the observations along
+/// `axis`. With observations along axis 1, each row is a variable, which is __numpy__'s default
+/// `rowvar=True`. With axis 0, each column is a variable.
+///
+/// Element `[i, j]` of the \[v, v\] result is the sum of the products of the deviations of
+/// variables `i` and `j` from their means, divided by `n - ddof` for `n` observations. A `ddof`
+/// of 1 gives the unbiased estimate, and 0 the maximum likelihood one. A 1-D Tensor is a single
+/// variable, whose variance is returned in a Tensor of shape \[1\].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::stats::cov;
+///
+/// // Two variables, one per row, observed three times
+/// let t = build_tensor(&[0.0, 1.0, 2.0, 2.0, 1.0, 0.0], &[2, 3]);
+/// assert_eq!(cov(&t, 1, 1), build_tensor(&[1.0, -1.0, -1.0, 1.0], &[2, 2]));
+///
+/// let x = Tensor::Element(vec![1.0, 2.0, 3.0, 4.0]);
+/// assert_eq!(cov(&x, 0, 0), Tensor::Element(vec![1.25]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the Tensor is not 1-D or 2-D, or if the axis is out of range.
+pub fn cov(tensor: &Tensor, axis: usize, ddof: usize) -> Tensor {
+    let (variables, observations) = centered_variables(tensor, axis);
+    let count = variables.len() / observations.max(1);
+    let divisor = observations as f64 - ddof as f64;
+
+    let out = parallel::map_collect(count * count, observations, |index| {
+        let (i, j) = (index / count, index % count);
+        let x = &variables[i * observations..(i + 1) * observations];
+        let y = &variables[j * observations..(j + 1) * observations];
+        x.iter().zip(y).map(|(a, b)| a * b).sum::<f64>() / divisor
+    });
+
+    if get_dimension(tensor).len() == 1 {
+        return Tensor::Element(out);
+    }
+    build_tensor(&out, &[count, count])
+}
+
+/// The Pearson correlation coefficients of the variables of a 1-D or 2-D Tensor, with the
+/// observations along `axis` like [cov()]. Element `[i, j]` is the covariance of variables `i`
+/// and `j` divided by both of their standard deviations, between -1 and 1. A variable that never
+/// changes has NaN correlations.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::stats::corrcoef;
+///
+/// // The second column is twice the first, and the third falls as they rise
+/// let t = build_tensor(&[1.0, 2.0, 3.0, 2.0, 4.0, 2.0, 3.0, 6.0, 1.0], &[3, 3]);
+/// let expected = build_tensor(&[1.0, 1.0, -1.0, 1.0, 1.0, -1.0, -1.0, -1.0, 1.0], &[3, 3]);
+/// assert_eq!(corrcoef(&t, 0), expected);
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the Tensor is not 1-D or 2-D, or if the axis is out of range.
+pub fn corrcoef(tensor: &Tensor, axis: usize) -> Tensor {
+    let covariance = cov(tensor, axis, 1);
+    let values = flatten_tensor(&covariance);
+    let count = (values.len() as f64).sqrt() as usize;
+
+    let out: Vec<f64> = (0..values.len())
+        .map(|index| {
+            let (i, j) = (index / count, index % count);
+            let scale = (values[i * count + i] * values[j * count + j]).sqrt();
+            // Rounding can push a perfect correlation just past 1
+            (values[index] / scale).clamp(-1.0, 1.0)
+        })
+        .collect();
+
+    build_tensor(&out, &get_dimension(&covariance))
+}
+
+/// The variables of the Tensor one after another, each less its mean, and the number of
+/// observations of each.
+fn centered_variables(tensor: &Tensor, axis: usize) -> (Vec<f64>, usize) {
+    let shape = get_dimension(tensor);
+    if shape.len() > 2 {
+        panic!("Covariance needs a 1-D or 2-D Tensor, got shape {shape:?}!");
+    }
+    let (_, observations, _) = axis_layout(&shape, axis);
+
+    let values = flatten_tensor(tensor);
+    let mut variables = if axis == 0 && shape.len() == 2 {
+        // Columns are the variables, so transpose them into rows
+        let count = shape[1];
+        (0..values.len()).map(|i| values[(i % observations) * count + i / observations]).collect()
+    } else {
+        values
+    };
+
+    for variable in variables.chunks_mut(observations.max(1)) {
+        let mean = variable.iter().sum::<f64>() / observations as f64;
+        variable.iter_mut().for_each(|x| *x -= mean);
+    }
+    (variables, observations)
+}
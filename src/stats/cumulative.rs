@@ -0,0 +1,111 @@
+use crate::Tensor;
+use crate::tensor_ops::{ flatten_tensor, get_dimension };
+use crate::tensor_ops::utilities::{ apply_along_axis, axis_layout };
+
+/// The running sum of the elements of a Tensor along an axis, keeping the shape. Without an axis
+/// the Tensor is flattened first and the result is 1-D.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::stats::cumsum;
+///
+/// let t = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]);
+///
+/// assert_eq!(cumsum(&t, None), Tensor::Element(vec![1.0, 3.0, 6.0, 10.0, 15.0, 21.0]));
+/// assert_eq!(cumsum(&t, Some(0)), build_tensor(&[1.0, 2.0, 3.0, 5.0, 7.0, 9.0], &[2, 3]));
+/// assert_eq!(cumsum(&t, Some(1)), build_tensor(&[1.0, 3.0, 6.0, 4.0, 9.0, 15.0], &[2, 3]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range.
+pub fn cumsum(tensor: &Tensor, axis: Option<usize>) -> Tensor {
+    scan(tensor, axis, |acc, x| acc + x)
+}
+
+/// The running product of the elements of a Tensor along an axis, or of the flattened Tensor,
+/// like [cumsum()].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::stats::cumprod;
+///
+/// let t = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]);
+///
+/// assert_eq!(cumprod(&t, None), Tensor::Element(vec![1.0, 2.0, 6.0, 24.0, 120.0, 720.0]));
+/// assert_eq!(cumprod(&t, Some(0)), build_tensor(&[1.0, 2.0, 3.0, 4.0, 10.0, 18.0], &[2, 3]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range.
+pub fn cumprod(tensor: &Tensor, axis: Option<usize>) -> Tensor {
+    scan(tensor, axis, |acc, x| acc * x)
+}
+
+/// The `n`th discrete difference along an axis, `out[i] = a[i + 1] - a[i]` applied `n` times,
+/// which shortens the axis by `n`. A difference of order 0 returns the Tensor unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::stats::diff;
+///
+/// let t = Tensor::Element(vec![1.0, 2.0, 4.0, 7.0, 0.0]);
+///
+/// assert_eq!(diff(&t, 1, 0), Tensor::Element(vec![1.0, 2.0, 3.0, -7.0]));
+/// assert_eq!(diff(&t, 2, 0), Tensor::Element(vec![1.0, 1.0, -10.0]));
+///
+/// let t = build_tensor(&[1.0, 3.0, 6.0, 10.0, 0.0, 5.0], &[2, 3]);
+/// assert_eq!(diff(&t, 1, 0), build_tensor(&[9.0, -3.0, -1.0], &[1, 3]));
+/// assert_eq!(diff(&t, 1, 1), build_tensor(&[2.0, 3.0, -10.0, 5.0], &[2, 2]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range or if `n` is not less than its length.
+pub fn diff(tensor: &Tensor, n: usize, axis: usize) -> Tensor {
+    let len = axis_layout(&get_dimension(tensor), axis).1;
+    if n == 0 {
+        return tensor.clone();
+    }
+    if n >= len {
+        panic!("Cannot take {n} differences along an axis of length {len}!");
+    }
+
+    apply_along_axis(tensor, axis, |lane| {
+        let mut values = lane.to_vec();
+        for order in 1..=n {
+            for i in 0..len - order {
+                values[i] = values[i + 1] - values[i];
+            }
+        }
+        values.truncate(len - n);
+        values
+    })
+}
+
+/// Replaces every element along `axis`, or of the flattened Tensor, by the fold of it and the
+/// elements before it.
+fn scan(tensor: &Tensor, axis: Option<usize>, op: impl Fn(f64, f64) -> f64) -> Tensor {
+    let running = |lane: &[f64]| {
+        let mut values = lane.to_vec();
+        for i in 1..values.len() {
+            values[i] = op(values[i - 1], values[i]);
+        }
+        values
+    };
+
+    match axis {
+        Some(axis) => apply_along_axis(tensor, axis, running),
+        None => Tensor::Element(running(&flatten_tensor(tensor))),
+    }
+}
@@ -0,0 +1,267 @@
+use crate::Tensor;
+use crate::stats::QuantileMethod;
+use crate::stats::quantiles::lane_quantile;
+use crate::tensor_ops::{ flatten_tensor, get_dimension };
+
+/// The bins of a [histogram()], given directly or estimated from the data like the string
+/// options of __numpy__'s `histogram_bin_edges`. Estimated bins have equal widths across the
+/// range.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub enum Bins {
+    /// A number of equal-width bins.
+    Count(usize),
+    /// The edges of the bins, in increasing order. The range is ignored.
+    Edges(Vec<f64>),
+    /// Width `range / (log2(n) + 1)`, which suits small, roughly normal samples.
+    Sturges,
+    /// Width `2 * IQR / cbrt(n)`, robust to outliers.
+    FreedmanDiaconis,
+    /// The smaller width of [Bins::Sturges] and [Bins::FreedmanDiaconis], or Sturges when the
+    /// interquartile range is 0.
+    Auto,
+}
+
+impl Default for Bins {
+    fn default() -> Self {
+        Bins::Count(10)
+    }
+}
+
+/// The options of [histogram()] and [histogram_bin_edges()].
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Default)]
+pub struct HistogramOptions {
+    pub bins: Bins,
+    /// The lower and upper edge of the bins, with values outside it left out. Defaults to the
+    /// smallest and largest value, widened by 0.5 on each side if they are equal.
+    pub range: Option<(f64, f64)>,
+    /// Divides the counts so that the histogram integrates to 1 over the range.
+    pub density: bool,
+}
+
+/// The edges of the bins a [histogram()] would use, as a 1-D Tensor one longer than the number
+/// of bins.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::stats::{ histogram_bin_edges, Bins, HistogramOptions };
+///
+/// let t = Tensor::Element(vec![1.0, 2.0, 2.0, 3.0, 5.0]);
+///
+/// let options = HistogramOptions { bins: Bins::Count(2), ..Default::default() };
+/// assert_eq!(histogram_bin_edges(&t, &options), Tensor::Element(vec![1.0, 3.0, 5.0]));
+///
+/// // Bins of width 4 / (log2(5) + 1) = 1.2 need 4 bins to cover the range
+/// let options = HistogramOptions { bins: Bins::Sturges, ..Default::default() };
+/// assert_eq!(histogram_bin_edges(&t, &options), Tensor::Element(vec![1.0, 2.0, 3.0, 4.0, 5.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic for the reasons [histogram()] does.
+pub fn histogram_bin_edges(tensor: &Tensor, options: &HistogramOptions) -> Tensor {
+    let values: Vec<f64> = flatten_tensor(tensor).into_iter().filter(|x| !x.is_nan()).collect();
+    Tensor::Element(bin_edges(&values, options))
+}
+
+/// Counts the elements of a Tensor falling in each bin, returning the counts and the edges of
+/// the bins as 1-D Tensors. Every bin includes its lower edge, and the last one its upper edge
+/// too. All elements are counted together, whatever the shape, and NaN is ignored.
+///
+/// With weights, each element adds its weight to its bin instead of 1. With
+/// [HistogramOptions::density], each count is divided by the total and the width of its bin.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::stats::{ histogram, Bins, HistogramOptions };
+///
+/// let t = build_tensor(&[0.5, 1.0, 1.5, 2.0, 2.5, 9.0], &[2, 3]);
+///
+/// let bins = Bins::Edges(vec![0.0, 1.0, 2.0, 3.0]);
+/// let options = HistogramOptions { bins, ..Default::default() };
+/// let (counts, edges) = histogram(&t, None, &options);
+/// assert_eq!(counts, Tensor::Element(vec![1.0, 2.0, 2.0]));
+/// assert_eq!(edges, Tensor::Element(vec![0.0, 1.0, 2.0, 3.0]));
+///
+/// let weights = build_tensor(&[1.0, 1.0, 1.0, 0.5, 0.5, 1.0], &[2, 3]);
+/// let options = HistogramOptions { density: true, ..options };
+/// let (density, _) = histogram(&t, Some(&weights), &options);
+/// assert_eq!(density, Tensor::Element(vec![0.25, 0.5, 0.25]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the weights have a different shape from the Tensor, if there are
+/// no bins, if the edges are not increasing, or if the range is not finite with its lower edge
+/// at most its upper edge.
+pub fn histogram(
+    tensor: &Tensor,
+    weights: Option<&Tensor>,
+    options: &HistogramOptions
+) -> (Tensor, Tensor) {
+    let data = flatten_tensor(tensor);
+    let weights = weights.map(|weights| checked_weights(tensor, weights));
+    let values: Vec<f64> = data.iter().copied().filter(|x| !x.is_nan()).collect();
+    let edges = bin_edges(&values, options);
+
+    let (first, last) = (edges[0], edges[edges.len() - 1]);
+    let mut counts = vec![0.0; edges.len() - 1];
+    for (i, &x) in data.iter().enumerate() {
+        if !(first..=last).contains(&x) {
+            continue;
+        }
+        // The bins before the first edge above x, with the upper edge counted in the last bin
+        let bin = (edges.partition_point(|&edge| edge <= x) - 1).min(counts.len() - 1);
+        counts[bin] += weights.as_ref().map_or(1.0, |weights| weights[i]);
+    }
+
+    if options.density {
+        let total: f64 = counts.iter().sum();
+        for (count, edge) in counts.iter_mut().zip(edges.windows(2)) {
+            *count /= total * (edge[1] - edge[0]);
+        }
+    }
+
+    (Tensor::Element(counts), Tensor::Element(edges))
+}
+
+/// Counts the occurrences of every integer in a Tensor of non-negative integers, so that element
+/// `i` of the 1-D result is the number of elements equal to `i`. The result has length one more
+/// than the largest value, or at least `min_length`. With weights, each element adds its weight
+/// instead of 1.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::stats::bincount;
+///
+/// let labels = Tensor::Element(vec![0.0, 1.0, 1.0, 3.0, 1.0]);
+/// assert_eq!(bincount(&labels, None, 0), Tensor::Element(vec![1.0, 3.0, 0.0, 1.0]));
+/// assert_eq!(bincount(&labels, None, 6), Tensor::Element(vec![1.0, 3.0, 0.0, 1.0, 0.0, 0.0]));
+///
+/// let weights = Tensor::Element(vec![0.5, 1.0, 2.0, 0.25, 1.0]);
+/// assert_eq!(bincount(&labels, Some(&weights), 0), Tensor::Element(vec![0.5, 4.0, 0.0, 0.25]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if any element is not a non-negative integer, or if the weights
+/// have a different shape from the Tensor.
+pub fn bincount(tensor: &Tensor, weights: Option<&Tensor>, min_length: usize) -> Tensor {
+    let data = flatten_tensor(tensor);
+    let weights = weights.map(|weights| checked_weights(tensor, weights));
+    if let Some(x) = data.iter().find(|x| !(x.fract() == 0.0 && **x >= 0.0)) {
+        panic!("bincount needs non-negative integers, got {x}!");
+    }
+
+    let length = data.iter()
+        .map(|&x| x as usize + 1)
+        .max()
+        .unwrap_or(0)
+        .max(min_length);
+    let mut counts = vec![0.0; length];
+    for (i, &x) in data.iter().enumerate() {
+        counts[x as usize] += weights.as_ref().map_or(1.0, |weights| weights[i]);
+    }
+
+    Tensor::Element(counts)
+}
+
+/// The flattened weights, checked to match the shape of the Tensor.
+fn checked_weights(tensor: &Tensor, weights: &Tensor) -> Vec<f64> {
+    let (shape, weights_shape) = (get_dimension(tensor), get_dimension(weights));
+    if shape != weights_shape {
+        panic!("Weights of shape {weights_shape:?} do not match a Tensor of shape {shape:?}!");
+    }
+    flatten_tensor(weights)
+}
+
+/// The edges of the bins for values without NaN, see [histogram_bin_edges()].
+fn bin_edges(values: &[f64], options: &HistogramOptions) -> Vec<f64> {
+    if let Bins::Edges(edges) = &options.bins {
+        if edges.len() < 2 {
+            panic!("A histogram needs at least one bin!");
+        }
+        let decreasing = edges.windows(2).any(|pair| pair[0] > pair[1]);
+        if decreasing || edges.iter().any(|edge| edge.is_nan()) {
+            panic!("Histogram bin edges must be increasing!");
+        }
+        return edges.clone();
+    }
+
+    let (first, last) = match options.range {
+        Some((first, last)) => {
+            if !(first.is_finite() && last.is_finite() && first <= last) {
+                panic!("Histogram range ({first}, {last}) must be finite with min <= max!");
+            }
+            (first, last)
+        },
+        None if values.is_empty() => (0.0, 1.0),
+        None => {
+            let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            if !(min.is_finite() && max.is_finite()) {
+                panic!("Histogram range ({min}, {max}) must be finite with min <= max!");
+            }
+            (min, max)
+        },
+    };
+    let (first, last) = if first == last { (first - 0.5, last + 0.5) } else { (first, last) };
+
+    let count = match options.bins {
+        Bins::Count(0) => panic!("A histogram needs at least one bin!"),
+        Bins::Count(count) => count,
+        _ => {
+            // The estimators only look at the values inside the range
+            let inside: Vec<f64> = values.iter()
+                .copied()
+                .filter(|x| (first..=last).contains(x))
+                .collect();
+            let width = estimated_width(&inside, &options.bins);
+            if width > 0.0 { ((last - first) / width).ceil() as usize } else { 1 }
+        },
+    };
+
+    let step = (last - first) / count as f64;
+    let mut edges: Vec<f64> = (0..=count).map(|i| first + i as f64 * step).collect();
+    edges[count] = last;
+    edges
+}
+
+/// The bin width chosen by an estimator, or 0 when there is too little data to choose one.
+fn estimated_width(values: &[f64], bins: &Bins) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let n = values.len() as f64;
+
+    let sturges = || {
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        (max - min) / (n.log2() + 1.0)
+    };
+    let freedman_diaconis = || {
+        let quartile = |q| lane_quantile(values, q, QuantileMethod::Linear, "histogram");
+        2.0 * (quartile(0.75) - quartile(0.25)) / n.cbrt()
+    };
+
+    match bins {
+        Bins::Sturges => sturges(),
+        Bins::FreedmanDiaconis => freedman_diaconis(),
+        Bins::Auto => {
+            let width = freedman_diaconis();
+            if width > 0.0 { width.min(sturges()) } else { sturges() }
+        },
+        Bins::Count(_) | Bins::Edges(_) => unreachable!(),
+    }
+}
@@ -0,0 +1,104 @@
+use crate::Tensor;
+use crate::tensor_ops::divide_tensors;
+use crate::tensor_ops::reductions::{ check_not_empty, reduce };
+
+/// Sums the elements of a Tensor like [crate::tensor_ops::sum()], treating NaN as 0.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::stats::nansum;
+///
+/// let t = build_tensor(&[1.0, f64::NAN, 3.0, 4.0, 5.0, 6.0], &[2, 3]);
+///
+/// assert_eq!(nansum(&t, None), Tensor::Element(vec![19.0]));
+/// assert_eq!(nansum(&t, Some(0)), Tensor::Element(vec![5.0, 5.0, 9.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range.
+pub fn nansum(tensor: &Tensor, axis: Option<usize>) -> Tensor {
+    reduce(tensor, axis, 0.0, |acc, x| if x.is_nan() { acc } else { acc + x }, |acc, x| acc + x)
+}
+
+/// The mean of the elements of a Tensor that are not NaN, like [crate::tensor_ops::mean()]. A lane
+/// with no such elements gives NaN.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::stats::nanmean;
+///
+/// let t = build_tensor(&[1.0, f64::NAN, 3.0, 4.0, 5.0, 6.0], &[2, 3]);
+///
+/// assert_eq!(nanmean(&t, None), Tensor::Element(vec![3.8]));
+/// assert_eq!(nanmean(&t, Some(1)), Tensor::Element(vec![2.0, 5.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range.
+pub fn nanmean(tensor: &Tensor, axis: Option<usize>) -> Tensor {
+    let count = reduce(
+        tensor,
+        axis,
+        0.0,
+        |acc, x| if x.is_nan() { acc } else { acc + 1.0 },
+        |acc, x| acc + x
+    );
+    divide_tensors(&nansum(tensor, axis), &count)
+}
+
+/// The largest element of a Tensor, like [crate::tensor_ops::max()], ignoring NaN. A lane with
+/// only NaN gives NaN.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::stats::nanmax;
+///
+/// let t = build_tensor(&[1.0, f64::NAN, 3.0, 4.0, f64::NAN, 2.0], &[2, 3]);
+///
+/// assert_eq!(nanmax(&t, None), Tensor::Element(vec![4.0]));
+/// assert_eq!(nanmax(&t, Some(1)), Tensor::Element(vec![3.0, 4.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range or the Tensor has no elements to reduce.
+pub fn nanmax(tensor: &Tensor, axis: Option<usize>) -> Tensor {
+    check_not_empty(tensor, axis, "nanmax");
+    // f64::max returns the other operand when one is NaN, so NaN stays only if nothing else came
+    reduce(tensor, axis, f64::NAN, f64::max, f64::max)
+}
+
+/// The smallest element of a Tensor, like [crate::tensor_ops::min()], ignoring NaN. A lane with
+/// only NaN gives NaN.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::stats::nanmin;
+///
+/// let t = build_tensor(&[1.0, f64::NAN, 3.0, 4.0, f64::NAN, 2.0], &[2, 3]);
+///
+/// assert_eq!(nanmin(&t, None), Tensor::Element(vec![1.0]));
+/// assert_eq!(nanmin(&t, Some(1)), Tensor::Element(vec![1.0, 2.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range or the Tensor has no elements to reduce.
+pub fn nanmin(tensor: &Tensor, axis: Option<usize>) -> Tensor {
+    check_not_empty(tensor, axis, "nanmin");
+    reduce(tensor, axis, f64::NAN, f64::min, f64::min)
+}
@@ -0,0 +1,159 @@
+use crate::Tensor;
+use crate::tensor_ops::flatten_tensor;
+use crate::tensor_ops::utilities::reduce_along_axis;
+
+/// How [quantile()] picks a value when the quantile falls between two sorted elements `i < j`,
+/// matching the `method` argument of __numpy__'s `quantile`.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+pub enum QuantileMethod {
+    /// Interpolates linearly between `i` and `j`.
+    #[default]
+    Linear,
+    /// Element `i`.
+    Lower,
+    /// Element `j`.
+    Higher,
+    /// Whichever of `i` and `j` is closer, with ties going to the even index.
+    Nearest,
+    /// The mean of `i` and `j`.
+    Midpoint,
+}
+
+/// The `q`th quantile of the elements of a Tensor, where `q` is between 0 and 1. With an axis,
+/// that axis is reduced and removed from the shape like [crate::tensor_ops::sum()], and without
+/// one the quantile of every element is returned in a Tensor of shape \[1\].
+///
+/// The quantile sits at position `q * (n - 1)` of the `n` sorted values, and `method` decides
+/// what happens between two of them. Each lane is partially sorted in `O(n)` time. NaN is
+/// propagated, so a lane holding NaN gives NaN.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::stats::{ quantile, QuantileMethod };
+///
+/// let t = build_tensor(&[1.0, 4.0, 2.0, 3.0, 10.0, 30.0, 20.0, 40.0], &[2, 4]);
+///
+/// // Position 0.25 * 3 = 0.75, between the sorted values 1 and 2
+/// let first = quantile(&t, 0.25, Some(1), QuantileMethod::Linear);
+/// assert_eq!(first, Tensor::Element(vec![1.75, 17.5]));
+///
+/// let lower = quantile(&t, 0.25, Some(1), QuantileMethod::Lower);
+/// assert_eq!(lower, Tensor::Element(vec![1.0, 10.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if `q` is not between 0 and 1, if the axis is out of range, or if
+/// there are no elements to reduce.
+pub fn quantile(tensor: &Tensor, q: f64, axis: Option<usize>, method: QuantileMethod) -> Tensor {
+    if !(0.0..=1.0).contains(&q) {
+        panic!("Quantile {q} must be between 0 and 1!");
+    }
+    reduce_lanes(tensor, axis, |lane| lane_quantile(lane, q, method, "quantile"))
+}
+
+/// The `p`th percentile of the elements of a Tensor, where `p` is between 0 and 100, see
+/// [quantile()].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::stats::{ percentile, QuantileMethod };
+///
+/// let t = Tensor::Element(vec![15.0, 20.0, 35.0, 40.0, 50.0]);
+///
+/// assert_eq!(percentile(&t, 40.0, None, QuantileMethod::Linear), Tensor::Element(vec![29.0]));
+/// assert_eq!(percentile(&t, 40.0, None, QuantileMethod::Nearest), Tensor::Element(vec![35.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if `p` is not between 0 and 100, if the axis is out of range, or if
+/// there are no elements to reduce.
+pub fn percentile(tensor: &Tensor, p: f64, axis: Option<usize>, method: QuantileMethod) -> Tensor {
+    if !(0.0..=100.0).contains(&p) {
+        panic!("Percentile {p} must be between 0 and 100!");
+    }
+    reduce_lanes(tensor, axis, |lane| lane_quantile(lane, p / 100.0, method, "percentile"))
+}
+
+/// The median of the elements of a Tensor, over one axis or the whole Tensor like [quantile()].
+/// An even number of elements gives the mean of the middle two.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::stats::median;
+///
+/// let t = build_tensor(&[3.0, 1.0, 2.0, 6.0, 4.0, 5.0], &[2, 3]);
+///
+/// assert_eq!(median(&t, None), Tensor::Element(vec![3.5]));
+/// assert_eq!(median(&t, Some(0)), Tensor::Element(vec![4.5, 2.5, 3.5]));
+/// assert_eq!(median(&t, Some(1)), Tensor::Element(vec![2.0, 5.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range or there are no elements to reduce.
+pub fn median(tensor: &Tensor, axis: Option<usize>) -> Tensor {
+    reduce_lanes(tensor, axis, |lane| lane_quantile(lane, 0.5, QuantileMethod::Linear, "median"))
+}
+
+/// Reduces every lane along `axis`, or all elements, with `func`.
+fn reduce_lanes(tensor: &Tensor, axis: Option<usize>, func: impl FnMut(&[f64]) -> f64) -> Tensor {
+    match axis {
+        Some(axis) => reduce_along_axis(tensor, axis, func),
+        None => {
+            let mut func = func;
+            Tensor::Element(vec![func(&flatten_tensor(tensor))])
+        },
+    }
+}
+
+/// The `q`th quantile of unsorted values, see [quantile()]. `name` is the statistic reported when
+/// there are no values.
+pub(super) fn lane_quantile(values: &[f64], q: f64, method: QuantileMethod, name: &str) -> f64 {
+    let Some(last) = values.len().checked_sub(1) else {
+        panic!("Cannot take the {name} of an empty Tensor!");
+    };
+    if values.iter().any(|x| x.is_nan()) {
+        return f64::NAN;
+    }
+
+    let position = q * last as f64;
+    let below = (position.floor() as usize).min(last);
+    let fraction = position - below as f64;
+
+    let mut values = values.to_vec();
+    let (_, &mut low, rest) = values.select_nth_unstable_by(below, f64::total_cmp);
+    if fraction == 0.0 {
+        return low;
+    }
+    // Everything after the partition point is at least `low`, so the next value is its minimum
+    let high = rest.iter().copied().fold(f64::INFINITY, f64::min);
+
+    match method {
+        QuantileMethod::Linear => lerp(low, high, fraction),
+        QuantileMethod::Lower => low,
+        QuantileMethod::Higher => high,
+        QuantileMethod::Nearest => {
+            if fraction < 0.5 || (fraction == 0.5 && below.is_multiple_of(2)) { low } else { high }
+        },
+        QuantileMethod::Midpoint => (low + high) / 2.0,
+    }
+}
+
+/// Linear interpolation, computed from the nearer end so that the result stays between `a` and
+/// `b` and is exact at both ends.
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    if t < 0.5 { a + (b - a) * t } else { b - (b - a) * (1.0 - t) }
+}
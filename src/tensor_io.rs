@@ -0,0 +1,107 @@
+//! # Tensor IO
+//!
+//! Reading and writing Tensors in the file formats used to exchange data with other tools. Every
+//! function returns a [std::io::Result], with [std::io::ErrorKind::InvalidData] used when a file
+//! does not follow its format. Tensors always hold `f64`, so data stored as another type is
+//! converted when it is read. Tensors are written as `f64` unless a [DType] is given to one of the
+//! `_as` functions, such as [save_safetensors_as()] to store weights as `F16` or `BF16`.
+//!
+//! ## NumPy
+//!
+//! [save_npy()] and [load_npy()] read and write single arrays in the `.npy` format, and
+//! [save_npz()] and [load_npz()] read and write archives of named arrays in the `.npz` format.
+//! The same functions are available as [crate::Tensor::save_npy()] and [crate::Tensor::load_npy()].
+//!
+//! ```
+//! use tensorium::Tensor;
+//!
+//! let t = Tensor::Element(vec![1.0, 2.0, 3.0]);
+//!
+//! let path = std::env::temp_dir().join("tensorium_tensor_io_doc.npy");
+//! t.save_npy(&path).unwrap();
+//! assert_eq!(Tensor::load_npy(&path).unwrap(), t);
+//! # std::fs::remove_file(&path).unwrap();
+//! ```
+//!
+//! Arrays too large to load can be memory-mapped with [crate::MmapTensor::open_npy()], and raw
+//! binary files of any [DType] with [crate::MmapTensor::open_raw()]. Both are `unsafe`, as the
+//! file must not change while it is mapped.
+//!
+//! ## safetensors
+//!
+//! [save_safetensors()] and [load_safetensors()] read and write named Tensors in the `.safetensors`
+//! format used to share model weights, and [load_safetensors_metadata()] reads the free-form
+//! metadata stored alongside them.
+//!
+//! ## Delimited text
+//!
+//! [read_csv()] and [write_csv()] read and write 2-D Tensors as CSV or other delimited text,
+//! configured by [CsvReadOptions] and [CsvWriteOptions].
+//!
+//! ## Arrow
+//!
+//! [read_arrow()] and [write_arrow()] convert between 2-D Tensors and the columns of Arrow IPC
+//! streams and files, as produced by `pyarrow` and other Arrow implementations.
+//!
+//! ## Checkpoints
+//!
+//! [save_checkpoint()] and [load_checkpoint()] read and write the full state of a training run as
+//! a [Checkpoint], in tensorium's own versioned format with a checksum per Tensor.
+//! [CheckpointWriter] writes the same format one Tensor at a time. Each Tensor records its own
+//! [DType], so [save_checkpoint_as()] can store weights as `f16` or `bf16`.
+
+pub(crate) mod utilities;
+
+mod dtype;
+pub use dtype::DType;
+
+pub(crate) mod zip;
+pub(crate) mod json;
+pub(crate) mod flatbuffers;
+
+pub(crate) mod npy;
+pub use npy::{
+    save_npy,
+    save_npy_as,
+    load_npy
+};
+
+mod npz;
+pub use npz::{
+    save_npz,
+    load_npz
+};
+
+mod safetensors;
+pub use safetensors::{
+    save_safetensors,
+    save_safetensors_as,
+    load_safetensors,
+    load_safetensors_metadata
+};
+
+mod csv;
+pub use csv::{
+    CsvReadOptions,
+    CsvWriteOptions,
+    FloatFormat,
+    read_csv,
+    write_csv
+};
+
+mod checkpoint;
+pub use checkpoint::{
+    Checkpoint,
+    CheckpointWriter,
+    save_checkpoint,
+    save_checkpoint_as,
+    load_checkpoint
+};
+
+mod arrow;
+pub use arrow::{
+    ArrowFormat,
+    read_arrow,
+    write_arrow,
+    write_arrow_as
+};
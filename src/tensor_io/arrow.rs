@@ -0,0 +1,533 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use crate::Tensor;
+use crate::tensor_io::dtype::DType;
+use crate::tensor_io::flatbuffers::{ Builder, Field, Table };
+use crate::tensor_io::utilities::invalid_data;
+use crate::tensor_ops::{ build_tensor, flatten_tensor, get_dimension };
+
+/// The magic string at the start and end of the file format, padded to 8 bytes at the start.
+const FILE_MAGIC: &[u8] = b"ARROW1";
+
+const CONTINUATION: u32 = 0xFFFF_FFFF;
+
+/// `MetadataVersion.V5`, the current version of the metadata.
+const METADATA_VERSION: i16 = 4;
+
+// `MessageHeader` union tags.
+const HEADER_SCHEMA: u8 = 1;
+const HEADER_DICTIONARY_BATCH: u8 = 2;
+const HEADER_RECORD_BATCH: u8 = 3;
+
+// `Type` union tags.
+const TYPE_INT: u8 = 2;
+const TYPE_FLOATING_POINT: u8 = 3;
+
+// `Precision` values of `FloatingPoint`.
+const PRECISION_HALF: i16 = 0;
+const PRECISION_SINGLE: i16 = 1;
+const PRECISION_DOUBLE: i16 = 2;
+
+/// The two layouts of the Arrow IPC format.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum ArrowFormat {
+    /// The streaming format, usually with the `.arrows` extension.
+    Stream,
+    /// The file or random access format, usually with the `.arrow` or `.feather` extension.
+    File,
+}
+
+/// Reads an Arrow IPC file or stream into a 2-D Tensor of shape \[rows, columns\], along with the
+/// column names. The format is detected from the contents. Every record batch is read and their
+/// rows are concatenated.
+///
+/// Columns may be signed or unsigned integers of 8 to 64 bits, `Float16`, `Float32` or `Float64`,
+/// and null values are read as NaN.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::tensor_io::{ read_arrow, write_arrow, ArrowFormat };
+///
+/// let t = build_tensor(&[1.0, 10.0, 2.0, 20.0, 3.0, 30.0], &[3, 2]);
+///
+/// let path = std::env::temp_dir().join("tensorium_read_arrow_doc.arrow");
+/// write_arrow(&t, &["x", "y"], &path, ArrowFormat::File).unwrap();
+///
+/// let (names, columns) = read_arrow(&path).unwrap();
+/// assert_eq!(names, ["x", "y"]);
+/// assert_eq!(columns, t);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, is not Arrow IPC, holds no rows, or holds a column
+/// of another type, a dictionary encoded column, or a compressed record batch.
+pub fn read_arrow(path: impl AsRef<Path>) -> io::Result<(Vec<String>, Tensor)> {
+    let bytes = fs::read(path)?;
+
+    let messages = if bytes.starts_with(FILE_MAGIC) {
+        file_messages(&bytes)?
+    } else {
+        stream_messages(&bytes, 0)?
+    };
+
+    let mut messages = messages.into_iter();
+    let (schema, _) = messages.next().ok_or_else(|| invalid_data("The Arrow data holds no schema"))?;
+    let header = message_header(schema, HEADER_SCHEMA, "a schema")?;
+    let (names, columns) = read_schema(&header)?;
+
+    let mut values: Vec<Vec<f64>> = vec![Vec::new(); columns.len()];
+    for (metadata, body) in messages {
+        let message = Table::root(metadata)?;
+        match message.u8(1, 0)? {
+            HEADER_RECORD_BATCH => {
+                let batch = message.table(2)?.ok_or_else(|| invalid_data("The Arrow record batch is empty"))?;
+                read_record_batch(&batch, body, &names, &columns, &mut values)?;
+            },
+            HEADER_DICTIONARY_BATCH => return Err(invalid_data("Dictionary encoded Arrow columns are not supported")),
+            _ => {},
+        }
+    }
+
+    let rows = values.first().map_or(0, |column| column.len());
+    if rows == 0 {
+        return Err(invalid_data("The Arrow data holds no rows"));
+    }
+
+    let mut data = Vec::with_capacity(rows * columns.len());
+    for row in 0..rows {
+        for column in &values {
+            data.push(column[row]);
+        }
+    }
+
+    Ok((names, build_tensor(&data, &[rows, columns.len()])))
+}
+
+/// Writes a 1-D or 2-D Tensor as a single Arrow record batch of `Float64` columns, one per column
+/// of the Tensor. A 1-D Tensor is written as a single column. The names must match the number of
+/// columns; if empty, columns are named `column_0`, `column_1` and so on.
+///
+/// # Errors
+///
+/// Returns an error of kind [io::ErrorKind::InvalidInput] if the Tensor has more than 2
+/// dimensions or the number of names is wrong, or any error from writing the file.
+pub fn write_arrow(tensor: &Tensor, names: &[&str], path: impl AsRef<Path>, format: ArrowFormat) -> io::Result<()> {
+    write_arrow_as(tensor, names, path, format, DType::F64)
+}
+
+/// Writes a 1-D or 2-D Tensor like [write_arrow()], as `Float16`, `Float32` or `Float64` columns
+/// for a `dtype` of `F16`, `F32` or `F64`. Values are rounded as described by [DType].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_io::{ read_arrow, write_arrow_as, ArrowFormat, DType };
+///
+/// let t = Tensor::Element(vec![0.1, 2.0, -1024.5]);
+///
+/// let path = std::env::temp_dir().join("tensorium_write_arrow_as_doc.arrows");
+/// write_arrow_as(&t, &["x"], &path, ArrowFormat::Stream, DType::F16).unwrap();
+///
+/// let (_, columns) = read_arrow(&path).unwrap();
+/// assert_eq!(columns, Tensor::Array(vec![
+///     Tensor::Element(vec![0.0999755859375]),
+///     Tensor::Element(vec![2.0]),
+///     Tensor::Element(vec![-1024.0])
+/// ]));
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error of kind [io::ErrorKind::InvalidInput] for any other `dtype`, or for the
+/// reasons [write_arrow()] does.
+pub fn write_arrow_as(
+    tensor: &Tensor,
+    names: &[&str],
+    path: impl AsRef<Path>,
+    format: ArrowFormat,
+    dtype: DType
+) -> io::Result<()> {
+    let precision = match dtype {
+        DType::F16 => PRECISION_HALF,
+        DType::F32 => PRECISION_SINGLE,
+        DType::F64 => PRECISION_DOUBLE,
+        _ => return Err(invalid_input(format!("write_arrow_as cannot write {dtype:?} columns"))),
+    };
+
+    let shape = get_dimension(tensor);
+    let (rows, columns) = match shape[..] {
+        [rows] => (rows, 1),
+        [rows, columns] => (rows, columns),
+        _ => return Err(invalid_input(format!("write_arrow requires a 1-D or 2-D Tensor, got shape {shape:?}"))),
+    };
+
+    let names: Vec<String> = if names.is_empty() {
+        (0..columns).map(|c| format!("column_{c}")).collect()
+    } else if names.len() == columns {
+        names.iter().map(|name| name.to_string()).collect()
+    } else {
+        return Err(invalid_input(format!("write_arrow got {} names for {columns} columns", names.len())));
+    };
+
+    // The body holds each column's values back to back, each padded to 8 bytes. Columns have no
+    // nulls, so their validity buffers are empty.
+    let values = flatten_tensor(tensor);
+    let column_len = rows * dtype.size();
+    let mut body = Vec::with_capacity(column_len.next_multiple_of(8) * columns);
+    for column in 0..columns {
+        for row in 0..rows {
+            dtype.encode(values[row * columns + column], &mut body);
+        }
+        body.resize(body.len().next_multiple_of(8), 0);
+    }
+
+    let schema = encode_message(HEADER_SCHEMA, 0, |builder| encode_schema(builder, &names, precision));
+    let batch = encode_message(HEADER_RECORD_BATCH, body.len(), |builder| {
+        encode_record_batch(builder, rows, columns, column_len)
+    });
+
+    let mut out = Vec::new();
+    if format == ArrowFormat::File {
+        out.extend_from_slice(FILE_MAGIC);
+        out.extend_from_slice(&[0, 0]);
+    }
+
+    write_message(&mut out, &schema, &[]);
+    let batch_offset = out.len();
+    write_message(&mut out, &batch, &body);
+    let batch_metadata_len = out.len() - batch_offset - body.len();
+
+    // End of stream marker
+    out.extend_from_slice(&CONTINUATION.to_le_bytes());
+    out.extend_from_slice(&0u32.to_le_bytes());
+
+    if format == ArrowFormat::File {
+        let mut builder = Builder::new();
+        let schema = encode_schema(&mut builder, &names, precision);
+        let dictionaries = builder.vector_of_structs(&[], 0);
+
+        // struct Block { offset: long; metaDataLength: int; bodyLength: long; }
+        let mut block = Vec::with_capacity(24);
+        block.extend_from_slice(&(batch_offset as i64).to_le_bytes());
+        block.extend_from_slice(&(batch_metadata_len as i32).to_le_bytes());
+        block.extend_from_slice(&[0; 4]);
+        block.extend_from_slice(&(body.len() as i64).to_le_bytes());
+        let batches = builder.vector_of_structs(&block, 1);
+
+        let footer = builder.table(&[
+            (0, Field::I16(METADATA_VERSION)),
+            (1, Field::Offset(schema)),
+            (2, Field::Offset(dictionaries)),
+            (3, Field::Offset(batches)),
+        ]);
+        let footer = builder.finish(footer);
+
+        out.extend_from_slice(&footer);
+        out.extend_from_slice(&(footer.len() as i32).to_le_bytes());
+        out.extend_from_slice(FILE_MAGIC);
+    }
+
+    fs::write(path, out)
+}
+
+/// The element type of a column.
+#[derive(Clone, Copy)]
+struct Column {
+    dtype: DType,
+    little_endian: bool,
+}
+
+/// The metadata and body of a message, and the position right after it.
+type RawMessage<'a> = (&'a [u8], &'a [u8], usize);
+
+fn invalid_input(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+/// Splits an IPC stream starting at `pos` into the metadata and body of each message.
+fn stream_messages(bytes: &[u8], mut pos: usize) -> io::Result<Vec<(&[u8], &[u8])>> {
+    let mut messages = Vec::new();
+    while let Some((metadata, body, next)) = next_message(bytes, pos)? {
+        messages.push((metadata, body));
+        pos = next;
+    }
+    Ok(messages)
+}
+
+/// Reads the message at `pos`, returning its metadata, body and the position after it, or `None`
+/// at the end of the stream.
+fn next_message(bytes: &[u8], mut pos: usize) -> io::Result<Option<RawMessage<'_>>> {
+    // A stream may end without the end of stream marker.
+    if pos >= bytes.len() {
+        return Ok(None);
+    }
+
+    let read_u32 = |pos: usize| -> io::Result<u32> {
+        let word = bytes.get(pos..pos + 4).ok_or_else(|| invalid_data("The Arrow stream is truncated"))?;
+        Ok(u32::from_le_bytes(word.try_into().unwrap()))
+    };
+
+    // Streams from before Arrow 0.15 have no continuation marker.
+    let mut length = read_u32(pos)?;
+    pos += 4;
+    if length == CONTINUATION {
+        length = read_u32(pos)?;
+        pos += 4;
+    }
+    if length == 0 {
+        return Ok(None);
+    }
+
+    message_at(bytes, pos, length as usize).map(Some)
+}
+
+/// The metadata of `length` bytes at `pos` and the body that follows it, plus the position after.
+fn message_at(bytes: &[u8], pos: usize, length: usize) -> io::Result<RawMessage<'_>> {
+    let truncated = || invalid_data("The Arrow data is truncated");
+
+    let metadata = bytes.get(pos..pos + length).ok_or_else(truncated)?;
+    let body_length = Table::root(metadata)?.i64(3, 0)?;
+    let body_start = pos + length;
+    let body_end = usize::try_from(body_length).ok()
+        .and_then(|length| body_start.checked_add(length))
+        .ok_or_else(|| invalid_data("The Arrow message has an invalid body length"))?;
+    let body = bytes.get(body_start..body_end).ok_or_else(truncated)?;
+
+    Ok((metadata, body, body_end))
+}
+
+/// Finds the messages of the file format through its footer.
+fn file_messages(bytes: &[u8]) -> io::Result<Vec<(&[u8], &[u8])>> {
+    if bytes.len() < 18 || !bytes.ends_with(FILE_MAGIC) {
+        return Err(invalid_data("The Arrow file is truncated: missing the closing magic string"));
+    }
+    let footer_len_pos = bytes.len() - FILE_MAGIC.len() - 4;
+    let footer_len = u32::from_le_bytes(bytes[footer_len_pos..footer_len_pos + 4].try_into().unwrap()) as usize;
+    let footer = footer_len_pos.checked_sub(footer_len)
+        .map(|start| &bytes[start..footer_len_pos])
+        .ok_or_else(|| invalid_data("The Arrow file footer is larger than the file"))?;
+    let footer = Table::root(footer)?;
+
+    // The schema message is not listed in the footer, it is the first message after the magic.
+    let (schema, _, _) = next_message(bytes, 8)?.ok_or_else(|| invalid_data("The Arrow file holds no schema"))?;
+    let mut messages = vec![(schema, &[][..])];
+
+    if let Some(batches) = footer.vector(3)? {
+        for index in 0..batches.len {
+            let block = batches.struct_bytes(index, 24)?;
+            let offset = i64::from_le_bytes(block[0..8].try_into().unwrap());
+            let metadata_len = i32::from_le_bytes(block[8..12].try_into().unwrap());
+            let offset = usize::try_from(offset).map_err(|_| invalid_data("The Arrow file has a negative block offset"))?;
+
+            // The block length counts the length prefix and padding, the metadata itself follows
+            // the continuation marker and the length.
+            let prefix = match bytes.get(offset..offset + 4) {
+                Some(word) if word == CONTINUATION.to_le_bytes() => 8,
+                Some(_) => 4,
+                None => return Err(invalid_data("The Arrow file has a block past its end")),
+            };
+            let length = (metadata_len as usize).checked_sub(prefix)
+                .ok_or_else(|| invalid_data("The Arrow file has an invalid block length"))?;
+            let (metadata, body, _) = message_at(bytes, offset + prefix, length)?;
+            messages.push((metadata, body));
+        }
+    }
+
+    Ok(messages)
+}
+
+/// The header table of a message, which must be of the given kind.
+fn message_header<'a>(metadata: &'a [u8], kind: u8, name: &str) -> io::Result<Table<'a>> {
+    let message = Table::root(metadata)?;
+    if message.u8(1, 0)? != kind {
+        return Err(invalid_data(format!("Expected {name} message in the Arrow data")));
+    }
+    message.table(2)?.ok_or_else(|| invalid_data(format!("The Arrow {name} message has no header")))
+}
+
+fn read_schema(schema: &Table) -> io::Result<(Vec<String>, Vec<Column>)> {
+    // Endianness: 0 is little and 1 is big
+    let little_endian = schema.i16(0, 0)? == 0;
+    let fields = schema.vector(1)?.ok_or_else(|| invalid_data("The Arrow schema has no fields"))?;
+
+    let mut names = Vec::with_capacity(fields.len);
+    let mut columns = Vec::with_capacity(fields.len);
+    for index in 0..fields.len {
+        let field = fields.table(index)?;
+        let name = field.string(0)?.unwrap_or_default();
+        if field.table(4)?.is_some() {
+            return Err(invalid_data(format!("Arrow column '{name}' is dictionary encoded, which is not supported")));
+        }
+
+        let kind = field.table(3)?;
+        let dtype = match (field.u8(2, 0)?, kind) {
+            (TYPE_INT, Some(int)) => match (int.i32(0, 0)?, int.bool(1, false)?) {
+                (8, true) => DType::I8,
+                (16, true) => DType::I16,
+                (32, true) => DType::I32,
+                (64, true) => DType::I64,
+                (8, false) => DType::U8,
+                (16, false) => DType::U16,
+                (32, false) => DType::U32,
+                (64, false) => DType::U64,
+                (bits, _) => return Err(invalid_data(format!("Arrow column '{name}' has an invalid {bits} bit integer type"))),
+            },
+            (TYPE_FLOATING_POINT, Some(float)) => match float.i16(0, 0)? {
+                PRECISION_HALF => DType::F16,
+                PRECISION_SINGLE => DType::F32,
+                PRECISION_DOUBLE => DType::F64,
+                precision => return Err(invalid_data(format!("Arrow column '{name}' has unknown float precision {precision}"))),
+            },
+            (kind, _) => return Err(invalid_data(format!(
+                "Arrow column '{name}' has type {kind}, only integer and floating point columns are supported"
+            ))),
+        };
+
+        names.push(name);
+        columns.push(Column { dtype, little_endian });
+    }
+
+    Ok((names, columns))
+}
+
+fn read_record_batch(
+    batch: &Table,
+    body: &[u8],
+    names: &[String],
+    columns: &[Column],
+    values: &mut [Vec<f64>]
+) -> io::Result<()> {
+    if batch.table(3)?.is_some() {
+        return Err(invalid_data("Compressed Arrow record batches are not supported"));
+    }
+
+    let rows = usize::try_from(batch.i64(0, 0)?).map_err(|_| invalid_data("The Arrow record batch has a negative length"))?;
+    let nodes = batch.vector(1)?.ok_or_else(|| invalid_data("The Arrow record batch has no field nodes"))?;
+    let buffers = batch.vector(2)?.ok_or_else(|| invalid_data("The Arrow record batch has no buffers"))?;
+    if nodes.len != columns.len() || buffers.len != 2 * columns.len() {
+        return Err(invalid_data("The Arrow record batch does not match the schema"));
+    }
+
+    // struct Buffer { offset: long; length: long; }, relative to the start of the body
+    let buffer = |index: usize| -> io::Result<&[u8]> {
+        let bytes = buffers.struct_bytes(index, 16)?;
+        let offset = i64::from_le_bytes(bytes[0..8].try_into().unwrap());
+        let length = i64::from_le_bytes(bytes[8..16].try_into().unwrap());
+        usize::try_from(offset).ok()
+            .zip(usize::try_from(length).ok())
+            .and_then(|(offset, length)| body.get(offset..offset.checked_add(length)?))
+            .ok_or_else(|| invalid_data("An Arrow buffer lies outside the message body"))
+    };
+
+    for (c, column) in columns.iter().enumerate() {
+        // struct FieldNode { length: long; null_count: long; }
+        let node = nodes.struct_bytes(c, 16)?;
+        let null_count = i64::from_le_bytes(node[8..16].try_into().unwrap());
+        if null_count < 0 {
+            let message = format!("Arrow column '{}' has a negative null count", names[c]);
+            return Err(invalid_data(message));
+        }
+
+        let validity = buffer(2 * c)?;
+        let data = buffer(2 * c + 1)?;
+        let size = column.dtype.size();
+        let needed = rows.checked_mul(size)
+            .ok_or_else(|| invalid_data(format!("Arrow batch length {rows} is too large")))?;
+        if data.len() < needed || (null_count > 0 && validity.len() < rows.div_ceil(8)) {
+            return Err(invalid_data(format!("Arrow column '{}' holds less data than its length requires", names[c])));
+        }
+
+        for row in 0..rows {
+            let valid = null_count == 0 || validity[row / 8] >> (row % 8) & 1 == 1;
+            values[c].push(if valid {
+                column.dtype.decode(&data[row * size..(row + 1) * size], column.little_endian)
+            } else {
+                f64::NAN
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds a complete `Message` whose header is created by `header`.
+fn encode_message(kind: u8, body_len: usize, header: impl FnOnce(&mut Builder) -> usize) -> Vec<u8> {
+    let mut builder = Builder::new();
+    let header = header(&mut builder);
+    let message = builder.table(&[
+        (0, Field::I16(METADATA_VERSION)),
+        (1, Field::U8(kind)),
+        (2, Field::Offset(header)),
+        (3, Field::I64(body_len as i64)),
+    ]);
+    builder.finish(message)
+}
+
+/// Builds a `Schema` of non-nullable `FloatingPoint` columns of the given precision.
+fn encode_schema(builder: &mut Builder, names: &[String], precision: i16) -> usize {
+    let fields: Vec<usize> = names.iter()
+        .map(|name| {
+            let name = builder.string(name);
+            let float = builder.table(&[(0, Field::I16(precision))]);
+            let children = builder.vector_of_offsets(&[]);
+            builder.table(&[
+                (0, Field::Offset(name)),
+                (1, Field::U8(0)),
+                (2, Field::U8(TYPE_FLOATING_POINT)),
+                (3, Field::Offset(float)),
+                (5, Field::Offset(children)),
+            ])
+        })
+        .collect();
+
+    let fields = builder.vector_of_offsets(&fields);
+    builder.table(&[(0, Field::I16(0)), (1, Field::Offset(fields))])
+}
+
+/// Builds a `RecordBatch` for `columns` columns of `rows` values, each `column_len` bytes long
+/// and laid out back to back with padding to 8 bytes.
+fn encode_record_batch(builder: &mut Builder, rows: usize, columns: usize, column_len: usize) -> usize {
+    let stride = column_len.next_multiple_of(8) as i64;
+    let column_len = column_len as i64;
+
+    let mut nodes = Vec::with_capacity(16 * columns);
+    let mut buffers = Vec::with_capacity(32 * columns);
+    for c in 0..columns {
+        nodes.extend_from_slice(&(rows as i64).to_le_bytes());
+        nodes.extend_from_slice(&0i64.to_le_bytes());
+
+        let offset = c as i64 * stride;
+        for (offset, length) in [(offset, 0), (offset, column_len)] {
+            buffers.extend_from_slice(&offset.to_le_bytes());
+            buffers.extend_from_slice(&length.to_le_bytes());
+        }
+    }
+
+    let nodes = builder.vector_of_structs(&nodes, columns);
+    let buffers = builder.vector_of_structs(&buffers, 2 * columns);
+    builder.table(&[
+        (0, Field::I64(rows as i64)),
+        (1, Field::Offset(nodes)),
+        (2, Field::Offset(buffers)),
+    ])
+}
+
+/// Appends an encapsulated message: the continuation marker, the padded metadata length, the
+/// metadata padded to 8 bytes, then the body.
+fn write_message(out: &mut Vec<u8>, metadata: &[u8], body: &[u8]) {
+    let padded = metadata.len().div_ceil(8) * 8;
+    out.extend_from_slice(&CONTINUATION.to_le_bytes());
+    out.extend_from_slice(&(padded as u32).to_le_bytes());
+    out.extend_from_slice(metadata);
+    out.extend(std::iter::repeat_n(0, padded - metadata.len()));
+    out.extend_from_slice(body);
+}
@@ -0,0 +1,538 @@
+use std::collections::{ HashMap, HashSet };
+use std::fs::File;
+use std::io::{ self, BufReader, BufWriter, Read, Write };
+use std::path::Path;
+use crate::Tensor;
+use crate::random::Rng;
+use crate::tensor_io::DType;
+use crate::tensor_io::utilities::{ crc32_update, invalid_data };
+use crate::tensor_ops::{ build_tensor, get_dimension };
+
+// A checkpoint is the magic string, the format version and minimum reader version as u32, then a
+// sequence of records, each a u32 tag, a u64 payload length and the payload. An end record with
+// tag 0 closes the file so truncation is detected. Everything is little-endian.
+const MAGIC: &[u8; 8] = b"TNSMCKPT";
+
+/// The version of the format written by this build. Readers accept any file whose minimum reader
+/// version is at most this, skipping records they do not know.
+const FORMAT_VERSION: u32 = 2;
+
+/// The oldest reader version able to read the files this build writes. Version 2 added the dtype
+/// of each tensor record, which version 1 readers would misread as data.
+const MIN_READER_VERSION: u32 = 2;
+
+const TAG_END: u32 = 0;
+const TAG_TENSOR: u32 = 1;
+const TAG_OPTIMIZER_TENSOR: u32 = 2;
+const TAG_RNG: u32 = 3;
+const TAG_STEP: u32 = 4;
+const TAG_METADATA: u32 = 5;
+
+/// The full state of a training run, read and written by [crate::tensor_io::load_checkpoint()] and
+/// [crate::tensor_io::save_checkpoint()].
+///
+/// Optimizer state is kept as named Tensors, such as the moment estimates of Adam keyed by the
+/// parameter they belong to. Scalars such as a learning rate can be stored as Tensors of shape
+/// \[1\] or as metadata.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Default)]
+pub struct Checkpoint {
+    /// The model parameters and buffers.
+    pub tensors: HashMap<String, Tensor>,
+    /// The optimizer state, kept apart from the model so names may repeat between the two.
+    pub optimizer_state: HashMap<String, Tensor>,
+    /// The random number generator, to resume the exact same sequence of values.
+    pub rng: Option<Rng>,
+    /// The number of training steps taken.
+    pub step: u64,
+    /// Free-form key/value pairs, such as hyperparameters or a run name.
+    pub metadata: HashMap<String, String>,
+}
+
+/// Writes a [Checkpoint] to a file. Entries are written in order of name so the same checkpoint
+/// always gives the same file. See [CheckpointWriter] to write a checkpoint one Tensor at a time.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::random::Rng;
+/// use tensorium::tensor_io::{ Checkpoint, save_checkpoint, load_checkpoint };
+///
+/// let mut checkpoint = Checkpoint::default();
+/// checkpoint.tensors.insert(String::from("weight"), Tensor::Element(vec![0.5, -0.5]));
+/// checkpoint.optimizer_state.insert(String::from("weight.momentum"), Tensor::Element(vec![0.0, 0.1]));
+/// checkpoint.rng = Some(Rng::new(7));
+/// checkpoint.step = 1000;
+/// checkpoint.metadata.insert(String::from("lr"), String::from("0.001"));
+///
+/// let path = std::env::temp_dir().join("tensorium_save_checkpoint_doc.ckpt");
+/// save_checkpoint(&checkpoint, &path).unwrap();
+/// assert_eq!(load_checkpoint(&path).unwrap(), checkpoint);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn save_checkpoint(checkpoint: &Checkpoint, path: impl AsRef<Path>) -> io::Result<()> {
+    save_checkpoint_as(checkpoint, path, DType::F64)
+}
+
+/// Writes a [Checkpoint] like [save_checkpoint()], with the values of every model and optimizer
+/// Tensor stored as `dtype`. Storing weights as `F16` or `BF16` quarters the size of the file at
+/// the cost of rounding them.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_io::{ Checkpoint, DType, save_checkpoint_as, load_checkpoint };
+///
+/// let mut checkpoint = Checkpoint::default();
+/// checkpoint.tensors.insert(String::from("weight"), Tensor::Element(vec![0.5, 0.1]));
+///
+/// let path = std::env::temp_dir().join("tensorium_save_checkpoint_as_doc.ckpt");
+/// save_checkpoint_as(&checkpoint, &path, DType::BF16).unwrap();
+/// let loaded = load_checkpoint(&path).unwrap();
+/// assert_eq!(loaded.tensors["weight"], Tensor::Element(vec![0.5, 0.10009765625]));
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error of kind [io::ErrorKind::InvalidInput] for a `dtype` other than `F64`, `F32`,
+/// `F16` or `BF16`, or an error if the file cannot be written.
+pub fn save_checkpoint_as(
+    checkpoint: &Checkpoint,
+    path: impl AsRef<Path>,
+    dtype: DType
+) -> io::Result<()> {
+    dtype_code(dtype)?;
+    let mut writer = CheckpointWriter::create(path)?;
+
+    writer.write_step(checkpoint.step)?;
+    if let Some(rng) = &checkpoint.rng {
+        writer.write_rng(rng)?;
+    }
+    for (key, value) in sorted(&checkpoint.metadata) {
+        writer.write_metadata(key, value)?;
+    }
+    for (name, tensor) in sorted(&checkpoint.tensors) {
+        writer.write_tensor_as(name, tensor, dtype)?;
+    }
+    for (name, tensor) in sorted(&checkpoint.optimizer_state) {
+        writer.write_optimizer_tensor_as(name, tensor, dtype)?;
+    }
+
+    writer.finish()?;
+    Ok(())
+}
+
+/// Reads a checkpoint written by [crate::tensor_io::save_checkpoint()] or [CheckpointWriter].
+/// Files written by newer versions of tensorium are read as long as they do not require a newer
+/// reader, ignoring any kind of entry this version does not know and any fields appended to the
+/// entries it does.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, is not a checkpoint, requires a newer reader, is
+/// truncated, holds the same name twice, or holds a Tensor whose checksum does not match its data.
+pub fn load_checkpoint(path: impl AsRef<Path>) -> io::Result<Checkpoint> {
+    let mut reader = BufReader::new(File::open(path)?);
+
+    let mut magic = [0u8; 8];
+    read_exact(&mut reader, &mut magic)?;
+    if &magic != MAGIC {
+        return Err(invalid_data("Not a tensorium checkpoint: missing magic string"));
+    }
+    let version = read_u32(&mut reader)?;
+    let min_reader_version = read_u32(&mut reader)?;
+    if min_reader_version > FORMAT_VERSION {
+        return Err(invalid_data(format!(
+            "The checkpoint was written by format version {version} and needs reader version {min_reader_version}, but this build reads up to version {FORMAT_VERSION}"
+        )));
+    }
+
+    let mut checkpoint = Checkpoint::default();
+    loop {
+        let tag = read_u32(&mut reader)?;
+        let length = read_u64(&mut reader)?;
+        if tag == TAG_END {
+            break;
+        }
+
+        // Every record is read through a reader limited to its length so a malformed record cannot
+        // run into the next one, and unknown records are skipped whole.
+        let mut record = (&mut reader).take(length);
+        match tag {
+            TAG_TENSOR | TAG_OPTIMIZER_TENSOR => {
+                let (name, tensor) = read_tensor_record(&mut record, version)?;
+                let map = if tag == TAG_TENSOR { &mut checkpoint.tensors } else { &mut checkpoint.optimizer_state };
+                if map.insert(name.clone(), tensor).is_some() {
+                    return Err(invalid_data(format!("The checkpoint holds tensor '{name}' more than once")));
+                }
+            },
+            TAG_RNG => {
+                let mut state = [0u64; 4];
+                for word in state.iter_mut() {
+                    *word = read_u64(&mut record)?;
+                }
+                if state.iter().all(|&word| word == 0) {
+                    return Err(invalid_data("The checkpoint holds an all-zero Rng state"));
+                }
+                checkpoint.rng = Some(Rng::from_state(state));
+            },
+            TAG_STEP => checkpoint.step = read_u64(&mut record)?,
+            TAG_METADATA => {
+                let key = read_string(&mut record)?;
+                let value = read_string(&mut record)?;
+                checkpoint.metadata.insert(key, value);
+            },
+            _ => {},
+        }
+
+        // Skip whatever is left: all of an unknown record, or fields a newer version appended to a
+        // known one.
+        io::copy(&mut record, &mut io::sink())?;
+        if record.limit() != 0 {
+            return Err(invalid_data("The checkpoint is truncated"));
+        }
+    }
+
+    Ok(checkpoint)
+}
+
+/// Writes a checkpoint entry by entry, so a checkpoint larger than memory can be written as its
+/// Tensors are produced. Each Tensor is written row by row without building a flattened copy.
+/// The checkpoint is only valid once [CheckpointWriter::finish()] has been called.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_io::{ CheckpointWriter, load_checkpoint };
+///
+/// let path = std::env::temp_dir().join("tensorium_checkpoint_writer_doc.ckpt");
+///
+/// let mut writer = CheckpointWriter::create(&path).unwrap();
+/// writer.write_step(10).unwrap();
+/// for layer in 0..3 {
+///     let weight = Tensor::Element(vec![layer as f64; 4]);
+///     writer.write_tensor(&format!("layer{layer}.weight"), &weight).unwrap();
+/// }
+/// writer.finish().unwrap();
+///
+/// let checkpoint = load_checkpoint(&path).unwrap();
+/// assert_eq!(checkpoint.step, 10);
+/// assert_eq!(checkpoint.tensors.len(), 3);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct CheckpointWriter<W: Write> {
+    writer: W,
+    tensor_names: HashSet<String>,
+    optimizer_names: HashSet<String>,
+}
+
+impl CheckpointWriter<BufWriter<File>> {
+    /// Creates a checkpoint file, replacing any existing file, and writes its header.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<CheckpointWriter<BufWriter<File>>> {
+        CheckpointWriter::new(BufWriter::new(File::create(path)?))
+    }
+}
+
+impl<W: Write> CheckpointWriter<W> {
+    /// Starts a checkpoint on any writer by writing the header.
+    pub fn new(mut writer: W) -> io::Result<CheckpointWriter<W>> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        writer.write_all(&MIN_READER_VERSION.to_le_bytes())?;
+
+        Ok(CheckpointWriter { writer, tensor_names: HashSet::new(), optimizer_names: HashSet::new() })
+    }
+
+    /// Writes a model Tensor.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [io::ErrorKind::InvalidInput] if a model Tensor of the same name was
+    /// already written or the name is longer than `u32::MAX` bytes, or any error from the
+    /// underlying writer.
+    pub fn write_tensor(&mut self, name: &str, tensor: &Tensor) -> io::Result<()> {
+        self.write_tensor_as(name, tensor, DType::F64)
+    }
+
+    /// Writes a model Tensor with its values stored as `dtype`, which is one of `F64`, `F32`, `F16`
+    /// or `BF16`. Loading the checkpoint converts the values back to `f64`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [io::ErrorKind::InvalidInput] for any other `dtype`, or for the
+    /// reasons [CheckpointWriter::write_tensor()] does.
+    pub fn write_tensor_as(&mut self, name: &str, tensor: &Tensor, dtype: DType) -> io::Result<()> {
+        let code = dtype_code(dtype)?;
+        if !self.tensor_names.insert(name.to_string()) {
+            return Err(duplicate_name(name));
+        }
+        write_tensor_record(&mut self.writer, TAG_TENSOR, name, tensor, dtype, code)
+    }
+
+    /// Writes an optimizer state Tensor.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [io::ErrorKind::InvalidInput] if an optimizer Tensor of the same
+    /// name was already written or the name is longer than `u32::MAX` bytes, or any error from the
+    /// underlying writer.
+    pub fn write_optimizer_tensor(&mut self, name: &str, tensor: &Tensor) -> io::Result<()> {
+        self.write_optimizer_tensor_as(name, tensor, DType::F64)
+    }
+
+    /// Writes an optimizer state Tensor with its values stored as `dtype`, like
+    /// [CheckpointWriter::write_tensor_as()].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [io::ErrorKind::InvalidInput] for a `dtype` other than `F64`,
+    /// `F32`, `F16` or `BF16`, or for the reasons [CheckpointWriter::write_optimizer_tensor()]
+    /// does.
+    pub fn write_optimizer_tensor_as(
+        &mut self,
+        name: &str,
+        tensor: &Tensor,
+        dtype: DType
+    ) -> io::Result<()> {
+        let code = dtype_code(dtype)?;
+        if !self.optimizer_names.insert(name.to_string()) {
+            return Err(duplicate_name(name));
+        }
+        write_tensor_record(&mut self.writer, TAG_OPTIMIZER_TENSOR, name, tensor, dtype, code)
+    }
+
+    /// Writes the state of a random number generator. If written more than once the last wins.
+    pub fn write_rng(&mut self, rng: &Rng) -> io::Result<()> {
+        write_record_header(&mut self.writer, TAG_RNG, 32)?;
+        for word in rng.state() {
+            self.writer.write_all(&word.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Writes the step counter. If written more than once the last wins.
+    pub fn write_step(&mut self, step: u64) -> io::Result<()> {
+        write_record_header(&mut self.writer, TAG_STEP, 8)?;
+        self.writer.write_all(&step.to_le_bytes())
+    }
+
+    /// Writes one metadata entry. If a key is written more than once the last wins.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [io::ErrorKind::InvalidInput] if the key or value is longer than
+    /// `u32::MAX` bytes, or any error from the underlying writer.
+    pub fn write_metadata(&mut self, key: &str, value: &str) -> io::Result<()> {
+        let key_length = length_u32(key.len(), "metadata key")?;
+        let value_length = length_u32(value.len(), "metadata value")?;
+
+        write_record_header(&mut self.writer, TAG_METADATA, (8 + key.len() + value.len()) as u64)?;
+        self.writer.write_all(&key_length.to_le_bytes())?;
+        self.writer.write_all(key.as_bytes())?;
+        self.writer.write_all(&value_length.to_le_bytes())?;
+        self.writer.write_all(value.as_bytes())
+    }
+
+    /// Marks the end of the checkpoint, flushes, and returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        write_record_header(&mut self.writer, TAG_END, 0)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+fn sorted<T>(map: &HashMap<String, T>) -> Vec<(&String, &T)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+fn duplicate_name(name: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, format!("Tensor '{name}' was already written to the checkpoint"))
+}
+
+fn write_record_header(writer: &mut impl Write, tag: u32, length: u64) -> io::Result<()> {
+    writer.write_all(&tag.to_le_bytes())?;
+    writer.write_all(&length.to_le_bytes())
+}
+
+/// Converts a length stored as a u32, checked before anything of the record is written so a
+/// failure leaves no partial record behind.
+fn length_u32(length: usize, what: &str) -> io::Result<u32> {
+    u32::try_from(length).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("A checkpoint {what} of length {length} does not fit in a u32")
+        )
+    })
+}
+
+/// The code stored in a tensor record for each dtype a checkpoint can hold.
+fn dtype_code(dtype: DType) -> io::Result<u8> {
+    match dtype {
+        DType::F64 => Ok(0),
+        DType::F32 => Ok(1),
+        DType::F16 => Ok(2),
+        DType::BF16 => Ok(3),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Checkpoints store F64, F32, F16 or BF16 Tensors, not {dtype:?}")
+        )),
+    }
+}
+
+fn dtype_from_code(code: u8) -> Option<DType> {
+    match code {
+        0 => Some(DType::F64),
+        1 => Some(DType::F32),
+        2 => Some(DType::F16),
+        3 => Some(DType::BF16),
+        _ => None,
+    }
+}
+
+/// A tensor record is the name, the number of dimensions, each dimension, the dtype code as a u8,
+/// the data in row-major order, and finally a CRC-32 of everything before it in the record.
+/// Records of format version 1 have no dtype code and always hold `f64` data.
+fn write_tensor_record(
+    writer: &mut impl Write,
+    tag: u32,
+    name: &str,
+    tensor: &Tensor,
+    dtype: DType,
+    code: u8
+) -> io::Result<()> {
+    let shape = get_dimension(tensor);
+    let name_length = length_u32(name.len(), "tensor name")?;
+    let rank = length_u32(shape.len(), "tensor rank")?;
+    let count: usize = shape.iter().product();
+    let length = 4 + name.len() + 4 + 8 * shape.len() + 1 + dtype.size() * count + 4;
+    write_record_header(writer, tag, length as u64)?;
+
+    let mut crc = 0;
+    let mut write = |bytes: &[u8]| -> io::Result<()> {
+        crc = crc32_update(crc, bytes);
+        writer.write_all(bytes)
+    };
+
+    write(&name_length.to_le_bytes())?;
+    write(name.as_bytes())?;
+    write(&rank.to_le_bytes())?;
+    for dim in &shape {
+        write(&(*dim as u64).to_le_bytes())?;
+    }
+    write(&[code])?;
+    write_leaves(tensor, dtype, &mut write)?;
+
+    writer.write_all(&crc.to_le_bytes())
+}
+
+/// Writes the values of every `Element` in order, which is the row-major order of the Tensor.
+fn write_leaves(
+    tensor: &Tensor,
+    dtype: DType,
+    write: &mut impl FnMut(&[u8]) -> io::Result<()>
+) -> io::Result<()> {
+    match tensor {
+        Tensor::Array(children) => {
+            children.iter().try_for_each(|child| write_leaves(child, dtype, write))
+        },
+        Tensor::Element(values) => {
+            let mut bytes = Vec::with_capacity(values.len() * dtype.size());
+            for &value in values {
+                dtype.encode(value, &mut bytes);
+            }
+            write(&bytes)
+        },
+    }
+}
+
+fn read_tensor_record(
+    reader: &mut io::Take<impl Read>,
+    version: u32
+) -> io::Result<(String, Tensor)> {
+    let mut crc = 0;
+    let mut read = |count: usize| -> io::Result<Vec<u8>> {
+        // Checked before allocating so a corrupt length cannot request a huge buffer
+        if count as u64 > reader.limit() {
+            return Err(invalid_data("A checkpoint tensor record is shorter than its contents require"));
+        }
+        let mut bytes = vec![0u8; count];
+        read_exact(reader, &mut bytes)?;
+        crc = crc32_update(crc, &bytes);
+        Ok(bytes)
+    };
+
+    let name_len = u32::from_le_bytes(read(4)?.try_into().unwrap()) as usize;
+    let name = String::from_utf8(read(name_len)?)
+        .map_err(|_| invalid_data("A checkpoint tensor name is not valid utf-8"))?;
+    let ndim = u32::from_le_bytes(read(4)?.try_into().unwrap()) as usize;
+
+    let mut shape = Vec::with_capacity(ndim.min(64));
+    for _ in 0..ndim {
+        shape.push(u64::from_le_bytes(read(8)?.try_into().unwrap()) as usize);
+    }
+    let dtype = if version < 2 {
+        DType::F64
+    } else {
+        let code = read(1)?[0];
+        dtype_from_code(code).ok_or_else(|| {
+            invalid_data(format!("Checkpoint tensor '{name}' has unknown dtype code {code}"))
+        })?
+    };
+    let size = shape.iter()
+        .try_fold(dtype.size(), |acc, &d| acc.checked_mul(d))
+        .filter(|_| !shape.is_empty())
+        .ok_or_else(|| invalid_data(format!("Checkpoint tensor '{name}' has an invalid shape {shape:?}")))?;
+
+    let values = dtype.decode_all(&read(size)?, true);
+
+    let expected = read_u32(reader)?;
+    if crc != expected {
+        return Err(invalid_data(format!(
+            "Checksum mismatch for checkpoint tensor '{name}': stored {expected:08x}, computed {crc:08x}"
+        )));
+    }
+
+    Ok((name, build_tensor(&values, &shape)))
+}
+
+fn read_exact(reader: &mut impl Read, bytes: &mut [u8]) -> io::Result<()> {
+    reader.read_exact(bytes).map_err(|error| match error.kind() {
+        io::ErrorKind::UnexpectedEof => invalid_data("The checkpoint is truncated"),
+        _ => error,
+    })
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    read_exact(reader, &mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0u8; 8];
+    read_exact(reader, &mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_string(reader: &mut io::Take<impl Read>) -> io::Result<String> {
+    let length = read_u32(reader)?;
+    // Checked before allocating, like the contents of a tensor record
+    if length as u64 > reader.limit() {
+        return Err(invalid_data("A checkpoint string is longer than its record"));
+    }
+    let mut bytes = vec![0u8; length as usize];
+    read_exact(reader, &mut bytes)?;
+    String::from_utf8(bytes).map_err(|_| invalid_data("A checkpoint string is not valid utf-8"))
+}
@@ -0,0 +1,284 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use crate::Tensor;
+use crate::tensor_io::utilities::invalid_data;
+use crate::tensor_ops::{ build_tensor, flatten_tensor, get_dimension };
+
+/// Options for [crate::tensor_io::read_csv()]. The defaults read comma separated values with no
+/// header, every column, `#` comments and empty fields read as NaN.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct CsvReadOptions {
+    /// The character separating fields. With a whitespace delimiter such as `' '` or `'\t'`, runs
+    /// of whitespace count as a single separator.
+    pub delimiter: char,
+    /// The number of lines skipped at the start of the file, such as a header, before comments or
+    /// blank lines are considered.
+    pub skip_rows: usize,
+    /// The columns to read, in the order they appear in the result. `None` reads every column.
+    pub columns: Option<Vec<usize>>,
+    /// The value of empty fields.
+    pub missing_value: f64,
+    /// Everything from this character to the end of the line is ignored. Lines that are empty
+    /// after removing the comment are skipped.
+    pub comment: Option<char>,
+}
+
+impl Default for CsvReadOptions {
+    fn default() -> Self {
+        CsvReadOptions {
+            delimiter: ',',
+            skip_rows: 0,
+            columns: None,
+            missing_value: f64::NAN,
+            comment: Some('#'),
+        }
+    }
+}
+
+/// How [crate::tensor_io::write_csv()] formats values.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum FloatFormat {
+    /// The shortest text that reads back as the same value, such as `0.1` or `3`.
+    Shortest,
+    /// A fixed number of digits after the decimal point, such as `0.100` for 3.
+    Fixed(usize),
+    /// Scientific notation with a fixed number of digits after the decimal point, such as
+    /// `1.00e-1` for 2.
+    Scientific(usize),
+}
+
+/// Options for [crate::tensor_io::write_csv()]. The defaults write comma separated values in the
+/// shortest exact format with no header.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct CsvWriteOptions {
+    /// The text separating fields.
+    pub delimiter: String,
+    /// A line written before the data, as is.
+    pub header: Option<String>,
+    /// How every value is formatted.
+    pub float_format: FloatFormat,
+}
+
+impl Default for CsvWriteOptions {
+    fn default() -> Self {
+        CsvWriteOptions {
+            delimiter: String::from(","),
+            header: None,
+            float_format: FloatFormat::Shortest,
+        }
+    }
+}
+
+/// Reads delimited text into a 2-D Tensor of shape \[rows, columns\], similar to __numpy__'s
+/// `loadtxt`. Fields may be surrounded by double quotes, and surrounding whitespace is ignored.
+/// `nan` and `inf` are read as their floating point values.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::tensor_io::{ read_csv, CsvReadOptions };
+///
+/// let path = std::env::temp_dir().join("tensorium_read_csv_doc.csv");
+/// std::fs::write(&path, "x,y,label\n1.5,2,0\n# a comment\n3,,1\n").unwrap();
+///
+/// let options = CsvReadOptions {
+///     skip_rows: 1,
+///     columns: Some(vec![0, 1]),
+///     missing_value: -1.0,
+///     ..Default::default()
+/// };
+///
+/// let t = read_csv(&path, &options).unwrap();
+/// assert_eq!(t, build_tensor(&[1.5, 2.0, 3.0, -1.0], &[2, 2]));
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, holds no data rows, has rows of different lengths,
+/// selects a column that does not exist, or holds a field that is not a number. Errors name the
+/// line and column at fault.
+pub fn read_csv(path: impl AsRef<Path>, options: &CsvReadOptions) -> io::Result<Tensor> {
+    let text = fs::read_to_string(path)?;
+
+    let mut values = Vec::new();
+    let mut rows = 0;
+    let mut width: Option<usize> = None;
+
+    for (index, line) in text.lines().enumerate().skip(options.skip_rows) {
+        let line_number = index + 1;
+        let fields = split_fields(line, options.delimiter, options.comment)
+            .ok_or_else(|| invalid_data(format!("Line {line_number}: unterminated quoted field")))?;
+        if fields.is_empty() {
+            continue;
+        }
+
+        match width {
+            None => width = Some(fields.len()),
+            Some(width) if width != fields.len() => {
+                return Err(invalid_data(format!(
+                    "Line {line_number}: expected {width} fields like the first row but found {}",
+                    fields.len()
+                )));
+            },
+            Some(_) => {},
+        }
+
+        let parse = |column: usize| -> io::Result<f64> {
+            let field = fields.get(column).ok_or_else(|| invalid_data(format!(
+                "Line {line_number}: column {column} was selected but the row only has {} fields",
+                fields.len()
+            )))?;
+            if field.is_empty() {
+                return Ok(options.missing_value);
+            }
+            field.parse().map_err(|_| invalid_data(format!(
+                "Line {line_number}, column {column}: cannot parse '{field}' as a number"
+            )))
+        };
+
+        match &options.columns {
+            Some(columns) => {
+                for &column in columns {
+                    values.push(parse(column)?);
+                }
+            },
+            None => {
+                for column in 0..fields.len() {
+                    values.push(parse(column)?);
+                }
+            },
+        }
+        rows += 1;
+    }
+
+    if rows == 0 {
+        return Err(invalid_data("The file holds no data rows"));
+    }
+
+    Ok(build_tensor(&values, &[rows, values.len() / rows]))
+}
+
+/// Writes a 1-D or 2-D Tensor as delimited text, similar to __numpy__'s `savetxt`. Each row of a
+/// 2-D Tensor becomes a line, and a 1-D Tensor is written as a single column.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::build_tensor;
+/// use tensorium::tensor_io::{ write_csv, CsvWriteOptions, FloatFormat };
+///
+/// let t = build_tensor(&[1.0, 0.25, -3.0, 10.0], &[2, 2]);
+/// let options = CsvWriteOptions {
+///     header: Some(String::from("a,b")),
+///     float_format: FloatFormat::Fixed(2),
+///     ..Default::default()
+/// };
+///
+/// let path = std::env::temp_dir().join("tensorium_write_csv_doc.csv");
+/// write_csv(&t, &path, &options).unwrap();
+/// assert_eq!(std::fs::read_to_string(&path).unwrap(), "a,b\n1.00,0.25\n-3.00,10.00\n");
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error of kind [io::ErrorKind::InvalidInput] if the Tensor has more than 2
+/// dimensions, or any error from writing the file.
+pub fn write_csv(tensor: &Tensor, path: impl AsRef<Path>, options: &CsvWriteOptions) -> io::Result<()> {
+    let shape = get_dimension(tensor);
+    let columns = match shape.len() {
+        1 => 1,
+        2 => shape[1],
+        _ => return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("write_csv requires a 1-D or 2-D Tensor, got shape {shape:?}")
+        )),
+    };
+
+    let mut out = String::new();
+    if let Some(header) = &options.header {
+        out.push_str(header);
+        out.push('\n');
+    }
+
+    let values = flatten_tensor(tensor);
+    for row in values.chunks(columns.max(1)) {
+        let fields: Vec<String> = row.iter().map(|&v| format_value(v, options.float_format)).collect();
+        out.push_str(&fields.join(&options.delimiter));
+        out.push('\n');
+    }
+
+    fs::write(path, out)
+}
+
+fn format_value(value: f64, format: FloatFormat) -> String {
+    match format {
+        FloatFormat::Shortest => value.to_string(),
+        FloatFormat::Fixed(digits) => format!("{value:.digits$}"),
+        FloatFormat::Scientific(digits) => format!("{value:.digits$e}"),
+    }
+}
+
+/// Splits a line into trimmed fields, removing double quotes around a field and unescaping `""`
+/// inside one. The line ends at a comment character outside quotes, and a line with nothing before
+/// its comment has no fields. Returns `None` if a quote is left open.
+fn split_fields(line: &str, delimiter: char, comment: Option<char>) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    // Whether the current field was quoted, so `""` is kept as an empty field
+    let mut quoted = false;
+    let mut blank = true;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if !in_quotes && Some(c) == comment {
+            break;
+        }
+        blank &= c.is_whitespace();
+
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                },
+                '"' => in_quotes = false,
+                c => field.push(c),
+            }
+        } else if c == '"' && field.trim().is_empty() {
+            field.clear();
+            in_quotes = true;
+            quoted = true;
+        } else if c == delimiter {
+            // Collapse runs of whitespace, which would otherwise read as empty fields
+            if !(delimiter.is_whitespace() && field.trim().is_empty() && !quoted) {
+                fields.push(field.trim().to_string());
+            }
+            field.clear();
+            quoted = false;
+        } else {
+            field.push(c);
+        }
+    }
+
+    if in_quotes {
+        return None;
+    }
+    if blank {
+        return Some(Vec::new());
+    }
+    if !(delimiter.is_whitespace() && field.trim().is_empty() && !quoted) {
+        fields.push(field.trim().to_string());
+    }
+    Some(fields)
+}
@@ -0,0 +1,86 @@
+use crate::{ bf16, f16 };
+
+/// The element types tensorium can read from and write to files. Tensors always hold `f64`, so
+/// every other type is converted on the way in and out. `F16` is IEEE half precision and `BF16` is
+/// bfloat16, see [crate::f16] and [crate::bf16].
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+pub enum DType {
+    Bool,
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    F16,
+    BF16,
+    F32,
+    F64,
+}
+
+impl DType {
+    /// The number of bytes one element occupies.
+    pub fn size(&self) -> usize {
+        match self {
+            DType::Bool | DType::I8 | DType::U8 => 1,
+            DType::I16 | DType::U16 | DType::F16 | DType::BF16 => 2,
+            DType::I32 | DType::U32 | DType::F32 => 4,
+            DType::I64 | DType::U64 | DType::F64 => 8,
+        }
+    }
+
+    /// Decodes one element from exactly [DType::size] bytes.
+    pub(crate) fn decode(&self, bytes: &[u8], little_endian: bool) -> f64 {
+        macro_rules! read {
+            ($t:ty) => {{
+                let array = bytes.try_into().unwrap();
+                if little_endian { <$t>::from_le_bytes(array) } else { <$t>::from_be_bytes(array) }
+            }};
+        }
+
+        match self {
+            DType::Bool => if bytes[0] != 0 { 1.0 } else { 0.0 },
+            DType::I8 => bytes[0] as i8 as f64,
+            DType::U8 => bytes[0] as f64,
+            DType::I16 => read!(i16) as f64,
+            DType::U16 => read!(u16) as f64,
+            DType::I32 => read!(i32) as f64,
+            DType::U32 => read!(u32) as f64,
+            DType::I64 => read!(i64) as f64,
+            DType::U64 => read!(u64) as f64,
+            DType::F16 => f16::from_bits(read!(u16)).to_f64(),
+            DType::BF16 => bf16::from_bits(read!(u16)).to_f64(),
+            DType::F32 => read!(f32) as f64,
+            DType::F64 => read!(f64),
+        }
+    }
+
+    /// Encodes one element, appending it to `out` in little-endian order. Integer types saturate
+    /// at their bounds and truncate towards zero, and float types round to nearest, ties to even.
+    pub(crate) fn encode(&self, value: f64, out: &mut Vec<u8>) {
+        match self {
+            DType::Bool => out.push((value != 0.0) as u8),
+            DType::I8 => out.push(value as i8 as u8),
+            DType::U8 => out.push(value as u8),
+            DType::I16 => out.extend_from_slice(&(value as i16).to_le_bytes()),
+            DType::U16 => out.extend_from_slice(&(value as u16).to_le_bytes()),
+            DType::I32 => out.extend_from_slice(&(value as i32).to_le_bytes()),
+            DType::U32 => out.extend_from_slice(&(value as u32).to_le_bytes()),
+            DType::I64 => out.extend_from_slice(&(value as i64).to_le_bytes()),
+            DType::U64 => out.extend_from_slice(&(value as u64).to_le_bytes()),
+            DType::F16 => out.extend_from_slice(&f16::from_f64(value).to_bits().to_le_bytes()),
+            DType::BF16 => out.extend_from_slice(&bf16::from_f64(value).to_bits().to_le_bytes()),
+            DType::F32 => out.extend_from_slice(&(value as f32).to_le_bytes()),
+            DType::F64 => out.extend_from_slice(&value.to_le_bytes()),
+        }
+    }
+
+    /// Decodes a whole buffer of elements.
+    pub(crate) fn decode_all(&self, bytes: &[u8], little_endian: bool) -> Vec<f64> {
+        bytes.chunks_exact(self.size()).map(|chunk| self.decode(chunk, little_endian)).collect()
+    }
+}
@@ -0,0 +1,248 @@
+//! Just enough of the FlatBuffers binary format to read and write Arrow IPC metadata.
+
+use std::io;
+use crate::tensor_io::utilities::invalid_data;
+
+fn malformed() -> io::Error {
+    invalid_data("Malformed FlatBuffers metadata")
+}
+
+fn read_bytes<const N: usize>(buf: &[u8], pos: usize) -> io::Result<[u8; N]> {
+    buf.get(pos..pos.checked_add(N).ok_or_else(malformed)?)
+        .map(|bytes| bytes.try_into().unwrap())
+        .ok_or_else(malformed)
+}
+
+fn read_u32(buf: &[u8], pos: usize) -> io::Result<usize> {
+    Ok(u32::from_le_bytes(read_bytes(buf, pos)?) as usize)
+}
+
+/// Follows the unsigned offset stored at `pos` to the position it points at.
+fn follow(buf: &[u8], pos: usize) -> io::Result<usize> {
+    pos.checked_add(read_u32(buf, pos)?).filter(|&p| p < buf.len()).ok_or_else(malformed)
+}
+
+/// A table inside a FlatBuffers buffer.
+#[derive(Clone, Copy)]
+pub(crate) struct Table<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Table<'a> {
+    /// The root table of a buffer.
+    pub fn root(buf: &'a [u8]) -> io::Result<Table<'a>> {
+        Ok(Table { buf, pos: follow(buf, 0)? })
+    }
+
+    /// The absolute position of a field, or `None` if the field is absent.
+    fn field(&self, index: usize) -> io::Result<Option<usize>> {
+        let soffset = i32::from_le_bytes(read_bytes(self.buf, self.pos)?) as isize;
+        let vtable = (self.pos as isize).checked_sub(soffset)
+            .filter(|&v| v >= 0)
+            .ok_or_else(malformed)? as usize;
+
+        let vtable_len = u16::from_le_bytes(read_bytes(self.buf, vtable)?) as usize;
+        let entry = 4 + 2 * index;
+        if entry + 2 > vtable_len {
+            return Ok(None);
+        }
+
+        let offset = u16::from_le_bytes(read_bytes(self.buf, vtable + entry)?) as usize;
+        Ok(if offset == 0 { None } else { Some(self.pos + offset) })
+    }
+
+    pub fn u8(&self, index: usize, default: u8) -> io::Result<u8> {
+        match self.field(index)? {
+            Some(pos) => Ok(read_bytes::<1>(self.buf, pos)?[0]),
+            None => Ok(default),
+        }
+    }
+
+    pub fn bool(&self, index: usize, default: bool) -> io::Result<bool> {
+        Ok(self.u8(index, default as u8)? != 0)
+    }
+
+    pub fn i16(&self, index: usize, default: i16) -> io::Result<i16> {
+        match self.field(index)? {
+            Some(pos) => Ok(i16::from_le_bytes(read_bytes(self.buf, pos)?)),
+            None => Ok(default),
+        }
+    }
+
+    pub fn i32(&self, index: usize, default: i32) -> io::Result<i32> {
+        match self.field(index)? {
+            Some(pos) => Ok(i32::from_le_bytes(read_bytes(self.buf, pos)?)),
+            None => Ok(default),
+        }
+    }
+
+    pub fn i64(&self, index: usize, default: i64) -> io::Result<i64> {
+        match self.field(index)? {
+            Some(pos) => Ok(i64::from_le_bytes(read_bytes(self.buf, pos)?)),
+            None => Ok(default),
+        }
+    }
+
+    pub fn table(&self, index: usize) -> io::Result<Option<Table<'a>>> {
+        match self.field(index)? {
+            Some(pos) => Ok(Some(Table { buf: self.buf, pos: follow(self.buf, pos)? })),
+            None => Ok(None),
+        }
+    }
+
+    pub fn vector(&self, index: usize) -> io::Result<Option<Vector<'a>>> {
+        match self.field(index)? {
+            Some(pos) => {
+                let start = follow(self.buf, pos)?;
+                let len = read_u32(self.buf, start)?;
+                Ok(Some(Vector { buf: self.buf, start: start + 4, len }))
+            },
+            None => Ok(None),
+        }
+    }
+
+    pub fn string(&self, index: usize) -> io::Result<Option<String>> {
+        match self.vector(index)? {
+            Some(vector) => {
+                let bytes = self.buf.get(vector.start..vector.start + vector.len).ok_or_else(malformed)?;
+                Ok(Some(String::from_utf8_lossy(bytes).into_owned()))
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+/// A vector inside a FlatBuffers buffer.
+#[derive(Clone, Copy)]
+pub(crate) struct Vector<'a> {
+    buf: &'a [u8],
+    start: usize,
+    pub len: usize,
+}
+
+impl<'a> Vector<'a> {
+    /// Element `index` of a vector of tables.
+    pub fn table(&self, index: usize) -> io::Result<Table<'a>> {
+        Ok(Table { buf: self.buf, pos: follow(self.buf, self.start + 4 * index)? })
+    }
+
+    /// The bytes of element `index` of a vector of structs of `size` bytes.
+    pub fn struct_bytes(&self, index: usize, size: usize) -> io::Result<&'a [u8]> {
+        let start = self.start + size * index;
+        self.buf.get(start..start + size).ok_or_else(malformed)
+    }
+}
+
+/// A value stored inline in a table being built.
+pub(crate) enum Field {
+    U8(u8),
+    I16(i16),
+    I64(i64),
+    /// An offset to an object already created in the builder.
+    Offset(usize),
+}
+
+/// Builds a FlatBuffers buffer back to front, like the reference implementation: objects must be
+/// created before the tables that refer to them. Objects are identified by their distance from the
+/// end of the buffer, which does not change as more is prepended.
+pub(crate) struct Builder {
+    /// The buffer so far, in final order.
+    buf: Vec<u8>,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder { buf: Vec::new() }
+    }
+
+    fn prepend(&mut self, bytes: &[u8]) {
+        self.buf.splice(0..0, bytes.iter().copied());
+    }
+
+    /// Pads so that after prepending `size` more bytes the front is aligned to `align`. The
+    /// finished buffer is padded to a multiple of 8, so alignment from the end is alignment from
+    /// the start.
+    fn align(&mut self, size: usize, align: usize) {
+        let padding = (align - (self.buf.len() + size) % align) % align;
+        self.prepend(&vec![0; padding]);
+    }
+
+    fn prepend_offset(&mut self, target: usize) {
+        self.align(4, 4);
+        let value = (self.buf.len() + 4 - target) as u32;
+        self.prepend(&value.to_le_bytes());
+    }
+
+    pub fn string(&mut self, value: &str) -> usize {
+        self.align(value.len() + 1, 4);
+        self.prepend(&[0]);
+        self.prepend(value.as_bytes());
+        self.prepend(&(value.len() as u32).to_le_bytes());
+        self.buf.len()
+    }
+
+    pub fn vector_of_offsets(&mut self, targets: &[usize]) -> usize {
+        self.align(4 * targets.len(), 4);
+        for &target in targets.iter().rev() {
+            self.prepend_offset(target);
+        }
+        self.prepend(&(targets.len() as u32).to_le_bytes());
+        self.buf.len()
+    }
+
+    /// A vector of `count` structs whose bytes are given back to back. Structs are aligned to 8.
+    pub fn vector_of_structs(&mut self, bytes: &[u8], count: usize) -> usize {
+        self.align(bytes.len(), 8);
+        self.prepend(bytes);
+        self.prepend(&(count as u32).to_le_bytes());
+        self.buf.len()
+    }
+
+    /// A table holding `fields`, each given with its index in the schema.
+    pub fn table(&mut self, fields: &[(usize, Field)]) -> usize {
+        let start = self.buf.len();
+        let mut positions = Vec::with_capacity(fields.len());
+
+        for (index, field) in fields.iter().rev() {
+            match field {
+                Field::U8(value) => self.prepend(&[*value]),
+                Field::I16(value) => {
+                    self.align(2, 2);
+                    self.prepend(&value.to_le_bytes());
+                },
+                Field::I64(value) => {
+                    self.align(8, 8);
+                    self.prepend(&value.to_le_bytes());
+                },
+                Field::Offset(target) => self.prepend_offset(*target),
+            }
+            positions.push((*index, self.buf.len()));
+        }
+
+        self.align(4, 4);
+        let table = self.buf.len() + 4;
+
+        let slots = fields.iter().map(|(index, _)| index + 1).max().unwrap_or(0);
+        let mut vtable = vec![0u16; 2 + slots];
+        vtable[0] = (2 * vtable.len()) as u16;
+        vtable[1] = (table - start) as u16;
+        for (index, position) in positions {
+            vtable[2 + index] = (table - position) as u16;
+        }
+
+        // The vtable sits right before the table, so the signed offset to it is its length.
+        self.prepend(&(2 * vtable.len() as i32).to_le_bytes());
+        let bytes: Vec<u8> = vtable.iter().flat_map(|v| v.to_le_bytes()).collect();
+        self.prepend(&bytes);
+
+        table
+    }
+
+    /// Writes the offset to the root table and returns the buffer.
+    pub fn finish(mut self, root: usize) -> Vec<u8> {
+        self.align(4, 8);
+        self.prepend_offset(root);
+        self.buf
+    }
+}
@@ -0,0 +1,279 @@
+use std::io;
+use crate::tensor_io::utilities::invalid_data;
+
+/// A parsed JSON value. Numbers keep their source text so integers larger than 2^53, such as byte
+/// offsets into large files, are not rounded through `f64`. Objects keep their keys in order.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub(crate) enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// Parses a complete JSON document. Errors name the byte offset at which parsing failed.
+    pub fn parse(text: &str) -> io::Result<JsonValue> {
+        let mut parser = Parser { bytes: text.as_bytes(), position: 0 };
+        let value = parser.parse_value(0)?;
+        parser.skip_whitespace();
+        if parser.position != parser.bytes.len() {
+            return Err(parser.error("trailing characters after the JSON value"));
+        }
+        Ok(value)
+    }
+
+    /// The value as a non-negative integer, if it is one.
+    pub fn as_usize(&self) -> Option<usize> {
+        match self {
+            JsonValue::Number(text) => text.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// A short name for the kind of value, for error messages.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            JsonValue::Null => "null",
+            JsonValue::Bool(_) => "a bool",
+            JsonValue::Number(_) => "a number",
+            JsonValue::String(_) => "a string",
+            JsonValue::Array(_) => "an array",
+            JsonValue::Object(_) => "an object",
+        }
+    }
+}
+
+/// Appends `value` to `out` as a quoted JSON string.
+pub(crate) fn write_json_string(value: &str, out: &mut String) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Nesting deeper than this is rejected rather than risking a stack overflow on hostile input.
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> io::Error {
+        invalid_data(format!("Invalid JSON at byte {}: {message}", self.position))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.bytes.get(self.position).is_some_and(|b| matches!(b, b' ' | b'\t' | b'\n' | b'\r')) {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, literal: &str) -> io::Result<()> {
+        if self.bytes[self.position..].starts_with(literal.as_bytes()) {
+            self.position += literal.len();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{literal}'")))
+        }
+    }
+
+    fn parse_value(&mut self, depth: usize) -> io::Result<JsonValue> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("nesting is too deep"));
+        }
+
+        self.skip_whitespace();
+        match self.bytes.get(self.position) {
+            None => Err(self.error("unexpected end of input")),
+            Some(b'n') => self.expect("null").map(|_| JsonValue::Null),
+            Some(b't') => self.expect("true").map(|_| JsonValue::Bool(true)),
+            Some(b'f') => self.expect("false").map(|_| JsonValue::Bool(false)),
+            Some(b'"') => self.parse_string().map(JsonValue::String),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            Some(b'[') => {
+                self.position += 1;
+                let mut items = Vec::new();
+                if self.close(b']') {
+                    return Ok(JsonValue::Array(items));
+                }
+                loop {
+                    items.push(self.parse_value(depth + 1)?);
+                    if self.separator(b']')? {
+                        return Ok(JsonValue::Array(items));
+                    }
+                }
+            },
+            Some(b'{') => {
+                self.position += 1;
+                let mut entries = Vec::new();
+                if self.close(b'}') {
+                    return Ok(JsonValue::Object(entries));
+                }
+                loop {
+                    self.skip_whitespace();
+                    if self.bytes.get(self.position) != Some(&b'"') {
+                        return Err(self.error("expected a string key"));
+                    }
+                    let key = self.parse_string()?;
+                    self.skip_whitespace();
+                    self.expect(":")?;
+                    entries.push((key, self.parse_value(depth + 1)?));
+                    if self.separator(b'}')? {
+                        return Ok(JsonValue::Object(entries));
+                    }
+                }
+            },
+            Some(_) => Err(self.error("unexpected character")),
+        }
+    }
+
+    /// Consumes `close` if it is the next non-whitespace byte.
+    fn close(&mut self, close: u8) -> bool {
+        self.skip_whitespace();
+        if self.bytes.get(self.position) == Some(&close) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consumes a comma, returning false, or the closing bracket, returning true.
+    fn separator(&mut self, close: u8) -> io::Result<bool> {
+        self.skip_whitespace();
+        match self.bytes.get(self.position) {
+            Some(b',') => {
+                self.position += 1;
+                Ok(false)
+            },
+            Some(&b) if b == close => {
+                self.position += 1;
+                Ok(true)
+            },
+            _ => Err(self.error(&format!("expected ',' or '{}'", close as char))),
+        }
+    }
+
+    fn parse_number(&mut self) -> io::Result<JsonValue> {
+        let start = self.position;
+        let digits = |parser: &mut Parser| {
+            let first = parser.position;
+            while parser.bytes.get(parser.position).is_some_and(|b| b.is_ascii_digit()) {
+                parser.position += 1;
+            }
+            parser.position > first
+        };
+
+        if self.bytes[self.position] == b'-' {
+            self.position += 1;
+        }
+        if !digits(self) {
+            return Err(self.error("expected a digit"));
+        }
+        if self.bytes.get(self.position) == Some(&b'.') {
+            self.position += 1;
+            if !digits(self) {
+                return Err(self.error("expected a digit after '.'"));
+            }
+        }
+        if self.bytes.get(self.position).is_some_and(|b| matches!(b, b'e' | b'E')) {
+            self.position += 1;
+            if self.bytes.get(self.position).is_some_and(|b| matches!(b, b'+' | b'-')) {
+                self.position += 1;
+            }
+            if !digits(self) {
+                return Err(self.error("expected a digit in the exponent"));
+            }
+        }
+
+        // The slice is ascii digits and signs, so it is always valid utf-8.
+        Ok(JsonValue::Number(String::from_utf8_lossy(&self.bytes[start..self.position]).into_owned()))
+    }
+
+    fn parse_string(&mut self) -> io::Result<String> {
+        // Skip the opening quote
+        self.position += 1;
+        let mut out: Vec<u8> = Vec::new();
+
+        loop {
+            match self.bytes.get(self.position) {
+                None => return Err(self.error("unterminated string")),
+                Some(b'"') => {
+                    self.position += 1;
+                    break;
+                },
+                Some(b'\\') => {
+                    self.position += 1;
+                    let escaped = match self.bytes.get(self.position) {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => self.parse_unicode_escape()?,
+                        _ => return Err(self.error("invalid escape sequence")),
+                    };
+                    self.position += 1;
+                    out.extend_from_slice(escaped.encode_utf8(&mut [0; 4]).as_bytes());
+                },
+                Some(&b) if b < 0x20 => return Err(self.error("control character in string")),
+                Some(&b) => {
+                    out.push(b);
+                    self.position += 1;
+                },
+            }
+        }
+
+        String::from_utf8(out).map_err(|_| self.error("string is not valid utf-8"))
+    }
+
+    /// Parses the `XXXX` of a `\uXXXX` escape, including a following low surrogate if needed. On
+    /// return the position is at the last hex digit consumed.
+    fn parse_unicode_escape(&mut self) -> io::Result<char> {
+        let high = self.hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            if !self.bytes[self.position + 1..].starts_with(b"\\u") {
+                return Err(self.error("unpaired surrogate in \\u escape"));
+            }
+            self.position += 2;
+            let low = self.hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("unpaired surrogate in \\u escape"));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+
+        char::from_u32(code).ok_or_else(|| self.error("invalid \\u escape"))
+    }
+
+    /// Reads the four hex digits after the current position, leaving the position on the last.
+    fn hex4(&mut self) -> io::Result<u32> {
+        let digits = self.bytes.get(self.position + 1..self.position + 5)
+            .ok_or_else(|| self.error("truncated \\u escape"))?;
+        let text = std::str::from_utf8(digits).map_err(|_| self.error("invalid \\u escape"))?;
+        let value = u32::from_str_radix(text, 16).map_err(|_| self.error("invalid \\u escape"))?;
+        self.position += 4;
+        Ok(value)
+    }
+}
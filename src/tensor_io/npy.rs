@@ -0,0 +1,379 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use crate::Tensor;
+use crate::tensor_io::dtype::DType;
+use crate::tensor_io::utilities::invalid_data;
+use crate::tensor_ops::{ build_tensor, flatten_tensor, get_dimension };
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// The total header of a written file is padded to a multiple of this many bytes, matching numpy,
+/// so the data that follows is aligned for memory mapping.
+const ARRAY_ALIGN: usize = 64;
+
+/// Writes a Tensor to a `.npy` file as little-endian `f64` in C order. The file can be read with
+/// `numpy.load`.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_io::{ save_npy, load_npy };
+///
+/// let t = Tensor::Array(Vec::from([
+///     Tensor::Element(vec![1.0, 2.0, 3.0]),
+///     Tensor::Element(vec![4.0, 5.0, 6.0])
+/// ]));
+///
+/// let path = std::env::temp_dir().join("tensorium_save_npy_doc.npy");
+/// save_npy(&t, &path).unwrap();
+/// assert_eq!(load_npy(&path).unwrap(), t);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn save_npy(tensor: &Tensor, path: impl AsRef<Path>) -> io::Result<()> {
+    save_npy_as(tensor, path, DType::F64)
+}
+
+/// Writes a Tensor to a `.npy` file like [save_npy()], converting its values to `dtype` as
+/// described by [DType]. `F16` is stored as numpy's `float16`.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_io::{ save_npy_as, load_npy, DType };
+///
+/// let t = Tensor::Element(vec![0.1, 1.0, 65519.0]);
+///
+/// let path = std::env::temp_dir().join("tensorium_save_npy_as_doc.npy");
+/// save_npy_as(&t, &path, DType::F16).unwrap();
+/// assert_eq!(load_npy(&path).unwrap(), Tensor::Element(vec![0.0999755859375, 1.0, 65504.0]));
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error of kind [io::ErrorKind::InvalidInput] for `BF16`, which numpy has no type
+/// for, or any error from writing the file.
+pub fn save_npy_as(tensor: &Tensor, path: impl AsRef<Path>, dtype: DType) -> io::Result<()> {
+    fs::write(path, encode_npy(tensor, dtype)?)
+}
+
+/// Reads a `.npy` file into a Tensor. Versions 1.0, 2.0 and 3.0 of the format are supported, with
+/// C or Fortran order, either byte order, and boolean, integer or floating point data, all of which
+/// are converted to `f64`. A 0-D array is read as a Tensor of shape \[1\].
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, is not a `.npy` file, uses a structured or
+/// otherwise unsupported dtype, or holds the wrong amount of data for its shape.
+pub fn load_npy(path: impl AsRef<Path>) -> io::Result<Tensor> {
+    decode_npy(&fs::read(path)?)
+}
+
+/// Encodes a Tensor as the bytes of a `.npy` file holding `dtype`.
+pub(crate) fn encode_npy(tensor: &Tensor, dtype: DType) -> io::Result<Vec<u8>> {
+    let descr = match dtype {
+        DType::Bool => "|b1",
+        DType::I8 => "|i1",
+        DType::I16 => "<i2",
+        DType::I32 => "<i4",
+        DType::I64 => "<i8",
+        DType::U8 => "|u1",
+        DType::U16 => "<u2",
+        DType::U32 => "<u4",
+        DType::U64 => "<u8",
+        DType::F16 => "<f2",
+        DType::F32 => "<f4",
+        DType::F64 => "<f8",
+        DType::BF16 => {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "numpy has no bfloat16 dtype"));
+        },
+    };
+
+    let shape = get_dimension(tensor);
+    let shape_repr = if shape.len() == 1 {
+        format!("({},)", shape[0])
+    } else {
+        format!("({})", shape.iter().map(|d| d.to_string()).collect::<Vec<String>>().join(", "))
+    };
+    let header = format!("{{'descr': '{descr}', 'fortran_order': False, 'shape': {shape_repr}, }}");
+
+    // Pad with spaces so magic + version + length + header + newline is a multiple of 64. Version
+    // 1.0 stores the length in 2 bytes and is used whenever the header fits.
+    let padded_len = |length_bytes: usize| {
+        let unpadded = MAGIC.len() + 2 + length_bytes + header.len() + 1;
+        header.len() + 1 + (ARRAY_ALIGN - unpadded % ARRAY_ALIGN) % ARRAY_ALIGN
+    };
+
+    let (version, length_bytes) = if padded_len(2) <= u16::MAX as usize { (1, 2) } else { (2, 4) };
+    let header_len = padded_len(length_bytes);
+
+    let mut out = Vec::new();
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&[version, 0]);
+    if version == 1 {
+        out.extend_from_slice(&(header_len as u16).to_le_bytes());
+    } else {
+        out.extend_from_slice(&(header_len as u32).to_le_bytes());
+    }
+    out.extend_from_slice(header.as_bytes());
+    out.extend(std::iter::repeat_n(b' ', header_len - header.len() - 1));
+    out.push(b'\n');
+
+    for value in flatten_tensor(tensor) {
+        dtype.encode(value, &mut out);
+    }
+
+    Ok(out)
+}
+
+/// The parsed contents of a `.npy` header.
+pub(crate) struct NpyHeader {
+    pub dtype: DType,
+    pub little_endian: bool,
+    pub fortran_order: bool,
+    pub shape: Vec<usize>,
+    /// The byte offset of the data from the start of the file.
+    pub data_offset: usize,
+}
+
+/// Parses the header at the start of a `.npy` file.
+pub(crate) fn parse_npy_header(bytes: &[u8]) -> io::Result<NpyHeader> {
+    if bytes.len() < 10 || &bytes[..6] != MAGIC {
+        return Err(invalid_data("Not a .npy file: missing magic string"));
+    }
+
+    let (header_len, header_start) = match bytes[6] {
+        1 => (u16::from_le_bytes([bytes[8], bytes[9]]) as usize, 10),
+        2 | 3 => {
+            let length = bytes.get(8..12).ok_or_else(|| invalid_data("Truncated .npy header"))?;
+            (u32::from_le_bytes(length.try_into().unwrap()) as usize, 12)
+        },
+        major => return Err(invalid_data(format!("Unsupported .npy format version {major}.{}", bytes[7]))),
+    };
+
+    let header_bytes = bytes.get(header_start..header_start + header_len)
+        .ok_or_else(|| invalid_data("Truncated .npy header"))?;
+
+    // Versions 1 and 2 are latin-1 and version 3 is utf-8. Every valid header is plain ascii
+    // anyway, so a lossy utf-8 conversion is enough to parse it.
+    let header = String::from_utf8_lossy(header_bytes);
+    let dict = match PyLiteral::parse(header.trim())? {
+        PyLiteral::Dict(entries) => entries,
+        _ => return Err(invalid_data("The .npy header is not a dictionary")),
+    };
+
+    let get = |key: &str| {
+        dict.iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+            .ok_or_else(|| invalid_data(format!("The .npy header is missing '{key}'")))
+    };
+
+    let (dtype, little_endian) = match get("descr")? {
+        PyLiteral::Str(descr) => parse_descr(descr)?,
+        _ => return Err(invalid_data("Structured dtypes are not supported")),
+    };
+    let fortran_order = match get("fortran_order")? {
+        PyLiteral::Bool(value) => *value,
+        _ => return Err(invalid_data("The .npy header 'fortran_order' is not a bool")),
+    };
+    let shape = match get("shape")? {
+        PyLiteral::Tuple(dims) | PyLiteral::List(dims) => dims.iter()
+            .map(|d| match d {
+                PyLiteral::Int(value) => Ok(*value),
+                _ => Err(invalid_data("The .npy header 'shape' holds a non-integer")),
+            })
+            .collect::<io::Result<Vec<usize>>>()?,
+        _ => return Err(invalid_data("The .npy header 'shape' is not a tuple")),
+    };
+
+    Ok(NpyHeader { dtype, little_endian, fortran_order, shape, data_offset: header_start + header_len })
+}
+
+/// Decodes the bytes of a `.npy` file into a Tensor.
+pub(crate) fn decode_npy(bytes: &[u8]) -> io::Result<Tensor> {
+    let header = parse_npy_header(bytes)?;
+
+    let end = header.shape.iter()
+        .try_fold(header.dtype.size(), |acc, &d| acc.checked_mul(d))
+        .and_then(|size| size.checked_add(header.data_offset))
+        .ok_or_else(|| invalid_data(format!("The .npy shape {:?} is too large", header.shape)))?;
+    let data = bytes.get(header.data_offset..end)
+        .ok_or_else(|| invalid_data("The .npy file holds less data than its shape requires"))?;
+    let mut values = header.dtype.decode_all(data, header.little_endian);
+
+    if header.fortran_order && header.shape.len() > 1 {
+        values = fortran_to_c_order(&values, &header.shape);
+    }
+
+    let shape = if header.shape.is_empty() { vec![1] } else { header.shape };
+    Ok(build_tensor(&values, &shape))
+}
+
+/// Reorders column-major values into row-major order.
+pub(crate) fn fortran_to_c_order(values: &[f64], shape: &[usize]) -> Vec<f64> {
+    let mut fortran_strides = vec![1; shape.len()];
+    for d in 1..shape.len() {
+        fortran_strides[d] = fortran_strides[d - 1] * shape[d - 1];
+    }
+
+    let mut out = Vec::with_capacity(values.len());
+    let mut index = vec![0; shape.len()];
+    for _ in 0..values.len() {
+        let offset: usize = index.iter().zip(fortran_strides.iter()).map(|(i, s)| i * s).sum();
+        out.push(values[offset]);
+
+        for d in (0..shape.len()).rev() {
+            index[d] += 1;
+            if index[d] < shape[d] {
+                break;
+            }
+            index[d] = 0;
+        }
+    }
+
+    out
+}
+
+/// Parses a numpy dtype descriptor such as `'<f8'` into a type and byte order.
+fn parse_descr(descr: &str) -> io::Result<(DType, bool)> {
+    let unsupported = || invalid_data(format!("Unsupported .npy dtype '{descr}'"));
+
+    let mut chars = descr.chars();
+    let little_endian = match chars.next().ok_or_else(unsupported)? {
+        '<' | '|' => true,
+        '>' => false,
+        '=' => cfg!(target_endian = "little"),
+        _ => return Err(unsupported()),
+    };
+
+    let dtype = match chars.as_str() {
+        "b1" | "?" => DType::Bool,
+        "i1" => DType::I8,
+        "i2" => DType::I16,
+        "i4" => DType::I32,
+        "i8" => DType::I64,
+        "u1" => DType::U8,
+        "u2" => DType::U16,
+        "u4" => DType::U32,
+        "u8" => DType::U64,
+        "f2" => DType::F16,
+        "f4" => DType::F32,
+        "f8" => DType::F64,
+        _ => return Err(unsupported()),
+    };
+
+    Ok((dtype, little_endian))
+}
+
+/// The subset of Python literals that appear in `.npy` headers.
+#[derive(Debug)]
+enum PyLiteral {
+    Str(String),
+    Int(usize),
+    Bool(bool),
+    Tuple(Vec<PyLiteral>),
+    List(Vec<PyLiteral>),
+    Dict(Vec<(String, PyLiteral)>),
+}
+
+impl PyLiteral {
+    fn parse(text: &str) -> io::Result<PyLiteral> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut position = 0;
+        let value = PyLiteral::parse_value(&chars, &mut position)?;
+        skip_whitespace(&chars, &mut position);
+        if position != chars.len() {
+            return Err(invalid_data("Trailing characters in .npy header"));
+        }
+        Ok(value)
+    }
+
+    fn parse_value(chars: &[char], position: &mut usize) -> io::Result<PyLiteral> {
+        skip_whitespace(chars, position);
+        let malformed = || invalid_data("Malformed .npy header");
+
+        match chars.get(*position).copied().ok_or_else(malformed)? {
+            quote @ ('\'' | '"') => {
+                let start = *position + 1;
+                let end = chars[start..].iter().position(|&c| c == quote).ok_or_else(malformed)? + start;
+                *position = end + 1;
+                Ok(PyLiteral::Str(chars[start..end].iter().collect()))
+            },
+            '0'..='9' => {
+                let start = *position;
+                while chars.get(*position).is_some_and(|c| c.is_ascii_digit()) {
+                    *position += 1;
+                }
+                // Python 2 era files may write long integers as `3L`.
+                if chars.get(*position) == Some(&'L') {
+                    *position += 1;
+                }
+                let digits: String = chars[start..*position].iter().filter(|c| c.is_ascii_digit()).collect();
+                digits.parse().map(PyLiteral::Int).map_err(|_| malformed())
+            },
+            'T' | 'F' => {
+                let rest: String = chars[*position..].iter().take(5).collect();
+                if rest.starts_with("True") {
+                    *position += 4;
+                    Ok(PyLiteral::Bool(true))
+                } else if rest.starts_with("False") {
+                    *position += 5;
+                    Ok(PyLiteral::Bool(false))
+                } else {
+                    Err(malformed())
+                }
+            },
+            open @ ('(' | '[' | '{') => {
+                let close = match open { '(' => ')', '[' => ']', _ => '}' };
+                *position += 1;
+
+                let mut items = Vec::new();
+                let mut entries = Vec::new();
+                loop {
+                    skip_whitespace(chars, position);
+                    if chars.get(*position) == Some(&close) {
+                        *position += 1;
+                        break;
+                    }
+
+                    let item = PyLiteral::parse_value(chars, position)?;
+                    if open == '{' {
+                        let PyLiteral::Str(key) = item else { return Err(malformed()) };
+                        skip_whitespace(chars, position);
+                        if chars.get(*position) != Some(&':') {
+                            return Err(malformed());
+                        }
+                        *position += 1;
+                        entries.push((key, PyLiteral::parse_value(chars, position)?));
+                    } else {
+                        items.push(item);
+                    }
+
+                    skip_whitespace(chars, position);
+                    match chars.get(*position) {
+                        Some(',') => *position += 1,
+                        Some(c) if *c == close => {},
+                        _ => return Err(malformed()),
+                    }
+                }
+
+                Ok(match open {
+                    '(' => PyLiteral::Tuple(items),
+                    '[' => PyLiteral::List(items),
+                    _ => PyLiteral::Dict(entries),
+                })
+            },
+            _ => Err(malformed()),
+        }
+    }
+}
+
+fn skip_whitespace(chars: &[char], position: &mut usize) {
+    while chars.get(*position).is_some_and(|c| c.is_whitespace()) {
+        *position += 1;
+    }
+}
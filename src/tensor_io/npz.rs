@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use crate::Tensor;
+use crate::tensor_io::dtype::DType;
+use crate::tensor_io::npy::{ decode_npy, encode_npy };
+use crate::tensor_io::zip::{ read_zip, write_zip };
+
+/// Writes named Tensors to a `.npz` archive, the format of `numpy.savez`. Each Tensor is stored
+/// uncompressed as `<name>.npy`, in order of name so the same Tensors always give the same file.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use tensorium::Tensor;
+/// use tensorium::tensor_io::{ save_npz, load_npz };
+///
+/// let mut tensors = HashMap::new();
+/// tensors.insert(String::from("weight"), Tensor::Element(vec![1.0, 2.0]));
+/// tensors.insert(String::from("bias"), Tensor::Element(vec![0.5]));
+///
+/// let path = std::env::temp_dir().join("tensorium_save_npz_doc.npz");
+/// save_npz(&tensors, &path).unwrap();
+/// assert_eq!(load_npz(&path).unwrap(), tensors);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written or the archive would exceed 4 GiB.
+pub fn save_npz(tensors: &HashMap<String, Tensor>, path: impl AsRef<Path>) -> io::Result<()> {
+    let mut names: Vec<&String> = tensors.keys().collect();
+    names.sort();
+
+    let entries = names.into_iter()
+        .map(|name| Ok((format!("{name}.npy"), encode_npy(&tensors[name], DType::F64)?)))
+        .collect::<io::Result<Vec<(String, Vec<u8>)>>>()?;
+
+    fs::write(path, write_zip(&entries)?)
+}
+
+/// Reads every array of a `.npz` archive, written by either `numpy.savez` or
+/// `numpy.savez_compressed`, keyed by name without the `.npy` extension. Each array is read as by
+/// [crate::tensor_io::load_npy()].
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, is not a valid zip archive, or holds an entry that
+/// is not a valid `.npy` file.
+pub fn load_npz(path: impl AsRef<Path>) -> io::Result<HashMap<String, Tensor>> {
+    let mut tensors = HashMap::new();
+
+    for (name, contents) in read_zip(&fs::read(path)?)? {
+        let name = name.strip_suffix(".npy").map(String::from).unwrap_or(name);
+        tensors.insert(name, decode_npy(&contents)?);
+    }
+
+    Ok(tensors)
+}
@@ -0,0 +1,374 @@
+use std::collections::HashMap;
+use std::fs::{ self, File };
+use std::io::{ self, Read };
+use std::path::Path;
+use crate::Tensor;
+use crate::tensor_io::dtype::DType;
+use crate::tensor_io::json::{ JsonValue, write_json_string };
+use crate::tensor_io::utilities::invalid_data;
+use crate::tensor_ops::{ build_tensor, flatten_tensor, get_dimension };
+
+/// Headers larger than this are rejected before being read, matching the reference implementation.
+const MAX_HEADER_SIZE: usize = 100_000_000;
+
+/// The key of the header entry holding free-form string metadata.
+const METADATA_KEY: &str = "__metadata__";
+
+/// Writes named Tensors to a `.safetensors` file as little-endian `F64`, along with string
+/// metadata stored under the `__metadata__` key of the header. Tensors are laid out in order of
+/// name so the same input always gives the same file.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use tensorium::Tensor;
+/// use tensorium::tensor_io::{ save_safetensors, load_safetensors, load_safetensors_metadata };
+///
+/// let mut tensors = HashMap::new();
+/// tensors.insert(String::from("bias"), Tensor::Element(vec![0.5, -0.5]));
+///
+/// let mut metadata = HashMap::new();
+/// metadata.insert(String::from("format"), String::from("pt"));
+///
+/// let path = std::env::temp_dir().join("tensorium_save_safetensors_doc.safetensors");
+/// save_safetensors(&tensors, &metadata, &path).unwrap();
+///
+/// assert_eq!(load_safetensors(&path).unwrap(), tensors);
+/// assert_eq!(load_safetensors_metadata(&path).unwrap(), metadata);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn save_safetensors(
+    tensors: &HashMap<String, Tensor>,
+    metadata: &HashMap<String, String>,
+    path: impl AsRef<Path>
+) -> io::Result<()> {
+    save_safetensors_as(tensors, metadata, path, DType::F64)
+}
+
+/// Writes named Tensors to a `.safetensors` file like [save_safetensors()], converting every value
+/// to `dtype` as described by [DType]. Model weights are commonly shared as `F16` or `BF16`, at a
+/// quarter of the size of `F64`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use tensorium::Tensor;
+/// use tensorium::tensor_io::{ save_safetensors_as, load_safetensors, DType };
+///
+/// let mut tensors = HashMap::new();
+/// tensors.insert(String::from("weight"), Tensor::Element(vec![1.0, 1.0 / 3.0]));
+///
+/// let path = std::env::temp_dir().join("tensorium_save_safetensors_as_doc.safetensors");
+/// save_safetensors_as(&tensors, &HashMap::new(), &path, DType::BF16).unwrap();
+///
+/// let loaded = load_safetensors(&path).unwrap();
+/// assert_eq!(loaded["weight"], Tensor::Element(vec![1.0, 0.333984375]));
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn save_safetensors_as(
+    tensors: &HashMap<String, Tensor>,
+    metadata: &HashMap<String, String>,
+    path: impl AsRef<Path>,
+    dtype: DType
+) -> io::Result<()> {
+    let mut names: Vec<&String> = tensors.keys().collect();
+    names.sort();
+
+    let mut header = String::from("{");
+    if !metadata.is_empty() {
+        let mut keys: Vec<&String> = metadata.keys().collect();
+        keys.sort();
+
+        write_json_string(METADATA_KEY, &mut header);
+        header.push_str(":{");
+        for (i, key) in keys.into_iter().enumerate() {
+            if i > 0 {
+                header.push(',');
+            }
+            write_json_string(key, &mut header);
+            header.push(':');
+            write_json_string(&metadata[key], &mut header);
+        }
+        header.push('}');
+    }
+
+    let mut data = Vec::new();
+    for name in names {
+        let tensor = &tensors[name];
+        let shape: Vec<String> = get_dimension(tensor).iter().map(|d| d.to_string()).collect();
+
+        let begin = data.len();
+        for value in flatten_tensor(tensor) {
+            dtype.encode(value, &mut data);
+        }
+
+        if header.len() > 1 {
+            header.push(',');
+        }
+        write_json_string(name, &mut header);
+        header.push_str(&format!(
+            ":{{\"dtype\":\"{}\",\"shape\":[{}],\"data_offsets\":[{begin},{}]}}",
+            dtype_name(dtype),
+            shape.join(","),
+            data.len()
+        ));
+    }
+    header.push('}');
+
+    // Pad with spaces so the data starts 8-byte aligned.
+    while header.len() % 8 != 0 {
+        header.push(' ');
+    }
+
+    let mut out = Vec::with_capacity(8 + header.len() + data.len());
+    out.extend_from_slice(&(header.len() as u64).to_le_bytes());
+    out.extend_from_slice(header.as_bytes());
+    out.extend_from_slice(&data);
+
+    fs::write(path, out)
+}
+
+/// Reads every Tensor of a `.safetensors` file, keyed by name. `BOOL`, integer and float data,
+/// including `F16` and `BF16`, are converted to `f64`, and a 0-D tensor is read as a Tensor of
+/// shape \[1\]. The file is read in a single pass; Tensors own their data so it cannot be
+/// borrowed from the file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or does not follow the format. The header is
+/// validated before any data is converted and the error describes what is wrong, for example a
+/// tensor whose byte range does not match its shape and dtype, byte ranges that overlap or leave
+/// gaps, or an unsupported dtype.
+pub fn load_safetensors(path: impl AsRef<Path>) -> io::Result<HashMap<String, Tensor>> {
+    let bytes = fs::read(path)?;
+
+    let header_len = header_len(&bytes)?;
+    if 8 + header_len > bytes.len() {
+        return Err(invalid_data(format!(
+            "The safetensors header length is {header_len} bytes but the file only holds {} after the length",
+            bytes.len() - 8
+        )));
+    }
+
+    let header = parse_header(&bytes[8..8 + header_len])?;
+    let data = &bytes[8 + header_len..];
+    let entries = validate_entries(&header, data.len())?;
+
+    let mut tensors = HashMap::new();
+    for entry in entries {
+        let values = entry.dtype.decode_all(&data[entry.begin..entry.end], true);
+        let shape = if entry.shape.is_empty() { vec![1] } else { entry.shape };
+        tensors.insert(entry.name, build_tensor(&values, &shape));
+    }
+
+    Ok(tensors)
+}
+
+/// Reads the `__metadata__` of a `.safetensors` file without reading any tensor data. A file
+/// without metadata gives an empty map.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, the header is malformed, or the metadata is not a
+/// map of strings to strings.
+pub fn load_safetensors_metadata(path: impl AsRef<Path>) -> io::Result<HashMap<String, String>> {
+    let mut file = File::open(path)?;
+
+    let mut length = [0u8; 8];
+    file.read_exact(&mut length)
+        .map_err(|_| invalid_data("The file is too small to hold a safetensors header"))?;
+    let header_len = header_len(&length)?;
+
+    let mut header_bytes = vec![0u8; header_len];
+    file.read_exact(&mut header_bytes)
+        .map_err(|_| invalid_data(format!("The safetensors header is truncated, expected {header_len} bytes")))?;
+
+    let header = parse_header(&header_bytes)?;
+    let mut metadata = HashMap::new();
+
+    let Some((_, value)) = header.iter().find(|(key, _)| key == METADATA_KEY) else {
+        return Ok(metadata);
+    };
+    let JsonValue::Object(entries) = value else {
+        return Err(invalid_data(format!("The safetensors {METADATA_KEY} is {}, expected an object", value.kind())));
+    };
+    for (key, value) in entries {
+        let JsonValue::String(text) = value else {
+            return Err(invalid_data(format!("The safetensors {METADATA_KEY} value of '{key}' is {}, expected a string", value.kind())));
+        };
+        metadata.insert(key.clone(), text.clone());
+    }
+
+    Ok(metadata)
+}
+
+/// One tensor described by the header, after validation.
+struct Entry {
+    name: String,
+    dtype: DType,
+    shape: Vec<usize>,
+    begin: usize,
+    end: usize,
+}
+
+/// Reads the little-endian header length from the first 8 bytes.
+fn header_len(bytes: &[u8]) -> io::Result<usize> {
+    let length = bytes.get(..8)
+        .ok_or_else(|| invalid_data("The file is too small to hold a safetensors header"))?;
+    let length = u64::from_le_bytes(length.try_into().unwrap());
+
+    if length > MAX_HEADER_SIZE as u64 {
+        return Err(invalid_data(format!(
+            "The safetensors header length of {length} bytes exceeds the limit of {MAX_HEADER_SIZE}"
+        )));
+    }
+    Ok(length as usize)
+}
+
+fn parse_header(bytes: &[u8]) -> io::Result<Vec<(String, JsonValue)>> {
+    let text = std::str::from_utf8(bytes)
+        .map_err(|_| invalid_data("The safetensors header is not valid utf-8"))?;
+    if !text.starts_with('{') {
+        return Err(invalid_data("The safetensors header does not start with '{'"));
+    }
+
+    match JsonValue::parse(text) {
+        Ok(JsonValue::Object(entries)) => Ok(entries),
+        Ok(value) => Err(invalid_data(format!("The safetensors header is {}, expected an object", value.kind()))),
+        Err(error) => Err(invalid_data(format!("The safetensors header is malformed: {error}"))),
+    }
+}
+
+/// Checks every tensor entry of the header and that their byte ranges exactly tile the data buffer.
+fn validate_entries(header: &[(String, JsonValue)], data_len: usize) -> io::Result<Vec<Entry>> {
+    let mut entries: Vec<Entry> = Vec::new();
+
+    for (name, value) in header {
+        if name == METADATA_KEY {
+            continue;
+        }
+        if entries.iter().any(|e| &e.name == name) {
+            return Err(invalid_data(format!("Tensor '{name}' appears more than once in the safetensors header")));
+        }
+
+        let JsonValue::Object(fields) = value else {
+            return Err(invalid_data(format!("Tensor '{name}' is {}, expected an object", value.kind())));
+        };
+        let field = |key: &str| {
+            fields.iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v)
+                .ok_or_else(|| invalid_data(format!("Tensor '{name}' is missing '{key}'")))
+        };
+        let integers = |key: &str| -> io::Result<Vec<usize>> {
+            let JsonValue::Array(items) = field(key)? else {
+                return Err(invalid_data(format!("Tensor '{name}' has a '{key}' that is not an array")));
+            };
+            items.iter()
+                .map(|item| item.as_usize().ok_or_else(|| {
+                    invalid_data(format!("Tensor '{name}' has a '{key}' holding something other than non-negative integers"))
+                }))
+                .collect()
+        };
+
+        let dtype = match field("dtype")? {
+            JsonValue::String(dtype) => parse_dtype(name, dtype)?,
+            other => return Err(invalid_data(format!("Tensor '{name}' has a 'dtype' that is {}", other.kind()))),
+        };
+        let shape = integers("shape")?;
+        let offsets = integers("data_offsets")?;
+        let [begin, end] = offsets[..] else {
+            return Err(invalid_data(format!("Tensor '{name}' has {} data_offsets, expected 2", offsets.len())));
+        };
+
+        if begin > end {
+            return Err(invalid_data(format!("Tensor '{name}' has data_offsets [{begin}, {end}] that end before they begin")));
+        }
+        let expected = shape.iter()
+            .try_fold(dtype.size(), |acc, &d| acc.checked_mul(d))
+            .ok_or_else(|| invalid_data(format!("Tensor '{name}' has a shape {shape:?} that is too large")))?;
+        if end - begin != expected {
+            return Err(invalid_data(format!(
+                "Tensor '{name}' has {} bytes of data but its shape {shape:?} and dtype need {expected}",
+                end - begin
+            )));
+        }
+
+        entries.push(Entry { name: name.clone(), dtype, shape, begin, end });
+    }
+
+    let mut ordered: Vec<&Entry> = entries.iter().collect();
+    ordered.sort_by_key(|e| (e.begin, e.end));
+
+    let mut position = 0;
+    for entry in ordered {
+        if entry.begin < position {
+            return Err(invalid_data(format!("Tensor '{}' overlaps the data of another tensor", entry.name)));
+        }
+        if entry.begin > position {
+            return Err(invalid_data(format!(
+                "Tensor '{}' starts at byte {} but the previous tensor ends at {position}, leaving a gap",
+                entry.name, entry.begin
+            )));
+        }
+        position = entry.end;
+    }
+    if position != data_len {
+        return Err(invalid_data(format!(
+            "The tensors cover {position} bytes but the data buffer holds {data_len}"
+        )));
+    }
+
+    Ok(entries)
+}
+
+fn parse_dtype(name: &str, dtype: &str) -> io::Result<DType> {
+    Ok(match dtype {
+        "BOOL" => DType::Bool,
+        "I8" => DType::I8,
+        "I16" => DType::I16,
+        "I32" => DType::I32,
+        "I64" => DType::I64,
+        "U8" => DType::U8,
+        "U16" => DType::U16,
+        "U32" => DType::U32,
+        "U64" => DType::U64,
+        "F16" => DType::F16,
+        "BF16" => DType::BF16,
+        "F32" => DType::F32,
+        "F64" => DType::F64,
+        "F8_E4M3" | "F8_E5M2" => {
+            return Err(invalid_data(format!("Tensor '{name}' has dtype {dtype}, which is not supported yet")));
+        },
+        _ => return Err(invalid_data(format!("Tensor '{name}' has unknown dtype '{dtype}'"))),
+    })
+}
+
+/// The name of a dtype in the header, the inverse of [parse_dtype].
+fn dtype_name(dtype: DType) -> &'static str {
+    match dtype {
+        DType::Bool => "BOOL",
+        DType::I8 => "I8",
+        DType::I16 => "I16",
+        DType::I32 => "I32",
+        DType::I64 => "I64",
+        DType::U8 => "U8",
+        DType::U16 => "U16",
+        DType::U32 => "U32",
+        DType::U64 => "U64",
+        DType::F16 => "F16",
+        DType::BF16 => "BF16",
+        DType::F32 => "F32",
+        DType::F64 => "F64",
+    }
+}
@@ -0,0 +1,379 @@
+//! A minimal zip archive reader and writer, enough for `.npz` files. Archives are written with
+//! stored (uncompressed) entries, and entries are read back whether they are stored or compressed
+//! with deflate, which covers both `numpy.savez` and `numpy.savez_compressed`.
+
+use std::io;
+use crate::tensor_io::utilities::{ crc32, invalid_data };
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4B50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4B50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0605_4B50;
+
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATE: u16 = 8;
+
+/// Builds a zip archive from `(name, contents)` entries, storing every entry uncompressed.
+pub(crate) fn write_zip(entries: &[(String, Vec<u8>)]) -> io::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut central = Vec::new();
+
+    for (name, contents) in entries.iter() {
+        let (offset, size, name_len) = (
+            u32::try_from(out.len()),
+            u32::try_from(contents.len()),
+            u16::try_from(name.len())
+        );
+        let (Ok(offset), Ok(size), Ok(name_len)) = (offset, size, name_len) else {
+            return Err(invalid_data("Archive entries larger than 4 GiB are not supported"));
+        };
+        let crc = crc32(contents);
+
+        // Local file header
+        out.extend_from_slice(&LOCAL_HEADER_SIGNATURE.to_le_bytes());
+        out.extend_from_slice(&20u16.to_le_bytes()); // version needed to extract
+        out.extend_from_slice(&0u16.to_le_bytes()); // flags
+        out.extend_from_slice(&METHOD_STORED.to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes()); // modification time
+        out.extend_from_slice(&0x21u16.to_le_bytes()); // modification date, 1980-01-01
+        out.extend_from_slice(&crc.to_le_bytes());
+        out.extend_from_slice(&size.to_le_bytes()); // compressed size
+        out.extend_from_slice(&size.to_le_bytes()); // uncompressed size
+        out.extend_from_slice(&name_len.to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes()); // extra field length
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(contents);
+
+        // Central directory header
+        central.extend_from_slice(&CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+        central.extend_from_slice(&20u16.to_le_bytes()); // version made by
+        central.extend_from_slice(&20u16.to_le_bytes()); // version needed to extract
+        central.extend_from_slice(&0u16.to_le_bytes()); // flags
+        central.extend_from_slice(&METHOD_STORED.to_le_bytes());
+        central.extend_from_slice(&0u16.to_le_bytes()); // modification time
+        central.extend_from_slice(&0x21u16.to_le_bytes()); // modification date
+        central.extend_from_slice(&crc.to_le_bytes());
+        central.extend_from_slice(&size.to_le_bytes());
+        central.extend_from_slice(&size.to_le_bytes());
+        central.extend_from_slice(&name_len.to_le_bytes());
+        central.extend_from_slice(&0u16.to_le_bytes()); // extra field length
+        central.extend_from_slice(&0u16.to_le_bytes()); // comment length
+        central.extend_from_slice(&0u16.to_le_bytes()); // disk number
+        central.extend_from_slice(&0u16.to_le_bytes()); // internal attributes
+        central.extend_from_slice(&0u32.to_le_bytes()); // external attributes
+        central.extend_from_slice(&offset.to_le_bytes());
+        central.extend_from_slice(name.as_bytes());
+    }
+
+    let (Ok(central_offset), Ok(central_size), Ok(count)) = (
+        u32::try_from(out.len()),
+        u32::try_from(central.len()),
+        u16::try_from(entries.len())
+    ) else {
+        return Err(invalid_data("Archive is too large for a zip without zip64 extensions"));
+    };
+    out.extend_from_slice(&central);
+
+    // End of central directory record
+    out.extend_from_slice(&END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes()); // this disk
+    out.extend_from_slice(&0u16.to_le_bytes()); // disk with the central directory
+    out.extend_from_slice(&count.to_le_bytes());
+    out.extend_from_slice(&count.to_le_bytes());
+    out.extend_from_slice(&central_size.to_le_bytes());
+    out.extend_from_slice(&central_offset.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes()); // comment length
+
+    Ok(out)
+}
+
+/// Reads every entry of a zip archive as `(name, contents)`, checking each entry's CRC-32.
+pub(crate) fn read_zip(bytes: &[u8]) -> io::Result<Vec<(String, Vec<u8>)>> {
+    // The end of central directory record sits at the end of the file, possibly followed by a
+    // comment of up to 65535 bytes.
+    let search_start = bytes.len().saturating_sub(22 + 0xFFFF);
+    let eocd = (search_start..bytes.len().saturating_sub(21))
+        .rev()
+        .find(|&i| read_u32(bytes, i).ok() == Some(END_OF_CENTRAL_DIRECTORY_SIGNATURE))
+        .ok_or_else(|| invalid_data("Not a zip archive: end of central directory not found"))?;
+
+    let count = read_u16(bytes, eocd + 10)? as usize;
+    let central_offset = read_u32(bytes, eocd + 16)? as usize;
+
+    let mut entries = Vec::with_capacity(count);
+    let mut cursor = central_offset;
+    for _ in 0..count {
+        if read_u32(bytes, cursor)? != CENTRAL_HEADER_SIGNATURE {
+            return Err(invalid_data("Corrupt zip central directory"));
+        }
+        let flags = read_u16(bytes, cursor + 8)?;
+        let method = read_u16(bytes, cursor + 10)?;
+        let crc = read_u32(bytes, cursor + 16)?;
+        let compressed_size = read_u32(bytes, cursor + 20)? as usize;
+        let uncompressed_size = read_u32(bytes, cursor + 24)? as usize;
+        let name_len = read_u16(bytes, cursor + 28)? as usize;
+        let extra_len = read_u16(bytes, cursor + 30)? as usize;
+        let comment_len = read_u16(bytes, cursor + 32)? as usize;
+        let local_offset = read_u32(bytes, cursor + 42)? as usize;
+        let name = String::from_utf8_lossy(slice(bytes, cursor + 46, name_len)?).into_owned();
+        cursor += 46 + name_len + extra_len + comment_len;
+
+        if flags & 1 != 0 {
+            return Err(invalid_data(format!("Zip entry '{name}' is encrypted")));
+        }
+        if compressed_size == 0xFFFF_FFFF || uncompressed_size == 0xFFFF_FFFF {
+            return Err(invalid_data(format!("Zip entry '{name}' uses unsupported zip64 extensions")));
+        }
+
+        if read_u32(bytes, local_offset)? != LOCAL_HEADER_SIGNATURE {
+            return Err(invalid_data(format!("Corrupt local header for zip entry '{name}'")));
+        }
+        let local_name_len = read_u16(bytes, local_offset + 26)? as usize;
+        let local_extra_len = read_u16(bytes, local_offset + 28)? as usize;
+        let data = slice(bytes, local_offset + 30 + local_name_len + local_extra_len, compressed_size)?;
+
+        let contents = match method {
+            METHOD_STORED => data.to_vec(),
+            METHOD_DEFLATE => inflate(data)?,
+            _ => return Err(invalid_data(format!("Zip entry '{name}' uses unsupported compression method {method}"))),
+        };
+
+        if contents.len() != uncompressed_size || crc32(&contents) != crc {
+            return Err(invalid_data(format!("Zip entry '{name}' failed its CRC check")));
+        }
+
+        entries.push((name, contents));
+    }
+
+    Ok(entries)
+}
+
+fn slice(bytes: &[u8], start: usize, len: usize) -> io::Result<&[u8]> {
+    bytes.get(start..start + len).ok_or_else(|| invalid_data("Unexpected end of zip archive"))
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> io::Result<u16> {
+    Ok(u16::from_le_bytes(slice(bytes, at, 2)?.try_into().unwrap()))
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> io::Result<u32> {
+    Ok(u32::from_le_bytes(slice(bytes, at, 4)?.try_into().unwrap()))
+}
+
+/// Base lengths and extra bits for length symbols 257..285.
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115,
+    131, 163, 195, 227, 258
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0
+];
+
+/// Base distances and extra bits for distance symbols 0..29.
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577
+];
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13
+];
+
+/// The order code length code lengths are stored in for dynamic blocks.
+const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+/// Reads a deflate stream one bit at a time, least significant bit first.
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    bit: u8,
+}
+
+impl BitReader<'_> {
+    fn bits(&mut self, count: u8) -> io::Result<u32> {
+        let mut value = 0;
+        for i in 0..count {
+            let byte = *self.bytes.get(self.position)
+                .ok_or_else(|| invalid_data("Unexpected end of deflate stream"))?;
+            value |= (((byte >> self.bit) & 1) as u32) << i;
+            self.bit += 1;
+            if self.bit == 8 {
+                self.bit = 0;
+                self.position += 1;
+            }
+        }
+        Ok(value)
+    }
+
+    fn align_to_byte(&mut self) {
+        if self.bit != 0 {
+            self.bit = 0;
+            self.position += 1;
+        }
+    }
+}
+
+/// A canonical Huffman code stored as the number of codes of each length and the symbols sorted
+/// by code.
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Huffman {
+        let mut counts = [0u16; 16];
+        for &length in lengths {
+            counts[length as usize] += 1;
+        }
+        counts[0] = 0;
+
+        let mut offsets = [0u16; 16];
+        for len in 1..15 {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+
+        let mut symbols = vec![0; lengths.len()];
+        for (symbol, &length) in lengths.iter().enumerate() {
+            if length != 0 {
+                symbols[offsets[length as usize] as usize] = symbol as u16;
+                offsets[length as usize] += 1;
+            }
+        }
+
+        Huffman { counts, symbols }
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> io::Result<u16> {
+        // Walk the code one bit at a time. Canonical codes of each length are consecutive, so
+        // `first` tracks the first code of the current length and `index` its first symbol.
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: i32 = 0;
+        for len in 1..16 {
+            code |= reader.bits(1)? as i32;
+            let count = self.counts[len] as i32;
+            if code - count < first {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first += count;
+            first <<= 1;
+            code <<= 1;
+        }
+        Err(invalid_data("Invalid Huffman code in deflate stream"))
+    }
+}
+
+/// Decompresses a raw deflate (RFC 1951) stream.
+pub(crate) fn inflate(bytes: &[u8]) -> io::Result<Vec<u8>> {
+    let mut reader = BitReader { bytes, position: 0, bit: 0 };
+    let mut out = Vec::new();
+
+    loop {
+        let last = reader.bits(1)? == 1;
+        match reader.bits(2)? {
+            0 => {
+                reader.align_to_byte();
+                let start = reader.position;
+                let header = bytes.get(start..start + 4)
+                    .ok_or_else(|| invalid_data("Unexpected end of deflate stream"))?;
+                let len = u16::from_le_bytes([header[0], header[1]]);
+                let nlen = u16::from_le_bytes([header[2], header[3]]);
+                if len != !nlen {
+                    return Err(invalid_data("Corrupt stored block in deflate stream"));
+                }
+                let data = bytes.get(start + 4..start + 4 + len as usize)
+                    .ok_or_else(|| invalid_data("Unexpected end of deflate stream"))?;
+                out.extend_from_slice(data);
+                reader.position = start + 4 + len as usize;
+            },
+            1 => {
+                let mut lengths = [0u8; 288];
+                lengths[..144].fill(8);
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                lengths[280..].fill(8);
+                let literals = Huffman::new(&lengths);
+                let distances = Huffman::new(&[5; 30]);
+                inflate_block(&mut reader, &mut out, &literals, &distances)?;
+            },
+            2 => {
+                let (literals, distances) = read_dynamic_tables(&mut reader)?;
+                inflate_block(&mut reader, &mut out, &literals, &distances)?;
+            },
+            _ => return Err(invalid_data("Invalid block type in deflate stream")),
+        }
+
+        if last {
+            return Ok(out);
+        }
+    }
+}
+
+fn read_dynamic_tables(reader: &mut BitReader) -> io::Result<(Huffman, Huffman)> {
+    let literal_count = reader.bits(5)? as usize + 257;
+    let distance_count = reader.bits(5)? as usize + 1;
+    let code_length_count = reader.bits(4)? as usize + 4;
+
+    let mut code_lengths = [0u8; 19];
+    for &index in CODE_LENGTH_ORDER.iter().take(code_length_count) {
+        code_lengths[index] = reader.bits(3)? as u8;
+    }
+    let code_length_code = Huffman::new(&code_lengths);
+
+    let mut lengths = Vec::with_capacity(literal_count + distance_count);
+    while lengths.len() < literal_count + distance_count {
+        let symbol = code_length_code.decode(reader)?;
+        let (value, repeat) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => {
+                let previous = *lengths.last()
+                    .ok_or_else(|| invalid_data("Repeat with no previous length in deflate stream"))?;
+                (previous, 3 + reader.bits(2)? as usize)
+            },
+            17 => (0, 3 + reader.bits(3)? as usize),
+            18 => (0, 11 + reader.bits(7)? as usize),
+            _ => return Err(invalid_data("Invalid code length symbol in deflate stream")),
+        };
+        lengths.extend(std::iter::repeat_n(value, repeat));
+    }
+    if lengths.len() > literal_count + distance_count {
+        return Err(invalid_data("Code lengths overflow in deflate stream"));
+    }
+
+    Ok((Huffman::new(&lengths[..literal_count]), Huffman::new(&lengths[literal_count..])))
+}
+
+fn inflate_block(
+    reader: &mut BitReader,
+    out: &mut Vec<u8>,
+    literals: &Huffman,
+    distances: &Huffman
+) -> io::Result<()> {
+    loop {
+        let symbol = literals.decode(reader)? as usize;
+        match symbol {
+            0..=255 => out.push(symbol as u8),
+            256 => return Ok(()),
+            257..=285 => {
+                let i = symbol - 257;
+                let length = LENGTH_BASE[i] as usize + reader.bits(LENGTH_EXTRA[i])? as usize;
+
+                let d = distances.decode(reader)? as usize;
+                if d >= 30 {
+                    return Err(invalid_data("Invalid distance symbol in deflate stream"));
+                }
+                let distance = DISTANCE_BASE[d] as usize + reader.bits(DISTANCE_EXTRA[d])? as usize;
+                if distance > out.len() {
+                    return Err(invalid_data("Distance too far back in deflate stream"));
+                }
+
+                // Copies may overlap their own output, so go byte by byte.
+                let start = out.len() - distance;
+                for k in 0..length {
+                    out.push(out[start + k]);
+                }
+            },
+            _ => return Err(invalid_data("Invalid literal/length symbol in deflate stream")),
+        }
+    }
+}
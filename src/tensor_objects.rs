@@ -1,6 +1,26 @@
 mod tensor;
+mod mmap_tensor;
+mod complex;
+mod complex_tensor;
+mod half;
+mod half_tensor;
 
 pub use tensor::{
     Tensor,
     TensorIndexResult
-};
\ No newline at end of file
+};
+pub use mmap_tensor::MmapTensor;
+pub use complex::{
+    Complex,
+    Float
+};
+pub use complex_tensor::ComplexTensor;
+pub use half::{
+    Half,
+    f16,
+    bf16
+};
+pub use half_tensor::HalfTensor;
+
+#[cfg(feature = "serde")]
+mod serde_support;
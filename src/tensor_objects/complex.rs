@@ -0,0 +1,185 @@
+use std::ops::{ Add, Div, Mul, Neg, Sub };
+
+/// A complex number with parts of type `T`, the element type of [crate::ComplexTensor].
+/// Addition, subtraction and multiplication work for any `T` with the matching operators, and
+/// division and the functions that need square roots or trigonometry, such as [Complex::abs()],
+/// for the types implementing [Float].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Complex;
+///
+/// let z = Complex::new(3.0, 4.0);
+/// assert_eq!(z.abs(), 5.0);
+/// assert_eq!(z * z.conj(), Complex::new(25.0, 0.0));
+/// assert_eq!(z + Complex::new(1.0, -4.0), Complex::new(4.0, 0.0));
+/// ```
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+pub struct Complex<T> {
+    pub re: T,
+    pub im: T,
+}
+
+impl<T> Complex<T> {
+    pub const fn new(re: T, im: T) -> Complex<T> {
+        Complex { re, im }
+    }
+}
+
+impl<T: Copy + Neg<Output = T>> Complex<T> {
+    /// The complex conjugate, with the imaginary part negated.
+    pub fn conj(self) -> Complex<T> {
+        Complex::new(self.re, -self.im)
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Complex<T> {
+    /// The squared magnitude, `re² + im²`, which needs no square root.
+    pub fn norm_sqr(self) -> T {
+        self.re * self.re + self.im * self.im
+    }
+}
+
+/// The float types whose complex numbers have the functions that need square roots or
+/// trigonometry: `f32` and `f64`.
+pub trait Float: Copy + Add<Output = Self> + Mul<Output = Self> + Neg<Output = Self> {
+    const ZERO: Self;
+    fn sin_cos(self) -> (Self, Self);
+    fn hypot(self, other: Self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn exp(self) -> Self;
+    fn is_nan(self) -> bool;
+    fn abs(self) -> Self;
+}
+
+/// Implements [Float] by forwarding to the inherent methods of a float type.
+macro_rules! impl_float {
+    ($float:ty) => {
+        impl Float for $float {
+            const ZERO: Self = 0.0;
+
+            fn sin_cos(self) -> (Self, Self) {
+                <$float>::sin_cos(self)
+            }
+
+            fn hypot(self, other: Self) -> Self {
+                <$float>::hypot(self, other)
+            }
+
+            fn atan2(self, other: Self) -> Self {
+                <$float>::atan2(self, other)
+            }
+
+            fn exp(self) -> Self {
+                <$float>::exp(self)
+            }
+
+            fn is_nan(self) -> bool {
+                <$float>::is_nan(self)
+            }
+
+            fn abs(self) -> Self {
+                <$float>::abs(self)
+            }
+        }
+    };
+}
+
+impl_float!(f32);
+impl_float!(f64);
+
+impl<T: Float> Complex<T> {
+    /// The number with magnitude `abs` at `angle` radians from the positive real axis.
+    pub fn from_polar(abs: T, angle: T) -> Complex<T> {
+        let (sin, cos) = angle.sin_cos();
+        Complex::new(abs * cos, abs * sin)
+    }
+
+    /// The magnitude, computed without overflow for large parts.
+    pub fn abs(self) -> T {
+        self.re.hypot(self.im)
+    }
+
+    /// The angle from the positive real axis in radians, in `[-π, π]`.
+    pub fn angle(self) -> T {
+        self.im.atan2(self.re)
+    }
+
+    /// `e` raised to this number.
+    pub fn exp(self) -> Complex<T> {
+        Complex::from_polar(self.re.exp(), self.im)
+    }
+
+    /// Whether either part is NaN.
+    pub fn is_nan(self) -> bool {
+        self.re.is_nan() || self.im.is_nan()
+    }
+}
+
+impl<T: Float> From<T> for Complex<T> {
+    fn from(re: T) -> Self {
+        Complex::new(re, T::ZERO)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Complex<T> {
+    type Output = Complex<T>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Complex::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Complex<T> {
+    type Output = Complex<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Complex::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Mul for Complex<T> {
+    type Output = Complex<T>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Complex::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re
+        )
+    }
+}
+
+/// Divides with Smith's algorithm, which scales by the ratio of the divisor's parts instead of
+/// dividing by `re² + im²`, so the result neither overflows nor underflows while it is itself
+/// representable.
+impl<T> Div for Complex<T>
+where
+    T: Float + Sub<Output = T> + Div<Output = T> + PartialOrd
+{
+    type Output = Complex<T>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let (a, b, c, d) = (self.re, self.im, rhs.re, rhs.im);
+        if c.abs() >= d.abs() {
+            let ratio = d / c;
+            let denominator = c + d * ratio;
+            Complex::new((a + b * ratio) / denominator, (b - a * ratio) / denominator)
+        } else {
+            let ratio = c / d;
+            let denominator = c * ratio + d;
+            Complex::new((a * ratio + b) / denominator, (b * ratio - a) / denominator)
+        }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Complex<T> {
+    type Output = Complex<T>;
+
+    fn neg(self) -> Self::Output {
+        Complex::new(-self.re, -self.im)
+    }
+}
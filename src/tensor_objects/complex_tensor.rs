@@ -0,0 +1,287 @@
+use std::ops::{ Add, Div, Mul, Neg, Sub };
+use crate::{ Complex, Tensor };
+use crate::parallel;
+use crate::tensor_ops::{
+    add_tensors,
+    broadcast_shape,
+    build_tensor,
+    flatten_tensor,
+    get_dimension,
+    matmul,
+    subtract_tensors
+};
+
+/// A Tensor of complex numbers. Like [crate::MmapTensor], it stores its values in row-major order
+/// with a shape, rather than nesting like [Tensor]. Real and imaginary parts convert to and from
+/// ordinary Tensors, and the arithmetic operators and [ComplexTensor::zip_with()] work element by
+/// element, broadcasting their operands like [crate::tensor_ops::broadcast_shape()].
+///
+/// [Tensor] holds `f64` values, and giving it a complex variant would break every exhaustive match
+/// on it, so complex values live in this separate container of `Complex<f64>` instead. For the
+/// same reason its element-wise operations do not go through [crate::tensor_ops::tensor_op()] and
+/// its SIMD kernels, which work on `f64`. They are split across threads with the same
+/// [crate::parallel] settings.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::{ Complex, ComplexTensor, Tensor };
+///
+/// let z = ComplexTensor::new(
+///     &Tensor::Element(vec![3.0, 0.0]),
+///     &Tensor::Element(vec![4.0, -2.0])
+/// );
+///
+/// assert_eq!(z.abs(), Tensor::Element(vec![5.0, 2.0]));
+/// assert_eq!(z.conj().imag(), Tensor::Element(vec![-4.0, 2.0]));
+///
+/// let squared = &z * &z;
+/// assert_eq!(squared.values(), &[Complex::new(-7.0, 24.0), Complex::new(-4.0, 0.0)]);
+/// ```
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct ComplexTensor {
+    values: Vec<Complex<f64>>,
+    shape: Vec<usize>,
+}
+
+impl ComplexTensor {
+    /// Combines a Tensor of real parts and a Tensor of imaginary parts.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the Tensors have different shapes.
+    pub fn new(real: &Tensor, imag: &Tensor) -> ComplexTensor {
+        let shape = get_dimension(real);
+        expect_same_shape(&shape, &get_dimension(imag));
+        let values = flatten_tensor(real).into_iter()
+            .zip(flatten_tensor(imag))
+            .map(|(re, im)| Complex::new(re, im))
+            .collect();
+        ComplexTensor { values, shape }
+    }
+
+    /// A Tensor with the given values in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the number of values does not match the shape.
+    pub fn from_values(values: Vec<Complex<f64>>, shape: &[usize]) -> ComplexTensor {
+        if values.len() != shape.iter().product::<usize>() {
+            panic!("{} values cannot fill a Tensor of shape {shape:?}!", values.len());
+        }
+        ComplexTensor { values, shape: shape.to_vec() }
+    }
+
+    /// The Tensor with imaginary parts of 0.
+    pub fn from_real(real: &Tensor) -> ComplexTensor {
+        let values = flatten_tensor(real).into_iter().map(Complex::from).collect();
+        ComplexTensor { values, shape: get_dimension(real) }
+    }
+
+    /// Builds every element from its magnitude and its angle in radians, see
+    /// [Complex::from_polar()].
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the Tensors have different shapes.
+    pub fn polar(abs: &Tensor, angle: &Tensor) -> ComplexTensor {
+        let shape = get_dimension(abs);
+        expect_same_shape(&shape, &get_dimension(angle));
+        let values = flatten_tensor(abs).into_iter()
+            .zip(flatten_tensor(angle))
+            .map(|(abs, angle)| Complex::from_polar(abs, angle))
+            .collect();
+        ComplexTensor { values, shape }
+    }
+
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// The values in row-major order.
+    pub fn values(&self) -> &[Complex<f64>] {
+        &self.values
+    }
+
+    /// The real part of every element.
+    pub fn real(&self) -> Tensor {
+        self.to_real(|z| z.re)
+    }
+
+    /// The imaginary part of every element.
+    pub fn imag(&self) -> Tensor {
+        self.to_real(|z| z.im)
+    }
+
+    /// The magnitude of every element.
+    pub fn abs(&self) -> Tensor {
+        self.to_real(Complex::abs)
+    }
+
+    /// The angle of every element in radians, in `[-π, π]`.
+    pub fn angle(&self) -> Tensor {
+        self.to_real(Complex::angle)
+    }
+
+    /// The complex conjugate of every element.
+    pub fn conj(&self) -> ComplexTensor {
+        self.map(Complex::conj)
+    }
+
+    /// Applies a function to every element.
+    pub fn map(&self, func: impl Fn(Complex<f64>) -> Complex<f64> + Sync) -> ComplexTensor {
+        let values = parallel::map_collect(self.values.len(), 1, |i| func(self.values[i]));
+        ComplexTensor { values, shape: self.shape.clone() }
+    }
+
+    /// Combines the matching elements of two Tensors, like [crate::tensor_ops::tensor_op()].
+    /// Tensors of different shapes are first broadcast to their common shape, see
+    /// [crate::tensor_ops::broadcast_shape()].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tensorium::{ Complex, ComplexTensor };
+    ///
+    /// let i = Complex::new(0.0, 1.0);
+    /// let column = ComplexTensor::from_values(vec![i, -i], &[2, 1]);
+    /// let row = ComplexTensor::from_values(vec![Complex::new(1.0, 0.0), i, -i], &[3]);
+    ///
+    /// let product = &column * &row;
+    /// assert_eq!(product.shape(), &[2, 3]);
+    /// assert_eq!(product.values()[1], Complex::new(-1.0, 0.0));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the shapes are not broadcastable.
+    pub fn zip_with(
+        &self,
+        other: &ComplexTensor,
+        func: impl Fn(Complex<f64>, Complex<f64>) -> Complex<f64> + Sync
+    ) -> ComplexTensor {
+        if self.shape == other.shape {
+            let values = parallel::map_collect(self.values.len(), 1, |i| {
+                func(self.values[i], other.values[i])
+            });
+            return ComplexTensor { values, shape: self.shape.clone() };
+        }
+
+        let shape = broadcast_shape(&self.shape, &other.shape);
+        let left = broadcast_strides(&self.shape, &shape);
+        let right = broadcast_strides(&other.shape, &shape);
+        let values = parallel::map_collect(shape.iter().product(), 1, |i| {
+            func(self.values[offset(i, &shape, &left)], other.values[offset(i, &shape, &right)])
+        });
+        ComplexTensor { values, shape }
+    }
+
+    /// Multiplies two Tensors as matrices, following the rules of [crate::tensor_ops::matmul()].
+    /// The product is computed from four real matrix products of the parts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tensorium::{ Complex, ComplexTensor };
+    ///
+    /// let i = Complex::new(0.0, 1.0);
+    /// let one = Complex::new(1.0, 0.0);
+    /// let zero = Complex::default();
+    ///
+    /// // Multiplying by i on the diagonal rotates each coordinate by a quarter turn
+    /// let a = ComplexTensor::from_values(vec![i, zero, zero, i], &[2, 2]);
+    /// let v = ComplexTensor::from_values(vec![one, i], &[2]);
+    /// assert_eq!(a.matmul(&v).values(), &[i, -one]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the inner dimensions do not match or if the batch dimensions
+    /// are not broadcastable.
+    pub fn matmul(&self, other: &ComplexTensor) -> ComplexTensor {
+        let (a, b) = (self.real(), self.imag());
+        let (c, d) = (other.real(), other.imag());
+
+        let real = subtract_tensors(&matmul(&a, &c), &matmul(&b, &d));
+        let imag = add_tensors(&matmul(&a, &d), &matmul(&b, &c));
+        ComplexTensor::new(&real, &imag)
+    }
+
+    fn to_real(&self, func: impl Fn(Complex<f64>) -> f64 + Sync) -> Tensor {
+        let values = parallel::map_collect(self.values.len(), 1, |i| func(self.values[i]));
+        build_tensor(&values, &self.shape)
+    }
+}
+
+/// The step in the values of a Tensor of `shape` for each dimension of the `output_shape` it is
+/// broadcast to, 0 along broadcast dimensions.
+fn broadcast_strides(shape: &[usize], output_shape: &[usize]) -> Vec<usize> {
+    let pad = output_shape.len() - shape.len();
+    let mut strides = vec![0; output_shape.len()];
+    let mut stride = 1;
+    for d in (pad..output_shape.len()).rev() {
+        if shape[d - pad] != 1 {
+            strides[d] = stride;
+        }
+        stride *= shape[d - pad];
+    }
+    strides
+}
+
+/// The position in the broadcast values of element `index` of a Tensor of `shape`.
+fn offset(mut index: usize, shape: &[usize], strides: &[usize]) -> usize {
+    let mut offset = 0;
+    for (&dim, &stride) in shape.iter().zip(strides).rev() {
+        offset += index % dim * stride;
+        index /= dim;
+    }
+    offset
+}
+
+fn expect_same_shape(left: &[usize], right: &[usize]) {
+    if left != right {
+        panic!("Complex Tensor shapes {left:?} and {right:?} do not match!");
+    }
+}
+
+impl From<&Tensor> for ComplexTensor {
+    fn from(tensor: &Tensor) -> Self {
+        ComplexTensor::from_real(tensor)
+    }
+}
+
+impl Neg for &ComplexTensor {
+    type Output = ComplexTensor;
+
+    fn neg(self) -> Self::Output {
+        self.map(|z| -z)
+    }
+}
+
+/// Implements an element-wise operator for owned and borrowed complex Tensors.
+macro_rules! complex_operator {
+    ($trait:ident, $method:ident) => {
+        impl $trait for &ComplexTensor {
+            type Output = ComplexTensor;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                self.zip_with(rhs, |a, b| a.$method(b))
+            }
+        }
+
+        impl $trait for ComplexTensor {
+            type Output = ComplexTensor;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                self.zip_with(&rhs, |a, b| a.$method(b))
+            }
+        }
+    };
+}
+
+complex_operator!(Add, add);
+complex_operator!(Sub, sub);
+complex_operator!(Mul, mul);
+complex_operator!(Div, div);
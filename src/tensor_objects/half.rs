@@ -0,0 +1,322 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{ Add, Div, Mul, Neg, Sub };
+use crate::tensor_io::DType;
+
+/// The 16-bit float types, the element types of [crate::HalfTensor]: [struct@f16] and [bf16].
+/// Values convert to and from `f32` and `f64` with round-to-nearest-even, and arithmetic is done
+/// in `f32` and rounded back.
+pub trait Half: Copy + Default + PartialEq + PartialOrd + fmt::Debug + Send + Sync + 'static {
+    /// The type as stored in files, see [crate::tensor_io].
+    const DTYPE: DType;
+
+    /// The value with the given bit pattern.
+    fn from_bits(bits: u16) -> Self;
+
+    /// The bit pattern of the value.
+    fn to_bits(self) -> u16;
+
+    /// The nearest value, rounding ties to even. Values beyond the largest finite value become
+    /// infinite, and NaN stays NaN.
+    fn from_f32(value: f32) -> Self;
+
+    /// Like [Half::from_f32()], rounding once from `f64` rather than twice through `f32`.
+    fn from_f64(value: f64) -> Self;
+
+    /// The exact `f32` value.
+    fn to_f32(self) -> f32;
+
+    /// The exact `f64` value.
+    fn to_f64(self) -> f64;
+}
+
+/// Implements a 16-bit float type with `$exponent` exponent bits and `$mantissa` stored mantissa
+/// bits.
+macro_rules! half_type {
+    ($name:ident, $dtype:expr, $exponent:expr, $mantissa:expr, $doc:literal) => {
+        #[doc = $doc]
+        #[allow(non_camel_case_types)]
+        #[derive(Clone, Copy)]
+        #[derive(Default)]
+        #[repr(transparent)]
+        pub struct $name(u16);
+
+        impl $name {
+            pub const ZERO: $name = $name(0);
+            pub const ONE: $name = $name(((1 << ($exponent - 1)) - 1) << $mantissa);
+            pub const INFINITY: $name = $name(EXPONENT_MASK << $mantissa);
+            pub const NEG_INFINITY: $name = $name(0x8000 | EXPONENT_MASK << $mantissa);
+            pub const NAN: $name = $name(EXPONENT_MASK << $mantissa | 1 << ($mantissa - 1));
+            /// The largest finite value.
+            pub const MAX: $name = $name((EXPONENT_MASK << $mantissa) - 1);
+            /// The smallest finite value.
+            pub const MIN: $name = $name(0x8000 | ((EXPONENT_MASK << $mantissa) - 1));
+            /// The smallest positive normal value.
+            pub const MIN_POSITIVE: $name = $name(1 << $mantissa);
+            /// The difference between 1 and the next larger value.
+            pub const EPSILON: $name = $name(((1 << ($exponent - 1)) - 1 - $mantissa) << $mantissa);
+
+            pub const fn from_bits(bits: u16) -> $name {
+                $name(bits)
+            }
+
+            pub const fn to_bits(self) -> u16 {
+                self.0
+            }
+
+            /// See [Half::from_f32()].
+            pub fn from_f32(value: f32) -> $name {
+                $name::from_f64(value as f64)
+            }
+
+            /// See [Half::from_f64()].
+            pub fn from_f64(value: f64) -> $name {
+                $name(round_bits(value, $exponent, $mantissa))
+            }
+
+            pub fn to_f32(self) -> f32 {
+                self.to_f64() as f32
+            }
+
+            pub fn to_f64(self) -> f64 {
+                bits_to_f64(self.0, $exponent, $mantissa)
+            }
+
+            pub const fn is_nan(self) -> bool {
+                self.0 & 0x7FFF > EXPONENT_MASK << $mantissa
+            }
+
+            pub const fn is_infinite(self) -> bool {
+                self.0 & 0x7FFF == EXPONENT_MASK << $mantissa
+            }
+
+            pub const fn is_finite(self) -> bool {
+                self.0 & 0x7FFF < EXPONENT_MASK << $mantissa
+            }
+
+            /// Whether the sign bit is set, including for `-0.0` and negative NaN.
+            pub const fn is_sign_negative(self) -> bool {
+                self.0 & 0x8000 != 0
+            }
+
+            pub const fn abs(self) -> $name {
+                $name(self.0 & 0x7FFF)
+            }
+        }
+
+        const EXPONENT_MASK: u16 = (1 << $exponent) - 1;
+
+        impl Half for $name {
+            const DTYPE: DType = $dtype;
+
+            fn from_bits(bits: u16) -> Self {
+                $name::from_bits(bits)
+            }
+
+            fn to_bits(self) -> u16 {
+                self.to_bits()
+            }
+
+            fn from_f32(value: f32) -> Self {
+                $name::from_f32(value)
+            }
+
+            fn from_f64(value: f64) -> Self {
+                $name::from_f64(value)
+            }
+
+            fn to_f32(self) -> f32 {
+                self.to_f32()
+            }
+
+            fn to_f64(self) -> f64 {
+                self.to_f64()
+            }
+        }
+
+        /// Compares values like floats: NaN equals nothing, and `0.0` equals `-0.0`.
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.to_f32() == other.to_f32()
+            }
+        }
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                self.to_f32().partial_cmp(&other.to_f32())
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(&self.to_f32(), f)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.to_f32(), f)
+            }
+        }
+
+        impl From<$name> for f32 {
+            fn from(value: $name) -> Self {
+                value.to_f32()
+            }
+        }
+
+        impl From<$name> for f64 {
+            fn from(value: $name) -> Self {
+                value.to_f64()
+            }
+        }
+
+        impl Neg for $name {
+            type Output = $name;
+
+            fn neg(self) -> Self::Output {
+                $name(self.0 ^ 0x8000)
+            }
+        }
+
+        half_operator!($name, Add, add, +);
+        half_operator!($name, Sub, sub, -);
+        half_operator!($name, Mul, mul, *);
+        half_operator!($name, Div, div, /);
+    };
+}
+
+/// Implements an arithmetic operator by computing in `f32`. `f32` has more than twice the
+/// precision of both types plus two bits, so rounding the `f32` result again gives the correctly
+/// rounded result.
+macro_rules! half_operator {
+    ($name:ident, $trait:ident, $method:ident, $op:tt) => {
+        impl $trait for $name {
+            type Output = $name;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                $name::from_f32(self.to_f32() $op rhs.to_f32())
+            }
+        }
+    };
+}
+
+mod binary16 {
+    use super::*;
+
+    half_type!(f16, DType::F16, 5, 10, "\
+An IEEE 754 half-precision float, with 5 exponent bits and 10 mantissa bits. It holds about 3
+decimal digits with a range of ±65504.
+
+# Examples
+
+```
+use tensorium::f16;
+
+let x = f16::from_f32(0.1);
+assert_eq!(x.to_bits(), 0x2E66);
+assert_eq!(x.to_f32(), 0.099975586);
+
+// Ties round to the value with an even mantissa
+assert_eq!(f16::from_f32(2049.0), f16::from_f32(2048.0));
+assert_eq!(f16::from_f32(2051.0), f16::from_f32(2052.0));
+
+assert!(f16::from_f32(70000.0).is_infinite());
+assert_eq!(f16::from_f32(1.5) * f16::from_f32(3.0), f16::from_f32(4.5));
+```");
+}
+
+mod brain16 {
+    use super::*;
+
+    half_type!(bf16, DType::BF16, 8, 7, "\
+A bfloat16 float, the upper half of an `f32`: 8 exponent bits and 7 mantissa bits. It has the
+range of `f32` with about 2 decimal digits.
+
+# Examples
+
+```
+use tensorium::bf16;
+
+let x = bf16::from_f32(3.14159);
+assert_eq!(x.to_bits(), 0x4049);
+assert_eq!(x.to_f32(), 3.140625);
+
+assert_eq!(bf16::from_f32(1e38).to_f32(), 9.96921e37);
+assert_eq!(bf16::from_f32(1.0) + bf16::EPSILON, bf16::from_f32(1.0078125));
+```");
+}
+
+pub use binary16::f16;
+pub use brain16::bf16;
+
+/// Rounds an `f64` to the nearest float with `exponent` exponent bits and `mantissa` stored
+/// mantissa bits, ties to even, and returns its bits.
+fn round_bits(value: f64, exponent: u32, mantissa: u32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 48) & 0x8000) as u16;
+    let infinity = (((1u64 << exponent) - 1) << mantissa) as u16;
+
+    if value.is_nan() {
+        // Keep the top of the payload and make sure the result is still a NaN
+        let payload = ((bits >> (52 - mantissa)) as u16) & ((1 << mantissa) - 1);
+        return sign | infinity | payload | 1 << (mantissa - 1);
+    }
+    if value.is_infinite() {
+        return sign | infinity;
+    }
+
+    let biased = ((bits >> 52) & 0x7FF) as i64;
+    if biased == 0 {
+        // Zero, or an f64 subnormal far below the smallest value of either type
+        return sign;
+    }
+
+    let min_exponent = 2 - (1i64 << (exponent - 1));
+    let exponent_value = biased - 1023;
+    let significand = (bits & ((1 << 52) - 1)) | 1 << 52;
+
+    // The value is significand * 2^(exponent_value - 52). Express it in units of the last place of
+    // the result, which is fixed for subnormals.
+    let unit_exponent = exponent_value.max(min_exponent) - mantissa as i64;
+    let shift = (unit_exponent - (exponent_value - 52)) as u32;
+    let units = if shift >= 54 {
+        0
+    } else {
+        let truncated = significand >> shift;
+        let remainder = significand & ((1 << shift) - 1);
+        let half = 1 << (shift - 1);
+        if remainder > half || (remainder == half && truncated & 1 == 1) {
+            truncated + 1
+        } else {
+            truncated
+        }
+    };
+
+    // Normal values carry the implicit bit in `units`, which adds the 1 of the biased exponent.
+    // A carry out of the mantissa moves to the exponent the same way.
+    let encoded = (((unit_exponent + mantissa as i64 - min_exponent) as u64) << mantissa) + units;
+    if encoded >= infinity as u64 {
+        sign | infinity
+    } else {
+        sign | encoded as u16
+    }
+}
+
+/// The exact value of the float with the given bits, see [round_bits].
+fn bits_to_f64(bits: u16, exponent: u32, mantissa: u32) -> f64 {
+    let sign = ((bits & 0x8000) as u64) << 48;
+    let biased = ((bits & 0x7FFF) >> mantissa) as i64;
+    let fraction = (bits & ((1 << mantissa) - 1)) as u64;
+    let bias = (1i64 << (exponent - 1)) - 1;
+
+    if biased == (1 << exponent) - 1 {
+        return f64::from_bits(sign | 0x7FF << 52 | fraction << (52 - mantissa));
+    }
+    if biased == 0 {
+        let scale = f64::from_bits(((1 - bias - mantissa as i64 + 1023) as u64) << 52);
+        let magnitude = fraction as f64 * scale;
+        return if sign != 0 { -magnitude } else { magnitude };
+    }
+    f64::from_bits(sign | ((biased - bias + 1023) as u64) << 52 | fraction << (52 - mantissa))
+}
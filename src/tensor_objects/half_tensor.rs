@@ -0,0 +1,179 @@
+use std::ops::{ Add, Div, Mul, Neg, Sub };
+use crate::Tensor;
+use crate::parallel;
+use crate::tensor_objects::half::Half;
+use crate::tensor_ops::{ build_tensor, flatten_tensor, get_dimension, matmul };
+
+/// A Tensor of 16-bit floats, [crate::f16] or [crate::bf16], taking a quarter of the memory of a
+/// [Tensor]. Like [crate::ComplexTensor], it stores its values in row-major order with a shape.
+///
+/// Converting from a Tensor rounds every value to the nearest 16-bit float, ties to even, and
+/// converting back is exact. The arithmetic operators and [HalfTensor::zip_with()] work element by
+/// element in `f32`, rounding each result once, split across threads like
+/// [crate::tensor_ops::tensor_op()].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::{ bf16, HalfTensor, Tensor };
+///
+/// let weights: HalfTensor<bf16> = HalfTensor::from(&Tensor::Element(vec![0.5, 1.0 / 3.0, 300.0]));
+/// assert_eq!(weights.to_tensor(), Tensor::Element(vec![0.5, 0.333984375, 300.0]));
+///
+/// let doubled = &weights + &weights;
+/// assert_eq!(doubled.to_tensor(), Tensor::Element(vec![1.0, 0.66796875, 600.0]));
+/// ```
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct HalfTensor<T: Half> {
+    values: Vec<T>,
+    shape: Vec<usize>,
+}
+
+impl<T: Half> HalfTensor<T> {
+    /// A Tensor with the given values in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the number of values does not match the shape.
+    pub fn from_values(values: Vec<T>, shape: &[usize]) -> HalfTensor<T> {
+        if values.len() != shape.iter().product::<usize>() {
+            panic!("{} values cannot fill a Tensor of shape {shape:?}!", values.len());
+        }
+        HalfTensor { values, shape: shape.to_vec() }
+    }
+
+    /// Rounds every value of a Tensor, see [Half::from_f64()].
+    pub fn from_tensor(tensor: &Tensor) -> HalfTensor<T> {
+        let data = flatten_tensor(tensor);
+        let values = parallel::map_collect(data.len(), 1, |i| T::from_f64(data[i]));
+        HalfTensor { values, shape: get_dimension(tensor) }
+    }
+
+    /// The exact values as a Tensor.
+    pub fn to_tensor(&self) -> Tensor {
+        let data = parallel::map_collect(self.values.len(), 1, |i| self.values[i].to_f64());
+        build_tensor(&data, &self.shape)
+    }
+
+    /// Rounds every value to another 16-bit type.
+    pub fn cast<U: Half>(&self) -> HalfTensor<U> {
+        let values = parallel::map_collect(self.values.len(), 1, |i| {
+            U::from_f32(self.values[i].to_f32())
+        });
+        HalfTensor { values, shape: self.shape.clone() }
+    }
+
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// The values in row-major order.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// The number of bytes taken by the values.
+    pub fn nbytes(&self) -> usize {
+        self.values.len() * 2
+    }
+
+    /// Applies a function to every element in `f32`, rounding each result.
+    pub fn map(&self, func: impl Fn(f32) -> f32 + Sync) -> HalfTensor<T> {
+        let values = parallel::map_collect(self.values.len(), 1, |i| {
+            T::from_f32(func(self.values[i].to_f32()))
+        });
+        HalfTensor { values, shape: self.shape.clone() }
+    }
+
+    /// Combines the matching elements of two Tensors of the same shape in `f32`, like
+    /// [crate::tensor_ops::tensor_op()], rounding each result.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the Tensors have different shapes.
+    pub fn zip_with(
+        &self,
+        other: &HalfTensor<T>,
+        func: impl Fn(f32, f32) -> f32 + Sync
+    ) -> HalfTensor<T> {
+        if self.shape != other.shape {
+            panic!("Half Tensor shapes {:?} and {:?} do not match!", self.shape, other.shape);
+        }
+        let values = parallel::map_collect(self.values.len(), 1, |i| {
+            T::from_f32(func(self.values[i].to_f32(), other.values[i].to_f32()))
+        });
+        HalfTensor { values, shape: self.shape.clone() }
+    }
+
+    /// Multiplies two Tensors as matrices, following the rules of [crate::tensor_ops::matmul()].
+    ///
+    /// Unlike the element-wise operations, which work in `f32`, the product runs on the `f64`
+    /// kernels of [crate::tensor_ops::matmul()], so it accumulates in `f64` rather than `f32`.
+    /// Products of two 16-bit floats are exact in `f64`, so for an inner dimension of `n` the sum
+    /// `s` differs from the exact result `x` by at most `(n - 1) * 2^-53 * Σ|a_ik * b_kj|`. `s` is
+    /// then rounded to `T`, adding a relative error of at most `2^-11` for `f16` and `2^-8` for
+    /// `bf16`, unless it overflows or is subnormal. When the terms cancel, so `|x|` is much smaller
+    /// than `Σ|a_ik * b_kj|`, the first error can exceed that rounding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tensorium::{ f16, HalfTensor, Tensor };
+    /// use tensorium::tensor_ops::build_tensor;
+    ///
+    /// let a: HalfTensor<f16> = HalfTensor::from(&build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]));
+    /// let v: HalfTensor<f16> = HalfTensor::from(&Tensor::Element(vec![0.5, -1.0]));
+    /// assert_eq!(a.matmul(&v).to_tensor(), Tensor::Element(vec![-1.5, -2.5]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the inner dimensions do not match or if the batch dimensions
+    /// are not broadcastable.
+    pub fn matmul(&self, other: &HalfTensor<T>) -> HalfTensor<T> {
+        HalfTensor::from_tensor(&matmul(&self.to_tensor(), &other.to_tensor()))
+    }
+}
+
+impl<T: Half> From<&Tensor> for HalfTensor<T> {
+    fn from(tensor: &Tensor) -> Self {
+        HalfTensor::from_tensor(tensor)
+    }
+}
+
+impl<T: Half + Neg<Output = T>> Neg for &HalfTensor<T> {
+    type Output = HalfTensor<T>;
+
+    fn neg(self) -> Self::Output {
+        let values = self.values.iter().map(|&x| -x).collect();
+        HalfTensor { values, shape: self.shape.clone() }
+    }
+}
+
+/// Implements an element-wise operator for owned and borrowed half Tensors.
+macro_rules! half_operator {
+    ($trait:ident, $method:ident) => {
+        impl<T: Half> $trait for &HalfTensor<T> {
+            type Output = HalfTensor<T>;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                self.zip_with(rhs, |a, b| a.$method(b))
+            }
+        }
+
+        impl<T: Half> $trait for HalfTensor<T> {
+            type Output = HalfTensor<T>;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                self.zip_with(&rhs, |a, b| a.$method(b))
+            }
+        }
+    };
+}
+
+half_operator!(Add, add);
+half_operator!(Sub, sub);
+half_operator!(Mul, mul);
+half_operator!(Div, div);
@@ -0,0 +1,279 @@
+use std::fs::File;
+use std::io;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+use memmap2::Mmap;
+use crate::Tensor;
+use crate::tensor_io::DType;
+use crate::tensor_io::npy::parse_npy_header;
+use crate::tensor_io::utilities::invalid_data;
+use crate::tensor_ops::build_tensor;
+
+/// A read-only Tensor backed by a memory-mapped file, for data larger than memory. Unlike
+/// [Tensor], an MmapTensor is a view of contiguous storage described by a shape, strides and an
+/// offset, so slicing and transposing only create a new view. Reading values, including with
+/// [MmapTensor::to_tensor()], only touches the pages of the file that hold them.
+///
+/// Views share the mapping, which stays open until the last view is dropped. Opening a file is
+/// `unsafe` because the file must not be modified or truncated while it is mapped, which no
+/// program can enforce against other processes.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::MmapTensor;
+/// use tensorium::tensor_ops::build_tensor;
+///
+/// let values: Vec<f64> = (0..12).map(|x| x as f64).collect();
+/// let path = std::env::temp_dir().join("tensorium_mmap_tensor_doc.npy");
+/// build_tensor(&values, &[4, 3]).save_npy(&path).unwrap();
+///
+/// // SAFETY: nothing else writes to the file while it is mapped
+/// let data = unsafe { MmapTensor::open_npy(&path) }.unwrap();
+/// assert_eq!(data.shape(), &[4, 3]);
+/// assert_eq!(data.get(&[2, 1]), Some(7.0));
+///
+/// // A minibatch of rows 1 and 2, read into an ordinary Tensor
+/// let batch = data.slice(1..3).to_tensor();
+/// assert_eq!(batch, build_tensor(&[3.0, 4.0, 5.0, 6.0, 7.0, 8.0], &[2, 3]));
+/// # drop(data);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct MmapTensor {
+    mmap: Arc<Mmap>,
+    dtype: DType,
+    little_endian: bool,
+    /// The byte offset of the first element of the view.
+    offset: usize,
+    shape: Vec<usize>,
+    /// The distance between consecutive elements of each dimension, in elements.
+    strides: Vec<usize>,
+}
+
+impl MmapTensor {
+    /// Maps a `.npy` file. Any dtype, byte order or memory order readable by
+    /// [crate::tensor_io::load_npy()] is supported, and a Fortran ordered file gives a view with
+    /// column-major strides. A 0-D array is mapped with shape \[1\].
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated, by this or any other process, until every view
+    /// of it has been dropped. Reading a changed file is undefined behavior, and reading past the
+    /// end of a truncated one can kill the process with `SIGBUS`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or mapped, is not a `.npy` file, or holds less
+    /// data than its shape requires.
+    pub unsafe fn open_npy(path: impl AsRef<Path>) -> io::Result<MmapTensor> {
+        // SAFETY: the caller keeps the file unchanged while it is mapped.
+        let mmap = unsafe { map_file(path.as_ref())? };
+        let header = parse_npy_header(&mmap)?;
+
+        let shape = if header.shape.is_empty() { vec![1] } else { header.shape };
+        let strides = if header.fortran_order { fortran_strides(&shape) } else { c_strides(&shape) };
+
+        MmapTensor::new(mmap, header.dtype, header.little_endian, header.data_offset, shape, strides)
+    }
+
+    /// Maps a file of raw values in row-major order, starting `offset` bytes into the file. The
+    /// values must be little-endian. The file may hold more data after the values.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [MmapTensor::open_npy()]: the file must not be modified or truncated until
+    /// every view of it has been dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or mapped, or is too small for the shape, or
+    /// of kind [io::ErrorKind::InvalidInput] if the shape is empty.
+    pub unsafe fn open_raw(
+        path: impl AsRef<Path>,
+        dtype: DType,
+        shape: &[usize],
+        offset: usize
+    ) -> io::Result<MmapTensor> {
+        if shape.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "An MmapTensor needs at least one dimension"));
+        }
+
+        // SAFETY: the caller keeps the file unchanged while it is mapped.
+        let mmap = unsafe { map_file(path.as_ref())? };
+        MmapTensor::new(mmap, dtype, true, offset, shape.to_vec(), c_strides(shape))
+    }
+
+    fn new(
+        mmap: Mmap,
+        dtype: DType,
+        little_endian: bool,
+        offset: usize,
+        shape: Vec<usize>,
+        strides: Vec<usize>
+    ) -> io::Result<MmapTensor> {
+        let required = shape.iter()
+            .try_fold(dtype.size(), |acc, &d| acc.checked_mul(d))
+            .and_then(|bytes| bytes.checked_add(offset));
+        match required {
+            Some(required) if required <= mmap.len() => {},
+            _ => return Err(invalid_data(format!(
+                "The file holds {} bytes, too few for shape {shape:?} of {dtype:?} at offset {offset}",
+                mmap.len()
+            ))),
+        }
+
+        Ok(MmapTensor { mmap: Arc::new(mmap), dtype, little_endian, offset, shape, strides })
+    }
+
+    /// The shape of the view.
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// The strides of the view, in elements.
+    pub fn strides(&self) -> &[usize] {
+        &self.strides
+    }
+
+    /// The element type stored in the file.
+    pub fn dtype(&self) -> DType {
+        self.dtype
+    }
+
+    /// The number of elements in the view.
+    pub fn len(&self) -> usize {
+        self.shape.iter().product()
+    }
+
+    /// Whether the view holds no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether the elements of the view are laid out back to back in row-major order.
+    pub fn is_contiguous(&self) -> bool {
+        self.strides == c_strides(&self.shape)
+    }
+
+    /// Reads the value at a full index, or `None` if the index has the wrong length or is out of
+    /// range.
+    pub fn get(&self, index: &[usize]) -> Option<f64> {
+        if index.len() != self.shape.len() || index.iter().zip(&self.shape).any(|(i, d)| i >= d) {
+            return None;
+        }
+
+        let element: usize = index.iter().zip(&self.strides).map(|(i, s)| i * s).sum();
+        Some(self.read(element))
+    }
+
+    /// A view of a range of the first dimension, like [Tensor::slice()].
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the range is out of bounds.
+    pub fn slice(&self, range: Range<usize>) -> MmapTensor {
+        self.slice_axis(0, range)
+    }
+
+    /// A view of a range of one dimension.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the axis does not exist or the range is out of bounds.
+    pub fn slice_axis(&self, axis: usize, range: Range<usize>) -> MmapTensor {
+        if axis >= self.shape.len() {
+            panic!("Axis {axis} is out of range for a Tensor with {} dimensions!", self.shape.len());
+        }
+        if range.start > range.end || range.end > self.shape[axis] {
+            panic!("Range {range:?} is out of bounds for axis {axis} of length {}!", self.shape[axis]);
+        }
+
+        let mut view = self.clone();
+        view.offset += range.start * self.strides[axis] * self.dtype.size();
+        view.shape[axis] = range.end - range.start;
+        view
+    }
+
+    /// A view with two dimensions swapped.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if either dimension does not exist.
+    pub fn transpose(&self, dim0: usize, dim1: usize) -> MmapTensor {
+        let ndim = self.shape.len();
+        if dim0 >= ndim || dim1 >= ndim {
+            panic!("Cannot transpose dimensions {dim0} and {dim1} of a Tensor with {ndim} dimensions!");
+        }
+
+        let mut view = self.clone();
+        view.shape.swap(dim0, dim1);
+        view.strides.swap(dim0, dim1);
+        view
+    }
+
+    /// Reads the view into an ordinary [Tensor] of `f64`.
+    pub fn to_tensor(&self) -> Tensor {
+        let count = self.len();
+        let size = self.dtype.size();
+
+        let values = if self.is_contiguous() {
+            let bytes = &self.mmap[self.offset..self.offset + count * size];
+            self.dtype.decode_all(bytes, self.little_endian)
+        } else {
+            let mut values = Vec::with_capacity(count);
+            let mut index = vec![0; self.shape.len()];
+            for _ in 0..count {
+                let element: usize = index.iter().zip(&self.strides).map(|(i, s)| i * s).sum();
+                values.push(self.read(element));
+
+                for d in (0..index.len()).rev() {
+                    index[d] += 1;
+                    if index[d] < self.shape[d] {
+                        break;
+                    }
+                    index[d] = 0;
+                }
+            }
+            values
+        };
+
+        build_tensor(&values, &self.shape)
+    }
+
+    /// Reads the element `element` strides past the start of the view.
+    fn read(&self, element: usize) -> f64 {
+        let start = self.offset + element * self.dtype.size();
+        self.dtype.decode(&self.mmap[start..start + self.dtype.size()], self.little_endian)
+    }
+}
+
+/// Maps a file for reading.
+///
+/// # Safety
+///
+/// The file must not be modified or truncated while the mapping exists.
+unsafe fn map_file(path: &Path) -> io::Result<Mmap> {
+    let file = File::open(path)?;
+    // SAFETY: the mapping is only ever read, and the caller upholds the condition memmap2 places
+    // on it, that the file is not modified while mapped.
+    unsafe { Mmap::map(&file) }
+}
+
+fn c_strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for d in (0..shape.len().saturating_sub(1)).rev() {
+        strides[d] = strides[d + 1] * shape[d + 1];
+    }
+    strides
+}
+
+fn fortran_strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for d in 1..shape.len() {
+        strides[d] = strides[d - 1] * shape[d - 1];
+    }
+    strides
+}
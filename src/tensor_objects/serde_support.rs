@@ -0,0 +1,188 @@
+//! Serialize and Deserialize for [Tensor], enabled by the `serde` feature.
+//!
+//! A Tensor is written as `{"shape": [2, 2], "dtype": "f64", "data": [1.0, 2.0, 3.0, 4.0]}` with
+//! the data in row-major order. Human readable formats such as JSON also accept a plain nested
+//! array like `[[1, 2], [3, 4]]` on input.
+
+use std::fmt;
+use serde::de::{ self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor };
+use serde::ser::{ Serialize, SerializeStruct, Serializer };
+use crate::Tensor;
+use crate::tensor_ops::{ build_tensor, flatten_tensor, get_dimension };
+
+const FIELDS: &[&str] = &["shape", "dtype", "data"];
+
+/// The only dtype written, since Tensors always hold `f64`.
+const DTYPE: &str = "f64";
+
+impl Serialize for Tensor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Tensor", 3)?;
+        state.serialize_field("shape", &get_dimension(self))?;
+        state.serialize_field("dtype", DTYPE)?;
+        state.serialize_field("data", &flatten_tensor(self))?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Tensor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Tensor, D::Error> {
+        // Only self-describing formats can tell a nested array apart from the struct form, so
+        // binary formats such as bincode are asked for the struct directly.
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(TensorVisitor { human_readable: true })
+        } else {
+            deserializer.deserialize_struct("Tensor", FIELDS, TensorVisitor { human_readable: false })
+        }
+    }
+}
+
+struct TensorVisitor {
+    human_readable: bool,
+}
+
+impl<'de> Visitor<'de> for TensorVisitor {
+    type Value = Tensor;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a {shape, dtype, data} map or a nested array of numbers")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Tensor, A::Error> {
+        let mut shape: Option<Vec<usize>> = None;
+        let mut data: Option<Vec<f64>> = None;
+        let mut dtype: Option<String> = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "shape" if shape.is_none() => shape = Some(map.next_value()?),
+                "data" if data.is_none() => data = Some(map.next_value()?),
+                "dtype" if dtype.is_none() => dtype = Some(map.next_value()?),
+                "shape" | "data" | "dtype" => return Err(de::Error::custom(format!("duplicate field `{key}`"))),
+                _ => return Err(de::Error::unknown_field(&key, FIELDS)),
+            }
+        }
+
+        if let Some(dtype) = dtype.filter(|d| d != DTYPE) {
+            return Err(de::Error::custom(format!("unsupported dtype `{dtype}`, expected `{DTYPE}`")));
+        }
+        let shape = shape.ok_or_else(|| de::Error::missing_field("shape"))?;
+        let data = data.ok_or_else(|| de::Error::missing_field("data"))?;
+
+        from_parts(&shape, &data)
+    }
+
+    /// Non-self-describing formats hand over the struct fields as a sequence in declaration order.
+    /// In human readable formats a sequence is a nested array.
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Tensor, A::Error> {
+        if self.human_readable {
+            return nested_to_tensor(NestedVisitor.visit_seq(seq)?);
+        }
+
+        let shape: Vec<usize> = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let dtype: String = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let data: Vec<f64> = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(2, &self))?;
+
+        if dtype != DTYPE {
+            return Err(de::Error::custom(format!("unsupported dtype `{dtype}`, expected `{DTYPE}`")));
+        }
+        from_parts(&shape, &data)
+    }
+}
+
+fn from_parts<E: de::Error>(shape: &[usize], data: &[f64]) -> Result<Tensor, E> {
+    if shape.is_empty() {
+        return Err(E::custom("a Tensor needs at least one dimension"));
+    }
+    let size = shape.iter().try_fold(1usize, |acc, &d| acc.checked_mul(d));
+    if size != Some(data.len()) {
+        return Err(E::custom(format!("shape {shape:?} does not match the {} data values", data.len())));
+    }
+    Ok(build_tensor(data, shape))
+}
+
+/// A nested array of numbers as read, before its shape is checked.
+enum Nested {
+    Number(f64),
+    List(Vec<Nested>),
+}
+
+impl<'de> Deserialize<'de> for Nested {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Nested, D::Error> {
+        deserializer.deserialize_any(NestedVisitor)
+    }
+}
+
+struct NestedVisitor;
+
+impl<'de> Visitor<'de> for NestedVisitor {
+    type Value = Nested;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a number or an array of numbers")
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Nested, E> {
+        Ok(Nested::Number(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Nested, E> {
+        Ok(Nested::Number(value as f64))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Nested, E> {
+        Ok(Nested::Number(value as f64))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Nested, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Nested::List(items))
+    }
+}
+
+fn nested_to_tensor<E: de::Error>(nested: Nested) -> Result<Tensor, E> {
+    // The shape is taken from the first element at every depth and everything else must match it.
+    let mut shape = Vec::new();
+    let mut probe = &nested;
+    while let Nested::List(items) = probe {
+        shape.push(items.len());
+        match items.first() {
+            Some(first) => probe = first,
+            None => break,
+        }
+    }
+
+    if shape.is_empty() {
+        return Err(E::custom("expected an array, found a single number"));
+    }
+
+    let mut data = Vec::with_capacity(shape.iter().product());
+    collect_nested(&nested, &shape, 0, &mut data)?;
+
+    Ok(build_tensor(&data, &shape))
+}
+
+fn collect_nested<E: de::Error>(nested: &Nested, shape: &[usize], depth: usize, data: &mut Vec<f64>) -> Result<(), E> {
+    match nested {
+        Nested::Number(value) if depth == shape.len() => {
+            data.push(*value);
+            Ok(())
+        },
+        Nested::Number(_) => Err(E::custom(format!(
+            "ragged nested array: found a number at depth {depth} where an array of length {} was expected",
+            shape[depth]
+        ))),
+        Nested::List(_) if depth == shape.len() => Err(E::custom(format!(
+            "ragged nested array: found an array at depth {depth} where a number was expected"
+        ))),
+        Nested::List(items) if items.len() != shape[depth] => Err(E::custom(format!(
+            "ragged nested array: found an array of length {} at depth {depth} where length {} was expected",
+            items.len(),
+            shape[depth]
+        ))),
+        Nested::List(items) => items.iter().try_for_each(|item| collect_nested(item, shape, depth + 1, data)),
+    }
+}
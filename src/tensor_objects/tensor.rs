@@ -1,12 +1,20 @@
 
+use std::io;
 use std::ops::{Add, Sub, Mul, Div, Rem, Range};
+use std::path::Path;
 use crate::tensor_ops::{
+    flatten_tensor,
     add_tensors,
     subtract_tensors,
     multiply_tensors,
     divide_tensors,
     remainder_tensors
 };
+use crate::lazy::LazyTensor;
+use crate::tensor_io::{
+    save_npy,
+    load_npy
+};
 
 #[derive(Debug)]
 #[derive(PartialEq)]
@@ -37,6 +45,43 @@ impl Tensor {
         }
     }
 
+    /// Writes the Tensor to a `.npy` file. See [crate::tensor_io::save_npy()].
+    pub fn save_npy(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        save_npy(self, path)
+    }
+
+    /// Reads a Tensor from a `.npy` file. See [crate::tensor_io::load_npy()].
+    pub fn load_npy(path: impl AsRef<Path>) -> io::Result<Tensor> {
+        load_npy(path)
+    }
+
+    /// Starts a lazy expression from a copy of the Tensor. See [crate::lazy].
+    pub fn lazy(&self) -> LazyTensor {
+        LazyTensor::new(self)
+    }
+
+    /// Consumes the Tensor and returns its values in row-major order. The values of an Element
+    /// Tensor are moved out without copying.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tensorium::Tensor;
+    /// use tensorium::tensor_ops::build_tensor;
+    ///
+    /// let values = Tensor::Element(vec![1.0, 2.0]).into_values();
+    /// assert_eq!(values, [1.0, 2.0]);
+    ///
+    /// let matrix = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]);
+    /// assert_eq!(matrix.into_values(), [1.0, 2.0, 3.0, 4.0]);
+    /// ```
+    pub fn into_values(self) -> Vec<f64> {
+        match self {
+            Tensor::Element(vec) => vec,
+            Tensor::Array(_) => flatten_tensor(&self),
+        }
+    }
+
 }
 
 
@@ -92,17 +92,31 @@ pub use standard_ops::{
     multiply_tensors,
     divide_tensors,
     remainder_tensors,
-    tensor_op
+    multiply_add_tensors,
+    negate_tensor,
+    abs_tensor,
+    sqrt_tensor,
+    square_tensor,
+    tensor_op,
+    map_tensor
 };
 
-mod utilities;
+pub(crate) mod simd;
+pub use simd::{
+    SimdLevel,
+    simd_level
+};
+
+pub(crate) mod utilities;
 pub use utilities::{
-    get_dimension
+    get_dimension,
+    flatten_tensor
 };
 
 mod tensor_creation;
 pub use tensor_creation::{
-    zero_tensor
+    zero_tensor,
+    build_tensor
 };
 
 mod broadcasting;
@@ -113,3 +127,80 @@ pub use broadcasting::{
     broadcast_tensor,
     expand_dims,
 };
+
+pub(crate) mod activations;
+pub use activations::{
+    GeluApproximation,
+    relu,
+    leaky_relu,
+    elu,
+    selu,
+    gelu,
+    silu,
+    swish,
+    softplus,
+    sigmoid,
+    softmax,
+    log_softmax,
+    logsumexp,
+};
+
+pub(crate) mod reductions;
+pub use reductions::{
+    sum,
+    mean,
+    max,
+    min
+};
+
+mod sorting;
+pub use sorting::{
+    SortOptions,
+    SearchSide,
+    UniqueResult,
+    sort,
+    argsort,
+    topk,
+    unique,
+    searchsorted,
+    partition,
+    argpartition
+};
+
+mod manipulation;
+pub use manipulation::{
+    PadMode,
+    pad,
+    tile,
+    repeat,
+    flip,
+    roll,
+    rot90,
+    diagonal,
+    diag_embed
+};
+
+pub(crate) mod matmul;
+pub use matmul::{
+    matmul
+};
+
+mod convolution;
+pub use convolution::{
+    PaddingMode,
+    Conv1dOptions,
+    Conv2dOptions,
+    ConvTranspose2dOptions,
+    conv1d,
+    conv2d,
+    conv_transpose2d,
+    max_pool2d,
+    avg_pool2d,
+    adaptive_avg_pool2d,
+};
+
+mod einsum;
+pub use einsum::{
+    einsum,
+    einsum_path
+};
@@ -0,0 +1,337 @@
+use std::f64::consts::PI;
+use crate::Tensor;
+use crate::tensor_ops::map_tensor;
+use crate::tensor_ops::simd::UnaryOp;
+use crate::tensor_ops::standard_ops::unary_tensor;
+use crate::tensor_ops::utilities::{ apply_along_axis, reduce_along_axis };
+
+/// The SELU constants from "Self-Normalizing Neural Networks" (Klambauer et al., 2017).
+const SELU_ALPHA: f64 = 1.673_263_242_354_377_3;
+const SELU_SCALE: f64 = 1.050_700_987_355_480_5;
+
+/// Selects how [crate::tensor_ops::gelu()] is computed.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum GeluApproximation {
+    /// `x * Φ(x)` using the exact Gaussian CDF.
+    Exact,
+    /// `0.5 * x * (1 + tanh(sqrt(2 / π) * (x + 0.044715 * x^3)))`.
+    Tanh,
+}
+
+/// Applies the rectified linear unit, `max(0, x)`, element-wise. NaN values stay NaN, as they do
+/// in [leaky_relu()].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::relu;
+///
+/// let t1 = Tensor::Element(vec![-1.0, 0.0, 2.0]);
+/// assert_eq!(relu(&t1), Tensor::Element(vec![0.0, 0.0, 2.0]));
+/// ```
+pub fn relu(tensor: &Tensor) -> Tensor {
+    unary_tensor(tensor, UnaryOp::Relu)
+}
+
+/// Applies the leaky rectified linear unit element-wise. Negative values are multiplied by
+/// `negative_slope`.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::leaky_relu;
+///
+/// let t1 = Tensor::Element(vec![-2.0, 3.0]);
+/// assert_eq!(leaky_relu(&t1, 0.1), Tensor::Element(vec![-0.2, 3.0]));
+/// ```
+pub fn leaky_relu(tensor: &Tensor, negative_slope: f64) -> Tensor {
+    map_tensor(tensor, |x| if x > 0.0 { x } else { negative_slope * x })
+}
+
+/// Applies the exponential linear unit element-wise. Negative values become
+/// `alpha * (exp(x) - 1)`.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::elu;
+///
+/// let t1 = Tensor::Element(vec![0.0, 3.0]);
+/// assert_eq!(elu(&t1, 1.0), Tensor::Element(vec![0.0, 3.0]));
+/// ```
+pub fn elu(tensor: &Tensor, alpha: f64) -> Tensor {
+    map_tensor(tensor, |x| if x > 0.0 { x } else { alpha * x.exp_m1() })
+}
+
+/// Applies the scaled exponential linear unit element-wise using the standard self-normalizing
+/// constants.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ selu, flatten_tensor };
+///
+/// let t1 = Tensor::Element(vec![1.0]);
+/// let v = flatten_tensor(&selu(&t1));
+/// assert!((v[0] - 1.0507009873554805).abs() < 1e-15);
+/// ```
+pub fn selu(tensor: &Tensor) -> Tensor {
+    map_tensor(tensor, |x| {
+        if x > 0.0 { SELU_SCALE * x } else { SELU_SCALE * SELU_ALPHA * x.exp_m1() }
+    })
+}
+
+/// Applies the Gaussian error linear unit element-wise, either exactly or with the tanh
+/// approximation.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ gelu, flatten_tensor, GeluApproximation };
+///
+/// let t1 = Tensor::Element(vec![1.0]);
+/// let exact = flatten_tensor(&gelu(&t1, GeluApproximation::Exact));
+/// let approx = flatten_tensor(&gelu(&t1, GeluApproximation::Tanh));
+///
+/// assert!((exact[0] - 0.8413447460685429).abs() < 1e-12);
+/// assert!((exact[0] - approx[0]).abs() < 1e-3);
+/// ```
+pub fn gelu(tensor: &Tensor, approximation: GeluApproximation) -> Tensor {
+    match approximation {
+        GeluApproximation::Exact => {
+            map_tensor(tensor, |x| 0.5 * x * (1.0 + erf(x / std::f64::consts::SQRT_2)))
+        },
+        GeluApproximation::Tanh => {
+            let k = (2.0 / PI).sqrt();
+            map_tensor(tensor, |x| 0.5 * x * (1.0 + (k * (x + 0.044715 * x * x * x)).tanh()))
+        }
+    }
+}
+
+/// Applies the sigmoid linear unit, `x * sigmoid(x)`, element-wise.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::silu;
+///
+/// let t1 = Tensor::Element(vec![0.0]);
+/// assert_eq!(silu(&t1), Tensor::Element(vec![0.0]));
+/// ```
+pub fn silu(tensor: &Tensor) -> Tensor {
+    map_tensor(tensor, |x| x * stable_sigmoid(x))
+}
+
+/// Alias for [crate::tensor_ops::silu()].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ silu, swish };
+///
+/// let t1 = Tensor::Element(vec![-1.0, 2.0]);
+/// assert_eq!(swish(&t1), silu(&t1));
+/// ```
+pub fn swish(tensor: &Tensor) -> Tensor {
+    silu(tensor)
+}
+
+/// Applies `ln(1 + exp(beta * x)) / beta` element-wise. The computation is rearranged so that large
+/// positive inputs do not overflow.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ softplus, flatten_tensor };
+///
+/// let t1 = Tensor::Element(vec![0.0, 1000.0]);
+/// let v = flatten_tensor(&softplus(&t1, 1.0));
+///
+/// assert!((v[0] - 2.0_f64.ln()).abs() < 1e-15);
+/// assert_eq!(v[1], 1000.0);
+/// ```
+pub fn softplus(tensor: &Tensor, beta: f64) -> Tensor {
+    map_tensor(tensor, |x| {
+        let bx = beta * x;
+        // ln(1 + e^z) = max(z, 0) + ln(1 + e^-|z|)
+        (bx.max(0.0) + (-bx.abs()).exp().ln_1p()) / beta
+    })
+}
+
+/// Applies the logistic sigmoid, `1 / (1 + exp(-x))`, element-wise without overflowing for large
+/// negative inputs.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::sigmoid;
+///
+/// let t1 = Tensor::Element(vec![0.0, -1000.0, 1000.0]);
+/// assert_eq!(sigmoid(&t1), Tensor::Element(vec![0.5, 0.0, 1.0]));
+/// ```
+pub fn sigmoid(tensor: &Tensor) -> Tensor {
+    map_tensor(tensor, stable_sigmoid)
+}
+
+/// Computes the softmax of the Tensor along `axis`. The maximum of each lane is subtracted before
+/// exponentiating so large inputs do not overflow.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::softmax;
+///
+/// let t1 = Tensor::Array(Vec::from([
+///     Tensor::Element(vec![1000.0, 1000.0]),
+///     Tensor::Element(vec![0.0, 0.0])
+/// ]));
+///
+/// assert_eq!(
+///     softmax(&t1, 1),
+///     Tensor::Array(Vec::from([
+///         Tensor::Element(vec![0.5, 0.5]),
+///         Tensor::Element(vec![0.5, 0.5])
+///     ]))
+/// );
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if `axis` is not a dimension of the Tensor.
+pub fn softmax(tensor: &Tensor, axis: usize) -> Tensor {
+    apply_along_axis(tensor, axis, |lane| {
+        let max = lane_max(lane);
+        let exps: Vec<f64> = lane.iter().map(|x| (x - max).exp()).collect();
+        let sum: f64 = exps.iter().sum();
+        exps.iter().map(|x| x / sum).collect()
+    })
+}
+
+/// Computes the log of the softmax of the Tensor along `axis` as `x - logsumexp(x)`, which stays
+/// finite where taking the log of [crate::tensor_ops::softmax()] would underflow to `-inf`.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ log_softmax, flatten_tensor };
+///
+/// let t1 = Tensor::Element(vec![0.0, 1000.0]);
+/// let v = flatten_tensor(&log_softmax(&t1, 0));
+///
+/// assert_eq!(v, vec![-1000.0, 0.0]);
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if `axis` is not a dimension of the Tensor.
+pub fn log_softmax(tensor: &Tensor, axis: usize) -> Tensor {
+    apply_along_axis(tensor, axis, |lane| {
+        let lse = lane_logsumexp(lane);
+        lane.iter().map(|x| x - lse).collect()
+    })
+}
+
+/// Computes `ln(sum(exp(x)))` along `axis`, removing that axis from the shape. Reducing a 1-D
+/// Tensor gives a Tensor of shape \[1\].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ logsumexp, flatten_tensor };
+///
+/// let t1 = Tensor::Array(Vec::from([
+///     Tensor::Element(vec![0.0, 0.0]),
+///     Tensor::Element(vec![1000.0, 1000.0])
+/// ]));
+/// let v = flatten_tensor(&logsumexp(&t1, 1));
+///
+/// assert!((v[0] - 2.0_f64.ln()).abs() < 1e-15);
+/// assert!((v[1] - (1000.0 + 2.0_f64.ln())).abs() < 1e-12);
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if `axis` is not a dimension of the Tensor.
+pub fn logsumexp(tensor: &Tensor, axis: usize) -> Tensor {
+    reduce_along_axis(tensor, axis, lane_logsumexp)
+}
+
+pub(crate) fn stable_sigmoid(x: f64) -> f64 {
+    if x >= 0.0 {
+        1.0 / (1.0 + (-x).exp())
+    } else {
+        let e = x.exp();
+        e / (1.0 + e)
+    }
+}
+
+fn lane_max(lane: &[f64]) -> f64 {
+    lane.iter().copied().fold(f64::NEG_INFINITY, f64::max)
+}
+
+fn lane_logsumexp(lane: &[f64]) -> f64 {
+    let max = lane_max(lane);
+
+    // An all -inf lane sums to zero and a +inf lane sums to infinity. Both would give NaN below.
+    if max.is_infinite() {
+        return max;
+    }
+
+    max + lane.iter().map(|x| (x - max).exp()).sum::<f64>().ln()
+}
+
+/// The error function. Small inputs use the all-positive series
+/// `erf(x) = 2/sqrt(π) * exp(-x^2) * Σ 2^n x^(2n+1) / (1·3·...·(2n+1))`, which avoids the
+/// cancellation of the Taylor series, and large inputs use the continued fraction for `erfc`.
+pub(crate) fn erf(x: f64) -> f64 {
+    if x.is_nan() {
+        return x;
+    }
+
+    let ax = x.abs();
+    let result = if ax < 3.0 {
+        let x2 = ax * ax;
+        let mut term = ax;
+        let mut sum = ax;
+        let mut n = 0.0;
+        while term > sum * 1e-17 {
+            n += 1.0;
+            term *= 2.0 * x2 / (2.0 * n + 1.0);
+            sum += term;
+        }
+        2.0 / PI.sqrt() * (-x2).exp() * sum
+    } else {
+        1.0 - erfc(ax)
+    };
+
+    if x < 0.0 { -result } else { result }
+}
+
+/// The complementary error function `1 - erf(x)`. Inputs of at least 3 use the continued fraction
+/// directly, so the small values in the upper tail keep their relative precision.
+pub(crate) fn erfc(x: f64) -> f64 {
+    if x.is_nan() || x < 3.0 {
+        return 1.0 - erf(x);
+    }
+
+    // erfc(x) = exp(-x^2) / sqrt(π) * 1 / (x + (1/2) / (x + 1 / (x + (3/2) / (x + ...))))
+    let mut fraction = x;
+    for k in (1..60).rev() {
+        fraction = x + (k as f64 / 2.0) / fraction;
+    }
+    (-x * x).exp() / PI.sqrt() / fraction
+}
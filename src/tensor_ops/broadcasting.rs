@@ -1,5 +1,6 @@
 use std::cmp::{ min, max };
 use crate:: { Tensor, TensorIndexResult };
+use crate::parallel;
 use crate::tensor_ops::get_dimension;
 
 /// Determines if two sets of dimensions are broadcastable between each other.
@@ -59,11 +60,7 @@ pub fn broadcast_shape(ldims: &Vec<usize>, rdims: &Vec<usize>) -> Vec<usize> {
 
     let longer_dim_length = max(ldims.len(), rdims.len());
 
-    let mut final_dims: Vec<usize> = Vec::new();
-
-    for _ in 0..longer_dim_length {
-        final_dims.push(1);
-    }
+    let mut final_dims: Vec<usize> = vec![1; longer_dim_length];
 
     for num in 0..longer_dim_length {
         let mut current_ldim = 1;
@@ -106,13 +103,8 @@ pub fn broadcast_shape(ldims: &Vec<usize>, rdims: &Vec<usize>) -> Vec<usize> {
 /// );
 /// ```
 pub fn expand_tensor(tensor: &Tensor, copy_value: u32) -> Tensor {
-    let mut new_tensor_vec = Vec::new();
-
-    for _ in 0..copy_value {
-        new_tensor_vec.push((*tensor).clone());
-    }
-
-    Tensor::Array(new_tensor_vec)
+    let size = get_dimension(tensor).iter().product();
+    Tensor::Array(parallel::map_collect(copy_value as usize, size, |_| tensor.clone()))
 }
 
 
@@ -179,7 +171,7 @@ pub fn expand_tensor(tensor: &Tensor, copy_value: u32) -> Tensor {
 /// never occur but due to the indexing method currently in place, we have to run a `match` call to
 /// ensure we have the correct [`tensorium::TensorIndexResult`] type.
 pub fn broadcast_tensor(tensor: &Tensor, target_shape: &Vec<usize>) -> Tensor {
-    let tensor_shape = get_dimension(&tensor);
+    let tensor_shape = get_dimension(tensor);
 
     // Check the Tensor's length against the target shape.
     if tensor_shape.len() != target_shape.len() {
@@ -187,7 +179,7 @@ pub fn broadcast_tensor(tensor: &Tensor, target_shape: &Vec<usize>) -> Tensor {
     }
 
     // Check that they are broadcastable
-    if !is_broadcastable(&tensor_shape, &target_shape) {
+    if !is_broadcastable(&tensor_shape, target_shape) {
         panic!("Tensor shapes are not broadcastable");
     }
 
@@ -199,11 +191,12 @@ pub fn broadcast_tensor(tensor: &Tensor, target_shape: &Vec<usize>) -> Tensor {
     // we need to drill deeper until we get an element. If it's an Element, we return the value.
     match tensor {
         Tensor::Array(_) => {
-            // Initialize our new Tensor
-            let mut new_vec: Vec<Tensor> = Vec::new();
+            let next_shape = target_shape.get(1..).unwrap().to_vec();
+            let child_size = next_shape.iter().product();
 
-            // For each dimension in this target dim, we need to fill our new Tensor with a value
-            for n in 0..current_target_dim {
+            // Each entry of this target dim is filled independently, so large Tensors are split
+            // across threads.
+            let new_vec = parallel::map_collect(current_target_dim, child_size, |n| {
 
                 // If the current Tensor is of shape 1 at this dimension, copy it by only using
                 // the Tensor at index[0]. Else use the Tensor at index[n]. We don't have to worry
@@ -211,7 +204,6 @@ pub fn broadcast_tensor(tensor: &Tensor, target_shape: &Vec<usize>) -> Tensor {
                 // the dimensions will be either the same or 1 here when we checked that they are
                 // broadcastable before the match call.
                 let tensor_index = if current_tensor_dim == 1 {0} else {n};
-                let next_shape = target_shape.get(1..).unwrap().to_vec();
 
                 // A Tensor::Array will only ever return a Tensor from an index operation
                 let tensor_value = match tensor.index(tensor_index) {
@@ -220,18 +212,15 @@ pub fn broadcast_tensor(tensor: &Tensor, target_shape: &Vec<usize>) -> Tensor {
                 };
 
                 // Because we have a Tensor::Array, we need to drill down to the next level by
-                // recursively calling broadcast_tensor on the next shape and next Tensor, while
-                // simultaneously pushing the result into our new Vector.
-                new_vec.push(
-                    broadcast_tensor(&tensor_value, &next_shape)
-                );
-            }
+                // recursively calling broadcast_tensor on the next shape and next Tensor.
+                broadcast_tensor(&tensor_value, &next_shape)
+            });
 
             Tensor::Array(new_vec)
         },
         Tensor::Element(_) => {
             // Initialize our new Tensor
-            let mut new_vec: Vec<f64> = Vec::new();
+            let mut new_vec: Vec<f64> = Vec::with_capacity(current_target_dim);
 
             // Loop through the target dim.
             for n in 0..current_target_dim {
@@ -0,0 +1,667 @@
+use crate::Tensor;
+use crate::memory::PoolBuffer;
+use crate::tensor_ops::{ build_tensor, flatten_tensor, get_dimension };
+use crate::tensor_ops::matmul::matmul_kernel;
+
+/// How the border of the input is filled when a convolution pads it.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum PaddingMode {
+    /// Pad with zeros.
+    Zeros,
+    /// Mirror the input without repeating the edge value, `[1, 2, 3] -> [3, 2, 1, 2, 3, 2, 1]`.
+    Reflect,
+    /// Repeat the edge value, `[1, 2, 3] -> [1, 1, 1, 2, 3, 3, 3]`.
+    Replicate,
+}
+
+/// Options for [crate::tensor_ops::conv1d()]. The defaults match an unpadded, unstrided, ungrouped
+/// convolution.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct Conv1dOptions {
+    pub stride: usize,
+    pub padding: usize,
+    pub dilation: usize,
+    pub groups: usize,
+    pub padding_mode: PaddingMode,
+}
+
+impl Default for Conv1dOptions {
+    fn default() -> Self {
+        Conv1dOptions {
+            stride: 1,
+            padding: 0,
+            dilation: 1,
+            groups: 1,
+            padding_mode: PaddingMode::Zeros,
+        }
+    }
+}
+
+/// Options for [crate::tensor_ops::conv2d()]. Every pair is given as `[height, width]`. The
+/// defaults match an unpadded, unstrided, ungrouped convolution.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct Conv2dOptions {
+    pub stride: [usize; 2],
+    pub padding: [usize; 2],
+    pub dilation: [usize; 2],
+    pub groups: usize,
+    pub padding_mode: PaddingMode,
+}
+
+impl Default for Conv2dOptions {
+    fn default() -> Self {
+        Conv2dOptions {
+            stride: [1, 1],
+            padding: [0, 0],
+            dilation: [1, 1],
+            groups: 1,
+            padding_mode: PaddingMode::Zeros,
+        }
+    }
+}
+
+/// Options for [crate::tensor_ops::conv_transpose2d()]. Every pair is given as `[height, width]`.
+/// `padding` removes rows and columns from the border of the output and `output_padding` adds
+/// them back on the bottom and right, which is needed to pick between the several output sizes
+/// that a strided convolution maps to the same input size.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct ConvTranspose2dOptions {
+    pub stride: [usize; 2],
+    pub padding: [usize; 2],
+    pub output_padding: [usize; 2],
+    pub dilation: [usize; 2],
+    pub groups: usize,
+}
+
+impl Default for ConvTranspose2dOptions {
+    fn default() -> Self {
+        ConvTranspose2dOptions {
+            stride: [1, 1],
+            padding: [0, 0],
+            output_padding: [0, 0],
+            dilation: [1, 1],
+            groups: 1,
+        }
+    }
+}
+
+/// Applies a 1-D convolution (strictly a cross-correlation, as in every ML library) over an input
+/// of shape \[N, C_in, L\] with a weight of shape \[C_out, C_in / groups, K\] and an optional bias
+/// of shape \[C_out\]. The output has shape \[N, C_out, L_out\] where
+/// `L_out = (L + 2 * padding - dilation * (K - 1) - 1) / stride + 1`.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ conv1d, Conv1dOptions };
+///
+/// // One batch, one channel: [1, 2, 3, 4]
+/// let input = Tensor::Array(Vec::from([
+///     Tensor::Array(Vec::from([Tensor::Element(vec![1.0, 2.0, 3.0, 4.0])]))
+/// ]));
+/// // A kernel that takes differences of neighbours
+/// let weight = Tensor::Array(Vec::from([
+///     Tensor::Array(Vec::from([Tensor::Element(vec![-1.0, 1.0])]))
+/// ]));
+///
+/// let output = conv1d(&input, &weight, None, &Conv1dOptions::default());
+/// assert_eq!(
+///     output,
+///     Tensor::Array(Vec::from([
+///         Tensor::Array(Vec::from([Tensor::Element(vec![1.0, 1.0, 1.0])]))
+///     ]))
+/// );
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the input or weight have the wrong number of dimensions, if the
+/// stride, dilation, kernel size or `groups` is 0, if the channels are not divisible by `groups`,
+/// or if the kernel does not fit in the padded input.
+pub fn conv1d(input: &Tensor, weight: &Tensor, bias: Option<&Tensor>, options: &Conv1dOptions) -> Tensor {
+    let input_shape = get_dimension(input);
+    let weight_shape = get_dimension(weight);
+    expect_rank(&input_shape, 3, "conv1d input");
+    expect_rank(&weight_shape, 3, "conv1d weight");
+
+    // A 1-D convolution is a 2-D convolution over an image of height one.
+    let (out, out_shape) = conv2d_core(
+        &flatten_tensor(input),
+        [input_shape[0], input_shape[1], 1, input_shape[2]],
+        &flatten_tensor(weight),
+        [weight_shape[0], weight_shape[1], 1, weight_shape[2]],
+        bias.map(flatten_tensor).as_deref(),
+        &Conv2dOptions {
+            stride: [1, options.stride],
+            padding: [0, options.padding],
+            dilation: [1, options.dilation],
+            groups: options.groups,
+            padding_mode: options.padding_mode,
+        }
+    );
+
+    build_tensor(&out, &[out_shape[0], out_shape[1], out_shape[3]])
+}
+
+/// Applies a 2-D convolution (strictly a cross-correlation, as in every ML library) over an NCHW
+/// input of shape \[N, C_in, H, W\] with a weight of shape \[C_out, C_in / groups, KH, KW\] and an
+/// optional bias of shape \[C_out\]. Each spatial output size is
+/// `(size + 2 * padding - dilation * (kernel - 1) - 1) / stride + 1`.
+///
+/// The input is unrolled into columns (im2col) so each group of each batch entry becomes a single
+/// matrix product.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ conv2d, build_tensor, Conv2dOptions, PaddingMode };
+///
+/// // A 1x1x3x3 input and a 1x1x2x2 kernel of ones
+/// let input = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0], &[1, 1, 3, 3]);
+/// let weight = build_tensor(&[1.0, 1.0, 1.0, 1.0], &[1, 1, 2, 2]);
+///
+/// let output = conv2d(&input, &weight, None, &Conv2dOptions::default());
+/// assert_eq!(output, build_tensor(&[12.0, 16.0, 24.0, 28.0], &[1, 1, 2, 2]));
+///
+/// // Replicate padding keeps the spatial size and repeats the edges
+/// let options = Conv2dOptions {
+///     padding: [1, 1],
+///     padding_mode: PaddingMode::Replicate,
+///     ..Conv2dOptions::default()
+/// };
+/// let weight = build_tensor(&[0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0], &[1, 1, 3, 3]);
+/// assert_eq!(conv2d(&input, &weight, None, &options), input);
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the input or weight have the wrong number of dimensions, if the
+/// stride, dilation, kernel size or `groups` is 0, if the channels are not divisible by `groups`,
+/// if reflect padding is not smaller than the input, or if the kernel does not fit in the padded
+/// input.
+pub fn conv2d(input: &Tensor, weight: &Tensor, bias: Option<&Tensor>, options: &Conv2dOptions) -> Tensor {
+    let input_shape = get_dimension(input);
+    let weight_shape = get_dimension(weight);
+    expect_rank(&input_shape, 4, "conv2d input");
+    expect_rank(&weight_shape, 4, "conv2d weight");
+
+    let (out, out_shape) = conv2d_core(
+        &flatten_tensor(input),
+        [input_shape[0], input_shape[1], input_shape[2], input_shape[3]],
+        &flatten_tensor(weight),
+        [weight_shape[0], weight_shape[1], weight_shape[2], weight_shape[3]],
+        bias.map(flatten_tensor).as_deref(),
+        options
+    );
+
+    build_tensor(&out, &out_shape)
+}
+
+/// Applies a 2-D transposed convolution, the gradient of [crate::tensor_ops::conv2d()] with respect
+/// to its input, over an NCHW input of shape \[N, C_in, H, W\]. The weight has shape
+/// \[C_in, C_out / groups, KH, KW\] and the optional bias has shape \[C_out\]. Each spatial output
+/// size is `(size - 1) * stride - 2 * padding + dilation * (kernel - 1) + output_padding + 1`.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::{ conv_transpose2d, build_tensor, ConvTranspose2dOptions };
+///
+/// // Each input value is spread over a 2x2 block of the output
+/// let input = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[1, 1, 2, 2]);
+/// let weight = build_tensor(&[1.0, 1.0, 1.0, 1.0], &[1, 1, 2, 2]);
+/// let options = ConvTranspose2dOptions { stride: [2, 2], ..ConvTranspose2dOptions::default() };
+///
+/// let output = conv_transpose2d(&input, &weight, None, &options);
+/// assert_eq!(
+///     output,
+///     build_tensor(&[
+///         1.0, 1.0, 2.0, 2.0,
+///         1.0, 1.0, 2.0, 2.0,
+///         3.0, 3.0, 4.0, 4.0,
+///         3.0, 3.0, 4.0, 4.0
+///     ], &[1, 1, 4, 4])
+/// );
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the input or weight have the wrong number of dimensions, if the
+/// stride, dilation, kernel size or `groups` is 0, if the channels are not divisible by `groups`,
+/// if `output_padding` is not smaller than either the stride or the dilation, or if the padding
+/// removes the whole output.
+pub fn conv_transpose2d(
+    input: &Tensor,
+    weight: &Tensor,
+    bias: Option<&Tensor>,
+    options: &ConvTranspose2dOptions
+) -> Tensor {
+    let input_shape = get_dimension(input);
+    let weight_shape = get_dimension(weight);
+    expect_rank(&input_shape, 4, "conv_transpose2d input");
+    expect_rank(&weight_shape, 4, "conv_transpose2d weight");
+
+    let (n, c_in, h, w) = (input_shape[0], input_shape[1], input_shape[2], input_shape[3]);
+    let (kh, kw) = (weight_shape[2], weight_shape[3]);
+    let groups = options.groups;
+
+    expect_at_least_one(&options.stride, "Stride");
+    expect_at_least_one(&options.dilation, "Dilation");
+    expect_at_least_one(&[kh, kw], "Kernel size");
+    expect_at_least_one(&[groups], "Groups");
+    if c_in % groups != 0 {
+        panic!("Input channels ({c_in}) must be divisible by groups ({groups})!");
+    }
+    if weight_shape[0] != c_in {
+        panic!("Weight expects {} input channels but the input has {c_in}!", weight_shape[0]);
+    }
+    for d in 0..2 {
+        let op = options.output_padding[d];
+        if op >= options.stride[d] && op >= options.dilation[d] {
+            panic!("Output padding must be smaller than either the stride or the dilation!");
+        }
+    }
+
+    let c_in_group = c_in / groups;
+    let c_out_group = weight_shape[1];
+    let c_out = c_out_group * groups;
+
+    let out_size = |size: usize, d: usize| -> usize {
+        let full = (size - 1) * options.stride[d]
+            + options.dilation[d] * ([kh, kw][d] - 1)
+            + options.output_padding[d]
+            + 1;
+        if full <= 2 * options.padding[d] {
+            panic!("Padding removes the entire output!");
+        }
+        full - 2 * options.padding[d]
+    };
+    let (ho, wo) = (out_size(h, 0), out_size(w, 1));
+
+    let x = flatten_tensor(input);
+    let wt = flatten_tensor(weight);
+    let bias = bias.map(flatten_tensor);
+    check_bias(bias.as_deref(), c_out);
+
+    let col_rows = c_out_group * kh * kw;
+    let mut out = vec![0.0; n * c_out * ho * wo];
+
+    for g in 0..groups {
+        // The group's weight is [C_in / groups, C_out / groups * KH * KW]. The columns are built
+        // from its transpose so every input pixel is scattered by one matrix product.
+        let wg = &wt[g * c_in_group * col_rows..(g + 1) * c_in_group * col_rows];
+        let mut wg_t = vec![0.0; col_rows * c_in_group];
+        for ci in 0..c_in_group {
+            for r in 0..col_rows {
+                wg_t[r * c_in_group + ci] = wg[ci * col_rows + r];
+            }
+        }
+
+        for b in 0..n {
+            let start = (b * c_in + g * c_in_group) * h * w;
+            let xg = &x[start..start + c_in_group * h * w];
+            let cols = matmul_kernel(&wg_t, xg, col_rows, c_in_group, h * w);
+
+            // col2im: add every column entry into the output pixel it lands on.
+            for oc in 0..c_out_group {
+                let plane_start = (b * c_out + g * c_out_group + oc) * ho * wo;
+                for ki in 0..kh {
+                    for kj in 0..kw {
+                        let row = (oc * kh + ki) * kw + kj;
+                        for i in 0..h {
+                            let oy = (i * options.stride[0] + ki * options.dilation[0]) as isize
+                                - options.padding[0] as isize;
+                            if oy < 0 || oy >= ho as isize {
+                                continue;
+                            }
+                            for j in 0..w {
+                                let ox = (j * options.stride[1] + kj * options.dilation[1]) as isize
+                                    - options.padding[1] as isize;
+                                if ox < 0 || ox >= wo as isize {
+                                    continue;
+                                }
+                                out[plane_start + oy as usize * wo + ox as usize] +=
+                                    cols[row * h * w + i * w + j];
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(bias) = bias {
+        add_bias(&mut out, &bias, n, c_out, ho * wo);
+    }
+
+    build_tensor(&out, &[n, c_out, ho, wo])
+}
+
+/// Applies 2-D max pooling over an NCHW input. Padding is filled with `-inf`, so padded positions
+/// never win.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::{ max_pool2d, build_tensor };
+///
+/// let input = build_tensor(&[
+///     1.0, 2.0, 5.0, 6.0,
+///     3.0, 4.0, 7.0, 8.0,
+///     9.0, 1.0, 1.0, 1.0,
+///     1.0, 1.0, 1.0, 2.0
+/// ], &[1, 1, 4, 4]);
+///
+/// let output = max_pool2d(&input, [2, 2], [2, 2], [0, 0]);
+/// assert_eq!(output, build_tensor(&[4.0, 8.0, 9.0, 2.0], &[1, 1, 2, 2]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the input is not 4-D, if the stride or kernel size is 0, if the
+/// padding is more than half of the kernel, or if the kernel does not fit in the padded input.
+pub fn max_pool2d(input: &Tensor, kernel_size: [usize; 2], stride: [usize; 2], padding: [usize; 2]) -> Tensor {
+    pool2d(input, kernel_size, stride, padding, f64::NEG_INFINITY, |window| {
+        window.iter().copied().fold(f64::NEG_INFINITY, f64::max)
+    })
+}
+
+/// Applies 2-D average pooling over an NCHW input. Padding is filled with zeros and counted in the
+/// average, so every window is divided by `kernel_size[0] * kernel_size[1]`.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::{ avg_pool2d, build_tensor };
+///
+/// let input = build_tensor(&[
+///     1.0, 2.0, 5.0, 6.0,
+///     3.0, 4.0, 7.0, 8.0,
+///     9.0, 1.0, 1.0, 1.0,
+///     1.0, 1.0, 1.0, 5.0
+/// ], &[1, 1, 4, 4]);
+///
+/// let output = avg_pool2d(&input, [2, 2], [2, 2], [0, 0]);
+/// assert_eq!(output, build_tensor(&[2.5, 6.5, 3.0, 2.0], &[1, 1, 2, 2]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the input is not 4-D, if the stride or kernel size is 0, if the
+/// padding is more than half of the kernel, or if the kernel does not fit in the padded input.
+pub fn avg_pool2d(input: &Tensor, kernel_size: [usize; 2], stride: [usize; 2], padding: [usize; 2]) -> Tensor {
+    let count = (kernel_size[0] * kernel_size[1]) as f64;
+    pool2d(input, kernel_size, stride, padding, 0.0, |window| {
+        window.iter().sum::<f64>() / count
+    })
+}
+
+/// Applies 2-D average pooling over an NCHW input so that the output has the requested spatial
+/// size. Output pixel `i` averages input rows `floor(i * H / OH)` up to `ceil((i + 1) * H / OH)`,
+/// and likewise for columns, so windows may overlap when the sizes do not divide evenly.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::{ adaptive_avg_pool2d, build_tensor };
+///
+/// let input = build_tensor(&[
+///     1.0, 2.0, 3.0,
+///     4.0, 5.0, 6.0
+/// ], &[1, 1, 2, 3]);
+///
+/// // Global average pooling
+/// let output = adaptive_avg_pool2d(&input, [1, 1]);
+/// assert_eq!(output, build_tensor(&[3.5], &[1, 1, 1, 1]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the input is not 4-D or if an output size is zero.
+pub fn adaptive_avg_pool2d(input: &Tensor, output_size: [usize; 2]) -> Tensor {
+    let shape = get_dimension(input);
+    expect_rank(&shape, 4, "adaptive_avg_pool2d input");
+    if output_size.contains(&0) {
+        panic!("Adaptive pooling output size must be positive!");
+    }
+
+    let (n, c, h, w) = (shape[0], shape[1], shape[2], shape[3]);
+    let [oh, ow] = output_size;
+    let x = flatten_tensor(input);
+
+    let mut out = Vec::with_capacity(n * c * oh * ow);
+    for plane in x.chunks(h * w) {
+        for i in 0..oh {
+            let (r0, r1) = (i * h / oh, ((i + 1) * h).div_ceil(oh));
+            for j in 0..ow {
+                let (c0, c1) = (j * w / ow, ((j + 1) * w).div_ceil(ow));
+                let mut sum = 0.0;
+                for r in r0..r1 {
+                    sum += plane[r * w + c0..r * w + c1].iter().sum::<f64>();
+                }
+                out.push(sum / ((r1 - r0) * (c1 - c0)) as f64);
+            }
+        }
+    }
+
+    build_tensor(&out, &[n, c, oh, ow])
+}
+
+/// The shared 2-D convolution on flat data. Returns the output values and their NCHW shape.
+fn conv2d_core(
+    x: &[f64],
+    input_shape: [usize; 4],
+    wt: &[f64],
+    weight_shape: [usize; 4],
+    bias: Option<&[f64]>,
+    options: &Conv2dOptions
+) -> (Vec<f64>, [usize; 4]) {
+    let [n, c_in, h, w] = input_shape;
+    let [c_out, c_in_group, kh, kw] = weight_shape;
+    let groups = options.groups;
+
+    expect_at_least_one(&options.stride, "Stride");
+    expect_at_least_one(&options.dilation, "Dilation");
+    expect_at_least_one(&[kh, kw], "Kernel size");
+    expect_at_least_one(&[groups], "Groups");
+    if c_in % groups != 0 || c_out % groups != 0 {
+        panic!("Input ({c_in}) and output ({c_out}) channels must be divisible by groups ({groups})!");
+    }
+    if c_in / groups != c_in_group {
+        panic!("Weight expects {c_in_group} channels per group but the input has {}!", c_in / groups);
+    }
+    check_bias(bias, c_out);
+
+    let (padded, hp, wp) = pad_planes(x, n * c_in, h, w, options.padding, options.padding_mode);
+
+    let kh_eff = options.dilation[0] * (kh - 1) + 1;
+    let kw_eff = options.dilation[1] * (kw - 1) + 1;
+    if kh_eff > hp || kw_eff > wp {
+        panic!("Kernel is larger than the padded input!");
+    }
+    let ho = (hp - kh_eff) / options.stride[0] + 1;
+    let wo = (wp - kw_eff) / options.stride[1] + 1;
+
+    let c_out_group = c_out / groups;
+    let col_rows = c_in_group * kh * kw;
+    let mut out = vec![0.0; n * c_out * ho * wo];
+    let mut cols = PoolBuffer::zeros(col_rows * ho * wo);
+
+    for b in 0..n {
+        for g in 0..groups {
+            // im2col: each row holds one (channel, ki, kj) tap across every output position.
+            for ci in 0..c_in_group {
+                let plane_start = (b * c_in + g * c_in_group + ci) * hp * wp;
+                let plane = &padded[plane_start..plane_start + hp * wp];
+                for ki in 0..kh {
+                    for kj in 0..kw {
+                        let row = (ci * kh + ki) * kw + kj;
+                        let col_row = &mut cols[row * ho * wo..(row + 1) * ho * wo];
+                        for oi in 0..ho {
+                            let y = oi * options.stride[0] + ki * options.dilation[0];
+                            for oj in 0..wo {
+                                let x = oj * options.stride[1] + kj * options.dilation[1];
+                                col_row[oi * wo + oj] = plane[y * wp + x];
+                            }
+                        }
+                    }
+                }
+            }
+
+            let wg = &wt[g * c_out_group * col_rows..(g + 1) * c_out_group * col_rows];
+            let result = matmul_kernel(wg, &cols, c_out_group, col_rows, ho * wo);
+
+            let start = (b * c_out + g * c_out_group) * ho * wo;
+            out[start..start + c_out_group * ho * wo].copy_from_slice(&result);
+        }
+    }
+
+    if let Some(bias) = bias {
+        add_bias(&mut out, bias, n, c_out, ho * wo);
+    }
+
+    (out, [n, c_out, ho, wo])
+}
+
+/// The shared pooling loop. `reduce` receives the values of one window, with padded positions
+/// filled by `pad_value`.
+fn pool2d(
+    input: &Tensor,
+    kernel_size: [usize; 2],
+    stride: [usize; 2],
+    padding: [usize; 2],
+    pad_value: f64,
+    reduce: impl Fn(&[f64]) -> f64
+) -> Tensor {
+    let shape = get_dimension(input);
+    expect_rank(&shape, 4, "pooling input");
+    expect_at_least_one(&stride, "Stride");
+    expect_at_least_one(&kernel_size, "Kernel size");
+    if padding[0] * 2 > kernel_size[0] || padding[1] * 2 > kernel_size[1] {
+        panic!("Pooling padding must be at most half of the kernel size!");
+    }
+
+    let (n, c, h, w) = (shape[0], shape[1], shape[2], shape[3]);
+    let (hp, wp) = (h + 2 * padding[0], w + 2 * padding[1]);
+    if kernel_size[0] > hp || kernel_size[1] > wp {
+        panic!("Kernel is larger than the padded input!");
+    }
+    let ho = (hp - kernel_size[0]) / stride[0] + 1;
+    let wo = (wp - kernel_size[1]) / stride[1] + 1;
+
+    let x = flatten_tensor(input);
+    let mut out = Vec::with_capacity(n * c * ho * wo);
+    let mut window = Vec::with_capacity(kernel_size[0] * kernel_size[1]);
+
+    for plane in x.chunks(h * w) {
+        for oi in 0..ho {
+            for oj in 0..wo {
+                window.clear();
+                for ki in 0..kernel_size[0] {
+                    for kj in 0..kernel_size[1] {
+                        let y = (oi * stride[0] + ki) as isize - padding[0] as isize;
+                        let x = (oj * stride[1] + kj) as isize - padding[1] as isize;
+                        if y < 0 || y >= h as isize || x < 0 || x >= w as isize {
+                            window.push(pad_value);
+                        } else {
+                            window.push(plane[y as usize * w + x as usize]);
+                        }
+                    }
+                }
+                out.push(reduce(&window));
+            }
+        }
+    }
+
+    build_tensor(&out, &[n, c, ho, wo])
+}
+
+/// Pads each of `planes` contiguous `h x w` planes. Returns the padded data and its new height and
+/// width.
+fn pad_planes(
+    x: &[f64],
+    planes: usize,
+    h: usize,
+    w: usize,
+    padding: [usize; 2],
+    mode: PaddingMode
+) -> (Vec<f64>, usize, usize) {
+    let [ph, pw] = padding;
+    if ph == 0 && pw == 0 {
+        return (x.to_vec(), h, w);
+    }
+    if mode == PaddingMode::Reflect && (ph >= h || pw >= w) {
+        panic!("Reflect padding must be smaller than the input size!");
+    }
+
+    let (hp, wp) = (h + 2 * ph, w + 2 * pw);
+    let mut out = vec![0.0; planes * hp * wp];
+
+    for p in 0..planes {
+        for y in 0..hp {
+            let sy = source_index(y as isize - ph as isize, h, mode);
+            for xx in 0..wp {
+                let sx = source_index(xx as isize - pw as isize, w, mode);
+                if let (Some(sy), Some(sx)) = (sy, sx) {
+                    out[p * hp * wp + y * wp + xx] = x[p * h * w + sy * w + sx];
+                }
+            }
+        }
+    }
+
+    (out, hp, wp)
+}
+
+/// Maps a possibly out of range coordinate to the input coordinate it is padded from, or `None`
+/// when the padding is zeros.
+fn source_index(i: isize, size: usize, mode: PaddingMode) -> Option<usize> {
+    let last = size as isize - 1;
+    if (0..=last).contains(&i) {
+        return Some(i as usize);
+    }
+
+    match mode {
+        PaddingMode::Zeros => None,
+        PaddingMode::Replicate => Some(i.clamp(0, last) as usize),
+        PaddingMode::Reflect => Some(if i < 0 { -i } else { 2 * last - i } as usize),
+    }
+}
+
+fn add_bias(out: &mut [f64], bias: &[f64], n: usize, c_out: usize, plane: usize) {
+    for b in 0..n {
+        for (c, value) in bias.iter().enumerate() {
+            let start = (b * c_out + c) * plane;
+            for o in out[start..start + plane].iter_mut() {
+                *o += value;
+            }
+        }
+    }
+}
+
+fn check_bias(bias: Option<&[f64]>, c_out: usize) {
+    if let Some(bias) = bias && bias.len() != c_out {
+        panic!("Bias has {} values but there are {c_out} output channels!", bias.len());
+    }
+}
+
+fn expect_at_least_one(values: &[usize], name: &str) {
+    if values.contains(&0) {
+        panic!("{name} must be at least 1!");
+    }
+}
+
+fn expect_rank(shape: &[usize], rank: usize, name: &str) {
+    if shape.len() != rank {
+        panic!("Expected a {rank}-D {name} but received shape {shape:?}!");
+    }
+}
@@ -0,0 +1,519 @@
+use std::collections::HashMap;
+use crate::Tensor;
+use crate::tensor_ops::{ broadcast_shape, build_tensor, flatten_tensor, get_dimension };
+use crate::tensor_ops::matmul::matmul_kernel;
+
+/// Ellipsis dimensions are given labels from the Unicode private use area so they can never clash
+/// with the letters of a subscript string.
+const ELLIPSIS_LABEL_START: u32 = 0xE000;
+
+/// An operand (or intermediate result) with one label per dimension.
+#[derive(Clone)]
+struct Operand {
+    labels: Vec<char>,
+    shape: Vec<usize>,
+    data: Vec<f64>,
+}
+
+/// A candidate contraction while searching for a path: the operand pair, its cost as
+/// `(result size, flops)` and the labels of the result.
+type PathStep = ((usize, usize), (usize, usize), Vec<char>);
+
+/// The parsed subscripts with every ellipsis replaced by explicit labels.
+struct Subscripts {
+    inputs: Vec<Vec<char>>,
+    output: Vec<char>,
+}
+
+/// Evaluates an Einstein summation over the operands, following __numpy__'s `einsum`.
+///
+/// The subscripts name every dimension of every operand with a letter, for example `"ij,jk->ik"`
+/// for a matrix product. Letters that appear in the output are kept and every other letter is
+/// summed over. Beyond that:
+///
+/// - **Implicit output:** without `->` the output is every letter that appears exactly once, in
+///   alphabetical order, so `"ij,jk"` is also a matrix product and `"ji"` is a transpose.
+/// - **Repeated letters:** a letter repeated within one operand takes the diagonal, so `"ii"` is
+///   the trace and `"ii->i"` is the diagonal.
+/// - **Ellipsis:** `...` stands for any number of leading dimensions. The ellipsis dimensions of all
+///   operands are broadcast together with the same rules as
+///   [crate::tensor_ops::broadcast_shape()] and, in implicit mode, come first in the output.
+///
+/// Operands are contracted two at a time. With three or more operands the order is picked greedily
+/// so every step produces the smallest possible intermediate, see
+/// [crate::tensor_ops::einsum_path()]. A result with no dimensions has shape \[1\].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ einsum, build_tensor, matmul };
+///
+/// let a = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]);
+/// let b = build_tensor(&[5.0, 6.0, 7.0, 8.0], &[2, 2]);
+///
+/// // Matrix product, explicitly and implicitly
+/// assert_eq!(einsum("ij,jk->ik", &[&a, &b]), matmul(&a, &b));
+/// assert_eq!(einsum("ij,jk", &[&a, &b]), matmul(&a, &b));
+///
+/// // Trace and diagonal
+/// assert_eq!(einsum("ii", &[&a]), Tensor::Element(vec![5.0]));
+/// assert_eq!(einsum("ii->i", &[&a]), Tensor::Element(vec![1.0, 4.0]));
+///
+/// // Batched attention scores over any leading dimensions
+/// let q = build_tensor(&[1.0, 0.0, 0.0, 1.0], &[1, 1, 2, 2]);
+/// let k = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[1, 1, 2, 2]);
+/// assert_eq!(
+///     einsum("...qd,...kd->...qk", &[&q, &k]),
+///     build_tensor(&[1.0, 3.0, 2.0, 4.0], &[1, 1, 2, 2])
+/// );
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the subscripts are malformed, if their number does not match the
+/// number of operands, if a term names a different number of dimensions than its operand has, if
+/// a letter is given different sizes, or if the ellipsis dimensions are not broadcastable.
+///
+/// ```should_panic
+/// use tensorium::tensor_ops::{ einsum, build_tensor };
+///
+/// let a = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]);
+///
+/// // j is 3 in the first operand and 2 in the second
+/// let c = einsum("ij,jk->ik", &[&a, &a]);
+/// ```
+pub fn einsum(subscripts: &str, operands: &[&Tensor]) -> Tensor {
+    let (parsed, mut remaining) = prepare(subscripts, operands);
+    let output = parsed.output;
+
+    let path = contraction_path(&remaining, &output);
+    for (i, j) in path {
+        // Remove the later index first so the earlier one stays valid.
+        let b = remaining.remove(j);
+        let a = remaining.remove(i);
+        let keep = needed_labels(remaining.iter(), &output);
+        remaining.push(contract(&a, &b, &keep));
+    }
+
+    let result = reduce(&remaining[0], &output);
+    let result = permute(&result, &output);
+
+    let shape = if result.shape.is_empty() { vec![1] } else { result.shape };
+    build_tensor(&result.data, &shape)
+}
+
+/// Returns the order in which [crate::tensor_ops::einsum()] contracts the operands, as a list of
+/// index pairs in the style of __numpy__'s `einsum_path`. At each step the two operands at the
+/// given positions are removed from the list and their contraction is appended to the end.
+///
+/// The pair chosen at each step is the one whose contraction produces the smallest intermediate,
+/// with ties broken by the fewest multiply-adds.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::{ einsum_path, zero_tensor };
+///
+/// // Contracting the two small matrices first avoids a 100x100 intermediate
+/// let a = zero_tensor(vec![100, 2]);
+/// let b = zero_tensor(vec![2, 100]);
+/// let c = zero_tensor(vec![100, 2]);
+///
+/// assert_eq!(einsum_path("ij,jk,kl->il", &[&a, &b, &c]), vec![(1, 2), (0, 1)]);
+/// ```
+///
+/// # Panics
+///
+/// This function will panic in the same cases as [crate::tensor_ops::einsum()].
+pub fn einsum_path(subscripts: &str, operands: &[&Tensor]) -> Vec<(usize, usize)> {
+    let (parsed, remaining) = prepare(subscripts, operands);
+    contraction_path(&remaining, &parsed.output)
+}
+
+/// Parses the subscripts, validates them against the operands and builds the labelled operands.
+/// Every operand is already reduced so it only holds labels that are needed later.
+fn prepare(subscripts: &str, operands: &[&Tensor]) -> (Subscripts, Vec<Operand>) {
+    let shapes: Vec<Vec<usize>> = operands.iter().map(|t| get_dimension(t)).collect();
+    let parsed = parse(subscripts, &shapes);
+
+    // Every letter must have a single size. Ellipsis labels are broadcast, so a size of 1 is
+    // expanded to the broadcast size.
+    let mut sizes: HashMap<char, usize> = HashMap::new();
+    for (labels, shape) in parsed.inputs.iter().zip(shapes.iter()) {
+        for (&label, &size) in labels.iter().zip(shape.iter()) {
+            let entry = sizes.entry(label).or_insert(size);
+            if is_ellipsis_label(label) {
+                if *entry == 1 {
+                    *entry = size;
+                }
+            } else if *entry != size {
+                panic!("Einsum letter '{label}' has mismatched sizes {} and {size}!", *entry);
+            }
+        }
+    }
+
+    let mut remaining: Vec<Operand> = Vec::new();
+    for ((labels, shape), tensor) in parsed.inputs.iter().zip(shapes.iter()).zip(operands.iter()) {
+        let operand = Operand { labels: labels.clone(), shape: shape.clone(), data: flatten_tensor(tensor) };
+        remaining.push(broadcast_ellipsis(&operand, &sizes));
+    }
+
+    for i in 0..remaining.len() {
+        let others = remaining.iter().enumerate().filter(|(j, _)| *j != i).map(|(_, op)| op);
+        let keep = needed_labels(others, &parsed.output);
+        let kept: Vec<char> = unique(&remaining[i].labels).into_iter().filter(|l| keep.contains(l)).collect();
+        remaining[i] = reduce(&remaining[i], &kept);
+    }
+
+    (parsed, remaining)
+}
+
+fn parse(subscripts: &str, shapes: &[Vec<usize>]) -> Subscripts {
+    let subscripts: String = subscripts.chars().filter(|c| !c.is_whitespace()).collect();
+    let (lhs, rhs) = match subscripts.split_once("->") {
+        Some((lhs, rhs)) => (lhs, Some(rhs)),
+        None => (subscripts.as_str(), None)
+    };
+
+    let terms: Vec<&str> = lhs.split(',').collect();
+    if terms.len() != shapes.len() {
+        panic!("Einsum subscripts name {} operands but {} were given!", terms.len(), shapes.len());
+    }
+
+    // Work out the broadcast shape of every operand's ellipsis dimensions.
+    let mut letters_per_term = Vec::new();
+    let mut ellipsis_shape: Vec<usize> = Vec::new();
+    let mut has_ellipsis = false;
+    for (term, shape) in terms.iter().zip(shapes.iter()) {
+        let (before, after) = split_ellipsis(term);
+        let letters = before.len() + after.as_ref().map_or(0, |a| a.len());
+
+        match after {
+            Some(_) => {
+                if letters > shape.len() {
+                    panic!("Einsum term '{term}' names more dimensions than its operand has!");
+                }
+                has_ellipsis = true;
+                let ellipsis_dims = shape[before.len()..shape.len() - (letters - before.len())].to_vec();
+                ellipsis_shape = broadcast_shape(&ellipsis_shape, &ellipsis_dims);
+            },
+            None => {
+                if letters != shape.len() {
+                    panic!("Einsum term '{term}' names {letters} dimensions but its operand has {}!", shape.len());
+                }
+            }
+        }
+        letters_per_term.push(letters);
+    }
+
+    let ellipsis_labels: Vec<char> = (0..ellipsis_shape.len())
+        .map(|i| char::from_u32(ELLIPSIS_LABEL_START + i as u32).unwrap())
+        .collect();
+
+    // Expand the ellipsis of each term to the trailing labels of the broadcast ellipsis.
+    let mut inputs = Vec::new();
+    for (term, shape) in terms.iter().zip(shapes.iter()) {
+        let (before, after) = split_ellipsis(term);
+        let mut labels = before.clone();
+        if let Some(after) = after {
+            let count = shape.len() - before.len() - after.len();
+            labels.extend_from_slice(&ellipsis_labels[ellipsis_labels.len() - count..]);
+            labels.extend(after);
+        }
+        inputs.push(labels);
+    }
+
+    let output = match rhs {
+        Some(rhs) => {
+            let (before, after) = split_ellipsis(rhs);
+            let mut output = before;
+            if let Some(after) = after {
+                output.extend_from_slice(&ellipsis_labels);
+                output.extend(after);
+            }
+
+            for (i, label) in output.iter().enumerate() {
+                if output[..i].contains(label) {
+                    panic!("Einsum output repeats the letter '{label}'!");
+                }
+                if !inputs.iter().any(|labels| labels.contains(label)) {
+                    panic!("Einsum output letter '{label}' does not appear in any input!");
+                }
+            }
+            output
+        },
+        None => {
+            let mut counts: HashMap<char, usize> = HashMap::new();
+            for labels in inputs.iter() {
+                for &label in labels.iter().filter(|l| !is_ellipsis_label(**l)) {
+                    *counts.entry(label).or_insert(0) += 1;
+                }
+            }
+
+            let mut letters: Vec<char> = counts.into_iter()
+                .filter(|(_, count)| *count == 1)
+                .map(|(label, _)| label)
+                .collect();
+            letters.sort();
+
+            let mut output = if has_ellipsis { ellipsis_labels.clone() } else { Vec::new() };
+            output.extend(letters);
+            output
+        }
+    };
+
+    Subscripts { inputs, output }
+}
+
+/// Splits a term into the letters before an ellipsis and, if there is one, the letters after it.
+fn split_ellipsis(term: &str) -> (Vec<char>, Option<Vec<char>>) {
+    let letters = |s: &str| -> Vec<char> {
+        s.chars()
+            .inspect(|c| {
+                if !c.is_ascii_alphabetic() {
+                    panic!("Invalid einsum subscript character '{c}'!");
+                }
+            })
+            .collect()
+    };
+
+    match term.split_once("...") {
+        Some((before, after)) => {
+            if after.contains("...") {
+                panic!("Einsum term '{term}' has more than one ellipsis!");
+            }
+            (letters(before), Some(letters(after)))
+        },
+        None => (letters(term), None)
+    }
+}
+
+fn is_ellipsis_label(label: char) -> bool {
+    label as u32 >= ELLIPSIS_LABEL_START
+}
+
+fn unique(labels: &[char]) -> Vec<char> {
+    let mut out = Vec::new();
+    for &label in labels {
+        if !out.contains(&label) {
+            out.push(label);
+        }
+    }
+    out
+}
+
+/// Labels that must survive because they are in the output or in one of the operands.
+fn needed_labels<'a>(operands: impl Iterator<Item = &'a Operand>, output: &[char]) -> Vec<char> {
+    let mut keep = output.to_vec();
+    for op in operands {
+        keep.extend_from_slice(&op.labels);
+    }
+    unique(&keep)
+}
+
+/// Expands ellipsis dimensions of size 1 to their broadcast size.
+fn broadcast_ellipsis(operand: &Operand, sizes: &HashMap<char, usize>) -> Operand {
+    let target: Vec<usize> = operand.labels.iter().map(|l| sizes[l]).collect();
+    if target == operand.shape {
+        return operand.clone();
+    }
+
+    let source_strides = strides(&operand.shape);
+    let total: usize = target.iter().product();
+    let mut data = Vec::with_capacity(total);
+    let mut index = vec![0; target.len()];
+    for _ in 0..total {
+        let offset: usize = index.iter()
+            .zip(operand.shape.iter().zip(source_strides.iter()))
+            .map(|(&i, (&dim, &stride))| if dim == 1 { 0 } else { i * stride })
+            .sum();
+        data.push(operand.data[offset]);
+        increment(&mut index, &target);
+    }
+
+    Operand { labels: operand.labels.clone(), shape: target, data }
+}
+
+/// Produces an operand with exactly the labels in `keep` (each once, in that order), taking
+/// diagonals over repeated labels and summing over every label that is not kept.
+fn reduce(operand: &Operand, keep: &[char]) -> Operand {
+    let labels = unique(&operand.labels);
+    if labels == keep && labels.len() == operand.labels.len() {
+        return operand.clone();
+    }
+
+    // Iterate over every assignment of the unique labels. A repeated label contributes the stride
+    // of each of its dimensions, which walks the diagonal.
+    let source_strides = strides(&operand.shape);
+    let mut label_sizes = Vec::new();
+    let mut label_strides = Vec::new();
+    for label in labels.iter() {
+        let mut stride = 0;
+        let mut size = 0;
+        for (d, l) in operand.labels.iter().enumerate() {
+            if l == label {
+                stride += source_strides[d];
+                size = operand.shape[d];
+            }
+        }
+        label_sizes.push(size);
+        label_strides.push(stride);
+    }
+
+    let out_shape: Vec<usize> = keep.iter()
+        .map(|k| label_sizes[labels.iter().position(|l| l == k).unwrap()])
+        .collect();
+    let out_strides = strides(&out_shape);
+    let out_stride_per_label: Vec<usize> = labels.iter()
+        .map(|l| keep.iter().position(|k| k == l).map_or(0, |p| out_strides[p]))
+        .collect();
+
+    let mut data = vec![0.0; out_shape.iter().product()];
+    let total: usize = label_sizes.iter().product();
+    let mut index = vec![0; labels.len()];
+    for _ in 0..total {
+        let mut source = 0;
+        let mut target = 0;
+        for (d, &i) in index.iter().enumerate() {
+            source += i * label_strides[d];
+            target += i * out_stride_per_label[d];
+        }
+        data[target] += operand.data[source];
+        increment(&mut index, &label_sizes);
+    }
+
+    Operand { labels: keep.to_vec(), shape: out_shape, data }
+}
+
+/// Reorders the dimensions of an operand with unique labels.
+fn permute(operand: &Operand, order: &[char]) -> Operand {
+    if operand.labels == order {
+        return operand.clone();
+    }
+
+    let source_strides = strides(&operand.shape);
+    let positions: Vec<usize> = order.iter()
+        .map(|l| operand.labels.iter().position(|x| x == l).unwrap())
+        .collect();
+    let shape: Vec<usize> = positions.iter().map(|&p| operand.shape[p]).collect();
+    let permuted_strides: Vec<usize> = positions.iter().map(|&p| source_strides[p]).collect();
+
+    let total: usize = shape.iter().product();
+    let mut data = Vec::with_capacity(total);
+    let mut index = vec![0; shape.len()];
+    for _ in 0..total {
+        let offset: usize = index.iter().zip(permuted_strides.iter()).map(|(i, s)| i * s).sum();
+        data.push(operand.data[offset]);
+        increment(&mut index, &shape);
+    }
+
+    Operand { labels: order.to_vec(), shape, data }
+}
+
+/// Contracts two reduced operands as a batched matrix product, keeping only the labels in `keep`.
+fn contract(a: &Operand, b: &Operand, keep: &[char]) -> Operand {
+    let shared: Vec<char> = a.labels.iter().filter(|l| b.labels.contains(l)).copied().collect();
+    let batch: Vec<char> = shared.iter().filter(|l| keep.contains(l)).copied().collect();
+    let summed: Vec<char> = shared.iter().filter(|l| !keep.contains(l)).copied().collect();
+    let a_free: Vec<char> = a.labels.iter().filter(|l| !shared.contains(l)).copied().collect();
+    let b_free: Vec<char> = b.labels.iter().filter(|l| !shared.contains(l)).copied().collect();
+
+    // Labels that only one side has and nobody needs are summed before the product.
+    let a_free: Vec<char> = a_free.into_iter().filter(|l| keep.contains(l)).collect();
+    let b_free: Vec<char> = b_free.into_iter().filter(|l| keep.contains(l)).collect();
+    let a = reduce(a, &[batch.clone(), a_free.clone(), summed.clone()].concat());
+    let b = reduce(b, &[batch.clone(), summed.clone(), b_free.clone()].concat());
+
+    let size_of = |op: &Operand, labels: &[char]| -> usize {
+        labels.iter().map(|l| op.shape[op.labels.iter().position(|x| x == l).unwrap()]).product()
+    };
+    let batch_size = size_of(&a, &batch);
+    let (m, k, n) = (size_of(&a, &a_free), size_of(&a, &summed), size_of(&b, &b_free));
+
+    let mut data = Vec::with_capacity(batch_size * m * n);
+    for i in 0..batch_size {
+        data.extend(matmul_kernel(
+            &a.data[i * m * k..(i + 1) * m * k],
+            &b.data[i * k * n..(i + 1) * k * n],
+            m,
+            k,
+            n
+        ));
+    }
+
+    let labels = [batch, a_free, b_free].concat();
+    let shape = labels.iter()
+        .map(|l| {
+            match a.labels.iter().position(|x| x == l) {
+                Some(p) => a.shape[p],
+                None => b.shape[b.labels.iter().position(|x| x == l).unwrap()]
+            }
+        })
+        .collect();
+
+    Operand { labels, shape, data }
+}
+
+/// Greedily picks the pair of operands whose contraction gives the smallest intermediate, with ties
+/// broken by the number of multiply-adds.
+fn contraction_path(operands: &[Operand], output: &[char]) -> Vec<(usize, usize)> {
+    let mut remaining: Vec<(Vec<char>, HashMap<char, usize>)> = operands.iter()
+        .map(|op| (op.labels.clone(), op.labels.iter().copied().zip(op.shape.iter().copied()).collect()))
+        .collect();
+
+    let mut path = Vec::new();
+    while remaining.len() > 1 {
+        let mut best: Option<PathStep> = None;
+
+        for i in 0..remaining.len() {
+            for j in i + 1..remaining.len() {
+                let mut keep = output.to_vec();
+                for (p, (labels, _)) in remaining.iter().enumerate() {
+                    if p != i && p != j {
+                        keep.extend_from_slice(labels);
+                    }
+                }
+
+                let union = unique(&[remaining[i].0.clone(), remaining[j].0.clone()].concat());
+                let size = |l: &char| remaining[i].1.get(l).or(remaining[j].1.get(l)).copied().unwrap();
+                let result: Vec<char> = union.iter().filter(|l| keep.contains(l)).copied().collect();
+                let result_size: usize = result.iter().map(size).product();
+                let flops: usize = union.iter().map(size).product();
+
+                if best.as_ref().is_none_or(|(_, cost, _)| (result_size, flops) < *cost) {
+                    best = Some(((i, j), (result_size, flops), result));
+                }
+            }
+        }
+
+        let ((i, j), _, result) = best.unwrap();
+        let mut sizes = remaining[i].1.clone();
+        sizes.extend(remaining[j].1.iter());
+        remaining.remove(j);
+        remaining.remove(i);
+        remaining.push((result, sizes));
+        path.push((i, j));
+    }
+
+    path
+}
+
+fn strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for d in (0..shape.len().saturating_sub(1)).rev() {
+        strides[d] = strides[d + 1] * shape[d + 1];
+    }
+    strides
+}
+
+/// Advances a row-major multi-index by one.
+fn increment(index: &mut [usize], shape: &[usize]) {
+    for d in (0..index.len()).rev() {
+        index[d] += 1;
+        if index[d] < shape[d] {
+            return;
+        }
+        index[d] = 0;
+    }
+}
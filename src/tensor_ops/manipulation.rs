@@ -0,0 +1,437 @@
+use std::iter::repeat_n;
+use crate::Tensor;
+use crate::parallel;
+use crate::tensor_ops::{ build_tensor, flatten_tensor, get_dimension };
+use crate::tensor_ops::utilities::axis_layout;
+
+/// How [pad()] fills the new elements.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum PadMode {
+    /// A constant value, `[1, 2, 3] -> [0, 0, 1, 2, 3, 0, 0]` for 0.
+    Constant(f64),
+    /// Mirror the Tensor without repeating the edge value, `[1, 2, 3] -> [3, 2, 1, 2, 3, 2, 1]`.
+    Reflect,
+    /// Repeat the edge value, `[1, 2, 3] -> [1, 1, 1, 2, 3, 3, 3]`.
+    Edge,
+    /// Continue from the other end, `[1, 2, 3] -> [2, 3, 1, 2, 3, 1, 2]`.
+    Wrap,
+}
+
+impl Default for PadMode {
+    fn default() -> Self {
+        PadMode::Constant(0.0)
+    }
+}
+
+/// Pads every axis of a Tensor with `widths[axis] = (before, after)` new elements, filled as
+/// `mode` says. Unlike the padding of [crate::tensor_ops::conv2d()], it works on any axes.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ build_tensor, pad, PadMode };
+///
+/// let t = Tensor::Element(vec![1.0, 2.0, 3.0]);
+/// let reflected = pad(&t, &[(2, 1)], PadMode::Reflect);
+/// assert_eq!(reflected, Tensor::Element(vec![3.0, 2.0, 1.0, 2.0, 3.0, 2.0]));
+///
+/// let wrapped = pad(&t, &[(1, 2)], PadMode::Wrap);
+/// assert_eq!(wrapped, Tensor::Element(vec![3.0, 1.0, 2.0, 3.0, 1.0, 2.0]));
+///
+/// // A border of -1 around a matrix
+/// let m = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]);
+/// let padded = pad(&m, &[(1, 0), (0, 1)], PadMode::Constant(-1.0));
+/// assert_eq!(padded, build_tensor(&[-1.0, -1.0, -1.0, 1.0, 2.0, -1.0, 3.0, 4.0, -1.0], &[3, 3]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if there is not one pair of widths per axis, if a reflected width is
+/// not smaller than its axis, or if an empty axis is padded with anything but a constant.
+pub fn pad(tensor: &Tensor, widths: &[(usize, usize)], mode: PadMode) -> Tensor {
+    let shape = get_dimension(tensor);
+    if widths.len() != shape.len() {
+        panic!(
+            "Padding needs one (before, after) pair per axis, got {} for a Tensor with {} \
+                dimensions!",
+            widths.len(),
+            shape.len()
+        );
+    }
+    for (axis, (&len, &(before, after))) in shape.iter().zip(widths).enumerate() {
+        let width = before.max(after);
+        if width == 0 {
+            continue;
+        }
+        if len == 0 && !matches!(mode, PadMode::Constant(_)) {
+            panic!("Cannot pad the empty axis {axis} with {mode:?}!");
+        }
+        if mode == PadMode::Reflect && width >= len {
+            panic!("Reflect padding of {width} must be smaller than the length {len} of axis \
+                {axis}!");
+        }
+    }
+
+    let out_shape: Vec<usize> = shape.iter()
+        .zip(widths)
+        .map(|(len, (before, after))| before + len + after)
+        .collect();
+    let fill = if let PadMode::Constant(value) = mode { value } else { 0.0 };
+
+    remap_each_axis(tensor, &out_shape, fill, |axis, index| {
+        let (i, len) = (index as isize - widths[axis].0 as isize, shape[axis] as isize);
+        if (0..len).contains(&i) {
+            return Some(i as usize);
+        }
+        let i = match mode {
+            PadMode::Constant(_) => return None,
+            PadMode::Reflect => if i < 0 { -i } else { 2 * (len - 1) - i },
+            PadMode::Edge => i.clamp(0, len - 1),
+            PadMode::Wrap => i.rem_euclid(len),
+        };
+        Some(i as usize)
+    })
+}
+
+/// Repeats a whole Tensor `reps[axis]` times along each axis. If `reps` is longer than the shape,
+/// the Tensor gains leading axes of length 1 first, and if it is shorter, the missing leading
+/// repetitions are 1, as in __numpy__.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ build_tensor, tile };
+///
+/// let t = Tensor::Element(vec![1.0, 2.0]);
+/// assert_eq!(tile(&t, &[3]), Tensor::Element(vec![1.0, 2.0, 1.0, 2.0, 1.0, 2.0]));
+/// assert_eq!(tile(&t, &[2, 2]), build_tensor(&[1.0, 2.0, 1.0, 2.0, 1.0, 2.0, 1.0, 2.0], &[2, 4]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if any repetition is 0.
+pub fn tile(tensor: &Tensor, reps: &[usize]) -> Tensor {
+    if reps.contains(&0) {
+        panic!("Tile repetitions must be at least 1, got {reps:?}!");
+    }
+
+    let mut shape = get_dimension(tensor);
+    let ndim = shape.len().max(reps.len());
+    let mut full_reps = vec![1; ndim - reps.len()];
+    full_reps.extend_from_slice(reps);
+    let mut full_shape = vec![1; ndim - shape.len()];
+    full_shape.append(&mut shape);
+
+    let out_shape: Vec<usize> = full_shape.iter()
+        .zip(&full_reps)
+        .map(|(len, rep)| len * rep)
+        .collect();
+    let tensor = build_tensor(&flatten_tensor(tensor), &full_shape);
+    remap_each_axis(&tensor, &out_shape, 0.0, |axis, index| Some(index % full_shape[axis]))
+}
+
+/// Repeats every element of a Tensor `repeats` times in a row along an axis, which grows that
+/// axis `repeats` times. Without an axis the Tensor is flattened first and the result is 1-D.
+/// Where [crate::tensor_ops::expand_tensor()] stacks copies of a whole Tensor on a new axis,
+/// this repeats within an existing one.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ build_tensor, repeat };
+///
+/// let t = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]);
+/// let flat = repeat(&t, 2, None);
+/// assert_eq!(flat, Tensor::Element(vec![1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0]));
+///
+/// let rows = repeat(&t, 2, Some(0));
+/// assert_eq!(rows, build_tensor(&[1.0, 2.0, 1.0, 2.0, 3.0, 4.0, 3.0, 4.0], &[4, 2]));
+///
+/// let columns = repeat(&t, 2, Some(1));
+/// assert_eq!(columns, build_tensor(&[1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0], &[2, 4]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if `repeats` is 0 or the axis is out of range.
+pub fn repeat(tensor: &Tensor, repeats: usize, axis: Option<usize>) -> Tensor {
+    if repeats == 0 {
+        panic!("Repeat count must be at least 1!");
+    }
+
+    let Some(axis) = axis else {
+        let values = flatten_tensor(tensor);
+        return Tensor::Element(values.into_iter().flat_map(|x| repeat_n(x, repeats)).collect());
+    };
+
+    let mut out_shape = get_dimension(tensor);
+    axis_layout(&out_shape, axis);
+    out_shape[axis] *= repeats;
+    remap_each_axis(tensor, &out_shape, 0.0, |a, index| {
+        Some(if a == axis { index / repeats } else { index })
+    })
+}
+
+/// Reverses the order of the elements of a Tensor along each of the given axes.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::{ build_tensor, flip };
+///
+/// let t = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]);
+/// assert_eq!(flip(&t, &[1]), build_tensor(&[3.0, 2.0, 1.0, 6.0, 5.0, 4.0], &[2, 3]));
+/// assert_eq!(flip(&t, &[0, 1]), build_tensor(&[6.0, 5.0, 4.0, 3.0, 2.0, 1.0], &[2, 3]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if an axis is out of range or given more than once.
+pub fn flip(tensor: &Tensor, axes: &[usize]) -> Tensor {
+    let shape = get_dimension(tensor);
+    check_axes(&shape, axes);
+
+    remap_each_axis(tensor, &shape, 0.0, |axis, index| {
+        Some(if axes.contains(&axis) { shape[axis] - 1 - index } else { index })
+    })
+}
+
+/// Shifts the elements of a Tensor `shift` places along an axis, wrapping the elements that fall
+/// off one end around to the other. A negative shift moves them towards the start. Without an
+/// axis the Tensor is shifted as if it were flat and keeps its shape.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ build_tensor, roll };
+///
+/// let t = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]);
+/// assert_eq!(roll(&t, 1, Some(1)), build_tensor(&[3.0, 1.0, 2.0, 6.0, 4.0, 5.0], &[2, 3]));
+/// assert_eq!(roll(&t, -1, None), build_tensor(&[2.0, 3.0, 4.0, 5.0, 6.0, 1.0], &[2, 3]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range.
+pub fn roll(tensor: &Tensor, shift: isize, axis: Option<usize>) -> Tensor {
+    let shape = get_dimension(tensor);
+    let Some(axis) = axis else {
+        let values = flatten_tensor(tensor);
+        let len = values.len() as isize;
+        let rolled: Vec<f64> = (0..len)
+            .map(|i| values[(i - shift).rem_euclid(len) as usize])
+            .collect();
+        return build_tensor(&rolled, &shape);
+    };
+
+    let len = axis_layout(&shape, axis).1 as isize;
+    remap_each_axis(tensor, &shape, 0.0, |a, index| {
+        Some(if a == axis { (index as isize - shift).rem_euclid(len) as usize } else { index })
+    })
+}
+
+/// Rotates a Tensor by 90 degrees `k` times in the plane of two axes, turning from the first axis
+/// towards the second, as in __numpy__. A negative `k` turns the other way. The two axes swap
+/// lengths for an odd `k`.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::{ build_tensor, rot90 };
+///
+/// // 1 2      2 4
+/// // 3 4  ->  1 3
+/// let t = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]);
+/// assert_eq!(rot90(&t, 1, [0, 1]), build_tensor(&[2.0, 4.0, 1.0, 3.0], &[2, 2]));
+/// assert_eq!(rot90(&t, -1, [0, 1]), build_tensor(&[3.0, 1.0, 4.0, 2.0], &[2, 2]));
+/// assert_eq!(rot90(&t, 2, [0, 1]), build_tensor(&[4.0, 3.0, 2.0, 1.0], &[2, 2]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if an axis is out of range or the two axes are the same.
+pub fn rot90(tensor: &Tensor, k: isize, axes: [usize; 2]) -> Tensor {
+    let shape = get_dimension(tensor);
+    check_axes(&shape, &axes);
+    let [a, b] = axes;
+    let (len_a, len_b) = (shape[a], shape[b]);
+    let turns = k.rem_euclid(4);
+
+    let mut out_shape = shape.clone();
+    if turns % 2 == 1 {
+        out_shape.swap(a, b);
+    }
+    let strides = strides(&shape);
+    remap(&flatten_tensor(tensor), &out_shape, 0.0, |index| {
+        let mut source = index.to_vec();
+        (source[a], source[b]) = match turns {
+            0 => (index[a], index[b]),
+            1 => (index[b], len_b - 1 - index[a]),
+            2 => (len_a - 1 - index[a], len_b - 1 - index[b]),
+            _ => (len_a - 1 - index[b], index[a]),
+        };
+        Some(source.iter().zip(&strides).map(|(i, stride)| i * stride).sum())
+    })
+}
+
+/// The diagonal of the matrices formed by two axes of a Tensor. Those axes are removed and the
+/// diagonal becomes the last axis, so a matrix gives a 1-D Tensor. A positive `offset` takes a
+/// diagonal above the main one, and a negative one below it.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ build_tensor, diagonal };
+///
+/// let m = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0], &[3, 3]);
+/// assert_eq!(diagonal(&m, 0, 0, 1), Tensor::Element(vec![1.0, 5.0, 9.0]));
+/// assert_eq!(diagonal(&m, 1, 0, 1), Tensor::Element(vec![2.0, 6.0]));
+/// assert_eq!(diagonal(&m, -2, 0, 1), Tensor::Element(vec![7.0]));
+///
+/// // The diagonals of a batch of two 2x2 matrices
+/// let batch = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0], &[2, 2, 2]);
+/// assert_eq!(diagonal(&batch, 0, 1, 2), build_tensor(&[1.0, 4.0, 5.0, 8.0], &[2, 2]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if an axis is out of range, if the two axes are the same, or if the
+/// offset leaves no diagonal.
+pub fn diagonal(tensor: &Tensor, offset: isize, axis1: usize, axis2: usize) -> Tensor {
+    let shape = get_dimension(tensor);
+    check_axes(&shape, &[axis1, axis2]);
+    let (rows, columns) = (shape[axis1] as isize, shape[axis2] as isize);
+    let len = if offset >= 0 { rows.min(columns - offset) } else { (rows + offset).min(columns) };
+    if len <= 0 {
+        panic!("Offset {offset} leaves no diagonal in a {rows}x{columns} matrix!");
+    }
+
+    let mut out_shape: Vec<usize> = shape.iter()
+        .enumerate()
+        .filter(|(axis, _)| *axis != axis1 && *axis != axis2)
+        .map(|(_, &len)| len)
+        .collect();
+    out_shape.push(len as usize);
+
+    let strides = strides(&shape);
+    let (row_start, column_start) = (offset.min(0).unsigned_abs(), offset.max(0) as usize);
+    remap(&flatten_tensor(tensor), &out_shape, 0.0, |index| {
+        let (batch, i) = index.split_at(index.len() - 1);
+        let mut batch = batch.iter();
+        Some((0..shape.len())
+            .map(|axis| {
+                let position = if axis == axis1 {
+                    row_start + i[0]
+                } else if axis == axis2 {
+                    column_start + i[0]
+                } else {
+                    *batch.next().unwrap()
+                };
+                position * strides[axis]
+            })
+            .sum())
+    })
+}
+
+/// Builds matrices with the last axis of a Tensor on a diagonal, and zeros elsewhere. The last
+/// axis of length `n` becomes two axes of length `n + |offset|`, with the values on the diagonal
+/// above the main one for a positive `offset` and below it for a negative one. This is the inverse
+/// of [diagonal()] over the last two axes.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ build_tensor, diag_embed };
+///
+/// let t = Tensor::Element(vec![1.0, 2.0]);
+/// assert_eq!(diag_embed(&t, 0), build_tensor(&[1.0, 0.0, 0.0, 2.0], &[2, 2]));
+///
+/// let below = diag_embed(&t, -1);
+/// assert_eq!(below, build_tensor(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 2.0, 0.0], &[3, 3]));
+/// ```
+pub fn diag_embed(tensor: &Tensor, offset: isize) -> Tensor {
+    let shape = get_dimension(tensor);
+    let n = shape[shape.len() - 1];
+    let size = n + offset.unsigned_abs();
+
+    let mut out_shape = shape.clone();
+    out_shape.push(size);
+    *out_shape.iter_mut().rev().nth(1).unwrap() = size;
+
+    remap(&flatten_tensor(tensor), &out_shape, 0.0, |index| {
+        let (batch, matrix) = index.split_at(index.len() - 2);
+        let (i, j) = (matrix[0], matrix[1]);
+        if j as isize - i as isize != offset {
+            return None;
+        }
+        let batch_start: usize = batch.iter()
+            .zip(&shape)
+            .fold(0, |flat, (&index, &len)| flat * len + index);
+        Some(batch_start * n + i.min(j))
+    })
+}
+
+/// Row-major strides of a shape, in elements.
+fn strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for axis in (0..shape.len().saturating_sub(1)).rev() {
+        strides[axis] = strides[axis + 1] * shape[axis + 1];
+    }
+    strides
+}
+
+/// Checks that every axis is in range and appears once.
+fn check_axes(shape: &[usize], axes: &[usize]) {
+    for (position, &axis) in axes.iter().enumerate() {
+        axis_layout(shape, axis);
+        if axes[..position].contains(&axis) {
+            panic!("Axis {axis} is given more than once!");
+        }
+    }
+}
+
+/// Builds a Tensor of `out_shape` whose index along each axis is read from the index
+/// `source(axis, index)` of that axis of the Tensor, or is `fill` if any axis gives `None`.
+fn remap_each_axis(
+    tensor: &Tensor,
+    out_shape: &[usize],
+    fill: f64,
+    source: impl Fn(usize, usize) -> Option<usize> + Sync
+) -> Tensor {
+    let strides = strides(&get_dimension(tensor));
+    remap(&flatten_tensor(tensor), out_shape, fill, |index| {
+        index.iter()
+            .enumerate()
+            .map(|(axis, &i)| source(axis, i).map(|i| i * strides[axis]))
+            .sum()
+    })
+}
+
+/// Builds a Tensor of `shape` whose element at each multi-index is the flat element
+/// `source(index)` of `values`, or `fill` where `source` gives `None`.
+fn remap(
+    values: &[f64],
+    shape: &[usize],
+    fill: f64,
+    source: impl Fn(&[usize]) -> Option<usize> + Sync
+) -> Tensor {
+    let count = shape.iter().product();
+    let out = parallel::map_collect(count, shape.len(), |flat| {
+        let mut index = vec![0; shape.len()];
+        let mut rest = flat;
+        for (i, &len) in index.iter_mut().zip(shape).rev() {
+            *i = rest % len;
+            rest /= len;
+        }
+        source(&index).map_or(fill, |i| values[i])
+    });
+    build_tensor(&out, shape)
+}
@@ -0,0 +1,165 @@
+use crate::Tensor;
+use crate::parallel;
+use crate::tensor_ops::{ broadcast_shape, build_tensor, flatten_tensor, get_dimension };
+
+/// Multiplies two Tensors as matrices, following __numpy__'s `matmul` rules:
+///
+/// - Two 2-D Tensors are multiplied as ordinary matrices.
+/// - A 1-D left Tensor is treated as a row vector and a 1-D right Tensor as a column vector. The
+///   added dimension is removed from the result afterwards.
+/// - Any dimensions in front of the last two are batch dimensions. They are broadcast against each
+///   other and a matrix product is taken for every batch entry.
+///
+/// Because tensorium has no 0-D Tensors, the dot product of two 1-D Tensors has shape \[1\].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::matmul;
+///
+/// let t1 = Tensor::Array(Vec::from([
+///     Tensor::Element(vec![1.0, 2.0]),
+///     Tensor::Element(vec![3.0, 4.0])
+/// ]));
+/// let t2 = Tensor::Array(Vec::from([
+///     Tensor::Element(vec![5.0, 6.0]),
+///     Tensor::Element(vec![7.0, 8.0])
+/// ]));
+///
+/// assert_eq!(
+///     matmul(&t1, &t2),
+///     Tensor::Array(Vec::from([
+///         Tensor::Element(vec![19.0, 22.0]),
+///         Tensor::Element(vec![43.0, 50.0])
+///     ]))
+/// );
+///
+/// // Matrix-vector products drop the vector dimension
+/// let v = Tensor::Element(vec![1.0, 1.0]);
+/// assert_eq!(matmul(&t1, &v), Tensor::Element(vec![3.0, 7.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the inner dimensions do not match or if the batch dimensions are
+/// not broadcastable.
+///
+/// ```should_panic
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::matmul;
+///
+/// // [2] @ [3] has mismatched inner dimensions
+/// let t1 = Tensor::Element(vec![1.0, 2.0]);
+/// let t2 = Tensor::Element(vec![1.0, 2.0, 3.0]);
+/// let t3 = matmul(&t1, &t2);
+/// ```
+pub fn matmul(ltensor: &Tensor, rtensor: &Tensor) -> Tensor {
+    let mut lshape = get_dimension(ltensor);
+    let mut rshape = get_dimension(rtensor);
+
+    let l_vector = lshape.len() == 1;
+    let r_vector = rshape.len() == 1;
+    if l_vector {
+        lshape.insert(0, 1);
+    }
+    if r_vector {
+        rshape.push(1);
+    }
+
+    let (m, k) = (lshape[lshape.len() - 2], lshape[lshape.len() - 1]);
+    let (k2, n) = (rshape[rshape.len() - 2], rshape[rshape.len() - 1]);
+    if k != k2 {
+        panic!("Inner dimensions do not match for matmul! ({k} vs {k2})");
+    }
+
+    let lbatch = lshape[..lshape.len() - 2].to_vec();
+    let rbatch = rshape[..rshape.len() - 2].to_vec();
+    let batch = broadcast_shape(&lbatch, &rbatch);
+    let batch_size: usize = batch.iter().product();
+
+    let lvalues = flatten_tensor(ltensor);
+    let rvalues = flatten_tensor(rtensor);
+
+    // Rows of every batch entry are spread across threads together, so a large batch of small
+    // matrices is split as well as a single large matrix
+    let mut out = vec![0.0; batch_size * m * n];
+    let rows = parallel::items_per_chunk(k * n);
+    parallel::for_each_chunk_mut(&mut out, rows * n, |start, piece| {
+        // The output is only split when it is not empty, so n is not 0 here
+        let first_row = start / n;
+        for (r, out_row) in piece.chunks_mut(n).enumerate() {
+            let (b, i) = ((first_row + r) / m, (first_row + r) % m);
+            let lrow = broadcast_offset(b, &batch, &lbatch) * m + i;
+            let rb = broadcast_offset(b, &batch, &rbatch);
+            let b_matrix = &rvalues[rb * k * n..(rb + 1) * k * n];
+            matmul_row(&lvalues[lrow * k..(lrow + 1) * k], b_matrix, n, out_row);
+        }
+    });
+
+    let mut shape = batch;
+    if !l_vector {
+        shape.push(m);
+    }
+    if !r_vector {
+        shape.push(n);
+    }
+    if shape.is_empty() {
+        shape.push(1);
+    }
+
+    build_tensor(&out, &shape)
+}
+
+/// Multiplies a row-major `m x k` matrix by a row-major `k x n` matrix. Large products are split
+/// across threads by rows, and each row is computed the same way whatever the thread count.
+pub(crate) fn matmul_kernel(a: &[f64], b: &[f64], m: usize, k: usize, n: usize) -> Vec<f64> {
+    let mut out = vec![0.0; m * n];
+    let rows = parallel::items_per_chunk(k * n);
+    parallel::for_each_chunk_mut(&mut out, rows * n, |start, piece| {
+        // The output is only split when it is not empty, so n is not 0 here
+        let first_row = start / n;
+        for (r, out_row) in piece.chunks_mut(n).enumerate() {
+            let i = first_row + r;
+            matmul_row(&a[i * k..(i + 1) * k], b, n, out_row);
+        }
+    });
+
+    out
+}
+
+/// Adds the product of a row of length `k` and a row-major `k x n` matrix to `out_row`. The loops
+/// are ordered so the innermost loop walks both `b` and the output contiguously.
+fn matmul_row(a_row: &[f64], b: &[f64], n: usize, out_row: &mut [f64]) {
+    for (p, &a_ip) in a_row.iter().enumerate() {
+        let b_row = &b[p * n..(p + 1) * n];
+        for (o, &b_pj) in out_row.iter_mut().zip(b_row.iter()) {
+            *o += a_ip * b_pj;
+        }
+    }
+}
+
+/// Maps the flat index of an entry in `full_shape` to the flat index of the entry it was broadcast
+/// from in `source_shape`. `source_shape` may have fewer dimensions than `full_shape`, in which
+/// case it is treated as padded with leading ones.
+pub(crate) fn broadcast_offset(index: usize, full_shape: &[usize], source_shape: &[usize]) -> usize {
+    let pad = full_shape.len() - source_shape.len();
+
+    let mut remaining = index;
+    let mut offset = 0;
+    let mut stride = 1;
+    for d in (0..full_shape.len()).rev() {
+        let coord = remaining % full_shape[d];
+        remaining /= full_shape[d];
+
+        if d >= pad {
+            let source_dim = source_shape[d - pad];
+            if source_dim != 1 {
+                offset += coord * stride;
+            }
+            stride *= source_dim;
+        }
+    }
+
+    offset
+}
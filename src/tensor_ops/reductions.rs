@@ -0,0 +1,160 @@
+use crate::Tensor;
+use crate::parallel;
+use crate::tensor_ops::{ build_tensor, flatten_tensor, get_dimension };
+use crate::tensor_ops::utilities::axis_layout;
+
+/// Sums the elements of a Tensor. With an axis, that axis is summed and removed from the shape,
+/// and without one the whole Tensor is summed to a Tensor of shape \[1\]. Reducing a 1-D Tensor
+/// also gives shape \[1\].
+///
+/// Whole-Tensor sums are split across threads in chunks, see [crate::parallel] for when the result
+/// is the same for every thread count.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ build_tensor, sum };
+///
+/// let t = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]);
+///
+/// assert_eq!(sum(&t, None), Tensor::Element(vec![21.0]));
+/// assert_eq!(sum(&t, Some(0)), Tensor::Element(vec![5.0, 7.0, 9.0]));
+/// assert_eq!(sum(&t, Some(1)), Tensor::Element(vec![6.0, 15.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range.
+pub fn sum(tensor: &Tensor, axis: Option<usize>) -> Tensor {
+    reduce(tensor, axis, 0.0, |acc, x| acc + x, |acc, x| acc + x)
+}
+
+/// The mean of the elements of a Tensor, over one axis or the whole Tensor like [sum()]. The mean
+/// of no elements is NaN.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ build_tensor, mean };
+///
+/// let t = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]);
+///
+/// assert_eq!(mean(&t, None), Tensor::Element(vec![3.5]));
+/// assert_eq!(mean(&t, Some(1)), Tensor::Element(vec![2.0, 5.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range.
+pub fn mean(tensor: &Tensor, axis: Option<usize>) -> Tensor {
+    let shape = get_dimension(tensor);
+    let count = match axis {
+        Some(axis) => axis_layout(&shape, axis).1,
+        None => shape.iter().product(),
+    } as f64;
+
+    let total = sum(tensor, axis);
+    crate::tensor_ops::map_tensor(&total, |x| x / count)
+}
+
+/// The largest element of a Tensor, over one axis or the whole Tensor like [sum()]. NaN is
+/// propagated, so any NaN makes the result NaN.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ build_tensor, max };
+///
+/// let t = build_tensor(&[1.0, 8.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]);
+///
+/// assert_eq!(max(&t, None), Tensor::Element(vec![8.0]));
+/// assert_eq!(max(&t, Some(0)), Tensor::Element(vec![4.0, 8.0, 6.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range or the Tensor has no elements to reduce.
+pub fn max(tensor: &Tensor, axis: Option<usize>) -> Tensor {
+    check_not_empty(tensor, axis, "max");
+    reduce(tensor, axis, f64::NEG_INFINITY, nan_max, nan_max)
+}
+
+/// The smallest element of a Tensor, over one axis or the whole Tensor like [sum()]. NaN is
+/// propagated, so any NaN makes the result NaN.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ build_tensor, min };
+///
+/// let t = build_tensor(&[1.0, 8.0, 3.0, 4.0, 5.0, 0.0], &[2, 3]);
+///
+/// assert_eq!(min(&t, None), Tensor::Element(vec![0.0]));
+/// assert_eq!(min(&t, Some(1)), Tensor::Element(vec![1.0, 0.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range or the Tensor has no elements to reduce.
+pub fn min(tensor: &Tensor, axis: Option<usize>) -> Tensor {
+    check_not_empty(tensor, axis, "min");
+    reduce(tensor, axis, f64::INFINITY, nan_min, nan_min)
+}
+
+fn nan_max(a: f64, b: f64) -> f64 {
+    if a.is_nan() || b.is_nan() { f64::NAN } else { a.max(b) }
+}
+
+fn nan_min(a: f64, b: f64) -> f64 {
+    if a.is_nan() || b.is_nan() { f64::NAN } else { a.min(b) }
+}
+
+pub(crate) fn check_not_empty(tensor: &Tensor, axis: Option<usize>, name: &str) {
+    let shape = get_dimension(tensor);
+    let count = match axis {
+        Some(axis) => axis_layout(&shape, axis).1,
+        None => shape.iter().product(),
+    };
+    if count == 0 {
+        panic!("Cannot take the {name} of an empty Tensor!");
+    }
+}
+
+/// Folds the values along `axis`, or all values, starting from `identity`. `combine` merges the
+/// partial results of chunks when the whole Tensor is reduced.
+pub(crate) fn reduce(
+    tensor: &Tensor,
+    axis: Option<usize>,
+    identity: f64,
+    fold: impl Fn(f64, f64) -> f64 + Sync,
+    combine: impl Fn(f64, f64) -> f64
+) -> Tensor {
+    let mut shape = get_dimension(tensor);
+    let values = flatten_tensor(tensor);
+
+    let Some(axis) = axis else {
+        let total = parallel::reduce(
+            values.len(),
+            |range| values[range].iter().fold(identity, |acc, &x| fold(acc, x)),
+            combine
+        );
+        return Tensor::Element(vec![total.unwrap_or(identity)]);
+    };
+
+    // Every output value is folded on one thread, so the result never depends on the threads
+    let (outer, axis_len, inner) = axis_layout(&shape, axis);
+    let out = parallel::map_collect(outer * inner, axis_len, |index| {
+        let start = (index / inner) * axis_len * inner + index % inner;
+        (0..axis_len).fold(identity, |acc, k| fold(acc, values[start + k * inner]))
+    });
+
+    shape.remove(axis);
+    if shape.is_empty() {
+        shape.push(1);
+    }
+    build_tensor(&out, &shape)
+}
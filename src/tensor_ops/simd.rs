@@ -0,0 +1,451 @@
+use std::mem::MaybeUninit;
+use std::sync::OnceLock;
+
+/// The instruction sets the element-wise kernels can use, as chosen by [simd_level()].
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+pub enum SimdLevel {
+    /// Plain scalar code, used when no supported instruction set is available.
+    Scalar,
+    /// SSE2, 2 lanes of `f64`. Always available on `x86_64`.
+    Sse2,
+    /// AVX2 with FMA, 4 lanes of `f64`.
+    Avx2,
+    /// AVX-512F, 8 lanes of `f64`.
+    Avx512,
+    /// NEON, 2 lanes of `f64`. Always available on `aarch64`.
+    Neon,
+}
+
+/// The widest instruction set supported by the CPU the program is running on. The check runs once
+/// and is cached, and every element-wise kernel dispatches on the result.
+///
+/// Every level computes bit-for-bit the same results as [SimdLevel::Scalar], so the choice only
+/// affects speed.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::tensor_ops::{ simd_level, SimdLevel };
+///
+/// let level = simd_level();
+/// if cfg!(target_arch = "x86_64") {
+///     assert_ne!(level, SimdLevel::Scalar);
+/// }
+/// ```
+pub fn simd_level() -> SimdLevel {
+    static LEVEL: OnceLock<SimdLevel> = OnceLock::new();
+    *LEVEL.get_or_init(detect)
+}
+
+#[cfg(target_arch = "x86_64")]
+fn detect() -> SimdLevel {
+    if is_x86_feature_detected!("avx512f") {
+        SimdLevel::Avx512
+    } else if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+        SimdLevel::Avx2
+    } else {
+        SimdLevel::Sse2
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+fn detect() -> SimdLevel {
+    if std::arch::is_aarch64_feature_detected!("neon") { SimdLevel::Neon } else { SimdLevel::Scalar }
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn detect() -> SimdLevel {
+    SimdLevel::Scalar
+}
+
+/// Every level the running CPU supports, from [SimdLevel::Scalar] up to [simd_level()].
+#[cfg(test)]
+pub(crate) fn supported_levels() -> Vec<SimdLevel> {
+    let mut levels = vec![SimdLevel::Scalar];
+    match simd_level() {
+        SimdLevel::Scalar => {},
+        SimdLevel::Sse2 => levels.push(SimdLevel::Sse2),
+        SimdLevel::Avx2 => levels.extend([SimdLevel::Sse2, SimdLevel::Avx2]),
+        SimdLevel::Avx512 => {
+            levels.push(SimdLevel::Sse2);
+            #[cfg(target_arch = "x86_64")]
+            if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+                levels.push(SimdLevel::Avx2);
+            }
+            levels.push(SimdLevel::Avx512);
+        },
+        SimdLevel::Neon => levels.push(SimdLevel::Neon),
+    }
+    levels
+}
+
+/// An operation on two inputs with a kernel for every [SimdLevel].
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+pub(crate) enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl BinaryOp {
+    fn apply(self, x: f64, y: f64) -> f64 {
+        match self {
+            BinaryOp::Add => x + y,
+            BinaryOp::Sub => x - y,
+            BinaryOp::Mul => x * y,
+            BinaryOp::Div => x / y,
+        }
+    }
+}
+
+/// An operation on one input with a kernel for every [SimdLevel].
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+pub(crate) enum UnaryOp {
+    Neg,
+    Abs,
+    Sqrt,
+    Square,
+    Relu,
+}
+
+impl UnaryOp {
+    fn apply(self, x: f64) -> f64 {
+        match self {
+            UnaryOp::Neg => -x,
+            UnaryOp::Abs => x.abs(),
+            UnaryOp::Sqrt => x.sqrt(),
+            UnaryOp::Square => x * x,
+            // Written so NaN passes through and -0.0 gives 0.0, like the vector kernels
+            UnaryOp::Relu => if x > 0.0 || x.is_nan() { x } else { 0.0 },
+        }
+    }
+}
+
+/// Writes `op(a[i], b[i])` to every element of `out` using the instruction set `level`, which the
+/// CPU must support.
+pub(crate) fn binary_with(
+    level: SimdLevel,
+    op: BinaryOp,
+    a: &[f64],
+    b: &[f64],
+    out: &mut [MaybeUninit<f64>]
+) {
+    assert!(a.len() == out.len() && b.len() == out.len(), "Element Tensors are different lengths!");
+    match level {
+        // SAFETY: callers only pass levels the CPU supports, and the lengths all match.
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Sse2 => unsafe { x86::binary_sse2(op, a, b, out) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => unsafe { x86::binary_avx2(op, a, b, out) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 => unsafe { x86::binary_avx512(op, a, b, out) },
+        #[cfg(target_arch = "aarch64")]
+        SimdLevel::Neon => unsafe { neon::binary(op, a, b, out) },
+        _ => scalar_binary(op, a, b, out, 0),
+    }
+}
+
+/// [binary_with()] at [simd_level()].
+pub(crate) fn binary(op: BinaryOp, a: &[f64], b: &[f64], out: &mut [MaybeUninit<f64>]) {
+    binary_with(simd_level(), op, a, b, out)
+}
+
+/// Writes `op(a[i])` to every element of `out` using the instruction set `level`, which the CPU
+/// must support.
+pub(crate) fn unary_with(level: SimdLevel, op: UnaryOp, a: &[f64], out: &mut [MaybeUninit<f64>]) {
+    assert!(a.len() == out.len(), "Element Tensors are different lengths!");
+    match level {
+        // SAFETY: callers only pass levels the CPU supports, and the lengths match.
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Sse2 => unsafe { x86::unary_sse2(op, a, out) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => unsafe { x86::unary_avx2(op, a, out) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 => unsafe { x86::unary_avx512(op, a, out) },
+        #[cfg(target_arch = "aarch64")]
+        SimdLevel::Neon => unsafe { neon::unary(op, a, out) },
+        _ => scalar_unary(op, a, out, 0),
+    }
+}
+
+/// [unary_with()] at [simd_level()].
+pub(crate) fn unary(op: UnaryOp, a: &[f64], out: &mut [MaybeUninit<f64>]) {
+    unary_with(simd_level(), op, a, out)
+}
+
+/// Writes `a[i] * b[i] + c[i]` with a single rounding to every element of `out` using the
+/// instruction set `level`, which the CPU must support.
+pub(crate) fn fma_with(
+    level: SimdLevel,
+    a: &[f64],
+    b: &[f64],
+    c: &[f64],
+    out: &mut [MaybeUninit<f64>]
+) {
+    assert!(
+        a.len() == out.len() && b.len() == out.len() && c.len() == out.len(),
+        "Element Tensors are different lengths!"
+    );
+    match level {
+        // SAFETY: callers only pass levels the CPU supports, and the lengths all match. SSE2 has
+        // no fused multiply-add, so it uses the scalar kernel.
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => unsafe { x86::fma_avx2(a, b, c, out) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 => unsafe { x86::fma_avx512(a, b, c, out) },
+        #[cfg(target_arch = "aarch64")]
+        SimdLevel::Neon => unsafe { neon::fma(a, b, c, out) },
+        _ => scalar_fma(a, b, c, out, 0),
+    }
+}
+
+/// [fma_with()] at [simd_level()].
+pub(crate) fn fma(a: &[f64], b: &[f64], c: &[f64], out: &mut [MaybeUninit<f64>]) {
+    fma_with(simd_level(), a, b, c, out)
+}
+
+/// Allocates `len` values for `kernel` to write, without the cost of initializing them first.
+/// `kernel` must write every element.
+pub(crate) fn filled(len: usize, kernel: impl FnOnce(&mut [MaybeUninit<f64>])) -> Vec<f64> {
+    let mut out = Vec::with_capacity(len);
+    fill(&mut out, len, kernel);
+    out
+}
+
+/// [filled()] into an empty buffer with room for at least `len` values.
+pub(crate) fn fill(out: &mut Vec<f64>, len: usize, kernel: impl FnOnce(&mut [MaybeUninit<f64>])) {
+    assert!(out.is_empty());
+    kernel(&mut out.spare_capacity_mut()[..len]);
+    // SAFETY: callers write each element of the output.
+    unsafe { out.set_len(len) };
+}
+
+/// The scalar kernels, starting at `start` so the vector kernels can use them for the elements
+/// left over after the last full vector.
+fn scalar_binary(op: BinaryOp, a: &[f64], b: &[f64], out: &mut [MaybeUninit<f64>], start: usize) {
+    for i in start..out.len() {
+        out[i].write(op.apply(a[i], b[i]));
+    }
+}
+
+fn scalar_unary(op: UnaryOp, a: &[f64], out: &mut [MaybeUninit<f64>], start: usize) {
+    for i in start..out.len() {
+        out[i].write(op.apply(a[i]));
+    }
+}
+
+fn scalar_fma(a: &[f64], b: &[f64], c: &[f64], out: &mut [MaybeUninit<f64>], start: usize) {
+    for i in start..out.len() {
+        out[i].write(a[i].mul_add(b[i], c[i]));
+    }
+}
+
+/// Generates the kernels for one vector width. `$load`, `$store` and the operations are the
+/// intrinsics for that width, and `$lanes` is the number of `f64` in a vector. Each kernel
+/// processes whole vectors and leaves the remainder to the scalar kernel.
+macro_rules! vector_kernels {
+    (
+        feature: $feature:literal,
+        lanes: $lanes:expr,
+        binary: $binary:ident,
+        unary: $unary:ident,
+        load: $load:expr,
+        store: $store:expr,
+        splat: $splat:expr,
+        add: $add:expr,
+        sub: $sub:expr,
+        mul: $mul:expr,
+        div: $div:expr,
+        max: $max:expr,
+        sqrt: $sqrt:expr,
+        neg: $neg:expr,
+        abs: $abs:expr $(,)?
+    ) => {
+        #[target_feature(enable = $feature)]
+        pub(super) unsafe fn $binary(op: BinaryOp, a: &[f64], b: &[f64], out: &mut [MaybeUninit<f64>]) {
+            let whole = out.len() / $lanes * $lanes;
+            macro_rules! run {
+                ($f:expr) => {
+                    for i in (0..whole).step_by($lanes) {
+                        // SAFETY: i + $lanes <= whole <= the length of every slice.
+                        unsafe {
+                            let x = $load(a.as_ptr().add(i));
+                            let y = $load(b.as_ptr().add(i));
+                            $store(out.as_mut_ptr().add(i).cast(), $f(x, y));
+                        }
+                    }
+                };
+            }
+            match op {
+                BinaryOp::Add => run!($add),
+                BinaryOp::Sub => run!($sub),
+                BinaryOp::Mul => run!($mul),
+                BinaryOp::Div => run!($div),
+            }
+            super::scalar_binary(op, a, b, out, whole);
+        }
+
+        #[target_feature(enable = $feature)]
+        pub(super) unsafe fn $unary(op: UnaryOp, a: &[f64], out: &mut [MaybeUninit<f64>]) {
+            let whole = out.len() / $lanes * $lanes;
+            macro_rules! run {
+                ($f:expr) => {
+                    for i in (0..whole).step_by($lanes) {
+                        // SAFETY: i + $lanes <= whole <= the length of both slices.
+                        unsafe {
+                            let x = $load(a.as_ptr().add(i));
+                            $store(out.as_mut_ptr().add(i).cast(), $f(x));
+                        }
+                    }
+                };
+            }
+            match op {
+                UnaryOp::Neg => run!($neg),
+                UnaryOp::Abs => run!($abs),
+                UnaryOp::Sqrt => run!($sqrt),
+                UnaryOp::Square => run!(|x| $mul(x, x)),
+                // max(0, x) keeps a NaN x, and adding 0.0 turns the -0.0 it may return into 0.0
+                UnaryOp::Relu => run!(|x| $add($max($splat(0.0), x), $splat(0.0))),
+            }
+            super::scalar_unary(op, a, out, whole);
+        }
+    };
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::*;
+    use std::mem::MaybeUninit;
+    use super::{ BinaryOp, UnaryOp };
+
+    vector_kernels! {
+        feature: "sse2",
+        lanes: 2,
+        binary: binary_sse2,
+        unary: unary_sse2,
+        load: _mm_loadu_pd,
+        store: _mm_storeu_pd,
+        splat: _mm_set1_pd,
+        add: _mm_add_pd,
+        sub: _mm_sub_pd,
+        mul: _mm_mul_pd,
+        div: _mm_div_pd,
+        max: _mm_max_pd,
+        sqrt: _mm_sqrt_pd,
+        neg: |x| _mm_xor_pd(x, _mm_set1_pd(-0.0)),
+        abs: |x| _mm_andnot_pd(_mm_set1_pd(-0.0), x),
+    }
+
+    vector_kernels! {
+        feature: "avx2,fma",
+        lanes: 4,
+        binary: binary_avx2,
+        unary: unary_avx2,
+        load: _mm256_loadu_pd,
+        store: _mm256_storeu_pd,
+        splat: _mm256_set1_pd,
+        add: _mm256_add_pd,
+        sub: _mm256_sub_pd,
+        mul: _mm256_mul_pd,
+        div: _mm256_div_pd,
+        max: _mm256_max_pd,
+        sqrt: _mm256_sqrt_pd,
+        neg: |x| _mm256_xor_pd(x, _mm256_set1_pd(-0.0)),
+        abs: |x| _mm256_andnot_pd(_mm256_set1_pd(-0.0), x),
+    }
+
+    vector_kernels! {
+        feature: "avx512f",
+        lanes: 8,
+        binary: binary_avx512,
+        unary: unary_avx512,
+        load: _mm512_loadu_pd,
+        store: _mm512_storeu_pd,
+        splat: _mm512_set1_pd,
+        add: _mm512_add_pd,
+        sub: _mm512_sub_pd,
+        mul: _mm512_mul_pd,
+        div: _mm512_div_pd,
+        max: _mm512_max_pd,
+        sqrt: _mm512_sqrt_pd,
+        // Flips the sign bit with integer instructions, as the f64 xor needs AVX-512DQ
+        neg: |x| _mm512_castsi512_pd(_mm512_xor_si512(_mm512_castpd_si512(x), _mm512_set1_epi64(i64::MIN))),
+        abs: _mm512_abs_pd,
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    pub(super) unsafe fn fma_avx2(a: &[f64], b: &[f64], c: &[f64], out: &mut [MaybeUninit<f64>]) {
+        let whole = out.len() / 4 * 4;
+        for i in (0..whole).step_by(4) {
+            // SAFETY: i + 4 <= whole <= the length of every slice.
+            unsafe {
+                let x = _mm256_loadu_pd(a.as_ptr().add(i));
+                let y = _mm256_loadu_pd(b.as_ptr().add(i));
+                let z = _mm256_loadu_pd(c.as_ptr().add(i));
+                _mm256_storeu_pd(out.as_mut_ptr().add(i).cast(), _mm256_fmadd_pd(x, y, z));
+            }
+        }
+        super::scalar_fma(a, b, c, out, whole);
+    }
+
+    #[target_feature(enable = "avx512f")]
+    pub(super) unsafe fn fma_avx512(a: &[f64], b: &[f64], c: &[f64], out: &mut [MaybeUninit<f64>]) {
+        let whole = out.len() / 8 * 8;
+        for i in (0..whole).step_by(8) {
+            // SAFETY: i + 8 <= whole <= the length of every slice.
+            unsafe {
+                let x = _mm512_loadu_pd(a.as_ptr().add(i));
+                let y = _mm512_loadu_pd(b.as_ptr().add(i));
+                let z = _mm512_loadu_pd(c.as_ptr().add(i));
+                _mm512_storeu_pd(out.as_mut_ptr().add(i).cast(), _mm512_fmadd_pd(x, y, z));
+            }
+        }
+        super::scalar_fma(a, b, c, out, whole);
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod neon {
+    use std::arch::aarch64::*;
+    use std::mem::MaybeUninit;
+    use super::{ BinaryOp, UnaryOp };
+
+    vector_kernels! {
+        feature: "neon",
+        lanes: 2,
+        binary: binary,
+        unary: unary,
+        load: vld1q_f64,
+        store: vst1q_f64,
+        splat: vdupq_n_f64,
+        add: vaddq_f64,
+        sub: vsubq_f64,
+        mul: vmulq_f64,
+        div: vdivq_f64,
+        // The NaN-propagating max, so NaN stays NaN like the scalar kernel
+        max: vmaxq_f64,
+        sqrt: vsqrtq_f64,
+        neg: vnegq_f64,
+        abs: vabsq_f64,
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn fma(a: &[f64], b: &[f64], c: &[f64], out: &mut [MaybeUninit<f64>]) {
+        let whole = out.len() / 2 * 2;
+        for i in (0..whole).step_by(2) {
+            // SAFETY: i + 2 <= whole <= the length of every slice.
+            unsafe {
+                let x = vld1q_f64(a.as_ptr().add(i));
+                let y = vld1q_f64(b.as_ptr().add(i));
+                let z = vld1q_f64(c.as_ptr().add(i));
+                vst1q_f64(out.as_mut_ptr().add(i).cast(), vfmaq_f64(z, x, y));
+            }
+        }
+        super::scalar_fma(a, b, c, out, whole);
+    }
+}
@@ -0,0 +1,355 @@
+use std::cmp::Ordering;
+use crate::Tensor;
+use crate::tensor_ops::{ build_tensor, flatten_tensor, get_dimension };
+use crate::tensor_ops::utilities::{ apply_along_axis, axis_layout };
+
+/// Options for [sort()] and [argsort()]. The defaults sort in ascending order with an unstable
+/// sort.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+pub struct SortOptions {
+    /// Sorts from largest to smallest.
+    pub descending: bool,
+    /// Keeps equal elements in their original order. An unstable sort is faster but may reorder
+    /// them, which shows in the indices of [argsort()] and in the order of `-0.0` and `0.0`.
+    pub stable: bool,
+}
+
+/// Which of several equal positions [searchsorted()] returns.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(Default)]
+pub enum SearchSide {
+    /// The first position, before any equal elements.
+    #[default]
+    Left,
+    /// The last position, after any equal elements.
+    Right,
+}
+
+/// The sorted distinct values of a Tensor, returned by [unique()].
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct UniqueResult {
+    /// The distinct values in ascending order, as a 1-D Tensor.
+    pub values: Tensor,
+    /// The flat index of the first occurrence of each distinct value.
+    pub indices: Tensor,
+    /// For each element of the Tensor, the index of its value in `values`, in the shape of the
+    /// Tensor.
+    pub inverse: Tensor,
+    /// How many times each distinct value occurs.
+    pub counts: Tensor,
+}
+
+/// Sorts the elements of a Tensor along an axis. Without an axis the Tensor is flattened first
+/// and the result is 1-D.
+///
+/// NaN is ordered after every other value, including infinity, so it comes last in ascending
+/// order and first in descending order. `-0.0` and `0.0` are equal.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ build_tensor, sort, SortOptions };
+///
+/// let t = build_tensor(&[3.0, 1.0, 2.0, 0.0, 5.0, 4.0], &[2, 3]);
+///
+/// let rows = sort(&t, Some(1), &SortOptions::default());
+/// assert_eq!(rows, build_tensor(&[1.0, 2.0, 3.0, 0.0, 4.0, 5.0], &[2, 3]));
+///
+/// let descending = SortOptions { descending: true, ..Default::default() };
+/// assert_eq!(sort(&t, None, &descending), Tensor::Element(vec![5.0, 4.0, 3.0, 2.0, 1.0, 0.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range.
+pub fn sort(tensor: &Tensor, axis: Option<usize>, options: &SortOptions) -> Tensor {
+    let sorted = |lane: &[f64]| {
+        let mut values = lane.to_vec();
+        let order = |a: &f64, b: &f64| directed(compare(*a, *b), options.descending);
+        if options.stable {
+            values.sort_by(order);
+        } else {
+            values.sort_unstable_by(order);
+        }
+        values
+    };
+
+    match axis {
+        Some(axis) => apply_along_axis(tensor, axis, sorted),
+        None => Tensor::Element(sorted(&flatten_tensor(tensor))),
+    }
+}
+
+/// The indices that would sort a Tensor along an axis, in the order [sort()] puts the elements.
+/// Without an axis the indices are into the flattened Tensor.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ argsort, SortOptions };
+///
+/// let t = Tensor::Element(vec![2.0, f64::NAN, 1.0, 2.0]);
+///
+/// // The stable sort keeps the two 2s in order, and NaN goes last
+/// let stable = SortOptions { stable: true, ..Default::default() };
+/// assert_eq!(argsort(&t, Some(0), &stable), Tensor::Element(vec![2.0, 0.0, 3.0, 1.0]));
+///
+/// let descending = SortOptions { stable: true, descending: true };
+/// assert_eq!(argsort(&t, None, &descending), Tensor::Element(vec![1.0, 0.0, 3.0, 2.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range.
+pub fn argsort(tensor: &Tensor, axis: Option<usize>, options: &SortOptions) -> Tensor {
+    let sorted = |lane: &[f64]| {
+        let mut indices: Vec<usize> = (0..lane.len()).collect();
+        let order = |a: &usize, b: &usize| {
+            directed(compare(lane[*a], lane[*b]), options.descending)
+        };
+        if options.stable {
+            indices.sort_by(order);
+        } else {
+            indices.sort_unstable_by(order);
+        }
+        indices.into_iter().map(|i| i as f64).collect()
+    };
+
+    match axis {
+        Some(axis) => apply_along_axis(tensor, axis, sorted),
+        None => Tensor::Element(sorted(&flatten_tensor(tensor))),
+    }
+}
+
+/// The `k` largest elements along an axis, or the `k` smallest when `largest` is false, and their
+/// indices along the axis. The axis is shortened to `k`, with the elements in sorted order and
+/// equal elements in the order they appear. NaN counts as the largest value, as for [sort()].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ build_tensor, topk };
+///
+/// let scores = build_tensor(&[0.1, 0.7, 0.2, 0.5, 0.3, 0.4, 0.9, 0.8], &[2, 4]);
+///
+/// let (values, indices) = topk(&scores, 2, 1, true);
+/// assert_eq!(values, build_tensor(&[0.7, 0.5, 0.9, 0.8], &[2, 2]));
+/// assert_eq!(indices, build_tensor(&[1.0, 3.0, 2.0, 3.0], &[2, 2]));
+///
+/// let (values, _) = topk(&scores, 1, 0, false);
+/// assert_eq!(values, build_tensor(&[0.1, 0.4, 0.2, 0.5], &[1, 4]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range, or if `k` is 0 or longer than the axis.
+pub fn topk(tensor: &Tensor, k: usize, axis: usize, largest: bool) -> (Tensor, Tensor) {
+    let len = axis_layout(&get_dimension(tensor), axis).1;
+    if k == 0 || k > len {
+        panic!("Cannot take the top {k} of an axis of length {len}!");
+    }
+
+    let indices = apply_along_axis(tensor, axis, |lane| {
+        // Ties are broken by index, so the chosen elements never depend on the selection
+        let order = |a: &usize, b: &usize| {
+            directed(compare(lane[*a], lane[*b]), largest).then(a.cmp(b))
+        };
+        let mut indices: Vec<usize> = (0..lane.len()).collect();
+        if k < len {
+            indices.select_nth_unstable_by(k - 1, order);
+            indices.truncate(k);
+        }
+        indices.sort_unstable_by(order);
+        indices.into_iter().map(|i| i as f64).collect()
+    });
+
+    (take_along_axis(tensor, &indices, axis), indices)
+}
+
+/// The sorted distinct values of a Tensor, with the index of the first occurrence of each, the
+/// inverse indices that rebuild the Tensor from them, and their counts. Every NaN counts as the
+/// same value, which comes last.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ build_tensor, unique };
+///
+/// let t = build_tensor(&[3.0, 1.0, 3.0, 2.0, 1.0, 3.0], &[2, 3]);
+/// let unique = unique(&t);
+///
+/// assert_eq!(unique.values, Tensor::Element(vec![1.0, 2.0, 3.0]));
+/// assert_eq!(unique.indices, Tensor::Element(vec![1.0, 3.0, 0.0]));
+/// assert_eq!(unique.inverse, build_tensor(&[2.0, 0.0, 2.0, 1.0, 0.0, 2.0], &[2, 3]));
+/// assert_eq!(unique.counts, Tensor::Element(vec![2.0, 1.0, 3.0]));
+/// ```
+pub fn unique(tensor: &Tensor) -> UniqueResult {
+    let data = flatten_tensor(tensor);
+    let mut order: Vec<usize> = (0..data.len()).collect();
+    // A stable sort puts the first occurrence of each value first among its equals
+    order.sort_by(|a, b| compare(data[*a], data[*b]));
+
+    let (mut values, mut indices, mut counts) = (Vec::new(), Vec::new(), Vec::<f64>::new());
+    let mut inverse = vec![0.0; data.len()];
+    for (position, &i) in order.iter().enumerate() {
+        let is_new = position == 0 || compare(data[order[position - 1]], data[i]).is_ne();
+        if is_new {
+            values.push(data[i]);
+            indices.push(i as f64);
+            counts.push(0.0);
+        }
+        counts[values.len() - 1] += 1.0;
+        inverse[i] = (values.len() - 1) as f64;
+    }
+
+    UniqueResult {
+        values: Tensor::Element(values),
+        indices: Tensor::Element(indices),
+        inverse: build_tensor(&inverse, &get_dimension(tensor)),
+        counts: Tensor::Element(counts),
+    }
+}
+
+/// Finds where each element of `values` would be inserted into the 1-D Tensor `sorted` to keep
+/// it in ascending order, with NaN ordered last as for [sort()]. The indices have the shape of
+/// `values`. If `sorted` is not sorted, the indices are meaningless.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ searchsorted, SearchSide };
+///
+/// let sorted = Tensor::Element(vec![1.0, 2.0, 2.0, 3.0, f64::NAN]);
+/// let values = Tensor::Element(vec![0.0, 2.0, 2.5, 10.0, f64::NAN]);
+///
+/// let left = searchsorted(&sorted, &values, SearchSide::Left);
+/// assert_eq!(left, Tensor::Element(vec![0.0, 1.0, 3.0, 4.0, 4.0]));
+///
+/// let right = searchsorted(&sorted, &values, SearchSide::Right);
+/// assert_eq!(right, Tensor::Element(vec![0.0, 3.0, 3.0, 4.0, 5.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if `sorted` is not 1-D.
+pub fn searchsorted(sorted: &Tensor, values: &Tensor, side: SearchSide) -> Tensor {
+    let Tensor::Element(sorted) = sorted else {
+        panic!("searchsorted needs a 1-D sorted Tensor, got shape {:?}!", get_dimension(sorted));
+    };
+
+    let positions: Vec<f64> = flatten_tensor(values).into_iter()
+        .map(|x| {
+            let position = match side {
+                SearchSide::Left => sorted.partition_point(|&y| compare(y, x).is_lt()),
+                SearchSide::Right => sorted.partition_point(|&y| compare(y, x).is_le()),
+            };
+            position as f64
+        })
+        .collect();
+
+    build_tensor(&positions, &get_dimension(values))
+}
+
+/// Partially sorts a Tensor along an axis so that the element at index `kth` is the one a full
+/// [sort()] would put there, with no larger elements before it and no smaller elements after it.
+/// The order within each side is unspecified. This takes `O(n)` time for each lane.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ flatten_tensor, partition };
+///
+/// let t = Tensor::Element(vec![7.0, 1.0, 5.0, 3.0, 9.0, 2.0]);
+/// let values = flatten_tensor(&partition(&t, 2, 0));
+///
+/// assert_eq!(values[2], 3.0);
+/// assert!(values[..2].iter().all(|&x| x <= 3.0));
+/// assert!(values[3..].iter().all(|&x| x >= 3.0));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range or `kth` is not less than its length.
+pub fn partition(tensor: &Tensor, kth: usize, axis: usize) -> Tensor {
+    check_kth(tensor, kth, axis);
+    apply_along_axis(tensor, axis, |lane| {
+        let mut values = lane.to_vec();
+        values.select_nth_unstable_by(kth, |a, b| compare(*a, *b));
+        values
+    })
+}
+
+/// The indices along an axis that would [partition()] a Tensor.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::{ argpartition, flatten_tensor };
+///
+/// let t = Tensor::Element(vec![7.0, 1.0, 5.0, 3.0, 9.0, 2.0]);
+/// let indices = flatten_tensor(&argpartition(&t, 4, 0));
+///
+/// // The second largest element, 7, is at index 0
+/// assert_eq!(indices[4], 0.0);
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the axis is out of range or `kth` is not less than its length.
+pub fn argpartition(tensor: &Tensor, kth: usize, axis: usize) -> Tensor {
+    check_kth(tensor, kth, axis);
+    apply_along_axis(tensor, axis, |lane| {
+        let mut indices: Vec<usize> = (0..lane.len()).collect();
+        indices.select_nth_unstable_by(kth, |a, b| compare(lane[*a], lane[*b]));
+        indices.into_iter().map(|i| i as f64).collect()
+    })
+}
+
+/// Orders two values with NaN after everything else and all NaNs equal.
+fn compare(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b).unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+}
+
+fn directed(ordering: Ordering, descending: bool) -> Ordering {
+    if descending { ordering.reverse() } else { ordering }
+}
+
+fn check_kth(tensor: &Tensor, kth: usize, axis: usize) {
+    let len = axis_layout(&get_dimension(tensor), axis).1;
+    if kth >= len {
+        panic!("Partition index {kth} is out of range for an axis of length {len}!");
+    }
+}
+
+/// Picks the elements of a Tensor along an axis at the indices of another Tensor, which has the
+/// same shape except along that axis.
+fn take_along_axis(tensor: &Tensor, indices: &Tensor, axis: usize) -> Tensor {
+    let values = flatten_tensor(tensor);
+    let (_, len, inner) = axis_layout(&get_dimension(tensor), axis);
+    let shape = get_dimension(indices);
+    let taken_len = shape[axis];
+
+    let taken: Vec<f64> = flatten_tensor(indices).into_iter()
+        .enumerate()
+        .map(|(position, index)| {
+            let (o, i) = (position / (taken_len * inner), position % inner);
+            values[o * len * inner + index as usize * inner + i]
+        })
+        .collect();
+
+    build_tensor(&taken, &shape)
+}
@@ -1,4 +1,8 @@
+use std::mem::MaybeUninit;
+use std::ops::Range;
 use crate::Tensor;
+use crate::parallel;
+use crate::tensor_ops::simd::{ self, BinaryOp, UnaryOp };
 
 /// Runs a piecewise function on between each element of two Tensors. The Tensors **must** be the
 /// same shape and of the same dimensionality. If they are not, see broadcasting under
@@ -38,44 +42,15 @@ use crate::Tensor;
 /// If you need to do element-wise operations on Tensor that do not match, see the section on
 /// broadcasting.
 pub fn tensor_op(ltensor: &Tensor, rtensor: &Tensor, func: fn(f64, f64) -> f64) -> Tensor {
-    match ltensor {
-        Tensor::Element(x) =>{
-            match rtensor {
-                Tensor::Element(y) => {
-                    if x.len() == y.len() {
-                        let mut outtensor: Vec<f64> = Vec::new();
-                        for n in 0..x.len() {
-                            outtensor.push(func(x[n], y[n]));
-                        }
-                        Tensor::Element(outtensor)
-                    } else {
-                        panic!("Element Tensors are different lengths!");
-                    }
-                },
-                Tensor::Array(_) => panic!("Dimensionality Mismatch!")
-            }
-        },
-        Tensor::Array(x) => {
-            match rtensor {
-                Tensor::Element(_) => panic!("Dimensionality Mismatch!"),
-                Tensor::Array(y) => {
-                    if x.len() == y.len() {
-                        let mut outtensor: Vec<Tensor> = Vec::new();
-                        for n in 0..x.len() {
-                            outtensor.push(tensor_op(&x[n], &y[n], func));
-                        }
-                        Tensor::Array(outtensor)
-                    } else {
-                        panic!("Element Tensors are different lengths!");
-                    }
-                }
-            }
+    zip_leaves(ltensor, rtensor, &|x, y, out| {
+        for n in 0..out.len() {
+            out[n].write(func(x[n], y[n]));
         }
-    }
+    })
 }
 
-/// Adds two Tensors element-wise. This uses [crate::tensor_ops::tensor_op()] under the hood so
-/// it can panic in the same way as that function.
+/// Adds two Tensors element-wise using the SIMD kernels for [crate::tensor_ops::simd_level()].
+/// It panics in the same way as [crate::tensor_ops::tensor_op()].
 ///
 /// # Examples
 ///
@@ -90,11 +65,11 @@ pub fn tensor_op(ltensor: &Tensor, rtensor: &Tensor, func: fn(f64, f64) -> f64)
 /// assert_eq!(t3, Tensor::Element(vec![4.0, 6.0]));
 /// ```
 pub fn add_tensors(ltensor: &Tensor, rtensor: &Tensor) -> Tensor {
-    tensor_op(ltensor, rtensor, |x, y| x + y)
+    binary_tensors(ltensor, rtensor, BinaryOp::Add)
 }
 
-/// Subtracts two Tensors element-wise. This uses [crate::tensor_ops::tensor_op()] under the hood
-/// so it can panic in the same way as that function.
+/// Subtracts two Tensors element-wise using the SIMD kernels for
+/// [crate::tensor_ops::simd_level()]. It panics in the same way as [crate::tensor_ops::tensor_op()].
 ///
 /// # Examples
 ///
@@ -109,11 +84,11 @@ pub fn add_tensors(ltensor: &Tensor, rtensor: &Tensor) -> Tensor {
 /// assert_eq!(t3, Tensor::Element(vec![-2.0, -2.0]));
 /// ```
 pub fn subtract_tensors(ltensor: &Tensor, rtensor: &Tensor) -> Tensor {
-    tensor_op(ltensor, rtensor, |x, y| x - y)
+    binary_tensors(ltensor, rtensor, BinaryOp::Sub)
 }
 
-/// Multiplies two Tensors element-wise. This uses [crate::tensor_ops::tensor_op()] under the
-/// hood so it can panic in the same way as that function.
+/// Multiplies two Tensors element-wise using the SIMD kernels for
+/// [crate::tensor_ops::simd_level()]. It panics in the same way as [crate::tensor_ops::tensor_op()].
 ///
 /// # Examples
 ///
@@ -128,11 +103,11 @@ pub fn subtract_tensors(ltensor: &Tensor, rtensor: &Tensor) -> Tensor {
 /// assert_eq!(t3, Tensor::Element(vec![3.0, 8.0]));
 /// ```
 pub fn multiply_tensors(ltensor: &Tensor, rtensor: &Tensor) -> Tensor {
-    tensor_op(ltensor, rtensor, |x, y| x * y)
+    binary_tensors(ltensor, rtensor, BinaryOp::Mul)
 }
 
-/// Divides two Tensors element-wise. This uses [crate::tensor_ops::tensor_op()] under the hood
-/// so it can panic in the same way as that function.
+/// Divides two Tensors element-wise using the SIMD kernels for [crate::tensor_ops::simd_level()].
+/// It panics in the same way as [crate::tensor_ops::tensor_op()].
 ///
 /// # Examples
 ///
@@ -147,7 +122,7 @@ pub fn multiply_tensors(ltensor: &Tensor, rtensor: &Tensor) -> Tensor {
 /// assert_eq!(t3, Tensor::Element(vec![0.5, 0.5]));
 /// ```
 pub fn divide_tensors(ltensor: &Tensor, rtensor: &Tensor) -> Tensor {
-    tensor_op(ltensor, rtensor, |x, y| x / y)
+    binary_tensors(ltensor, rtensor, BinaryOp::Div)
 }
 
 /// Remainder of two Tensors element-wise. This uses [crate::tensor_ops::tensor_op] under the
@@ -167,4 +142,199 @@ pub fn divide_tensors(ltensor: &Tensor, rtensor: &Tensor) -> Tensor {
 /// ```
 pub fn remainder_tensors(ltensor: &Tensor, rtensor: &Tensor) -> Tensor {
     tensor_op(ltensor, rtensor, |x, y| x % y)
-}
\ No newline at end of file
+}
+
+/// Computes `a * b + c` element-wise with a single rounding, like [f64::mul_add()], using the
+/// SIMD kernels for [crate::tensor_ops::simd_level()]. The three Tensors must be the same shape.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::multiply_add_tensors;
+///
+/// let a = Tensor::Element(vec![1.0, 2.0]);
+/// let b = Tensor::Element(vec![3.0, 4.0]);
+/// let c = Tensor::Element(vec![0.5, -1.0]);
+///
+/// assert_eq!(multiply_add_tensors(&a, &b, &c), Tensor::Element(vec![3.5, 7.0]));
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the shapes of the Tensors do not match.
+pub fn multiply_add_tensors(a: &Tensor, b: &Tensor, c: &Tensor) -> Tensor {
+    match (a, b, c) {
+        (Tensor::Element(x), Tensor::Element(y), Tensor::Element(z)) => {
+            if x.len() != y.len() || x.len() != z.len() {
+                panic!("Element Tensors are different lengths!");
+            }
+            Tensor::Element(fill_leaf(x.len(), |range, out| {
+                simd::fma(&x[range.clone()], &y[range.clone()], &z[range], out)
+            }))
+        },
+        (Tensor::Array(x), Tensor::Array(y), Tensor::Array(z)) => {
+            if x.len() != y.len() || x.len() != z.len() {
+                panic!("Element Tensors are different lengths!");
+            }
+            Tensor::Array(map_children(x, |n| multiply_add_tensors(&x[n], &y[n], &z[n])))
+        },
+        _ => panic!("Dimensionality Mismatch!")
+    }
+}
+
+/// Negates every element of a Tensor using the SIMD kernels.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::negate_tensor;
+///
+/// let t = Tensor::Element(vec![1.0, -2.0]);
+/// assert_eq!(negate_tensor(&t), Tensor::Element(vec![-1.0, 2.0]));
+/// ```
+pub fn negate_tensor(tensor: &Tensor) -> Tensor {
+    unary_tensor(tensor, UnaryOp::Neg)
+}
+
+/// The absolute value of every element of a Tensor, using the SIMD kernels.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::abs_tensor;
+///
+/// let t = Tensor::Element(vec![1.0, -2.0]);
+/// assert_eq!(abs_tensor(&t), Tensor::Element(vec![1.0, 2.0]));
+/// ```
+pub fn abs_tensor(tensor: &Tensor) -> Tensor {
+    unary_tensor(tensor, UnaryOp::Abs)
+}
+
+/// The square root of every element of a Tensor, using the SIMD kernels. Negative values give NaN.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::sqrt_tensor;
+///
+/// let t = Tensor::Element(vec![4.0, 2.25]);
+/// assert_eq!(sqrt_tensor(&t), Tensor::Element(vec![2.0, 1.5]));
+/// ```
+pub fn sqrt_tensor(tensor: &Tensor) -> Tensor {
+    unary_tensor(tensor, UnaryOp::Sqrt)
+}
+
+/// The square of every element of a Tensor, using the SIMD kernels.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::square_tensor;
+///
+/// let t = Tensor::Element(vec![3.0, -0.5]);
+/// assert_eq!(square_tensor(&t), Tensor::Element(vec![9.0, 0.25]));
+/// ```
+pub fn square_tensor(tensor: &Tensor) -> Tensor {
+    unary_tensor(tensor, UnaryOp::Square)
+}
+
+/// Runs a function on every element of a single Tensor, returning a new Tensor of the same shape.
+/// This is the unary counterpart to [crate::tensor_ops::tensor_op()] and forms the foundation for
+/// the element-wise activation functions.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::map_tensor;
+///
+/// let t1 = Tensor::Array(Vec::from([
+///     Tensor::Element(vec![1.0, 2.0]),
+///     Tensor::Element(vec![3.0, 4.0])
+/// ]));
+/// let t2 = map_tensor(&t1, |x| x * x);
+///
+/// assert_eq!(
+///     t2,
+///     Tensor::Array(Vec::from([
+///         Tensor::Element(vec![1.0, 4.0]),
+///         Tensor::Element(vec![9.0, 16.0])
+///     ]))
+/// );
+/// ```
+pub fn map_tensor(tensor: &Tensor, func: impl Fn(f64) -> f64) -> Tensor {
+    map_with(tensor, &func)
+}
+
+fn map_with<F: Fn(f64) -> f64>(tensor: &Tensor, func: &F) -> Tensor {
+    match tensor {
+        Tensor::Element(x) => Tensor::Element(x.iter().map(|&v| func(v)).collect()),
+        Tensor::Array(x) => Tensor::Array(x.iter().map(|t| map_with(t, func)).collect())
+    }
+}
+
+/// Applies a binary kernel to every pair of Element leaves, panicking like [tensor_op()].
+fn binary_tensors(ltensor: &Tensor, rtensor: &Tensor, op: BinaryOp) -> Tensor {
+    zip_leaves(ltensor, rtensor, &|x, y, out| simd::binary(op, x, y, out))
+}
+
+/// Applies a unary kernel to every Element leaf.
+pub(crate) fn unary_tensor(tensor: &Tensor, op: UnaryOp) -> Tensor {
+    match tensor {
+        Tensor::Element(x) => {
+            Tensor::Element(fill_leaf(x.len(), |range, out| simd::unary(op, &x[range], out)))
+        },
+        Tensor::Array(x) => Tensor::Array(map_children(x, |n| unary_tensor(&x[n], op)))
+    }
+}
+
+/// Runs `kernel` on every pair of Element leaves, which it must fill completely.
+fn zip_leaves<K>(ltensor: &Tensor, rtensor: &Tensor, kernel: &K) -> Tensor
+where
+    K: Fn(&[f64], &[f64], &mut [MaybeUninit<f64>]) + Sync
+{
+    match (ltensor, rtensor) {
+        (Tensor::Element(x), Tensor::Element(y)) => {
+            if x.len() != y.len() {
+                panic!("Element Tensors are different lengths!");
+            }
+            Tensor::Element(fill_leaf(x.len(), |range, out| kernel(&x[range.clone()], &y[range], out)))
+        },
+        (Tensor::Array(x), Tensor::Array(y)) => {
+            if x.len() != y.len() {
+                panic!("Element Tensors are different lengths!");
+            }
+            Tensor::Array(map_children(x, |n| zip_leaves(&x[n], &y[n], kernel)))
+        },
+        _ => panic!("Dimensionality Mismatch!")
+    }
+}
+
+/// Builds an Element leaf of `len` values, with `kernel` filling a range of it at a time. Large
+/// leaves are split across threads.
+fn fill_leaf(len: usize, kernel: impl Fn(Range<usize>, &mut [MaybeUninit<f64>]) + Sync) -> Vec<f64> {
+    simd::filled(len, |out| {
+        parallel::for_each_chunk_mut(out, parallel::items_per_chunk(1), |start, piece| {
+            kernel(start..start + piece.len(), piece)
+        })
+    })
+}
+
+/// Computes `func(n)` for every child of an Array, splitting large Tensors across threads.
+fn map_children(children: &[Tensor], func: impl Fn(usize) -> Tensor + Sync) -> Vec<Tensor> {
+    let child_size = children.first().map(element_count).unwrap_or(0);
+    parallel::map_collect(children.len(), child_size, func)
+}
+
+/// The number of values in a Tensor, assuming it is not ragged.
+fn element_count(tensor: &Tensor) -> usize {
+    match tensor {
+        Tensor::Element(x) => x.len(),
+        Tensor::Array(x) => x.len() * x.first().map(element_count).unwrap_or(0)
+    }
+}
@@ -3,11 +3,7 @@ use crate::Tensor;
 /// # DO NOT USE IDK WHY THIS IS HERE I DON'T REMEMBER CREATING IT
 pub fn zero_tensor(shape: Vec<usize>) -> Tensor {
     if shape.len() == 1 {
-        let mut outvec: Vec<f64> = Vec::new();
-        for _ in 0..shape[0] {
-            outvec.push(0.0);
-        }
-        Tensor::Element(outvec)
+        Tensor::Element(vec![0.0; shape[0]])
     } else {
         let mut outvec: Vec<Tensor> = Vec::new();
         for _ in 0..shape[0] {
@@ -16,4 +12,57 @@ pub fn zero_tensor(shape: Vec<usize>) -> Tensor {
         }
         Tensor::Array(outvec)
     }
-}
\ No newline at end of file
+}
+
+/// Builds a nested Tensor of the given shape from a flat, row-major slice of values. This is the
+/// inverse of [crate::tensor_ops::flatten_tensor()].
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::build_tensor;
+///
+/// let t1 = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]);
+/// assert_eq!(
+///     t1,
+///     Tensor::Array(Vec::from([
+///         Tensor::Element(vec![1.0, 2.0, 3.0]),
+///         Tensor::Element(vec![4.0, 5.0, 6.0])
+///     ]))
+/// );
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if the shape is empty or if the number of values does not match the
+/// product of the shape.
+///
+/// ```should_panic
+/// use tensorium::tensor_ops::build_tensor;
+///
+/// // 5 values cannot fill a [2, 3] Tensor
+/// let t1 = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0], &[2, 3]);
+/// ```
+pub fn build_tensor(data: &[f64], shape: &[usize]) -> Tensor {
+    if shape.is_empty() {
+        panic!("Cannot build a Tensor with an empty shape!");
+    }
+
+    if data.len() != shape.iter().product::<usize>() {
+        panic!("Data length does not match the requested shape!");
+    }
+
+    if shape.len() == 1 {
+        return Tensor::Element(data.to_vec());
+    }
+
+    // Each entry of the outer dimension owns an equally sized, contiguous chunk of the data.
+    let chunk_size: usize = shape[1..].iter().product();
+    let mut outvec: Vec<Tensor> = Vec::new();
+    for n in 0..shape[0] {
+        outvec.push(build_tensor(&data[n * chunk_size..(n + 1) * chunk_size], &shape[1..]));
+    }
+
+    Tensor::Array(outvec)
+}
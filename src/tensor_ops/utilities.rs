@@ -22,7 +22,7 @@ pub fn get_dimension(tensor: &Tensor) -> Vec<usize>{
         Tensor::Element(x) => dimensions.push(x.len()),
         Tensor::Array(x) => {
             let subdims: Vec<Vec<usize>> = x.iter()
-                .map(|t| get_dimension(&t))
+                .map(get_dimension)
                 .collect();
             if !subdims.first().map(|first| subdims.iter().all(|y| y == first)).unwrap_or(true){
                 panic!("Dimensions do not match!")
@@ -37,4 +37,109 @@ pub fn get_dimension(tensor: &Tensor) -> Vec<usize>{
     };
 
     dimensions
-}
\ No newline at end of file
+}
+
+/// Collects every value of the input Tensor into a single row-major `Vec`. Combined with
+/// [crate::tensor_ops::get_dimension()] and [crate::tensor_ops::build_tensor()] this lets
+/// functions work on a flat buffer and rebuild the nested Tensor afterwards.
+///
+/// # Examples
+///
+/// ```
+/// use tensorium::Tensor;
+/// use tensorium::tensor_ops::flatten_tensor;
+///
+/// let t1 = Tensor::Array(Vec::from([
+///     Tensor::Element(vec![1.0, 2.0, 3.0]),
+///     Tensor::Element(vec![4.0, 5.0, 6.0])
+/// ]));
+/// assert_eq!(flatten_tensor(&t1), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+/// ```
+pub fn flatten_tensor(tensor: &Tensor) -> Vec<f64> {
+    let mut values = Vec::new();
+    flatten_into(tensor, &mut values);
+    values
+}
+
+fn flatten_into(tensor: &Tensor, values: &mut Vec<f64>) {
+    match tensor {
+        Tensor::Element(x) => values.extend_from_slice(x),
+        Tensor::Array(x) => {
+            for t in x.iter() {
+                flatten_into(t, values);
+            }
+        }
+    }
+}
+
+
+/// Splits a shape around `axis` into `(outer, axis_len, inner)` so that the element at
+/// `[o, k, i]` of the equivalent 3-D view lives at `o * axis_len * inner + k * inner + i` in the
+/// flattened Tensor.
+pub(crate) fn axis_layout(shape: &[usize], axis: usize) -> (usize, usize, usize) {
+    if axis >= shape.len() {
+        panic!("Axis {axis} is out of range for a Tensor with {} dimensions!", shape.len());
+    }
+
+    let outer: usize = shape[..axis].iter().product();
+    let inner: usize = shape[axis + 1..].iter().product();
+
+    (outer, shape[axis], inner)
+}
+
+/// Runs `func` on every 1-D lane of the Tensor along `axis` and stitches the returned lanes back
+/// together. Every returned lane must be the same length, which replaces `shape[axis]` in the
+/// output shape.
+pub(crate) fn apply_along_axis(
+    tensor: &Tensor,
+    axis: usize,
+    mut func: impl FnMut(&[f64]) -> Vec<f64>
+) -> Tensor {
+    let mut shape = get_dimension(tensor);
+    let (outer, axis_len, inner) = axis_layout(&shape, axis);
+    let values = flatten_tensor(tensor);
+
+    let mut lanes: Vec<Vec<f64>> = Vec::with_capacity(outer * inner);
+    let mut lane = vec![0.0; axis_len];
+    for o in 0..outer {
+        for i in 0..inner {
+            for (k, value) in lane.iter_mut().enumerate() {
+                *value = values[o * axis_len * inner + k * inner + i];
+            }
+            lanes.push(func(&lane));
+        }
+    }
+
+    let out_len = lanes.first().map(|l| l.len()).unwrap_or(axis_len);
+    if lanes.iter().any(|l| l.len() != out_len) {
+        panic!("Lanes returned along an axis must all be the same length!");
+    }
+
+    let mut out = vec![0.0; outer * out_len * inner];
+    for o in 0..outer {
+        for i in 0..inner {
+            for (k, value) in lanes[o * inner + i].iter().enumerate() {
+                out[o * out_len * inner + k * inner + i] = *value;
+            }
+        }
+    }
+
+    shape[axis] = out_len;
+    crate::tensor_ops::build_tensor(&out, &shape)
+}
+
+/// Reduces every 1-D lane of the Tensor along `axis` to a single value, removing that axis from
+/// the shape. Reducing a 1-D Tensor gives a Tensor of shape \[1\].
+pub(crate) fn reduce_along_axis(
+    tensor: &Tensor,
+    axis: usize,
+    mut func: impl FnMut(&[f64]) -> f64
+) -> Tensor {
+    let reduced = apply_along_axis(tensor, axis, |lane| vec![func(lane)]);
+    let mut shape = get_dimension(&reduced);
+    if shape.len() > 1 {
+        shape.remove(axis);
+    }
+
+    crate::tensor_ops::build_tensor(&flatten_tensor(&reduced), &shape)
+}
@@ -1,3 +1,49 @@
 mod tensor_ops_tests;
 mod slicing_tests;
-mod broadcasting_tests;
\ No newline at end of file
+mod broadcasting_tests;
+mod init_tests;
+mod activation_tests;
+mod convolution_tests;
+mod linalg_tests;
+mod einsum_tests;
+mod npy_tests;
+mod safetensors_tests;
+#[cfg(feature = "serde")]
+mod serde_tests;
+mod csv_tests;
+mod checkpoint_tests;
+mod arrow_tests;
+mod mmap_tensor_tests;
+mod simd_tests;
+mod parallel_tests;
+mod lazy_tests;
+mod memory_tests;
+mod sparse_tests;
+mod complex_tests;
+mod fft_tests;
+mod half_tests;
+mod quantization_tests;
+mod statistics_tests;
+mod sorting_tests;
+mod manipulation_tests;
+mod data_tests;
+
+use std::path::PathBuf;
+use crate::Tensor;
+use crate::tensor_ops::build_tensor;
+
+/// A file in `src/tests/fixtures`.
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/src/tests/fixtures")).join(name)
+}
+
+/// A path in the temporary directory for this test process. Tests run in parallel, so every test
+/// file needs its own file names.
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("tensorium_tests_{}_{name}", std::process::id()))
+}
+
+/// The Tensor stored in most of the fixtures.
+fn two_by_three() -> Tensor {
+    build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3])
+}
@@ -0,0 +1,140 @@
+use crate::Tensor;
+use crate::tensor_ops;
+use crate::tensor_ops::{flatten_tensor, GeluApproximation};
+
+fn assert_close(actual: &[f64], expected: &[f64], tol: f64) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert!((a - e).abs() < tol, "{a} != {e}");
+    }
+}
+
+#[test]
+fn softmax_along_rows_and_columns() {
+    let t = Tensor::Array(Vec::from([
+        Tensor::Element(Vec::from([1.0, 2.0, 3.0])),
+        Tensor::Element(Vec::from([1.0, 1.0, 1.0]))
+    ]));
+
+    let rows = flatten_tensor(&tensor_ops::softmax(&t, 1));
+    let e = [1.0_f64.exp(), 2.0_f64.exp(), 3.0_f64.exp()];
+    let s: f64 = e.iter().sum();
+    assert_close(
+        &rows,
+        &[e[0] / s, e[1] / s, e[2] / s, 1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0],
+        1e-15
+    );
+
+    let cols = flatten_tensor(&tensor_ops::softmax(&t, 0));
+    let s1 = 1.0 + 1.0_f64.exp();
+    let s2 = 1.0 + 2.0_f64.exp();
+    assert_close(
+        &cols,
+        &[0.5, 1.0_f64.exp() / s1, 2.0_f64.exp() / s2, 0.5, 1.0 / s1, 1.0 / s2],
+        1e-15
+    );
+}
+
+#[test]
+fn log_softmax_matches_log_of_softmax() {
+    let t = Tensor::Array(Vec::from([
+        Tensor::Array(Vec::from([
+            Tensor::Element(Vec::from([0.5, -1.0])),
+            Tensor::Element(Vec::from([2.0, 3.0]))
+        ]))
+    ]));
+
+    let log_sm = flatten_tensor(&tensor_ops::log_softmax(&t, 1));
+    let sm: Vec<f64> = flatten_tensor(&tensor_ops::softmax(&t, 1)).iter().map(|x| x.ln()).collect();
+
+    assert_close(&log_sm, &sm, 1e-14);
+}
+
+#[test]
+fn logsumexp_removes_axis() {
+    let t = Tensor::Array(Vec::from([
+        Tensor::Array(Vec::from([
+            Tensor::Element(Vec::from([0.0, 0.0])),
+            Tensor::Element(Vec::from([0.0, 0.0])),
+            Tensor::Element(Vec::from([0.0, 0.0]))
+        ])),
+        Tensor::Array(Vec::from([
+            Tensor::Element(Vec::from([f64::NEG_INFINITY, 0.0])),
+            Tensor::Element(Vec::from([f64::NEG_INFINITY, 0.0])),
+            Tensor::Element(Vec::from([f64::NEG_INFINITY, 0.0]))
+        ]))
+    ]));
+
+    let lse = tensor_ops::logsumexp(&t, 1);
+    assert_eq!(tensor_ops::get_dimension(&lse), Vec::from([2, 2]));
+
+    let v = flatten_tensor(&lse);
+    let ln3 = 3.0_f64.ln();
+    assert_close(&v[..2], &[ln3, ln3], 1e-15);
+    assert_eq!(v[2], f64::NEG_INFINITY);
+    assert!((v[3] - ln3).abs() < 1e-15);
+}
+
+#[test]
+fn gelu_reference_values() {
+    let t = Tensor::Element(Vec::from([-3.0, -1.0, 0.0, 0.5, 2.0, 4.0]));
+
+    // x * Φ(x) computed with Python's math.erf
+    let exact = flatten_tensor(&tensor_ops::gelu(&t, GeluApproximation::Exact));
+    assert_close(
+        &exact,
+        &[
+            -0.00404969409489031,
+            -0.15865525393145707,
+            0.0,
+            0.34573123063700656,
+            1.9544997361036416,
+            3.9998733150326675
+        ],
+        1e-12
+    );
+
+    let approx = flatten_tensor(&tensor_ops::gelu(&t, GeluApproximation::Tanh));
+    assert_close(&approx, &exact, 1e-3);
+}
+
+#[test]
+fn exponential_units() {
+    let t = Tensor::Element(Vec::from([-1.0, 2.0]));
+
+    let elu = flatten_tensor(&tensor_ops::elu(&t, 0.5));
+    assert_close(&elu, &[0.5 * ((-1.0_f64).exp() - 1.0), 2.0], 1e-15);
+
+    let selu = flatten_tensor(&tensor_ops::selu(&t));
+    assert_close(&selu, &[-1.1113307378125628, 2.101401974710961], 1e-14);
+
+    let silu = flatten_tensor(&tensor_ops::silu(&t));
+    assert_close(&silu, &[-1.0 / (1.0 + 1.0_f64.exp()), 2.0 / (1.0 + (-2.0_f64).exp())], 1e-15);
+}
+
+#[test]
+fn stable_at_extremes() {
+    let t = Tensor::Element(Vec::from([-1000.0, 1000.0]));
+
+    assert_eq!(tensor_ops::sigmoid(&t), Tensor::Element(Vec::from([0.0, 1.0])));
+    assert_eq!(tensor_ops::softplus(&t, 1.0), Tensor::Element(Vec::from([0.0, 1000.0])));
+    assert_eq!(tensor_ops::relu(&t), Tensor::Element(Vec::from([0.0, 1000.0])));
+    assert_eq!(tensor_ops::leaky_relu(&t, 0.01), Tensor::Element(Vec::from([-10.0, 1000.0])));
+}
+
+#[test]
+fn relu_propagates_nan() {
+    // Long enough to run through the vector kernels as well as the scalar remainder
+    let values: Vec<f64> = (0..19).map(|i| [f64::NAN, -0.0, -1.0, 2.0][i % 4]).collect();
+
+    let t = Tensor::Element(values.clone());
+    let relu = flatten_tensor(&tensor_ops::relu(&t));
+    let leaky = flatten_tensor(&tensor_ops::leaky_relu(&t, 0.5));
+
+    for (i, x) in values.iter().enumerate() {
+        assert_eq!(relu[i].is_nan(), x.is_nan(), "relu({x}) gave {}", relu[i]);
+        assert_eq!(leaky[i].is_nan(), x.is_nan(), "leaky_relu({x}) gave {}", leaky[i]);
+    }
+    assert_eq!(relu[1].to_bits(), 0.0f64.to_bits());
+    assert_eq!(&relu[2..4], &[0.0, 2.0]);
+}
@@ -0,0 +1,166 @@
+use std::fs;
+use std::io::ErrorKind;
+use crate::Tensor;
+use crate::tensor_io::{ArrowFormat, DType, read_arrow, write_arrow, write_arrow_as};
+use crate::tensor_ops::{build_tensor, flatten_tensor, get_dimension};
+use super::{fixture, temp_path};
+
+/// Checks the contents of the mixed fixtures: Int32, Float32, nullable Int64 and UInt8 columns
+/// spread over two record batches.
+fn assert_mixed(names: &[String], t: &Tensor) {
+    assert_eq!(names, ["a", "b", "c", "d"]);
+    assert_eq!(get_dimension(t), vec![3, 4]);
+
+    let values = flatten_tensor(t);
+    assert_eq!(values[0..4], [1.0, 0.5, 10.0, 0.0]);
+    assert_eq!(values[4..6], [-2.0, 1.5]);
+    assert!(values[6].is_nan());
+    assert_eq!(values[7], 255.0);
+    assert_eq!(values[8..12], [3.0, -2.25, -1099511627776.0, 7.0]);
+}
+
+#[test]
+fn read_stream_fixture() {
+    let (names, t) = read_arrow(fixture("mixed.arrows")).unwrap();
+    assert_mixed(&names, &t);
+}
+
+#[test]
+fn read_file_fixture() {
+    let (names, t) = read_arrow(fixture("mixed.arrow")).unwrap();
+    assert_mixed(&names, &t);
+}
+
+#[test]
+fn round_trip_both_formats() {
+    let t = build_tensor(&[1.0, -1.0, 0.1, 2.0, -2.0, 0.2, 3.0, -3.0, 0.3], &[3, 3]);
+
+    for (format, name) in [(ArrowFormat::Stream, "round_trip.arrows"), (ArrowFormat::File, "round_trip.arrow")] {
+        let path = temp_path(name);
+        write_arrow(&t, &["x", "y", "z"], &path, format).unwrap();
+
+        let (names, loaded) = read_arrow(&path).unwrap();
+        assert_eq!(names, ["x", "y", "z"]);
+        assert_eq!(loaded, t);
+        fs::remove_file(&path).unwrap();
+    }
+}
+
+#[test]
+fn round_trip_float16_and_float32() {
+    // Three rows leave each Float16 column 6 bytes long, so the columns need padding
+    let t = build_tensor(&[1.0, -1.0, 0.1, 2.0, -2.0, 1e5, 3.0, -3.0, 1e-7], &[3, 3]);
+
+    for format in [ArrowFormat::Stream, ArrowFormat::File] {
+        let path = temp_path("half.arrow");
+
+        write_arrow_as(&t, &["x", "y", "z"], &path, format, DType::F16).unwrap();
+        let (names, loaded) = read_arrow(&path).unwrap();
+        assert_eq!(names, ["x", "y", "z"]);
+        let expected = [1.0, -1.0, 0.0999755859375, 2.0, -2.0, f64::INFINITY, 3.0, -3.0, 1.1920928955078125e-7];
+        assert_eq!(loaded, build_tensor(&expected, &[3, 3]));
+
+        write_arrow_as(&t, &[], &path, format, DType::F32).unwrap();
+        let (_, loaded) = read_arrow(&path).unwrap();
+        let expected: Vec<f64> = flatten_tensor(&t).iter().map(|&x| x as f32 as f64).collect();
+        assert_eq!(loaded, build_tensor(&expected, &[3, 3]));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
+
+#[test]
+fn write_as_rejects_other_dtypes() {
+    let t = Tensor::Element(vec![1.0, 2.0]);
+    for dtype in [DType::BF16, DType::I32, DType::Bool] {
+        let error = write_arrow_as(&t, &[], temp_path("dtype.arrows"), ArrowFormat::Stream, dtype).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    }
+}
+
+#[test]
+fn write_1d_with_default_name() {
+    let path = temp_path("column.arrows");
+    write_arrow(&Tensor::Element(vec![4.0, 5.0]), &[], &path, ArrowFormat::Stream).unwrap();
+
+    let (names, loaded) = read_arrow(&path).unwrap();
+    assert_eq!(names, ["column_0"]);
+    assert_eq!(loaded, build_tensor(&[4.0, 5.0], &[2, 1]));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn written_file_has_arrow_framing() {
+    let path = temp_path("framing.arrow");
+    write_arrow(&build_tensor(&[1.0, 2.0], &[1, 2]), &[], &path, ArrowFormat::File).unwrap();
+    let bytes = fs::read(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(&bytes[..8], b"ARROW1\0\0");
+    assert_eq!(&bytes[bytes.len() - 6..], b"ARROW1");
+    // The schema message starts with the continuation marker and 8-byte aligned metadata
+    assert_eq!(&bytes[8..12], &[0xFF; 4]);
+    assert_eq!(u32::from_le_bytes(bytes[12..16].try_into().unwrap()) % 8, 0);
+}
+
+#[test]
+fn write_rejects_bad_names() {
+    let t = build_tensor(&[1.0, 2.0], &[1, 2]);
+    let error = write_arrow(&t, &["only_one"], temp_path("names.arrows"), ArrowFormat::Stream).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn read_rejects_truncated_stream() {
+    let bytes = fs::read(fixture("mixed.arrows")).unwrap();
+    let path = temp_path("truncated.arrows");
+    fs::write(&path, &bytes[..bytes.len() - 20]).unwrap();
+
+    let error = read_arrow(&path).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn read_rejects_negative_null_counts() {
+    let path = temp_path("negative_nulls.arrows");
+    write_arrow(&Tensor::Element(vec![0.5; 77]), &[], &path, ArrowFormat::Stream).unwrap();
+
+    // The column's field node is its length of 77 rows followed by a null count of 0, and it has
+    // an empty validity buffer
+    let mut bytes = fs::read(&path).unwrap();
+    let node = [77i64.to_le_bytes(), 0i64.to_le_bytes()].concat();
+    let start = (0..bytes.len() - 16).find(|&i| bytes[i..i + 16] == node[..]).unwrap();
+    bytes[start + 8..start + 16].copy_from_slice(&(-1i64).to_le_bytes());
+    fs::write(&path, bytes).unwrap();
+
+    let error = read_arrow(&path).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(error.to_string().contains("negative null count"), "{error}");
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn read_rejects_overflowing_batch_lengths() {
+    let path = temp_path("huge_batch.arrows");
+    write_arrow(&Tensor::Element(vec![0.5; 77]), &[], &path, ArrowFormat::Stream).unwrap();
+
+    // Both the batch length and the column's field node length are 77 rows
+    let mut bytes = fs::read(&path).unwrap();
+    let rows = 77i64.to_le_bytes();
+    let huge = (1i64 << 62).to_le_bytes();
+    let mut replaced = 0;
+    for start in 0..bytes.len() - 8 {
+        if bytes[start..start + 8] == rows {
+            bytes[start..start + 8].copy_from_slice(&huge);
+            replaced += 1;
+        }
+    }
+    assert_eq!(replaced, 2);
+    fs::write(&path, bytes).unwrap();
+
+    let error = read_arrow(&path).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(error.to_string().contains("is too large"), "{error}");
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,253 @@
+use std::fs;
+use std::io::ErrorKind;
+use crate::Tensor;
+use crate::random::Rng;
+use crate::tensor_io::{
+    Checkpoint,
+    CheckpointWriter,
+    DType,
+    load_checkpoint,
+    save_checkpoint,
+    save_checkpoint_as
+};
+use crate::tensor_io::utilities::crc32;
+use crate::tensor_ops::build_tensor;
+use super::temp_path;
+
+fn sample_checkpoint() -> Checkpoint {
+    let mut checkpoint = Checkpoint::default();
+    checkpoint.tensors.insert(String::from("conv.weight"), build_tensor(&[0.5; 24], &[2, 3, 2, 2]));
+    checkpoint.tensors.insert(String::from("conv.bias"), Tensor::Element(vec![0.1, -0.1]));
+    checkpoint.optimizer_state.insert(String::from("conv.bias"), Tensor::Element(vec![1e-3, 2e-3]));
+    checkpoint.rng = Some(Rng::new(1234));
+    checkpoint.step = 5_000_000_000;
+    checkpoint.metadata.insert(String::from("optimizer"), String::from("adam"));
+    checkpoint.metadata.insert(String::from("run"), String::from("résumé"));
+    checkpoint
+}
+
+/// Saves a checkpoint, lets `edit` change the bytes, and loads the result.
+fn load_edited(name: &str, edit: impl FnOnce(&mut Vec<u8>)) -> std::io::Result<Checkpoint> {
+    let path = temp_path(name);
+    save_checkpoint(&sample_checkpoint(), &path).unwrap();
+
+    let mut bytes = fs::read(&path).unwrap();
+    edit(&mut bytes);
+    fs::write(&path, bytes).unwrap();
+
+    let result = load_checkpoint(&path);
+    fs::remove_file(&path).unwrap();
+    result
+}
+
+#[test]
+fn round_trip() {
+    let checkpoint = sample_checkpoint();
+    let path = temp_path("round_trip.ckpt");
+    save_checkpoint(&checkpoint, &path).unwrap();
+
+    let loaded = load_checkpoint(&path).unwrap();
+    assert_eq!(loaded, checkpoint);
+    fs::remove_file(&path).unwrap();
+
+    // The restored generator continues the same sequence
+    let mut original = checkpoint.rng.unwrap();
+    let mut restored = loaded.rng.unwrap();
+    assert_eq!(original.next_u64(), restored.next_u64());
+}
+
+#[test]
+fn save_is_deterministic() {
+    let first = temp_path("deterministic_a.ckpt");
+    let second = temp_path("deterministic_b.ckpt");
+    save_checkpoint(&sample_checkpoint(), &first).unwrap();
+    save_checkpoint(&sample_checkpoint(), &second).unwrap();
+
+    assert_eq!(fs::read(&first).unwrap(), fs::read(&second).unwrap());
+    fs::remove_file(&first).unwrap();
+    fs::remove_file(&second).unwrap();
+}
+
+#[test]
+fn streaming_writer_to_memory() {
+    let mut writer = CheckpointWriter::new(Vec::new()).unwrap();
+    for i in 0..4 {
+        writer.write_tensor(&format!("block{i}"), &Tensor::Element(vec![i as f64; 3])).unwrap();
+    }
+    writer.write_step(4).unwrap();
+    let bytes = writer.finish().unwrap();
+
+    let path = temp_path("streaming.ckpt");
+    fs::write(&path, bytes).unwrap();
+    let checkpoint = load_checkpoint(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(checkpoint.step, 4);
+    assert_eq!(checkpoint.tensors.len(), 4);
+    assert_eq!(checkpoint.tensors["block2"], Tensor::Element(vec![2.0; 3]));
+    assert_eq!(checkpoint.rng, None);
+}
+
+#[test]
+fn writer_rejects_duplicate_names() {
+    let mut writer = CheckpointWriter::new(Vec::new()).unwrap();
+    writer.write_tensor("w", &Tensor::Element(vec![1.0])).unwrap();
+    // The same name is fine in the optimizer state
+    writer.write_optimizer_tensor("w", &Tensor::Element(vec![1.0])).unwrap();
+
+    let error = writer.write_tensor("w", &Tensor::Element(vec![2.0])).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn half_precision_round_trip() {
+    let checkpoint = sample_checkpoint();
+    let full = temp_path("full.ckpt");
+    let half = temp_path("half.ckpt");
+    save_checkpoint(&checkpoint, &full).unwrap();
+    save_checkpoint_as(&checkpoint, &half, DType::F16).unwrap();
+
+    let loaded = load_checkpoint(&half).unwrap();
+    // 28 values at 6 fewer bytes each
+    let saved = fs::metadata(&full).unwrap().len() - fs::metadata(&half).unwrap().len();
+    fs::remove_file(&full).unwrap();
+    fs::remove_file(&half).unwrap();
+
+    assert_eq!(saved, 28 * 6);
+    assert_eq!(loaded.tensors["conv.weight"], checkpoint.tensors["conv.weight"]);
+    let bias = Tensor::Element(vec![0.0999755859375, -0.0999755859375]);
+    assert_eq!(loaded.tensors["conv.bias"], bias);
+    assert_eq!(loaded.step, checkpoint.step);
+    assert_eq!(loaded.metadata, checkpoint.metadata);
+}
+
+#[test]
+fn writer_mixes_dtypes() {
+    let mut writer = CheckpointWriter::new(Vec::new()).unwrap();
+    writer.write_tensor_as("w", &Tensor::Element(vec![0.1]), DType::BF16).unwrap();
+    writer.write_tensor_as("b", &Tensor::Element(vec![0.1]), DType::F32).unwrap();
+    writer.write_optimizer_tensor("w", &Tensor::Element(vec![0.1])).unwrap();
+    let bytes = writer.finish().unwrap();
+
+    let path = temp_path("mixed.ckpt");
+    fs::write(&path, bytes).unwrap();
+    let checkpoint = load_checkpoint(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(checkpoint.tensors["w"], Tensor::Element(vec![0.10009765625]));
+    assert_eq!(checkpoint.tensors["b"], Tensor::Element(vec![0.1f32 as f64]));
+    assert_eq!(checkpoint.optimizer_state["w"], Tensor::Element(vec![0.1]));
+}
+
+#[test]
+fn unsupported_dtypes_are_rejected() {
+    let mut writer = CheckpointWriter::new(Vec::new()).unwrap();
+    let error = writer.write_tensor_as("w", &Tensor::Element(vec![1.0]), DType::I32).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    // The name is still free after the failed write
+    writer.write_tensor("w", &Tensor::Element(vec![1.0])).unwrap();
+
+    let path = temp_path("unsupported.ckpt");
+    let error = save_checkpoint_as(&sample_checkpoint(), &path, DType::U8).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    assert!(!path.exists());
+}
+
+#[test]
+fn version_1_files_are_read_as_f64() {
+    // A version 1 tensor record has no dtype code between the shape and the data
+    let mut record = Vec::new();
+    record.extend_from_slice(&1u32.to_le_bytes());
+    record.extend_from_slice(b"w");
+    record.extend_from_slice(&1u32.to_le_bytes());
+    record.extend_from_slice(&2u64.to_le_bytes());
+    record.extend_from_slice(&0.5f64.to_le_bytes());
+    record.extend_from_slice(&(-1.5f64).to_le_bytes());
+    record.extend_from_slice(&crc32(&record).to_le_bytes());
+
+    let mut bytes = b"TNSMCKPT".to_vec();
+    bytes.extend_from_slice(&1u32.to_le_bytes());
+    bytes.extend_from_slice(&1u32.to_le_bytes());
+    bytes.extend_from_slice(&1u32.to_le_bytes());
+    bytes.extend_from_slice(&(record.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&record);
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(&0u64.to_le_bytes());
+
+    let path = temp_path("version_1.ckpt");
+    fs::write(&path, bytes).unwrap();
+    let checkpoint = load_checkpoint(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(checkpoint.tensors["w"], Tensor::Element(vec![0.5, -1.5]));
+}
+
+#[test]
+fn corrupted_tensor_fails_checksum() {
+    let result = load_edited("corrupt.ckpt", |bytes| {
+        // Flip a bit in the last value of the last tensor, just before its checksum and the end record
+        let index = bytes.len() - 12 - 4 - 1;
+        bytes[index] ^= 0x10;
+    });
+
+    let error = result.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(error.to_string().contains("Checksum mismatch for checkpoint tensor 'conv.bias'"), "{error}");
+}
+
+#[test]
+fn truncated_file_is_rejected() {
+    let error = load_edited("truncated.ckpt", |bytes| bytes.truncate(bytes.len() - 20)).unwrap_err();
+    assert!(error.to_string().contains("truncated"), "{error}");
+
+    // Cutting exactly the end record off is also caught
+    let error = load_edited("no_end.ckpt", |bytes| bytes.truncate(bytes.len() - 12)).unwrap_err();
+    assert!(error.to_string().contains("truncated"), "{error}");
+}
+
+#[test]
+fn unknown_records_from_newer_versions_are_skipped() {
+    let loaded = load_edited("newer.ckpt", |bytes| {
+        // A newer writer: format version 7, still readable by version 2, with a record kind 99
+        // inserted before the end record
+        bytes[8..12].copy_from_slice(&7u32.to_le_bytes());
+        let end = bytes.split_off(bytes.len() - 12);
+        bytes.extend_from_slice(&99u32.to_le_bytes());
+        bytes.extend_from_slice(&5u64.to_le_bytes());
+        bytes.extend_from_slice(b"extra");
+        bytes.extend_from_slice(&end);
+    }).unwrap();
+
+    assert_eq!(loaded, sample_checkpoint());
+}
+
+#[test]
+fn oversized_string_lengths_are_rejected() {
+    let error = load_edited("long_string.ckpt", |bytes| {
+        // A metadata record whose key claims to be 4 GiB long
+        let end = bytes.split_off(bytes.len() - 12);
+        bytes.extend_from_slice(&5u32.to_le_bytes());
+        bytes.extend_from_slice(&4u64.to_le_bytes());
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        bytes.extend_from_slice(&end);
+    }).unwrap_err();
+
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(error.to_string().contains("longer than its record"), "{error}");
+}
+
+#[test]
+fn newer_required_reader_is_rejected() {
+    let error = load_edited("too_new.ckpt", |bytes| {
+        bytes[8..12].copy_from_slice(&4u32.to_le_bytes());
+        bytes[12..16].copy_from_slice(&3u32.to_le_bytes());
+    }).unwrap_err();
+
+    assert!(error.to_string().contains("needs reader version 3"), "{error}");
+}
+
+#[test]
+fn missing_magic_is_rejected() {
+    let error = load_edited("magic.ckpt", |bytes| bytes[0] = b'X').unwrap_err();
+    assert!(error.to_string().contains("Not a tensorium checkpoint"), "{error}");
+}
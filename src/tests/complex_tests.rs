@@ -0,0 +1,218 @@
+use std::f64::consts::{ FRAC_PI_2, PI };
+use crate::{ Complex, ComplexTensor, Tensor };
+use crate::parallel::{ with_parallel_config, ParallelConfig };
+use crate::tensor_ops::{ build_tensor, flatten_tensor };
+
+type ComplexFn = fn(Complex<f64>, Complex<f64>) -> Complex<f64>;
+
+fn assert_close(actual: &[Complex<f64>], expected: &[Complex<f64>]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((*a - *e).abs() < 1e-12, "{a:?} != {e:?}");
+    }
+}
+
+fn sample(len: usize, seed: usize) -> Vec<Complex<f64>> {
+    (0..len)
+        .map(|i| {
+            let x = ((i * 7919 + seed * 104729) % 1000) as f64 / 100.0 - 5.0;
+            Complex::new(x, (x * 3.0).sin())
+        })
+        .collect()
+}
+
+#[test]
+fn scalar_arithmetic() {
+    let a = Complex::new(1.0, 2.0);
+    let b = Complex::new(3.0, -1.0);
+
+    assert_eq!(a + b, Complex::new(4.0, 1.0));
+    assert_eq!(a - b, Complex::new(-2.0, 3.0));
+    assert_eq!(a * b, Complex::new(5.0, 5.0));
+    assert_eq!((a * Complex::new(2.0, -1.0)) / Complex::new(2.0, -1.0), a);
+    assert_eq!(Complex::new(5.0, 5.0) / Complex::new(1.0, 2.0), Complex::new(3.0, -1.0));
+    assert_eq!(-a, Complex::new(-1.0, -2.0));
+    assert_eq!(a.conj(), Complex::new(1.0, -2.0));
+    assert_eq!(a.norm_sqr(), 5.0);
+
+    // Integer parts work for the operators that do not need floats
+    assert_eq!(Complex::new(1, 2) * Complex::new(3, 4), Complex::new(-5, 10));
+    assert_eq!(Complex::new(1.0f32, 1.0).abs(), 2.0f32.sqrt());
+}
+
+#[test]
+fn division_keeps_extreme_magnitudes() {
+    // re² + im² of these divisors overflows to infinity or underflows to 0
+    let big = Complex::new(1e300, 1e300);
+    assert_eq!(big / big, Complex::new(1.0, 0.0));
+    assert_eq!(Complex::new(2e300, 0.0) / Complex::new(1e300, 1e300), Complex::new(1.0, -1.0));
+
+    let small = Complex::new(1e-300, -1e-300);
+    assert_eq!(small / small, Complex::new(1.0, 0.0));
+    assert_eq!(Complex::new(1e-300, 0.0) / Complex::new(0.0, 1e-300), Complex::new(0.0, -1.0));
+
+    assert_eq!(Complex::new(1.0f32, 0.0) / Complex::new(0.0, 2.0), Complex::new(0.0, -0.5));
+}
+
+#[test]
+fn scalar_polar_form() {
+    let z = Complex::from_polar(2.0, FRAC_PI_2);
+    assert!(z.re.abs() < 1e-15);
+    assert_eq!(z.im, 2.0);
+    assert_eq!(Complex::new(-1.0, 0.0).angle(), PI);
+    assert_eq!(Complex::new(0.0, -3.0).angle(), -FRAC_PI_2);
+
+    let w = Complex::new(0.0, PI).exp();
+    assert!((w - Complex::new(-1.0, 0.0)).abs() < 1e-15);
+
+    assert_eq!(Complex::new(1e300, 1e300).abs(), 1e300 * 2.0f64.sqrt());
+    assert!(Complex::new(1.0, f64::NAN).is_nan());
+    assert_eq!(Complex::from(2.5), Complex::new(2.5, 0.0));
+}
+
+#[test]
+fn parts_round_trip() {
+    let real = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]);
+    let imag = build_tensor(&[-1.0, 0.0, 1.0, 0.5, 0.0, -0.5], &[2, 3]);
+
+    let z = ComplexTensor::new(&real, &imag);
+    assert_eq!(z.shape(), &[2, 3]);
+    assert_eq!(z.values()[3], Complex::new(4.0, 0.5));
+    assert_eq!(z.real(), real);
+    assert_eq!(z.imag(), imag);
+
+    let from_real = ComplexTensor::from(&real);
+    assert_eq!(from_real.imag(), build_tensor(&[0.0; 6], &[2, 3]));
+    assert_eq!(from_real.real(), real);
+}
+
+#[test]
+fn abs_angle_and_polar() {
+    let z = ComplexTensor::from_values(
+        vec![Complex::new(3.0, 4.0), Complex::new(0.0, -2.0), Complex::new(-1.0, 0.0)],
+        &[3]
+    );
+    assert_eq!(z.abs(), Tensor::Element(vec![5.0, 2.0, 1.0]));
+    assert_eq!(z.angle(), Tensor::Element(vec![(4.0f64).atan2(3.0), -FRAC_PI_2, PI]));
+
+    let rebuilt = ComplexTensor::polar(&z.abs(), &z.angle());
+    assert_close(rebuilt.values(), z.values());
+}
+
+#[test]
+fn element_wise_operators() {
+    let a = ComplexTensor::from_values(sample(12, 1), &[3, 4]);
+    let b = ComplexTensor::from_values(sample(12, 2), &[3, 4]);
+
+    let checks: [(ComplexTensor, ComplexFn); 4] = [
+        (&a + &b, |x, y| x + y),
+        (&a - &b, |x, y| x - y),
+        (&a * &b, |x, y| x * y),
+        (a.clone() / b.clone(), |x, y| x / y),
+    ];
+    for (result, func) in checks {
+        assert_eq!(result.shape(), &[3, 4]);
+        for i in 0..12 {
+            assert_eq!(result.values()[i], func(a.values()[i], b.values()[i]));
+        }
+    }
+
+    assert_eq!((-&a).values()[5], -a.values()[5]);
+    assert_eq!((&a * &a.conj()).imag(), build_tensor(&[0.0; 12], &[3, 4]));
+}
+
+#[test]
+fn element_wise_operators_broadcast() {
+    let a = ComplexTensor::from_values(sample(12, 1), &[3, 1, 4]);
+    let b = ComplexTensor::from_values(sample(8, 2), &[2, 4]);
+    let scalar = ComplexTensor::from_values(sample(1, 3), &[1]);
+
+    let sum = &a + &b;
+    assert_eq!(sum.shape(), &[3, 2, 4]);
+    for i in 0..3 {
+        for j in 0..2 {
+            for k in 0..4 {
+                let expected = a.values()[i * 4 + k] + b.values()[j * 4 + k];
+                assert_eq!(sum.values()[(i * 2 + j) * 4 + k], expected);
+            }
+        }
+    }
+
+    let quotient = &scalar / &a;
+    assert_eq!(quotient.shape(), &[3, 1, 4]);
+    for i in 0..12 {
+        assert_eq!(quotient.values()[i], scalar.values()[0] / a.values()[i]);
+    }
+}
+
+#[test]
+fn element_wise_is_the_same_for_any_thread_count() {
+    let a = ComplexTensor::from_values(sample(5000, 3), &[50, 100]);
+    let b = ComplexTensor::from_values(sample(5000, 4), &[50, 100]);
+
+    let serial = with_parallel_config(
+        ParallelConfig { threads: 1, grain_size: 64, deterministic: true },
+        || &a * &b
+    );
+    let parallel = with_parallel_config(
+        ParallelConfig { threads: 4, grain_size: 64, deterministic: true },
+        || &a * &b
+    );
+    assert_eq!(serial, parallel);
+}
+
+#[test]
+fn matmul_matches_scalar_products() {
+    let a = ComplexTensor::from_values(sample(6, 5), &[2, 3]);
+    let b = ComplexTensor::from_values(sample(12, 6), &[3, 4]);
+
+    let product = a.matmul(&b);
+    assert_eq!(product.shape(), &[2, 4]);
+
+    let mut expected = Vec::new();
+    for i in 0..2 {
+        for j in 0..4 {
+            let mut total = Complex::default();
+            for k in 0..3 {
+                total = total + a.values()[i * 3 + k] * b.values()[k * 4 + j];
+            }
+            expected.push(total);
+        }
+    }
+    assert_close(product.values(), &expected);
+}
+
+#[test]
+fn matmul_follows_real_rules() {
+    let batch = ComplexTensor::from_values(sample(24, 7), &[2, 3, 4]);
+    let vector = ComplexTensor::from_values(sample(4, 8), &[4]);
+    assert_eq!(batch.matmul(&vector).shape(), &[2, 3]);
+    assert_eq!(vector.matmul(&vector).shape(), &[1]);
+
+    // Real inputs give the real product
+    let real = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]);
+    let z = ComplexTensor::from_real(&real);
+    let product = z.matmul(&z);
+    assert_eq!(flatten_tensor(&product.real()), vec![7.0, 10.0, 15.0, 22.0]);
+    assert_eq!(flatten_tensor(&product.imag()), vec![0.0; 4]);
+}
+
+#[test]
+#[should_panic(expected = "Complex Tensor shapes [2] and [3] do not match!")]
+fn mismatched_parts_panic() {
+    ComplexTensor::new(&Tensor::Element(vec![1.0, 2.0]), &Tensor::Element(vec![1.0, 2.0, 3.0]));
+}
+
+#[test]
+#[should_panic(expected = "Shapes are not broadcastable!")]
+fn mismatched_operands_panic() {
+    let a = ComplexTensor::from_values(sample(2, 1), &[2]);
+    let b = ComplexTensor::from_values(sample(3, 1), &[3]);
+    let _ = &a + &b;
+}
+
+#[test]
+#[should_panic(expected = "3 values cannot fill a Tensor of shape [2, 2]!")]
+fn wrong_value_count_panics() {
+    ComplexTensor::from_values(sample(3, 1), &[2, 2]);
+}
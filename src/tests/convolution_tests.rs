@@ -0,0 +1,203 @@
+use crate::Tensor;
+use crate::random::Rng;
+use crate::tensor_ops;
+use crate::tensor_ops::{
+    build_tensor,
+    flatten_tensor,
+    Conv1dOptions,
+    Conv2dOptions,
+    ConvTranspose2dOptions,
+    PaddingMode,
+    zero_tensor
+};
+
+#[test]
+fn batched_matmul_broadcasts() {
+    let l = build_tensor(&[1.0, 0.0, 0.0, 1.0, 1.0, 2.0, 3.0, 4.0], &[2, 2, 2]);
+    let r = build_tensor(&[1.0, 1.0], &[2, 1]);
+
+    assert_eq!(
+        tensor_ops::matmul(&l, &r),
+        build_tensor(&[1.0, 1.0, 3.0, 7.0], &[2, 2, 1])
+    );
+
+    let v = Tensor::Element(Vec::from([1.0, 2.0, 3.0]));
+    assert_eq!(tensor_ops::matmul(&v, &v), Tensor::Element(Vec::from([14.0])));
+}
+
+#[test]
+fn conv2d_stride_padding_bias() {
+    let input = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0], &[1, 1, 3, 3]);
+    let weight = build_tensor(&[1.0, 1.0, 1.0, 1.0], &[1, 1, 2, 2]);
+    let bias = Tensor::Element(Vec::from([1.0]));
+    let options = Conv2dOptions { stride: [2, 2], padding: [1, 1], ..Conv2dOptions::default() };
+
+    assert_eq!(
+        tensor_ops::conv2d(&input, &weight, Some(&bias), &options),
+        build_tensor(&[2.0, 6.0, 12.0, 29.0], &[1, 1, 2, 2])
+    );
+}
+
+#[test]
+fn conv2d_groups() {
+    let input = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[1, 2, 1, 2]);
+    let weight = build_tensor(&[10.0, 100.0], &[2, 1, 1, 1]);
+    let options = Conv2dOptions { groups: 2, ..Conv2dOptions::default() };
+
+    assert_eq!(
+        tensor_ops::conv2d(&input, &weight, None, &options),
+        build_tensor(&[10.0, 20.0, 300.0, 400.0], &[1, 2, 1, 2])
+    );
+}
+
+#[test]
+fn conv1d_channels_and_batches() {
+    let input = build_tensor(
+        &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 2.0, 4.0, 6.0, 8.0, 10.0, 12.0],
+        &[2, 2, 3]
+    );
+    let weight = build_tensor(&[1.0, 0.0, 0.0, 1.0], &[1, 2, 2]);
+    let bias = Tensor::Element(Vec::from([0.5]));
+
+    assert_eq!(
+        tensor_ops::conv1d(&input, &weight, Some(&bias), &Conv1dOptions::default()),
+        build_tensor(&[6.5, 8.5, 12.5, 16.5], &[2, 1, 2])
+    );
+}
+
+#[test]
+fn conv1d_reflect_and_dilation() {
+    let input = build_tensor(&[1.0, 2.0, 3.0], &[1, 1, 3]);
+    let weight = build_tensor(&[1.0, 0.0, -1.0], &[1, 1, 3]);
+    let options = Conv1dOptions {
+        padding: 1,
+        padding_mode: PaddingMode::Reflect,
+        ..Conv1dOptions::default()
+    };
+
+    // Reflect padding gives [2, 1, 2, 3, 2]
+    assert_eq!(
+        tensor_ops::conv1d(&input, &weight, None, &options),
+        build_tensor(&[0.0, -2.0, 0.0], &[1, 1, 3])
+    );
+
+    let input = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0], &[1, 1, 5]);
+    let weight = build_tensor(&[1.0, 1.0], &[1, 1, 2]);
+    let options = Conv1dOptions { dilation: 2, ..Conv1dOptions::default() };
+    assert_eq!(
+        tensor_ops::conv1d(&input, &weight, None, &options),
+        build_tensor(&[4.0, 6.0, 8.0], &[1, 1, 3])
+    );
+
+    let options = Conv1dOptions { dilation: 2, stride: 2, ..Conv1dOptions::default() };
+    assert_eq!(
+        tensor_ops::conv1d(&input, &weight, None, &options),
+        build_tensor(&[4.0, 8.0], &[1, 1, 2])
+    );
+}
+
+#[test]
+fn conv_transpose2d_padding_and_output_padding() {
+    let input = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[1, 1, 2, 2]);
+    let weight = build_tensor(&[1.0; 9], &[1, 1, 3, 3]);
+    let options = ConvTranspose2dOptions {
+        stride: [2, 2],
+        padding: [1, 1],
+        output_padding: [1, 1],
+        ..ConvTranspose2dOptions::default()
+    };
+
+    assert_eq!(
+        tensor_ops::conv_transpose2d(&input, &weight, None, &options),
+        build_tensor(&[
+            1.0, 3.0, 2.0, 2.0,
+            4.0, 10.0, 6.0, 6.0,
+            3.0, 7.0, 4.0, 4.0,
+            3.0, 7.0, 4.0, 4.0
+        ], &[1, 1, 4, 4])
+    );
+}
+
+#[test]
+fn conv_transpose2d_is_adjoint_of_conv2d() {
+    // <conv2d(x, w), y> == <x, conv_transpose2d(y, w)> for matching options
+    let mut rng = Rng::new(1);
+    let mut random = |shape: &[usize]| {
+        let n: usize = shape.iter().product();
+        let values: Vec<f64> = (0..n).map(|_| rng.uniform(-1.0, 1.0)).collect();
+        build_tensor(&values, shape)
+    };
+
+    let x = random(&[2, 4, 7, 6]);
+    let w = random(&[2, 2, 3, 3]);
+    let y = random(&[2, 2, 3, 2]);
+
+    let conv = tensor_ops::conv2d(&x, &w, None, &Conv2dOptions {
+        stride: [2, 2],
+        padding: [1, 1],
+        dilation: [2, 2],
+        groups: 2,
+        padding_mode: PaddingMode::Zeros
+    });
+    let transposed = tensor_ops::conv_transpose2d(&y, &w, None, &ConvTranspose2dOptions {
+        stride: [2, 2],
+        padding: [1, 1],
+        output_padding: [0, 1],
+        dilation: [2, 2],
+        groups: 2
+    });
+
+    let lhs: f64 = flatten_tensor(&conv).iter().zip(flatten_tensor(&y)).map(|(a, b)| a * b).sum();
+    let rhs: f64 = flatten_tensor(&x).iter().zip(flatten_tensor(&transposed)).map(|(a, b)| a * b).sum();
+    assert!((lhs - rhs).abs() < 1e-12);
+}
+
+#[test]
+fn padded_pooling() {
+    let input = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0], &[1, 1, 3, 3]);
+
+    assert_eq!(
+        tensor_ops::max_pool2d(&input, [3, 3], [2, 2], [1, 1]),
+        build_tensor(&[5.0, 6.0, 8.0, 9.0], &[1, 1, 2, 2])
+    );
+    assert_eq!(
+        tensor_ops::avg_pool2d(&input, [3, 3], [2, 2], [1, 1]),
+        build_tensor(&[12.0 / 9.0, 16.0 / 9.0, 24.0 / 9.0, 28.0 / 9.0], &[1, 1, 2, 2])
+    );
+}
+
+#[test]
+fn adaptive_pooling_uneven_windows() {
+    let input = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0], &[1, 1, 1, 5]);
+
+    assert_eq!(
+        tensor_ops::adaptive_avg_pool2d(&input, [1, 3]),
+        build_tensor(&[1.5, 3.0, 4.5], &[1, 1, 1, 3])
+    );
+}
+
+#[test]
+#[should_panic(expected = "Stride must be at least 1!")]
+fn zero_stride_is_rejected() {
+    let input = zero_tensor(vec![1, 1, 3, 3]);
+    let weight = zero_tensor(vec![1, 1, 2, 2]);
+    let options = Conv2dOptions { stride: [0, 1], ..Conv2dOptions::default() };
+
+    tensor_ops::conv2d(&input, &weight, None, &options);
+}
+
+#[test]
+#[should_panic(expected = "Groups must be at least 1!")]
+fn zero_groups_are_rejected() {
+    let input = zero_tensor(vec![1, 2, 3]);
+    let weight = zero_tensor(vec![2, 1, 2]);
+    let options = Conv1dOptions { groups: 0, ..Conv1dOptions::default() };
+
+    tensor_ops::conv1d(&input, &weight, None, &options);
+}
+
+#[test]
+#[should_panic(expected = "Stride must be at least 1!")]
+fn zero_pooling_stride_is_rejected() {
+    tensor_ops::max_pool2d(&zero_tensor(vec![1, 1, 4, 4]), [2, 2], [2, 0], [0, 0]);
+}
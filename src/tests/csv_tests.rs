@@ -0,0 +1,131 @@
+use std::fs;
+use std::io::ErrorKind;
+use crate::Tensor;
+use crate::tensor_io::{CsvReadOptions, CsvWriteOptions, FloatFormat, read_csv, write_csv};
+use crate::tensor_ops::{build_tensor, flatten_tensor};
+use super::temp_path;
+
+fn read_text(name: &str, text: &str, options: &CsvReadOptions) -> std::io::Result<Tensor> {
+    let path = temp_path(name);
+    fs::write(&path, text).unwrap();
+    let result = read_csv(&path, options);
+    fs::remove_file(&path).unwrap();
+    result
+}
+
+fn write_text(name: &str, tensor: &Tensor, options: &CsvWriteOptions) -> String {
+    let path = temp_path(name);
+    write_csv(tensor, &path, options).unwrap();
+    let text = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    text
+}
+
+#[test]
+fn read_default() {
+    let t = read_text("default.csv", "1,2,3\n4,5,6\n", &CsvReadOptions::default()).unwrap();
+    assert_eq!(t, build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]));
+}
+
+#[test]
+fn read_comments_blank_lines_and_crlf() {
+    let text = "# leading comment\r\n1, 2 # trailing comment\r\n\r\n3,4\r\n";
+    let t = read_text("comments.csv", text, &CsvReadOptions::default()).unwrap();
+    assert_eq!(t, build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]));
+}
+
+#[test]
+fn read_missing_values_default_to_nan() {
+    let t = read_text("missing.csv", "1,,3\n", &CsvReadOptions::default()).unwrap();
+    let values = flatten_tensor(&t);
+
+    assert_eq!(values[0], 1.0);
+    assert!(values[1].is_nan());
+    assert_eq!(values[2], 3.0);
+}
+
+#[test]
+fn read_selected_columns_in_order() {
+    let options = CsvReadOptions { columns: Some(vec![2, 0]), ..Default::default() };
+    let t = read_text("columns.csv", "1,2,3\n4,5,6\n", &options).unwrap();
+    assert_eq!(t, build_tensor(&[3.0, 1.0, 6.0, 4.0], &[2, 2]));
+}
+
+#[test]
+fn read_whitespace_delimiter() {
+    let options = CsvReadOptions { delimiter: ' ', ..Default::default() };
+    let t = read_text("spaces.txt", "  1   2.5\n-3 4e2  \n", &options).unwrap();
+    assert_eq!(t, build_tensor(&[1.0, 2.5, -3.0, 400.0], &[2, 2]));
+}
+
+#[test]
+fn read_quoted_fields() {
+    let options = CsvReadOptions { delimiter: ';', ..Default::default() };
+    let t = read_text("quoted.csv", "\"1.5\";\" 2 \"\n\"\";3\n", &options).unwrap();
+    let values = flatten_tensor(&t);
+
+    assert_eq!(values[..2], [1.5, 2.0]);
+    assert!(values[2].is_nan());
+    assert_eq!(values[3], 3.0);
+}
+
+#[test]
+fn read_comment_characters_inside_quotes() {
+    let options = CsvReadOptions { columns: Some(vec![1]), ..Default::default() };
+    let text = "\"a#b\",1 # note\n\"#\",2\n";
+    let t = read_text("quoted_comment.csv", text, &options).unwrap();
+    assert_eq!(t, build_tensor(&[1.0, 2.0], &[2, 1]));
+}
+
+#[test]
+fn read_errors_name_the_line() {
+    let options = CsvReadOptions { skip_rows: 1, ..Default::default() };
+
+    let error = read_text("bad_value.csv", "a,b\n1,2\n3,x\n", &options).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(error.to_string().contains("Line 3, column 1: cannot parse 'x'"), "{error}");
+
+    let error = read_text("ragged.csv", "a,b\n1,2\n3\n", &options).unwrap_err();
+    assert!(error.to_string().contains("Line 3: expected 2 fields"), "{error}");
+
+    let error = read_text("empty.csv", "a,b\n# nothing\n", &options).unwrap_err();
+    assert!(error.to_string().contains("no data rows"), "{error}");
+}
+
+#[test]
+fn write_1d_as_column() {
+    let text = write_text("column.csv", &Tensor::Element(vec![1.0, 0.1, -2.5]), &CsvWriteOptions::default());
+    assert_eq!(text, "1\n0.1\n-2.5\n");
+}
+
+#[test]
+fn write_scientific_with_delimiter() {
+    let options = CsvWriteOptions {
+        delimiter: String::from("\t"),
+        float_format: FloatFormat::Scientific(2),
+        ..Default::default()
+    };
+    let text = write_text("scientific.tsv", &build_tensor(&[1234.5, -0.001], &[1, 2]), &options);
+    assert_eq!(text, "1.23e3\t-1.00e-3\n");
+}
+
+#[test]
+fn write_rejects_3d() {
+    let t = build_tensor(&[0.0; 8], &[2, 2, 2]);
+    let error = write_csv(&t, temp_path("3d.csv"), &CsvWriteOptions::default()).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn round_trip_is_exact() {
+    let values = [0.1, 1.0 / 3.0, -1e-300, 6.02214076e23, f64::INFINITY, 42.0];
+    let t = build_tensor(&values, &[3, 2]);
+
+    let path = temp_path("round_trip.csv");
+    let write_options = CsvWriteOptions { header: Some(String::from("a,b")), ..Default::default() };
+    write_csv(&t, &path, &write_options).unwrap();
+
+    let read_options = CsvReadOptions { skip_rows: 1, ..Default::default() };
+    assert_eq!(read_csv(&path, &read_options).unwrap(), t);
+    fs::remove_file(&path).unwrap();
+}
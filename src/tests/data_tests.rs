@@ -0,0 +1,177 @@
+use std::collections::HashSet;
+use crate::Tensor;
+use crate::tensor_ops::{ build_tensor, flatten_tensor, get_dimension };
+use crate::data::{
+    default_collate,
+    random_split,
+    train_val_split,
+    DataLoader,
+    DataLoaderOptions,
+    Dataset,
+    TensorDataset
+};
+
+/// A dataset of `len` samples, where sample `i` has features `[i, -i]` and label `i`.
+fn numbered(len: usize) -> TensorDataset {
+    let features: Vec<f64> = (0..len).flat_map(|i| [i as f64, -(i as f64)]).collect();
+    let labels: Vec<f64> = (0..len).map(|i| i as f64).collect();
+    TensorDataset::new(vec![build_tensor(&features, &[len, 2]), Tensor::Element(labels)])
+}
+
+/// The labels of every batch of one epoch, in order.
+fn epoch_labels<D: Dataset>(loader: &mut DataLoader<D>) -> Vec<f64> {
+    loader.iter().flat_map(|batch| flatten_tensor(&batch[1])).collect()
+}
+
+/// A dataset whose samples panic when read at one index.
+struct Faulty {
+    bad: usize,
+}
+
+impl Dataset for Faulty {
+    fn len(&self) -> usize {
+        20
+    }
+
+    fn get(&self, index: usize) -> Vec<Tensor> {
+        if index == self.bad {
+            panic!("Sample {index} is corrupt!");
+        }
+        vec![Tensor::Element(vec![index as f64])]
+    }
+}
+
+#[test]
+fn tensor_dataset_slices_the_first_axis() {
+    let dataset = numbered(5);
+    assert_eq!(dataset.len(), 5);
+    assert!(!dataset.is_empty());
+    assert_eq!(dataset.get(3), [Tensor::Element(vec![3.0, -3.0]), Tensor::Element(vec![3.0])]);
+}
+
+#[test]
+#[should_panic(expected = "TensorDataset Tensors have 3 and 4 samples along their first axis!")]
+fn tensor_dataset_rejects_mismatched_lengths() {
+    TensorDataset::new(vec![build_tensor(&[0.0; 6], &[3, 2]), Tensor::Element(vec![0.0; 4])]);
+}
+
+#[test]
+fn batches_stack_samples_and_drop_the_last() {
+    let options = DataLoaderOptions { batch_size: 4, ..Default::default() };
+    let mut loader = DataLoader::new(numbered(10), options.clone());
+    assert_eq!(loader.len(), 3);
+
+    let batches: Vec<Vec<Tensor>> = loader.iter().collect();
+    assert_eq!(batches.len(), 3);
+    assert_eq!(get_dimension(&batches[0][0]), [4, 2]);
+    assert_eq!(get_dimension(&batches[0][1]), [4, 1]);
+    assert_eq!(get_dimension(&batches[2][0]), [2, 2]);
+    assert_eq!(batches[2][0], build_tensor(&[8.0, -8.0, 9.0, -9.0], &[2, 2]));
+
+    let options = DataLoaderOptions { drop_last: true, ..options };
+    let mut dropping = DataLoader::new(numbered(10), options);
+    assert_eq!(dropping.len(), 2);
+    assert_eq!(epoch_labels(&mut dropping), (0..8).map(|i| i as f64).collect::<Vec<f64>>());
+}
+
+#[test]
+fn shuffling_is_seeded_and_changes_every_epoch() {
+    let options = DataLoaderOptions { batch_size: 3, shuffle: true, seed: 9, ..Default::default() };
+    let mut first = DataLoader::new(numbered(30), options.clone());
+    let mut second = DataLoader::new(numbered(30), options);
+
+    let epochs: Vec<Vec<f64>> = (0..3).map(|_| epoch_labels(&mut first)).collect();
+    for epoch in &epochs {
+        assert_eq!(*epoch, epoch_labels(&mut second));
+
+        // Every sample is seen exactly once an epoch
+        let mut sorted = epoch.clone();
+        sorted.sort_by(f64::total_cmp);
+        assert_eq!(sorted, (0..30).map(|i| i as f64).collect::<Vec<f64>>());
+    }
+    assert_ne!(epochs[0], epochs[1]);
+    assert_ne!(epochs[1], epochs[2]);
+}
+
+#[test]
+fn workers_give_the_same_batches_in_order() {
+    for workers in 1..=4 {
+        let options = DataLoaderOptions {
+            batch_size: 3,
+            shuffle: true,
+            seed: 5,
+            ..Default::default()
+        };
+        let mut serial = DataLoader::new(numbered(25), options.clone());
+        let options = DataLoaderOptions { workers, prefetch: 1, ..options };
+        let mut parallel = DataLoader::new(numbered(25), options);
+
+        for _ in 0..2 {
+            let expected: Vec<Vec<Tensor>> = serial.iter().collect();
+            let actual: Vec<Vec<Tensor>> = parallel.iter().collect();
+            assert_eq!(actual, expected);
+        }
+
+        // Stopping part way through an epoch leaves no worker waiting
+        assert_eq!(parallel.iter().take(2).count(), 2);
+    }
+}
+
+#[test]
+fn custom_collate_functions() {
+    let options = DataLoaderOptions { batch_size: 4, workers: 2, ..Default::default() };
+    let mut loader = DataLoader::new(numbered(8), options).with_collate(|samples| {
+        let total: f64 = samples.iter().flat_map(|sample| flatten_tensor(&sample[1])).sum();
+        vec![Tensor::Element(vec![total])]
+    });
+
+    let sums: Vec<Vec<Tensor>> = loader.iter().collect();
+    assert_eq!(sums, [vec![Tensor::Element(vec![6.0])], vec![Tensor::Element(vec![22.0])]]);
+}
+
+#[test]
+#[should_panic(expected = "Cannot collate field 0 with samples of shapes [2] and [3]!")]
+fn default_collate_rejects_ragged_samples() {
+    default_collate(vec![
+        vec![Tensor::Element(vec![1.0, 2.0])],
+        vec![Tensor::Element(vec![1.0, 2.0, 3.0])],
+    ]);
+}
+
+#[test]
+#[should_panic(expected = "Sample 13 is corrupt!")]
+fn worker_panics_reach_the_caller() {
+    let options = DataLoaderOptions { batch_size: 2, workers: 3, ..Default::default() };
+    let mut loader = DataLoader::new(Faulty { bad: 13 }, options);
+    for _ in loader.iter() {}
+}
+
+#[test]
+fn splits_cover_the_dataset_without_overlap() {
+    let parts = random_split(numbered(20), &[10, 7, 3], 1);
+    let mut seen = HashSet::new();
+    for (part, len) in parts.iter().zip([10, 7, 3]) {
+        assert_eq!(part.len(), len);
+        for i in 0..part.len() {
+            let label = flatten_tensor(&part.get(i)[1])[0] as usize;
+            assert_eq!(label, part.indices()[i]);
+            assert!(seen.insert(label));
+        }
+    }
+    assert_eq!(seen.len(), 20);
+
+    // The same seed gives the same split
+    let again = random_split(numbered(20), &[10, 7, 3], 1);
+    assert_eq!(again[1].indices(), parts[1].indices());
+
+    let (train, val) = train_val_split(numbered(20), 0.25, 3);
+    assert_eq!((train.len(), val.len()), (15, 5));
+    let train: HashSet<usize> = train.indices().iter().copied().collect();
+    assert!(val.indices().iter().all(|i| !train.contains(i)));
+}
+
+#[test]
+#[should_panic(expected = "Split lengths add up to 9, but the dataset has 10 samples!")]
+fn random_split_rejects_wrong_lengths() {
+    random_split(numbered(10), &[5, 4], 0);
+}
@@ -0,0 +1,118 @@
+use crate::Tensor;
+use crate::random::Rng;
+use crate::tensor_ops;
+use crate::tensor_ops::{build_tensor, einsum, flatten_tensor, get_dimension, matmul};
+
+fn random_tensor(shape: &[usize], seed: u64) -> Tensor {
+    let mut rng = Rng::new(seed);
+    let n: usize = shape.iter().product();
+    let values: Vec<f64> = (0..n).map(|_| rng.uniform(-1.0, 1.0)).collect();
+    build_tensor(&values, shape)
+}
+
+fn assert_tensor_close(actual: &Tensor, expected: &Tensor) {
+    assert_eq!(get_dimension(actual), get_dimension(expected));
+    for (a, e) in flatten_tensor(actual).iter().zip(flatten_tensor(expected)) {
+        assert!((a - e).abs() < 1e-12, "{a} != {e}");
+    }
+}
+
+#[test]
+fn attention_scores() {
+    let (b, h, q, k, d) = (2, 3, 4, 5, 6);
+    let queries = random_tensor(&[b, h, q, d], 1);
+    let keys = random_tensor(&[b, h, k, d], 2);
+
+    let scores = einsum("bhqd,bhkd->bhqk", &[&queries, &keys]);
+
+    let qv = flatten_tensor(&queries);
+    let kv = flatten_tensor(&keys);
+    let mut expected = Vec::new();
+    for bi in 0..b {
+        for hi in 0..h {
+            for qi in 0..q {
+                for ki in 0..k {
+                    let mut sum = 0.0;
+                    for di in 0..d {
+                        sum += qv[((bi * h + hi) * q + qi) * d + di] * kv[((bi * h + hi) * k + ki) * d + di];
+                    }
+                    expected.push(sum);
+                }
+            }
+        }
+    }
+
+    assert_tensor_close(&scores, &build_tensor(&expected, &[b, h, q, k]));
+}
+
+#[test]
+fn chained_contraction() {
+    let a = random_tensor(&[3, 4], 3);
+    let b = random_tensor(&[4, 5], 4);
+    let c = random_tensor(&[5, 2], 5);
+
+    let expected = matmul(&matmul(&a, &b), &c);
+    assert_tensor_close(&einsum("ij,jk,kl->il", &[&a, &b, &c]), &expected);
+    assert_tensor_close(&einsum("ij,jk,kl", &[&a, &b, &c]), &expected);
+
+    // A full contraction of four operands down to a single value
+    let v = random_tensor(&[3], 6);
+    let w = random_tensor(&[2], 7);
+    let vabcw = matmul(&matmul(&v, &expected), &w);
+    assert_tensor_close(&einsum("i,ij,jk,kl,l->", &[&v, &a, &b, &c, &w]), &vabcw);
+}
+
+#[test]
+fn single_operand_forms() {
+    let a = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]);
+
+    assert_eq!(einsum("ji", &[&a]), build_tensor(&[1.0, 4.0, 2.0, 5.0, 3.0, 6.0], &[3, 2]));
+    assert_eq!(einsum("ij->", &[&a]), Tensor::Element(Vec::from([21.0])));
+    assert_eq!(einsum("ij->j", &[&a]), Tensor::Element(Vec::from([5.0, 7.0, 9.0])));
+
+    let cube = build_tensor(&(0..8).map(|x| x as f64).collect::<Vec<f64>>(), &[2, 2, 2]);
+    assert_eq!(einsum("iii->i", &[&cube]), Tensor::Element(Vec::from([0.0, 7.0])));
+    assert_eq!(einsum("iij->j", &[&cube]), Tensor::Element(Vec::from([6.0, 8.0])));
+}
+
+#[test]
+fn implicit_repeated_letters_are_summed() {
+    let a = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]);
+    let b = build_tensor(&[5.0, 6.0, 7.0, 8.0], &[2, 2]);
+
+    assert_eq!(einsum("ij,ij", &[&a, &b]), Tensor::Element(Vec::from([70.0])));
+    assert_eq!(
+        einsum("i,j", &[&Tensor::Element(Vec::from([1.0, 2.0])), &Tensor::Element(Vec::from([3.0, 4.0]))]),
+        build_tensor(&[3.0, 4.0, 6.0, 8.0], &[2, 2])
+    );
+}
+
+#[test]
+fn ellipsis_broadcasting() {
+    let a = random_tensor(&[2, 1, 3, 4], 8);
+    let b = random_tensor(&[5, 4, 2], 9);
+
+    let result = einsum("...ij,...jk->...ik", &[&a, &b]);
+    assert_eq!(get_dimension(&result), Vec::from([2, 5, 3, 2]));
+
+    // Matches broadcast batched matmul
+    assert_tensor_close(&result, &matmul(&a, &b));
+    assert_tensor_close(&einsum("...ij,...jk", &[&a, &b]), &matmul(&a, &b));
+
+    let shape = tensor_ops::broadcast_shape(&Vec::from([2, 1]), &Vec::from([5]));
+    assert_eq!(get_dimension(&result)[..2].to_vec(), shape);
+}
+
+#[test]
+fn path_prefers_small_intermediates() {
+    let a = random_tensor(&[2, 50], 10);
+    let b = random_tensor(&[50, 50], 11);
+    let v = random_tensor(&[50], 12);
+
+    // a @ b @ v is cheapest as a @ (b @ v)
+    assert_eq!(tensor_ops::einsum_path("ij,jk,k->i", &[&a, &b, &v]), Vec::from([(1, 2), (0, 1)]));
+    assert_tensor_close(
+        &einsum("ij,jk,k->i", &[&a, &b, &v]),
+        &matmul(&a, &matmul(&b, &v))
+    );
+}
@@ -0,0 +1,302 @@
+use std::f64::consts::PI;
+use crate::{ Complex, ComplexTensor, Tensor };
+use crate::fft::{
+    cached_lengths,
+    fft,
+    fft2,
+    fftfreq,
+    fftn,
+    fftshift,
+    ifft,
+    ifft2,
+    ifftn,
+    ifftshift,
+    irfft,
+    rfft,
+    rfftfreq,
+    FftNorm
+};
+use crate::tensor_ops::{ build_tensor, flatten_tensor };
+
+type C64 = Complex<f64>;
+
+fn signal(len: usize, seed: usize) -> Vec<C64> {
+    (0..len)
+        .map(|i| {
+            let x = ((i * 7919 + seed * 104729) % 1000) as f64 / 100.0 - 5.0;
+            C64::new(x, (x * 1.7 + seed as f64).cos())
+        })
+        .collect()
+}
+
+/// The transform by its definition, in quadratic time.
+fn naive_dft(x: &[C64]) -> Vec<C64> {
+    let n = x.len();
+    (0..n)
+        .map(|k| {
+            x.iter().enumerate().fold(C64::default(), |total, (j, &value)| {
+                total + value * C64::from_polar(1.0, -2.0 * PI * ((j * k) % n) as f64 / n as f64)
+            })
+        })
+        .collect()
+}
+
+fn assert_close(actual: &[C64], expected: &[C64], tolerance: f64) {
+    assert_eq!(actual.len(), expected.len());
+    let scale = expected.iter().map(|z| z.abs()).fold(1.0, f64::max);
+    for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
+        assert!((*a - *e).abs() <= tolerance * scale, "at {i}: {a:?} != {e:?}");
+    }
+}
+
+fn assert_real_close(actual: &Tensor, expected: &[f64], tolerance: f64) {
+    let actual = flatten_tensor(actual);
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() <= tolerance, "{a} != {e}");
+    }
+}
+
+fn vector(values: Vec<C64>) -> ComplexTensor {
+    let len = values.len();
+    ComplexTensor::from_values(values, &[len])
+}
+
+#[test]
+fn matches_naive_dft_for_every_small_length() {
+    for len in 1..=64 {
+        let x = signal(len, len);
+        let spectrum = fft(&vector(x.clone()), None, 0, FftNorm::Backward);
+        assert_close(spectrum.values(), &naive_dft(&x), 1e-12);
+    }
+}
+
+#[test]
+fn matches_naive_dft_for_large_prime_factors() {
+    // Primes and products with a large prime, which use Bluestein's algorithm
+    for len in [97, 101, 127, 211, 2 * 211, 3 * 101, 1009] {
+        let x = signal(len, 3);
+        let spectrum = fft(&vector(x.clone()), None, 0, FftNorm::Backward);
+        assert_close(spectrum.values(), &naive_dft(&x), 1e-11);
+    }
+}
+
+#[test]
+fn matches_naive_dft_for_smooth_lengths() {
+    for len in [128, 360, 500, 729, 1000, 1024] {
+        let x = signal(len, 4);
+        let spectrum = fft(&vector(x.clone()), None, 0, FftNorm::Backward);
+        assert_close(spectrum.values(), &naive_dft(&x), 1e-11);
+    }
+}
+
+#[test]
+fn inverse_round_trips_for_every_norm() {
+    for norm in [FftNorm::Backward, FftNorm::Ortho, FftNorm::Forward] {
+        for len in [1, 2, 7, 12, 97, 256] {
+            let x = vector(signal(len, 5));
+            let back = ifft(&fft(&x, None, 0, norm), None, 0, norm);
+            assert_close(back.values(), x.values(), 1e-12);
+        }
+    }
+}
+
+#[test]
+fn norm_modes_scale_like_numpy() {
+    let x = vector(signal(8, 6));
+    let backward = fft(&x, None, 0, FftNorm::Backward);
+    let ortho = fft(&x, None, 0, FftNorm::Ortho);
+    let forward = fft(&x, None, 0, FftNorm::Forward);
+
+    let scaled = |t: &ComplexTensor, factor: f64| -> Vec<C64> {
+        t.values().iter().map(|z| C64::new(z.re * factor, z.im * factor)).collect()
+    };
+    assert_close(ortho.values(), &scaled(&backward, 1.0 / 8f64.sqrt()), 1e-15);
+    assert_close(forward.values(), &scaled(&backward, 1.0 / 8.0), 1e-15);
+
+    // The orthonormal transform preserves energy
+    let energy = |values: &[C64]| values.iter().map(|z| z.norm_sqr()).sum::<f64>();
+    assert!((energy(ortho.values()) - energy(x.values())).abs() < 1e-10);
+
+    // The inverse with forward norm does not scale
+    let unscaled = ifft(&x, None, 0, FftNorm::Forward);
+    let conjugated: Vec<C64> = x.values().iter().map(|z| z.conj()).collect();
+    let expected: Vec<C64> = naive_dft(&conjugated).iter().map(|z| z.conj()).collect();
+    assert_close(unscaled.values(), &expected, 1e-12);
+}
+
+#[test]
+fn pads_and_crops_to_n() {
+    let x = signal(6, 7);
+
+    let padded = fft(&vector(x.clone()), Some(10), 0, FftNorm::Backward);
+    let mut zero_padded = x.clone();
+    zero_padded.resize(10, C64::default());
+    assert_eq!(padded.shape(), &[10]);
+    assert_close(padded.values(), &naive_dft(&zero_padded), 1e-12);
+
+    let cropped = fft(&vector(x.clone()), Some(4), 0, FftNorm::Backward);
+    assert_close(cropped.values(), &naive_dft(&x[..4]), 1e-12);
+}
+
+#[test]
+fn transforms_along_any_axis() {
+    let values = signal(24, 8);
+    let x = ComplexTensor::from_values(values.clone(), &[2, 3, 4]);
+
+    let along_middle = fft(&x, None, 1, FftNorm::Backward);
+    assert_eq!(along_middle.shape(), &[2, 3, 4]);
+    for o in 0..2 {
+        for i in 0..4 {
+            let lane: Vec<C64> = (0..3).map(|k| values[o * 12 + k * 4 + i]).collect();
+            let expected = naive_dft(&lane);
+            for (k, expected) in expected.iter().enumerate() {
+                let actual = along_middle.values()[o * 12 + k * 4 + i];
+                assert!((actual - *expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    let padded = fft(&x, Some(5), 0, FftNorm::Backward);
+    assert_eq!(padded.shape(), &[5, 3, 4]);
+}
+
+#[test]
+fn rfft_keeps_non_negative_frequencies() {
+    for len in [1, 2, 5, 8, 13, 100] {
+        let real: Vec<f64> = signal(len, 9).iter().map(|z| z.re).collect();
+        let full = naive_dft(&real.iter().map(|&x| C64::from(x)).collect::<Vec<C64>>());
+
+        let half = rfft(&Tensor::Element(real.clone()), None, 0, FftNorm::Backward);
+        assert_eq!(half.shape(), &[len / 2 + 1]);
+        assert_close(half.values(), &full[..len / 2 + 1], 1e-12);
+
+        let back = irfft(&half, Some(len), 0, FftNorm::Backward);
+        assert_real_close(&back, &real, 1e-12);
+    }
+}
+
+#[test]
+fn irfft_defaults_to_even_length() {
+    let x = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0], &[2, 4]);
+    let half = rfft(&x, None, 1, FftNorm::Ortho);
+    assert_eq!(half.shape(), &[2, 3]);
+
+    let back = irfft(&half, None, 1, FftNorm::Ortho);
+    assert_real_close(&back, &flatten_tensor(&x), 1e-12);
+}
+
+#[test]
+fn irfft_ignores_imaginary_dc_and_nyquist() {
+    let half = vector(vec![C64::new(4.0, 3.0), C64::new(0.0, -2.0), C64::new(0.0, 5.0)]);
+    let clean = vector(vec![C64::new(4.0, 0.0), C64::new(0.0, -2.0), C64::new(0.0, 0.0)]);
+    assert_eq!(
+        irfft(&half, Some(4), 0, FftNorm::Backward),
+        irfft(&clean, Some(4), 0, FftNorm::Backward)
+    );
+}
+
+#[test]
+fn multi_axis_transforms() {
+    let values = signal(60, 10);
+    let x = ComplexTensor::from_values(values, &[3, 4, 5]);
+
+    let sequential = fft(&fft(&fft(&x, None, 0, FftNorm::Backward), None, 1, FftNorm::Backward),
+        None, 2, FftNorm::Backward);
+    assert_close(fftn(&x, None, None, FftNorm::Backward).values(), sequential.values(), 1e-12);
+
+    let last_two = fft(&fft(&x, None, 1, FftNorm::Ortho), None, 2, FftNorm::Ortho);
+    assert_close(fft2(&x, None, FftNorm::Ortho).values(), last_two.values(), 1e-12);
+
+    // With only s, the last len(s) axes are transformed
+    let shaped = fftn(&x, Some(&[6, 2]), None, FftNorm::Backward);
+    assert_eq!(shaped.shape(), &[3, 6, 2]);
+    assert_eq!(fft2(&x, Some(&[6, 2]), FftNorm::Backward), shaped);
+
+    let first = fftn(&x, None, Some(&[0]), FftNorm::Backward);
+    assert_eq!(first, fft(&x, None, 0, FftNorm::Backward));
+
+    for norm in [FftNorm::Backward, FftNorm::Ortho, FftNorm::Forward] {
+        let back = ifftn(&fftn(&x, None, None, norm), None, None, norm);
+        assert_close(back.values(), x.values(), 1e-12);
+        let back = ifft2(&fft2(&x, None, norm), None, norm);
+        assert_close(back.values(), x.values(), 1e-12);
+    }
+}
+
+#[test]
+fn frequencies() {
+    assert_eq!(fftfreq(1, 1.0), Tensor::Element(vec![0.0]));
+    assert_eq!(fftfreq(5, 0.1), Tensor::Element(vec![0.0, 2.0, 4.0, -4.0, -2.0]));
+    assert_eq!(fftfreq(6, 1.0 / 6.0), Tensor::Element(vec![0.0, 1.0, 2.0, -3.0, -2.0, -1.0]));
+    assert_eq!(rfftfreq(6, 1.0 / 6.0), Tensor::Element(vec![0.0, 1.0, 2.0, 3.0]));
+    assert_eq!(rfftfreq(5, 0.1), Tensor::Element(vec![0.0, 2.0, 4.0]));
+}
+
+#[test]
+fn shifts() {
+    assert_eq!(
+        fftshift(&fftfreq(6, 1.0 / 6.0), None),
+        Tensor::Element(vec![-3.0, -2.0, -1.0, 0.0, 1.0, 2.0])
+    );
+
+    let grid = build_tensor(&(0..12).map(|x| x as f64).collect::<Vec<f64>>(), &[3, 4]);
+    assert_eq!(
+        fftshift(&grid, None),
+        build_tensor(&[10.0, 11.0, 8.0, 9.0, 2.0, 3.0, 0.0, 1.0, 6.0, 7.0, 4.0, 5.0], &[3, 4])
+    );
+    assert_eq!(
+        fftshift(&grid, Some(&[1])),
+        build_tensor(&[2.0, 3.0, 0.0, 1.0, 6.0, 7.0, 4.0, 5.0, 10.0, 11.0, 8.0, 9.0], &[3, 4])
+    );
+    assert_eq!(ifftshift(&fftshift(&grid, None), None), grid);
+    assert_eq!(ifftshift(&fftshift(&grid, Some(&[0])), Some(&[0])), grid);
+}
+
+#[test]
+fn shifts_spectra() {
+    let spectrum = fft(&vector(signal(5, 2)), None, 0, FftNorm::Backward);
+    let shifted = fftshift(&spectrum, None);
+    let values = spectrum.values();
+    assert_eq!(shifted.shape(), &[5]);
+    assert_eq!(shifted.values(), &[values[3], values[4], values[0], values[1], values[2]]);
+    assert_eq!(ifftshift(&shifted, None), spectrum);
+
+    let grid = ComplexTensor::from_values(signal(12, 3), &[3, 4]);
+    let spectra = fft2(&grid, None, FftNorm::Backward);
+    let real_shifted = fftshift(&spectra.real(), Some(&[1]));
+    assert_eq!(fftshift(&spectra, Some(&[1])).real(), real_shifted);
+    assert_eq!(ifftshift(&fftshift(&spectra, None), None), spectra);
+}
+
+#[test]
+#[should_panic(expected = "Invalid number of FFT data points (0) specified!")]
+fn zero_length_panics() {
+    fft(&vector(signal(4, 1)), Some(0), 0, FftNorm::Backward);
+}
+
+#[test]
+#[should_panic(expected = "Axis 1 is out of range for a Tensor with 1 dimensions!")]
+fn axis_out_of_range_panics() {
+    fft(&vector(signal(4, 1)), None, 1, FftNorm::Backward);
+}
+
+#[test]
+#[should_panic(expected = "A two-dimensional FFT needs a Tensor with at least 2 dimensions!")]
+fn fft2_needs_two_dimensions() {
+    fft2(&vector(signal(4, 1)), None, FftNorm::Backward);
+}
+
+#[test]
+fn plan_cache_is_bounded() {
+    // Frame lengths that change every call, including primes that need Bluestein plans
+    for len in 1000..1040 {
+        let signal = Tensor::Element((0..len).map(|t| (t % 7) as f64).collect());
+        let spectrum = rfft(&signal, None, 0, FftNorm::Backward);
+        assert_eq!(spectrum.shape(), &[len / 2 + 1]);
+
+        let lengths = cached_lengths();
+        assert!(lengths.len() <= 16, "{lengths:?}");
+        assert!(lengths.contains(&len), "{lengths:?}");
+    }
+}
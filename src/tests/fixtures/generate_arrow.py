@@ -0,0 +1,179 @@
+"""Generates the Arrow IPC fixtures used by arrow_tests.rs.
+
+pyarrow is not needed: the FlatBuffers metadata is laid out by hand with the standard library. The
+layout is deliberately different from tensorium's writer, with parents before children and each
+vtable after its table, so the reader is tested against layouts it does not produce itself.
+Run from this directory with `python3 generate_arrow.py`.
+"""
+
+import struct
+
+
+class Table:
+    def __init__(self, *fields):
+        # Each field is (index, format, value) where format is a struct code or "offset"
+        self.fields = fields
+
+
+class String:
+    def __init__(self, value):
+        self.value = value.encode("utf-8")
+
+
+class Vector:
+    def __init__(self, items):
+        self.items = items
+
+
+class Structs:
+    def __init__(self, data, count):
+        self.data = data
+        self.count = count
+
+
+def pad(buf, align):
+    buf.extend(b"\0" * ((align - len(buf) % align) % align))
+
+
+def write(buf, obj):
+    """Writes `obj` at the end of `buf` and returns its position."""
+    if isinstance(obj, String):
+        pad(buf, 4)
+        pos = len(buf)
+        buf.extend(struct.pack("<I", len(obj.value)) + obj.value + b"\0")
+        return pos
+
+    if isinstance(obj, Structs):
+        # Align the elements, which follow the 4 byte length, to 8
+        while (len(buf) + 4) % 8:
+            buf.append(0)
+        pos = len(buf)
+        buf.extend(struct.pack("<I", obj.count) + obj.data)
+        return pos
+
+    if isinstance(obj, Vector):
+        pad(buf, 4)
+        pos = len(buf)
+        buf.extend(struct.pack("<I", len(obj.items)))
+        slots = []
+        for _ in obj.items:
+            slots.append(len(buf))
+            buf.extend(b"\0\0\0\0")
+        for slot, item in zip(slots, obj.items):
+            child = write(buf, item)
+            buf[slot:slot + 4] = struct.pack("<I", child - slot)
+        return pos
+
+    # A table: soffset, then the fields largest first, then its vtable, then its children
+    pad(buf, 8)
+    table = len(buf)
+    buf.extend(b"\0\0\0\0")
+    field_positions = {}
+    children = []
+    for index, fmt, value in sorted(obj.fields, key=lambda f: -struct.calcsize("<I" if f[1] == "offset" else "<" + f[1])):
+        size = 4 if fmt == "offset" else struct.calcsize("<" + fmt)
+        pad(buf, size)
+        field_positions[index] = len(buf)
+        if fmt == "offset":
+            children.append((len(buf), value))
+            buf.extend(b"\0\0\0\0")
+        else:
+            buf.extend(struct.pack("<" + fmt, value))
+    table_size = len(buf) - table
+
+    pad(buf, 2)
+    vtable = len(buf)
+    slots = max(field_positions) + 1 if field_positions else 0
+    entries = [field_positions[i] - table if i in field_positions else 0 for i in range(slots)]
+    buf.extend(struct.pack("<HH" + "H" * slots, 4 + 2 * slots, table_size, *entries))
+    buf[table:table + 4] = struct.pack("<i", table - vtable)
+
+    for slot, child in children:
+        pos = write(buf, child)
+        buf[slot:slot + 4] = struct.pack("<I", pos - slot)
+    return table
+
+
+def finish(root):
+    buf = bytearray(b"\0\0\0\0")
+    pos = write(buf, root)
+    buf[0:4] = struct.pack("<I", pos)
+    pad(buf, 8)
+    return bytes(buf)
+
+
+def field(name, type_tag, type_table, nullable):
+    return Table((0, "offset", String(name)), (1, "B", nullable), (2, "B", type_tag),
+                 (3, "offset", type_table), (5, "offset", Vector([])))
+
+
+INT, FLOAT = 2, 3
+schema = Table((0, "h", 0), (1, "offset", Vector([
+    field("a", INT, Table((0, "i", 32), (1, "B", 1)), False),
+    field("b", FLOAT, Table((0, "h", 1)), False),
+    field("c", INT, Table((0, "i", 64), (1, "B", 1)), True),
+    field("d", INT, Table((0, "i", 8), (1, "B", 0)), False),
+])))
+
+
+def message(header_tag, header, body_length):
+    return finish(Table((0, "h", 4), (1, "B", header_tag), (2, "offset", header), (3, "q", body_length)))
+
+
+def record_batch(a, b, c, d):
+    """One batch of the four columns, where None in `c` is a null."""
+    rows = len(a)
+    buffers = []
+    body = bytearray()
+
+    def add(data):
+        pad(body, 8)
+        buffers.append((len(body), len(data)))
+        body.extend(data)
+
+    add(b"")
+    add(struct.pack("<%di" % rows, *a))
+    add(b"")
+    add(struct.pack("<%df" % rows, *b))
+    validity = sum(1 << i for i, v in enumerate(c) if v is not None)
+    add(bytes([validity]))
+    add(struct.pack("<%dq" % rows, *[0 if v is None else v for v in c]))
+    add(b"")
+    add(bytes(d))
+    pad(body, 8)
+
+    nulls = [0, 0, sum(v is None for v in c), 0]
+    nodes = b"".join(struct.pack("<qq", rows, n) for n in nulls)
+    buffer_bytes = b"".join(struct.pack("<qq", o, n) for o, n in buffers)
+    header = Table((0, "q", rows), (1, "offset", Structs(nodes, 4)), (2, "offset", Structs(buffer_bytes, 8)))
+    return message(3, header, len(body)), bytes(body)
+
+
+def encapsulate(metadata, body):
+    padded = metadata + b"\0" * ((8 - len(metadata) % 8) % 8)
+    return struct.pack("<Ii", 0xFFFFFFFF, len(padded)) + padded + body
+
+
+schema_message = encapsulate(message(1, schema, 0), b"")
+batches = [
+    record_batch([1, -2], [0.5, 1.5], [10, None], [0, 255]),
+    record_batch([3], [-2.25], [-(1 << 40)], [7]),
+]
+
+stream = schema_message + b"".join(encapsulate(*batch) for batch in batches)
+with open("mixed.arrows", "wb") as f:
+    f.write(stream + struct.pack("<Ii", 0xFFFFFFFF, 0))
+
+# The file format: no end of stream marker, so the batches must be found through the footer
+blocks = b""
+out = bytearray(b"ARROW1\0\0" + schema_message)
+for metadata, body in batches:
+    encapsulated = encapsulate(metadata, body)
+    blocks += struct.pack("<qi4xq", len(out), len(encapsulated) - len(body), len(body))
+    out.extend(encapsulated)
+
+footer = finish(Table((0, "h", 4), (1, "offset", schema), (2, "offset", Structs(b"", 0)),
+                      (3, "offset", Structs(blocks, 2))))
+out.extend(footer + struct.pack("<i", len(footer)) + b"ARROW1")
+with open("mixed.arrow", "wb") as f:
+    f.write(out)
@@ -0,0 +1,57 @@
+"""Generates the .npy and .npz fixtures used by npy_tests.rs.
+
+Only the standard library is used, so the files are written byte for byte as numpy would write
+them without numpy being installed. Run from this directory with `python3 generate_npy.py`.
+"""
+
+import io
+import struct
+import zipfile
+
+
+def npy(descr, fortran_order, shape, fmt, values, version=(1, 0)):
+    shape_repr = "({},)".format(shape[0]) if len(shape) == 1 else "({})".format(", ".join(map(str, shape)))
+    header = "{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}".format(descr, fortran_order, shape_repr)
+
+    length_size = 2 if version[0] == 1 else 4
+    unpadded = 6 + 2 + length_size + len(header) + 1
+    header += " " * ((64 - unpadded % 64) % 64) + "\n"
+
+    out = b"\x93NUMPY" + bytes(version)
+    out += struct.pack("<H" if length_size == 2 else "<I", len(header))
+    out += header.encode("latin-1" if version[0] < 3 else "utf-8")
+    out += struct.pack(fmt.format(len(values)), *values)
+    return out
+
+
+def write(name, contents):
+    with open(name, "wb") as f:
+        f.write(contents)
+
+
+def npz(name, arrays, compression):
+    buffer = io.BytesIO()
+    with zipfile.ZipFile(buffer, "w", compression) as archive:
+        for key, contents in arrays:
+            archive.writestr(key + ".npy", contents)
+    write(name, buffer.getvalue())
+
+
+f8_c = npy("<f8", False, (2, 3), "<{}d", [1.0, 2.0, 3.0, 4.0, 5.0, 6.0])
+# [[1, 2, 3], [4, 5, 6]] stored column by column
+i4_fortran = npy("<i4", True, (2, 3), "<{}i", [1, 4, 2, 5, 3, 6])
+f4_big_v2 = npy(">f4", False, (4,), ">{}f", [0.5, -1.5, 2.25, 1e3], version=(2, 0))
+u1_v3 = npy("|u1", False, (2, 2), "<{}B", [0, 127, 128, 255], version=(3, 0))
+b1 = npy("|b1", False, (3,), "<{}B", [1, 0, 1])
+# struct rounds to the nearest float16, ties to even, like numpy
+f2 = npy("<f2", False, (2, 3), "<{}e", [0.1, -2.5, 65504.0, 6e-8, float("inf"), 2049.0])
+
+write("f8_c.npy", f8_c)
+write("i4_fortran.npy", i4_fortran)
+write("f4_big_v2.npy", f4_big_v2)
+write("u1_v3.npy", u1_v3)
+write("b1.npy", b1)
+write("f2.npy", f2)
+
+npz("stored.npz", [("a", f8_c), ("b", b1)], zipfile.ZIP_STORED)
+npz("deflated.npz", [("a", f8_c), ("b", i4_fortran)], zipfile.ZIP_DEFLATED)
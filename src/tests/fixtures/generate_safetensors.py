@@ -0,0 +1,53 @@
+"""Generates the .safetensors fixture used by safetensors_tests.rs.
+
+Only the standard library is used. The layout follows the reference implementation: an 8-byte
+little-endian header length, a JSON header padded with spaces to a multiple of 8, then the data.
+Run from this directory with `python3 generate_safetensors.py`.
+"""
+
+import json
+import struct
+
+tensors = [
+    ("embedding", "F32", [2, 2], struct.pack("<4f", 0.5, -1.0, 2.0, 0.25)),
+    ("ids", "I64", [3], struct.pack("<3q", -7, 0, 1 << 40)),
+    ("mask", "BOOL", [4], bytes([1, 0, 0, 1])),
+    ("scale", "F64", [], struct.pack("<d", 3.5)),
+    ("pixels", "U8", [1, 2], bytes([0, 255])),
+]
+
+
+def bf16(value):
+    """Rounds to the nearest bfloat16, ties to even, through the bits of the float32."""
+    bits = struct.unpack("<I", struct.pack("<f", value))[0]
+    return struct.pack("<H", (bits + 0x7FFF + ((bits >> 16) & 1)) >> 16)
+
+
+half = [
+    ("weight", "F16", [2, 2], struct.pack("<4e", 0.1, -1.0, 1e-6, 65504.0)),
+    ("bias", "BF16", [3], bf16(1.0 / 3.0) + bf16(-300.5) + bf16(1e30)),
+]
+
+header = {"__metadata__": {"format": "pt", "note": "quote \" and é"}}
+data = b""
+for name, dtype, shape, contents in tensors:
+    header[name] = {"dtype": dtype, "shape": shape, "data_offsets": [len(data), len(data) + len(contents)]}
+    data += contents
+
+text = json.dumps(header).encode("utf-8")
+text += b" " * ((8 - len(text) % 8) % 8)
+
+with open("mixed.safetensors", "wb") as f:
+    f.write(struct.pack("<Q", len(text)) + text + data)
+
+header = {}
+data = b""
+for name, dtype, shape, contents in half:
+    header[name] = {"dtype": dtype, "shape": shape, "data_offsets": [len(data), len(data) + len(contents)]}
+    data += contents
+
+text = json.dumps(header).encode("utf-8")
+text += b" " * ((8 - len(text) % 8) % 8)
+
+with open("half.safetensors", "wb") as f:
+    f.write(struct.pack("<Q", len(text)) + text + data)
@@ -0,0 +1,218 @@
+use crate::{ bf16, f16, HalfTensor, Tensor };
+use crate::tensor_ops::build_tensor;
+
+#[test]
+fn every_f16_survives_a_round_trip() {
+    for bits in 0..=u16::MAX {
+        let x = f16::from_bits(bits);
+        if x.is_nan() {
+            assert!(x.to_f64().is_nan());
+            assert!(f16::from_f64(x.to_f64()).is_nan());
+            assert!(f16::from_f32(x.to_f32()).is_nan());
+        } else {
+            assert_eq!(f16::from_f64(x.to_f64()).to_bits(), bits);
+            assert_eq!(f16::from_f32(x.to_f32()).to_bits(), bits);
+        }
+    }
+}
+
+#[test]
+fn every_bf16_matches_the_top_of_an_f32() {
+    for bits in 0..=u16::MAX {
+        let x = bf16::from_bits(bits);
+        let widened = f32::from_bits((bits as u32) << 16);
+        if x.is_nan() {
+            assert!(widened.is_nan());
+        } else {
+            assert_eq!(x.to_f32(), widened);
+            assert_eq!(bf16::from_f32(widened).to_bits(), bits);
+        }
+    }
+}
+
+#[test]
+fn f16_midpoints_round_to_even() {
+    // Every pair of neighbouring positive values, from the subnormals to the largest finite value
+    for bits in 0..0x7BFF_u16 {
+        let low = f16::from_bits(bits).to_f64();
+        let high = f16::from_bits(bits + 1).to_f64();
+        let middle = (low + high) / 2.0;
+
+        let even = if bits % 2 == 0 { bits } else { bits + 1 };
+        assert_eq!(f16::from_f64(middle).to_bits(), even, "{middle}");
+        assert_eq!(f16::from_f64(-middle).to_bits(), even | 0x8000);
+
+        let step = (high - low) / 1024.0;
+        assert_eq!(f16::from_f64(middle - step).to_bits(), bits);
+        assert_eq!(f16::from_f64(middle + step).to_bits(), bits + 1);
+    }
+}
+
+#[test]
+fn bf16_rounds_like_f32_bit_arithmetic() {
+    // The usual bfloat16 conversion: add just under half of the dropped bits, plus the low kept bit
+    let reference = |value: f32| {
+        let bits = value.to_bits();
+        ((bits + 0x7FFF + ((bits >> 16) & 1)) >> 16) as u16
+    };
+
+    let mut bits = 0u32;
+    while bits < 0x7F80_0000 {
+        for value in [f32::from_bits(bits), -f32::from_bits(bits)] {
+            assert_eq!(bf16::from_f32(value).to_bits(), reference(value), "{value}");
+        }
+        bits += 0x1_0001;
+    }
+    for offset in [0x7FFF, 0x8000, 0x8001, 0x18000] {
+        let value = f32::from_bits(0x3F80_0000 + offset);
+        assert_eq!(bf16::from_f32(value).to_bits(), reference(value));
+    }
+}
+
+#[test]
+fn f16_limits() {
+    assert_eq!(f16::MAX.to_f64(), 65504.0);
+    assert_eq!(f16::MIN.to_f64(), -65504.0);
+    assert_eq!(f16::MIN_POSITIVE.to_f64(), 2f64.powi(-14));
+    assert_eq!(f16::EPSILON.to_f64(), 2f64.powi(-10));
+    assert_eq!(f16::ONE.to_f64(), 1.0);
+    assert_eq!(f16::from_bits(1).to_f64(), 2f64.powi(-24));
+
+    // Halfway to the next power of two rounds up to infinity
+    assert_eq!(f16::from_f64(65519.99).to_f64(), 65504.0);
+    assert_eq!(f16::from_f64(65520.0), f16::INFINITY);
+    assert_eq!(f16::from_f64(-1e300), f16::NEG_INFINITY);
+
+    // Halfway to the smallest subnormal rounds to even, which is zero
+    assert_eq!(f16::from_f64(2f64.powi(-25)).to_bits(), 0);
+    assert_eq!(f16::from_f64(2f64.powi(-25) * 1.001).to_bits(), 1);
+    assert_eq!(f16::from_f64(-1e-300).to_bits(), 0x8000);
+    assert_eq!(f16::from_f64(f64::MIN_POSITIVE / 4.0).to_bits(), 0);
+}
+
+#[test]
+fn bf16_limits() {
+    assert_eq!(bf16::MAX.to_f32(), f32::from_bits(0x7F7F_0000));
+    assert_eq!(bf16::MIN_POSITIVE.to_f32(), f32::MIN_POSITIVE);
+    assert_eq!(bf16::EPSILON.to_f32(), 2f32.powi(-7));
+    assert_eq!(bf16::ONE.to_f32(), 1.0);
+    assert_eq!(bf16::from_f32(f32::MAX), bf16::INFINITY);
+    assert_eq!(bf16::from_f64(1e-45).to_bits(), 0);
+}
+
+#[test]
+fn converts_from_f64_with_a_single_rounding() {
+    // Just above a midpoint, which the rounding to f32 would move onto it
+    let value = 1.0 + 2f64.powi(-11) + 2f64.powi(-40);
+    assert_eq!(f16::from_f64(value).to_f64(), 1.0 + 2f64.powi(-10));
+    assert_eq!(f16::from_f32(value as f32).to_f64(), 1.0);
+
+    let value = 1.0 + 2f64.powi(-8) + 2f64.powi(-40);
+    assert_eq!(bf16::from_f64(value).to_f64(), 1.0 + 2f64.powi(-7));
+}
+
+#[test]
+fn nan_is_kept() {
+    assert!(f16::NAN.is_nan());
+    assert!(f16::from_f32(f32::NAN).is_nan());
+    assert!(bf16::from_f64(-f64::NAN).is_nan());
+    assert!(bf16::from_f64(-f64::NAN).is_sign_negative());
+
+    // A payload only in the dropped bits must still give a NaN rather than infinity
+    let quiet_low = f32::from_bits(0x7F80_0001);
+    assert!(f16::from_f32(quiet_low).is_nan());
+    assert!(bf16::from_f32(quiet_low).is_nan());
+
+    assert!(!f16::INFINITY.is_nan());
+    assert!(f16::INFINITY.is_infinite());
+    assert!(!f16::NAN.is_finite());
+    assert!(f16::MAX.is_finite());
+}
+
+#[test]
+fn arithmetic_rounds_each_result() {
+    let third = f16::from_f32(1.0) / f16::from_f32(3.0);
+    assert_eq!(third, f16::from_f64(1.0 / 3.0));
+    assert_eq!(f16::from_f32(2048.0) + f16::ONE, f16::from_f32(2048.0));
+    assert_eq!(f16::MAX + f16::MAX, f16::INFINITY);
+    assert_eq!(f16::from_f32(0.5) - f16::ONE, f16::from_f32(-0.5));
+    assert_eq!(-f16::from_f32(2.0), f16::from_f32(-2.0));
+
+    assert_eq!(bf16::from_f32(3.0) * bf16::from_f32(1.5), bf16::from_f32(4.5));
+    assert_eq!(bf16::from_f32(256.0) + bf16::ONE, bf16::from_f32(256.0));
+    assert_eq!(bf16::from_f32(-7.5).abs(), bf16::from_f32(7.5));
+}
+
+#[test]
+fn compares_like_floats() {
+    assert_ne!(f16::NAN, f16::NAN);
+    assert_eq!(f16::ZERO, -f16::ZERO);
+    assert!(f16::from_f32(-1.0) < f16::ZERO);
+    assert!(bf16::ONE > bf16::ZERO);
+    assert_eq!(f16::NAN.partial_cmp(&f16::ONE), None);
+
+    assert_eq!(format!("{}", f16::from_f32(0.5)), "0.5");
+    assert_eq!(format!("{:?}", bf16::from_f32(-2.0)), "-2.0");
+    assert_eq!(f32::from(bf16::from_f32(1.5)), 1.5);
+    assert_eq!(f64::from(f16::from_f32(1.5)), 1.5);
+}
+
+#[test]
+fn half_tensor_round_trip() {
+    let t = build_tensor(&[0.1, -2.5, 1e5, 3.0, 1e-8, 0.0], &[2, 3]);
+
+    let half: HalfTensor<f16> = HalfTensor::from(&t);
+    assert_eq!(half.shape(), &[2, 3]);
+    assert_eq!(half.nbytes(), 12);
+    assert_eq!(half.values()[0].to_bits(), 0x2E66);
+    assert!(half.values()[2].is_infinite());
+
+    let back = half.to_tensor();
+    assert_eq!(back, build_tensor(&[0.0999755859375, -2.5, f64::INFINITY, 3.0, 0.0, 0.0], &[2, 3]));
+
+    let brain: HalfTensor<bf16> = half.cast();
+    assert_eq!(brain.values()[1], bf16::from_f32(-2.5));
+    assert_eq!(brain.values()[0], bf16::from_f64(0.0999755859375));
+}
+
+#[test]
+fn half_tensor_arithmetic() {
+    let a: HalfTensor<f16> = HalfTensor::from(&Tensor::Element(vec![1.0, 2.0, 2048.0]));
+    let b: HalfTensor<f16> = HalfTensor::from(&Tensor::Element(vec![3.0, 0.5, 1.0]));
+
+    assert_eq!((&a + &b).to_tensor(), Tensor::Element(vec![4.0, 2.5, 2048.0]));
+    assert_eq!((&a - &b).to_tensor(), Tensor::Element(vec![-2.0, 1.5, 2047.0]));
+    assert_eq!((&a * &b).to_tensor(), Tensor::Element(vec![3.0, 1.0, 2048.0]));
+    assert_eq!((a.clone() / b.clone()).values()[0], f16::from_f64(1.0 / 3.0));
+    assert_eq!((-&a).to_tensor(), Tensor::Element(vec![-1.0, -2.0, -2048.0]));
+    assert_eq!(a.map(|x| x.sqrt()).values()[0], f16::ONE);
+    assert_eq!(a.zip_with(&b, f32::max).to_tensor(), Tensor::Element(vec![3.0, 2.0, 2048.0]));
+}
+
+#[test]
+fn half_tensor_matmul_rounds_once() {
+    // 4096 ones sum exactly, where adding them one at a time in f16 would stop at 2048
+    let ones = vec![f16::ONE; 4096];
+    let row = HalfTensor::from_values(ones.clone(), &[1, 4096]);
+    let column = HalfTensor::from_values(ones, &[4096, 1]);
+    assert_eq!(row.matmul(&column).to_tensor(), build_tensor(&[4096.0], &[1, 1]));
+
+    let a = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]);
+    let b = build_tensor(&[1.0, 0.0, 0.0, 1.0, 1.0, 1.0], &[3, 2]);
+    let (a, b) = (HalfTensor::<bf16>::from(&a), HalfTensor::<bf16>::from(&b));
+    assert_eq!(a.matmul(&b).to_tensor(), build_tensor(&[4.0, 5.0, 10.0, 11.0], &[2, 2]));
+}
+
+#[test]
+#[should_panic(expected = "3 values cannot fill a Tensor of shape [2, 2]!")]
+fn from_values_checks_the_shape() {
+    HalfTensor::from_values(vec![f16::ZERO; 3], &[2, 2]);
+}
+
+#[test]
+#[should_panic(expected = "Half Tensor shapes [2] and [3] do not match!")]
+fn arithmetic_checks_shapes() {
+    let a: HalfTensor<bf16> = HalfTensor::from(&Tensor::Element(vec![1.0, 2.0]));
+    let b: HalfTensor<bf16> = HalfTensor::from(&Tensor::Element(vec![1.0, 2.0, 3.0]));
+    let _ = &a + &b;
+}
@@ -0,0 +1,111 @@
+use crate::init;
+use crate::init::{FanMode, Nonlinearity};
+use crate::random::Rng;
+use crate::tensor_ops::{flatten_tensor, get_dimension, zero_tensor};
+
+#[test]
+fn same_seed_same_weights() {
+    let mut t1 = zero_tensor(Vec::from([4, 3]));
+    let mut t2 = zero_tensor(Vec::from([4, 3]));
+
+    init::xavier_uniform(&mut t1, 1.0, &mut Rng::new(3));
+    init::xavier_uniform(&mut t2, 1.0, &mut Rng::new(3));
+
+    assert_eq!(t1, t2);
+}
+
+#[test]
+fn kaiming_normal_std() {
+    let mut rng = Rng::new(11);
+    let mut t = zero_tensor(Vec::from([200, 50]));
+
+    init::kaiming_normal(&mut t, FanMode::FanIn, Nonlinearity::Relu, &mut rng);
+
+    let values = flatten_tensor(&t);
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let var = values.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / values.len() as f64;
+
+    // std = sqrt(2) / sqrt(50) = 0.2
+    assert!(mean.abs() < 0.01);
+    assert!((var.sqrt() - 0.2).abs() < 0.01);
+}
+
+#[test]
+fn xavier_uniform_bound() {
+    let mut rng = Rng::new(5);
+    let mut t = zero_tensor(Vec::from([6, 4, 2]));
+
+    init::xavier_uniform(&mut t, 2.0, &mut rng);
+
+    // fan_in = 8, fan_out = 12, bound = 2 * sqrt(6 / 20)
+    let bound = 2.0 * (6.0_f64 / 20.0).sqrt();
+    assert!(flatten_tensor(&t).iter().all(|x| x.abs() <= bound));
+    assert_eq!(get_dimension(&t), Vec::from([6, 4, 2]));
+}
+
+#[test]
+fn orthogonal_tall_columns() {
+    let mut rng = Rng::new(9);
+    let mut t = zero_tensor(Vec::from([5, 3]));
+
+    init::orthogonal(&mut t, 1.0, &mut rng);
+
+    let q = flatten_tensor(&t);
+    for a in 0..3 {
+        for b in 0..3 {
+            let dot: f64 = (0..5).map(|i| q[i * 3 + a] * q[i * 3 + b]).sum();
+            let expected = if a == b { 1.0 } else { 0.0 };
+            assert!((dot - expected).abs() < 1e-10);
+        }
+    }
+}
+
+#[test]
+fn constant_fill() {
+    let mut t = zero_tensor(Vec::from([2, 2, 2]));
+
+    init::ones_(&mut t);
+    assert!(flatten_tensor(&t).iter().all(|&x| x == 1.0));
+
+    init::constant_(&mut t, -3.0);
+    assert!(flatten_tensor(&t).iter().all(|&x| x == -3.0));
+
+    init::zeros_(&mut t);
+    assert_eq!(t, zero_tensor(Vec::from([2, 2, 2])));
+}
+
+fn trunc_normal_mean(mean: f64, std: f64, a: f64, b: f64) -> f64 {
+    let mut t = zero_tensor(Vec::from([20000]));
+    init::trunc_normal(&mut t, mean, std, a, b, &mut Rng::new(17));
+
+    let values = flatten_tensor(&t);
+    assert!(values.iter().all(|x| (a..=b).contains(x)));
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+#[test]
+fn trunc_normal_mean_matches_the_truncated_distribution() {
+    // (φ(-1) - φ(2)) / (Φ(2) - Φ(-1)) = 0.229637
+    assert!((trunc_normal_mean(0.0, 1.0, -1.0, 2.0) - 0.229637).abs() < 0.01);
+    assert!((trunc_normal_mean(3.0, 2.0, 1.0, 7.0) - (3.0 + 2.0 * 0.229637)).abs() < 0.02);
+}
+
+#[test]
+fn trunc_normal_samples_far_tails() {
+    // The mean of a standard normal beyond x is close to x + 1 / x
+    assert!((trunc_normal_mean(0.0, 1.0, 10.0, 11.0) - 10.098).abs() < 0.005);
+    assert!((trunc_normal_mean(0.0, 1.0, -11.0, -10.0) + 10.098).abs() < 0.005);
+    assert!((trunc_normal_mean(0.0, 1.0, 40.0, 41.0) - 40.025).abs() < 0.001);
+}
+
+#[test]
+#[should_panic(expected = "standard deviation must be positive")]
+fn trunc_normal_rejects_zero_std() {
+    trunc_normal_mean(5.0, 0.0, -1.0, 1.0);
+}
+
+#[test]
+#[should_panic(expected = "must be finite")]
+fn trunc_normal_rejects_nan_bounds() {
+    trunc_normal_mean(0.0, 1.0, f64::NAN, 1.0);
+}
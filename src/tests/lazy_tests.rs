@@ -0,0 +1,298 @@
+use crate::Tensor;
+use crate::lazy::LazyTensor;
+use crate::parallel::{ with_parallel_config, ParallelConfig };
+use crate::tensor_ops::{
+    add_tensors,
+    broadcast_tensor,
+    build_tensor,
+    divide_tensors,
+    expand_dims,
+    flatten_tensor,
+    get_dimension,
+    map_tensor,
+    matmul,
+    multiply_tensors,
+    relu,
+    sigmoid,
+    subtract_tensors,
+    sum
+};
+
+fn values(len: usize, seed: usize) -> Vec<f64> {
+    (0..len).map(|i| ((i * 7919 + seed * 104729) % 1000) as f64 / 13.0 - 30.0).collect()
+}
+
+/// Broadcasts for the eager functions, which need matching shapes.
+fn expand(tensor: &Tensor, shape: &[usize]) -> Tensor {
+    let missing = shape.len() - get_dimension(tensor).len();
+    broadcast_tensor(&expand_dims(tensor.clone(), missing), &shape.to_vec())
+}
+
+fn assert_bitwise_eq(actual: &Tensor, expected: &Tensor) {
+    let (actual, expected) = (flatten_tensor(actual), flatten_tensor(expected));
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(&expected) {
+        assert_eq!(a.to_bits(), e.to_bits(), "{a} != {e}");
+    }
+}
+
+#[test]
+fn eval_matches_eager_ops() {
+    let a = build_tensor(&values(600, 1), &[20, 30]);
+    let b = build_tensor(&values(600, 2), &[20, 30]);
+    let c = build_tensor(&values(600, 3), &[20, 30]);
+
+    let lazy = ((a.lazy() * b.lazy() - c.lazy()) / 3.0).relu().sigmoid();
+    let eager = sigmoid(&relu(&divide_tensors(
+        &subtract_tensors(&multiply_tensors(&a, &b), &c),
+        &expand(&Tensor::Element(vec![3.0]), &[20, 30])
+    )));
+
+    assert_eq!(lazy.shape(), &[20, 30]);
+    assert_bitwise_eq(&lazy.eval(), &eager);
+}
+
+#[test]
+fn eval_broadcasts_like_eager_ops() {
+    let a = build_tensor(&values(24, 1), &[2, 3, 4]);
+    let row = build_tensor(&values(4, 2), &[4]);
+    let column = build_tensor(&values(3, 3), &[3, 1]);
+
+    let lazy = (&a.lazy() + &row.lazy()) * column.lazy();
+    let shape = [2, 3, 4];
+    let eager = multiply_tensors(
+        &add_tensors(&a, &expand(&row, &shape)),
+        &expand(&column, &shape)
+    );
+    assert_eq!(lazy.shape(), &[2, 3, 4]);
+    assert_bitwise_eq(&lazy.eval(), &eager);
+
+    let outer = column.lazy() - row.lazy();
+    assert_eq!(outer.shape(), &[3, 4]);
+    let eager = subtract_tensors(&expand(&column, &[3, 4]), &expand(&row, &[3, 4]));
+    assert_bitwise_eq(&outer.eval(), &eager);
+
+    let expanded = row.lazy().broadcast_to(&[2, 3, 4]);
+    assert_eq!(expanded.eval(), expand(&row, &[2, 3, 4]));
+}
+
+#[test]
+fn eval_is_the_same_for_any_thread_count() {
+    let a = build_tensor(&values(5000, 1), &[50, 100]);
+    let b = build_tensor(&values(100, 2), &[100]);
+    let expr = (a.lazy() * b.lazy()).tanh() + a.lazy().square();
+
+    let config = |threads| ParallelConfig { threads, grain_size: 64, deterministic: true };
+    let serial = with_parallel_config(config(1), || expr.eval());
+    for threads in [2, 3, 8] {
+        assert_bitwise_eq(&with_parallel_config(config(threads), || expr.eval()), &serial);
+    }
+}
+
+#[test]
+fn scalar_operators() {
+    let x = Tensor::Element(vec![1.0, 2.0, 4.0]).lazy();
+
+    assert_eq!((x.clone() + 1.0).eval(), Tensor::Element(vec![2.0, 3.0, 5.0]));
+    assert_eq!((1.0 - x.clone()).eval(), Tensor::Element(vec![0.0, -1.0, -3.0]));
+    assert_eq!((&x * 2.0).eval(), Tensor::Element(vec![2.0, 4.0, 8.0]));
+    assert_eq!((8.0 / &x).eval(), Tensor::Element(vec![8.0, 4.0, 2.0]));
+    assert_eq!((&x % 3.0).eval(), Tensor::Element(vec![1.0, 2.0, 1.0]));
+    assert_eq!((-&x).eval(), Tensor::Element(vec![-1.0, -2.0, -4.0]));
+    assert_eq!(
+        x.pow(&LazyTensor::scalar(2.0)).sqrt().eval(),
+        Tensor::Element(vec![1.0, 2.0, 4.0])
+    );
+}
+
+#[test]
+fn maximum_and_minimum_propagate_nan() {
+    let x = Tensor::Element(vec![1.0, f64::NAN, 3.0]).lazy();
+    let y = Tensor::Element(vec![2.0, 0.0, f64::NAN]).lazy();
+
+    let max = flatten_tensor(&x.maximum(&y).eval());
+    assert_eq!(max[0], 2.0);
+    assert!(max[1].is_nan() && max[2].is_nan());
+
+    let min = flatten_tensor(&x.minimum(&y).eval());
+    assert_eq!(min[0], 1.0);
+    assert!(min[1].is_nan() && min[2].is_nan());
+}
+
+#[test]
+fn map_applies_function_pointer() {
+    let a = build_tensor(&values(12, 1), &[3, 4]);
+    let lazy = a.lazy().map(|x| x * x - 1.0);
+    assert_eq!(lazy.eval(), map_tensor(&a, |x| x * x - 1.0));
+}
+
+#[test]
+fn whole_chain_is_one_pass() {
+    let a = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]).lazy();
+    let b = build_tensor(&[1.0, 2.0], &[2]).lazy();
+
+    let plan = ((&a + &b).exp() * &a).explain();
+    assert_eq!(plan, "\
+$0 = input [2, 2]
+$1 = input [2]
+$2 = fused [2, 2]
+    %0 = load $0
+    %1 = load $1 broadcast
+    %2 = add %0 %1
+    %3 = exp %2
+    %4 = mul %3 %0
+result $2
+");
+}
+
+#[test]
+fn shared_subexpressions_are_computed_once() {
+    let a = Tensor::Element(vec![1.0, 2.0, 3.0]).lazy();
+    let shared = (&a * 2.0).ln();
+
+    let plan = (&shared + &shared * &shared).explain();
+    assert_eq!(plan.matches("ln").count(), 1);
+    assert_eq!(plan.matches("load").count(), 1);
+
+    // A shared operand of a matmul is computed once, outside the passes that use it
+    let m = build_tensor(&values(4, 1), &[2, 2]).lazy();
+    let shared = m.relu();
+    let expr = shared.matmul(&shared) + &shared;
+    let plan = expr.explain();
+    assert_eq!(plan.matches("relu").count(), 1);
+    assert_eq!(plan.matches("fused").count(), 2);
+
+    let eager_shared = relu(&build_tensor(&values(4, 1), &[2, 2]));
+    let eager = add_tensors(&matmul(&eager_shared, &eager_shared), &eager_shared);
+    assert_bitwise_eq(&expr.eval(), &eager);
+}
+
+#[test]
+fn shared_operands_are_not_inlined_again() {
+    let a = build_tensor(&values(4, 1), &[2, 2]);
+    let b = build_tensor(&values(4, 2), &[2, 2]);
+
+    // x feeds the matmul, so the final pass loads it rather than adding a and b a second time
+    let lazy_a = a.lazy();
+    let x = &lazy_a + b.lazy();
+    let expr = &x * 2.0 + x.matmul(&lazy_a);
+    assert_eq!(expr.explain(), "\
+$0 = input [2, 2]
+$1 = input [2, 2]
+$2 = fused [2, 2]
+    %0 = load $0
+    %1 = load $1
+    %2 = add %0 %1
+$3 = matmul $2 $0 -> [2, 2]
+$4 = fused [2, 2]
+    %0 = load $2
+    %1 = const 2
+    %2 = mul %0 %1
+    %3 = load $3
+    %4 = add %2 %3
+result $4
+");
+
+    let eager_x = add_tensors(&a, &b);
+    let doubled = multiply_tensors(&eager_x, &expand(&Tensor::Element(vec![2.0]), &[2, 2]));
+    let eager = add_tensors(&doubled, &matmul(&eager_x, &a));
+    assert_bitwise_eq(&expr.eval(), &eager);
+}
+
+#[test]
+fn matmul_and_sum_split_passes() {
+    let a = build_tensor(&values(6, 1), &[2, 3]);
+    let b = build_tensor(&values(12, 2), &[3, 4]);
+
+    let expr = ((a.lazy() + 1.0).matmul(&b.lazy()) * 2.0).sum(Some(0)).relu();
+    assert_eq!(expr.shape(), &[4]);
+    assert_eq!(expr.explain(), "\
+$0 = input [2, 3]
+$1 = fused [2, 3]
+    %0 = load $0
+    %1 = const 1
+    %2 = add %0 %1
+$2 = input [3, 4]
+$3 = matmul $1 $2 -> [2, 4]
+$4 = fused [2, 4]
+    %0 = load $3
+    %1 = const 2
+    %2 = mul %0 %1
+$5 = sum $4 axis 0 -> [4]
+$6 = fused [4]
+    %0 = load $5
+    %1 = relu %0
+result $6
+");
+
+    let product = matmul(&add_tensors(&a, &expand(&Tensor::Element(vec![1.0]), &[2, 3])), &b);
+    let doubled = multiply_tensors(&product, &expand(&Tensor::Element(vec![2.0]), &[2, 4]));
+    let eager = relu(&sum(&doubled, Some(0)));
+    assert_bitwise_eq(&expr.eval(), &eager);
+
+    let total = a.lazy().sum(None);
+    assert_eq!(total.shape(), &[1]);
+    assert_eq!(total.eval(), sum(&a, None));
+}
+
+#[test]
+fn long_chains_do_not_overflow_the_stack() {
+    let mut x = Tensor::Element(vec![0.0, 1.0]).lazy();
+    for _ in 0..20_000 {
+        x = x + 1.0;
+    }
+
+    // Passes are split every 256 operations
+    assert_eq!(x.explain().lines().filter(|line| line.contains("fused")).count(), 79);
+    assert_eq!(x.eval(), Tensor::Element(vec![20_000.0, 20_001.0]));
+
+    let identity = build_tensor(&[1.0, 0.0, 0.0, 1.0], &[2, 2]).lazy();
+    let mut y = identity.clone();
+    for _ in 0..20_000 {
+        y = y.matmul(&identity);
+    }
+    assert_eq!(y.eval(), build_tensor(&[1.0, 0.0, 0.0, 1.0], &[2, 2]));
+
+    // Dropping the last handles frees both chains
+    drop(x);
+    drop(y);
+}
+
+#[test]
+fn matmul_shapes() {
+    let m = LazyTensor::new(&build_tensor(&values(24, 1), &[2, 3, 4]));
+    let v = LazyTensor::new(&Tensor::Element(values(4, 2)));
+
+    assert_eq!(m.matmul(&v).shape(), &[2, 3]);
+    assert_eq!(v.matmul(&v).shape(), &[1]);
+    assert_eq!(
+        m.matmul(&LazyTensor::new(&build_tensor(&values(20, 3), &[4, 5]))).shape(),
+        &[2, 3, 5]
+    );
+}
+
+#[test]
+fn input_is_a_plan_of_its_own() {
+    let a = Tensor::Element(vec![1.0, 2.0]);
+    assert_eq!(a.lazy().explain(), "$0 = input [2]\nresult $0\n");
+    assert_eq!(a.lazy().eval(), a);
+}
+
+#[test]
+#[should_panic(expected = "Shapes [2, 3] and [2] are not broadcastable!")]
+fn mismatched_shapes_panic_when_built() {
+    let _ = build_tensor(&values(6, 1), &[2, 3]).lazy() + Tensor::Element(vec![1.0, 2.0]).lazy();
+}
+
+#[test]
+#[should_panic(expected = "Inner dimensions do not match for matmul! (3 vs 2)")]
+fn mismatched_matmul_panics_when_built() {
+    let a = build_tensor(&values(6, 1), &[2, 3]).lazy();
+    let _ = a.matmul(&a.broadcast_to(&[2, 3]).sum(Some(1)).broadcast_to(&[2]));
+}
+
+#[test]
+#[should_panic(expected = "Cannot broadcast shape [3] to [3, 2]!")]
+fn invalid_broadcast_to_panics() {
+    let _ = Tensor::Element(vec![1.0, 2.0, 3.0]).lazy().broadcast_to(&[3, 2]);
+}
@@ -0,0 +1,173 @@
+use crate::Tensor;
+use crate::linalg;
+use crate::linalg::NormOrder;
+use crate::random::Rng;
+use crate::tensor_ops::{build_tensor, flatten_tensor, get_dimension, matmul};
+
+fn random_tensor(shape: &[usize], seed: u64) -> Tensor {
+    let mut rng = Rng::new(seed);
+    let n: usize = shape.iter().product();
+    let values: Vec<f64> = (0..n).map(|_| rng.uniform(-1.0, 1.0)).collect();
+    build_tensor(&values, shape)
+}
+
+fn transpose(tensor: &Tensor) -> Tensor {
+    let shape = get_dimension(tensor);
+    let (m, n) = (shape[0], shape[1]);
+    let v = flatten_tensor(tensor);
+    let t: Vec<f64> = (0..n * m).map(|i| v[(i % m) * n + i / m]).collect();
+    build_tensor(&t, &[n, m])
+}
+
+fn assert_tensor_close(actual: &Tensor, expected: &Tensor, tol: f64) {
+    assert_eq!(get_dimension(actual), get_dimension(expected));
+    for (a, e) in flatten_tensor(actual).iter().zip(flatten_tensor(expected)) {
+        assert!((a - e).abs() < tol, "{a} != {e}");
+    }
+}
+
+fn identity(n: usize) -> Tensor {
+    let values: Vec<f64> = (0..n * n).map(|i| if i % (n + 1) == 0 { 1.0 } else { 0.0 }).collect();
+    build_tensor(&values, &[n, n])
+}
+
+#[test]
+fn inverse_and_determinant() {
+    let a = random_tensor(&[4, 4], 1);
+
+    assert_tensor_close(&matmul(&a, &linalg::inv(&a)), &identity(4), 1e-12);
+
+    let det_a = flatten_tensor(&linalg::det(&a))[0];
+    let det_inv = flatten_tensor(&linalg::det(&linalg::inv(&a)))[0];
+    assert!((det_a * det_inv - 1.0).abs() < 1e-12);
+
+    let (p, l, u) = linalg::lu(&a);
+    assert_tensor_close(&matmul(&p, &matmul(&l, &u)), &a, 1e-12);
+}
+
+#[test]
+fn batched_solve_broadcasts_rhs() {
+    let a = random_tensor(&[3, 3, 3], 2);
+    let b = random_tensor(&[3, 2], 3);
+
+    let x = linalg::solve(&a, &b);
+    assert_eq!(get_dimension(&x), Vec::from([3, 3, 2]));
+
+    let ax = matmul(&a, &x);
+    let values = flatten_tensor(&ax);
+    let b_values = flatten_tensor(&b);
+    for batch in 0..3 {
+        for (i, expected) in b_values.iter().enumerate() {
+            assert!((values[batch * 6 + i] - expected).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn qr_wide_and_tall() {
+    for shape in [[5, 3], [3, 5]] {
+        let a = random_tensor(&shape, 4);
+        let (q, r) = linalg::qr(&a);
+
+        assert_tensor_close(&matmul(&q, &r), &a, 1e-12);
+        assert_tensor_close(&matmul(&transpose(&q), &q), &identity(3), 1e-12);
+
+        let r_values = flatten_tensor(&r);
+        for i in 0..3 {
+            for j in 0..i {
+                assert_eq!(r_values[i * shape[1] + j], 0.0);
+            }
+        }
+    }
+}
+
+#[test]
+fn cholesky_reconstructs() {
+    let m = random_tensor(&[4, 4], 5);
+    let spd = matmul(&m, &transpose(&m)) + identity(4);
+
+    let l = linalg::cholesky(&spd);
+    assert_tensor_close(&matmul(&l, &transpose(&l)), &spd, 1e-12);
+}
+
+#[test]
+fn eigh_reconstructs() {
+    let m = random_tensor(&[5, 5], 6);
+    let sym = matmul(&m, &transpose(&m)) - identity(5);
+
+    let (values, vectors) = linalg::eigh(&sym);
+    let w = flatten_tensor(&values);
+    assert!(w.windows(2).all(|pair| pair[0] <= pair[1]));
+
+    // A V = V diag(w)
+    let scaled: Vec<f64> = flatten_tensor(&vectors).iter()
+        .enumerate()
+        .map(|(i, v)| v * w[i % 5])
+        .collect();
+    assert_tensor_close(&matmul(&sym, &vectors), &build_tensor(&scaled, &[5, 5]), 1e-12);
+    assert_tensor_close(&matmul(&transpose(&vectors), &vectors), &identity(5), 1e-12);
+}
+
+#[test]
+fn svd_rank_deficient() {
+    // The third row is the sum of the first two
+    let a = build_tensor(&[
+        1.0, 2.0, 3.0, 4.0,
+        0.0, 1.0, 0.0, 1.0,
+        1.0, 3.0, 3.0, 5.0
+    ], &[3, 4]);
+
+    let (u, s, vh) = linalg::svd(&a);
+    let s_values = flatten_tensor(&s);
+    assert!(s_values[2].abs() < 1e-12);
+
+    let us: Vec<f64> = flatten_tensor(&u).iter()
+        .enumerate()
+        .map(|(i, x)| x * s_values[i % 3])
+        .collect();
+    assert_tensor_close(&matmul(&build_tensor(&us, &[3, 3]), &vh), &a, 1e-12);
+    assert_tensor_close(&matmul(&transpose(&u), &u), &identity(3), 1e-12);
+
+    assert_eq!(linalg::matrix_rank(&a), Tensor::Element(Vec::from([2.0])));
+}
+
+#[test]
+fn pinv_and_lstsq() {
+    let a = random_tensor(&[6, 3], 7);
+    let a_pinv = linalg::pinv(&a);
+
+    // Moore-Penrose condition A A+ A = A
+    assert_tensor_close(&matmul(&a, &matmul(&a_pinv, &a)), &a, 1e-12);
+
+    let b = random_tensor(&[6], 8);
+    let x = linalg::lstsq(&a, &b);
+    assert_tensor_close(&x, &matmul(&a_pinv, &b), 1e-12);
+
+    // The residual is orthogonal to the columns of A
+    let residual = b - matmul(&a, &x);
+    assert_tensor_close(&matmul(&residual, &a), &Tensor::Element(Vec::from([0.0; 3])), 1e-12);
+}
+
+#[test]
+fn matrix_norms_and_trace() {
+    let a = random_tensor(&[2, 3, 4], 9);
+    let (_, s, _) = linalg::svd(&a);
+    let s = flatten_tensor(&s);
+
+    let nuclear = flatten_tensor(&linalg::norm(&a, NormOrder::Nuclear));
+    assert!((nuclear[0] - s[..3].iter().sum::<f64>()).abs() < 1e-12);
+    assert!((nuclear[1] - s[3..].iter().sum::<f64>()).abs() < 1e-12);
+
+    let two = flatten_tensor(&linalg::norm(&a, NormOrder::P(2.0)));
+    assert!((two[0] - s[0]).abs() < 1e-12);
+
+    let fro = flatten_tensor(&linalg::norm(&a, NormOrder::Frobenius));
+    let sum_sq: f64 = s[..3].iter().map(|x| x * x).sum();
+    assert!((fro[0] - sum_sq.sqrt()).abs() < 1e-12);
+
+    let v = Tensor::Element(Vec::from([1.0, -2.0, 2.0]));
+    assert_eq!(linalg::norm(&v, NormOrder::P(3.0)), Tensor::Element(Vec::from([17.0_f64.powf(1.0 / 3.0)])));
+
+    let batch = build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0], &[2, 2, 2]);
+    assert_eq!(linalg::trace(&batch), Tensor::Element(Vec::from([5.0, 13.0])));
+}
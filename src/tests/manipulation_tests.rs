@@ -0,0 +1,212 @@
+use crate::Tensor;
+use crate::tensor_ops::{
+    build_tensor,
+    diag_embed,
+    diagonal,
+    expand_tensor,
+    flatten_tensor,
+    flip,
+    get_dimension,
+    pad,
+    repeat,
+    roll,
+    rot90,
+    tile,
+    PadMode
+};
+
+/// A Tensor holding 0, 1, 2, ... in row-major order.
+fn arange(shape: &[usize]) -> Tensor {
+    let values: Vec<f64> = (0..shape.iter().product::<usize>()).map(|i| i as f64).collect();
+    build_tensor(&values, shape)
+}
+
+#[test]
+fn pad_modes_on_a_matrix() {
+    // 0 1 2
+    // 3 4 5
+    let t = arange(&[2, 3]);
+    let widths = [(1, 1), (2, 1)];
+
+    let edge = pad(&t, &widths, PadMode::Edge);
+    assert_eq!(get_dimension(&edge), [4, 6]);
+    assert_eq!(flatten_tensor(&edge), [
+        0.0, 0.0, 0.0, 1.0, 2.0, 2.0,
+        0.0, 0.0, 0.0, 1.0, 2.0, 2.0,
+        3.0, 3.0, 3.0, 4.0, 5.0, 5.0,
+        3.0, 3.0, 3.0, 4.0, 5.0, 5.0,
+    ]);
+
+    let wrap = pad(&t, &widths, PadMode::Wrap);
+    assert_eq!(flatten_tensor(&wrap), [
+        4.0, 5.0, 3.0, 4.0, 5.0, 3.0,
+        1.0, 2.0, 0.0, 1.0, 2.0, 0.0,
+        4.0, 5.0, 3.0, 4.0, 5.0, 3.0,
+        1.0, 2.0, 0.0, 1.0, 2.0, 0.0,
+    ]);
+
+    let reflect = pad(&t, &widths, PadMode::Reflect);
+    assert_eq!(flatten_tensor(&reflect), [
+        5.0, 4.0, 3.0, 4.0, 5.0, 4.0,
+        2.0, 1.0, 0.0, 1.0, 2.0, 1.0,
+        5.0, 4.0, 3.0, 4.0, 5.0, 4.0,
+        2.0, 1.0, 0.0, 1.0, 2.0, 1.0,
+    ]);
+
+    let constant = pad(&t, &widths, PadMode::default());
+    assert_eq!(flatten_tensor(&constant)[..6], [0.0; 6]);
+    assert_eq!(flatten_tensor(&constant)[6..12], [0.0, 0.0, 0.0, 1.0, 2.0, 0.0]);
+}
+
+#[test]
+fn pad_wraps_more_than_once() {
+    let t = Tensor::Element(vec![1.0, 2.0]);
+    let wrapped = pad(&t, &[(3, 0)], PadMode::Wrap);
+    assert_eq!(wrapped, Tensor::Element(vec![2.0, 1.0, 2.0, 1.0, 2.0]));
+    assert_eq!(pad(&t, &[(0, 0)], PadMode::Reflect), t);
+}
+
+#[test]
+#[should_panic(expected = "Reflect padding of 2 must be smaller than the length 2 of axis 1!")]
+fn pad_rejects_wide_reflections() {
+    pad(&arange(&[3, 2]), &[(2, 2), (0, 2)], PadMode::Reflect);
+}
+
+#[test]
+#[should_panic(expected = "pair per axis, got 1 for a Tensor with 2 dimensions!")]
+fn pad_rejects_missing_widths() {
+    pad(&arange(&[3, 2]), &[(1, 1)], PadMode::Edge);
+}
+
+#[test]
+fn tile_repeats_the_whole_tensor() {
+    let t = arange(&[2, 2]);
+    let tiled = tile(&t, &[2, 3]);
+    assert_eq!(get_dimension(&tiled), [4, 6]);
+    let values = flatten_tensor(&tiled);
+    for (i, value) in values.iter().enumerate() {
+        let (row, column) = (i / 6, i % 6);
+        assert_eq!(*value, ((row % 2) * 2 + column % 2) as f64);
+    }
+
+    // Extra repetitions add leading axes, and missing ones leave leading axes alone
+    assert_eq!(get_dimension(&tile(&t, &[3, 1, 1])), [3, 2, 2]);
+    assert_eq!(tile(&t, &[1, 1, 1]), build_tensor(&flatten_tensor(&t), &[1, 2, 2]));
+    assert_eq!(tile(&t, &[2]), build_tensor(&[0.0, 1.0, 0.0, 1.0, 2.0, 3.0, 2.0, 3.0], &[2, 4]));
+}
+
+#[test]
+fn tile_matches_expand_tensor_on_a_new_axis() {
+    let t = arange(&[2, 3]);
+    assert_eq!(tile(&t, &[4, 1, 1]), expand_tensor(&t, 4));
+}
+
+#[test]
+#[should_panic(expected = "Tile repetitions must be at least 1, got [2, 0]!")]
+fn tile_rejects_zero_repetitions() {
+    tile(&arange(&[2, 2]), &[2, 0]);
+}
+
+#[test]
+fn repeat_along_a_middle_axis() {
+    let t = arange(&[2, 2, 2]);
+    let repeated = repeat(&t, 3, Some(1));
+    assert_eq!(get_dimension(&repeated), [2, 6, 2]);
+    assert_eq!(flatten_tensor(&repeated)[..12], [
+        0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 2.0, 3.0, 2.0, 3.0, 2.0, 3.0,
+    ]);
+}
+
+#[test]
+#[should_panic(expected = "Axis 2 is out of range for a Tensor with 2 dimensions!")]
+fn repeat_rejects_a_missing_axis() {
+    repeat(&arange(&[2, 2]), 2, Some(2));
+}
+
+#[test]
+fn flip_twice_is_the_identity() {
+    let t = arange(&[2, 3, 4]);
+    for axes in [vec![0], vec![1, 2], vec![0, 1, 2], vec![]] {
+        assert_eq!(flip(&flip(&t, &axes), &axes), t);
+    }
+    let flipped = flatten_tensor(&flip(&t, &[0, 1, 2]));
+    assert_eq!(flipped, (0..24).rev().map(|i| i as f64).collect::<Vec<f64>>());
+}
+
+#[test]
+#[should_panic(expected = "Axis 1 is given more than once!")]
+fn flip_rejects_repeated_axes() {
+    flip(&arange(&[2, 2]), &[1, 1]);
+}
+
+#[test]
+fn roll_wraps_in_both_directions() {
+    let t = arange(&[3, 4]);
+    for shift in -9..=9 {
+        let rolled = roll(&t, shift, Some(1));
+        let values = flatten_tensor(&rolled);
+        for (i, value) in values.iter().enumerate() {
+            let (row, column) = (i / 4, (i % 4) as isize);
+            assert_eq!(*value, (row * 4) as f64 + (column - shift).rem_euclid(4) as f64);
+        }
+        assert_eq!(roll(&rolled, -shift, Some(1)), t);
+    }
+
+    assert_eq!(roll(&t, 4, Some(0)), roll(&t, 1, Some(0)));
+    assert_eq!(roll(&t, 4, None), roll(&t, 1, Some(0)));
+}
+
+#[test]
+fn rot90_turns_a_full_circle() {
+    let t = arange(&[2, 3, 4]);
+    let once = rot90(&t, 1, [1, 2]);
+    assert_eq!(get_dimension(&once), [2, 4, 3]);
+
+    let mut turned = t.clone();
+    for _ in 0..4 {
+        turned = rot90(&turned, 1, [1, 2]);
+    }
+    assert_eq!(turned, t);
+    assert_eq!(rot90(&t, 3, [1, 2]), rot90(&t, -1, [1, 2]));
+    assert_eq!(rot90(&t, 1, [2, 1]), rot90(&t, -1, [1, 2]));
+    assert_eq!(rot90(&t, 2, [0, 2]), flip(&t, &[0, 2]));
+}
+
+#[test]
+#[should_panic(expected = "Axis 0 is given more than once!")]
+fn rot90_rejects_a_single_axis() {
+    rot90(&arange(&[2, 2]), 1, [0, 0]);
+}
+
+#[test]
+fn diagonal_of_non_square_batches() {
+    let t = arange(&[2, 3, 4]);
+    assert_eq!(diagonal(&t, 0, 1, 2), build_tensor(&[0.0, 5.0, 10.0, 12.0, 17.0, 22.0], &[2, 3]));
+    assert_eq!(diagonal(&t, 2, 1, 2), build_tensor(&[2.0, 7.0, 14.0, 19.0], &[2, 2]));
+    assert_eq!(diagonal(&t, -1, 1, 2), build_tensor(&[4.0, 9.0, 16.0, 21.0], &[2, 2]));
+
+    // Axes 0 and 2 leave axis 1 as the batch
+    let outer = diagonal(&t, 0, 0, 2);
+    assert_eq!(outer, build_tensor(&[0.0, 13.0, 4.0, 17.0, 8.0, 21.0], &[3, 2]));
+}
+
+#[test]
+#[should_panic(expected = "Offset 4 leaves no diagonal in a 3x4 matrix!")]
+fn diagonal_rejects_offsets_past_the_matrix() {
+    diagonal(&arange(&[3, 4]), 4, 0, 1);
+}
+
+#[test]
+fn diag_embed_inverts_diagonal() {
+    let t = arange(&[2, 3]);
+    for offset in -2..=2 {
+        let embedded = diag_embed(&t, offset);
+        let size = 3 + offset.unsigned_abs();
+        assert_eq!(get_dimension(&embedded), [2, size, size]);
+        assert_eq!(diagonal(&embedded, offset, 1, 2), t);
+
+        // Everything off the diagonal is zero
+        let total: f64 = flatten_tensor(&embedded).iter().sum();
+        assert_eq!(total, flatten_tensor(&t).iter().sum::<f64>());
+    }
+}
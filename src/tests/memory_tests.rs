@@ -0,0 +1,191 @@
+use crate::Tensor;
+use crate::memory::{PoolBuffer, Pool, PoolConfig, pool_stats, size_class};
+use crate::tensor_ops::{build_tensor, conv2d, flatten_tensor, Conv2dOptions};
+
+fn pool(max_cached_bytes: usize) -> Pool {
+    Pool::new(PoolConfig { enabled: true, max_cached_bytes })
+}
+
+#[test]
+fn size_classes() {
+    assert_eq!(size_class(64), 64);
+    assert_eq!(size_class(65), 80);
+    assert_eq!(size_class(100), 112);
+    assert_eq!(size_class(128), 128);
+    assert_eq!(size_class(129), 160);
+    assert_eq!(size_class(1_000_000), 1_048_576);
+    assert_eq!(size_class(600_000), 655_360);
+
+    for len in 64..5000 {
+        let class = size_class(len);
+        assert!(class >= len && class * 4 <= len * 5, "{len} -> {class}");
+        assert_eq!(size_class(class), class);
+    }
+}
+
+#[test]
+fn reuses_buffers_of_the_same_class() {
+    let mut pool = pool(1 << 20);
+
+    let (first, class) = pool.take(100);
+    assert_eq!((first.capacity(), class), (112, 112));
+    let address = first.as_ptr();
+    assert_eq!(pool.stats().bytes_live, 112 * 8);
+
+    assert!(pool.give(first, class).is_none());
+    assert_eq!(pool.stats().bytes_live, 0);
+    assert_eq!(pool.stats().bytes_cached, 112 * 8);
+
+    // 110 elements round up to the same class
+    let (second, _) = pool.take(110);
+    assert_eq!(second.as_ptr(), address);
+    assert!(second.is_empty());
+
+    let stats = pool.stats();
+    assert_eq!((stats.hits, stats.misses), (1, 1));
+    assert_eq!(stats.hit_rate(), 0.5);
+    assert_eq!(stats.bytes_cached, 0);
+}
+
+#[test]
+fn tracks_peak_bytes() {
+    let mut pool = pool(1 << 20);
+
+    let a = pool.take(64);
+    let b = pool.take(128);
+    pool.give(a.0, a.1);
+    let c = pool.take(256);
+
+    let stats = pool.stats();
+    assert_eq!(stats.bytes_live, (128 + 256) * 8);
+    assert_eq!(stats.peak_bytes_live, (128 + 256) * 8);
+
+    pool.give(b.0, b.1);
+    pool.give(c.0, c.1);
+    assert_eq!(pool.stats().bytes_live, 0);
+    assert_eq!(pool.stats().peak_bytes_live, (128 + 256) * 8);
+
+    pool.reset_stats();
+    let stats = pool.stats();
+    assert_eq!((stats.peak_bytes_live, stats.hits, stats.misses), (0, 0, 0));
+    assert_eq!(stats.hit_rate(), 0.0);
+}
+
+#[test]
+fn small_buffers_are_not_pooled() {
+    let mut pool = pool(1 << 20);
+
+    let (buffer, class) = pool.take(10);
+    assert_eq!((buffer.capacity(), class), (10, 0));
+    assert!(pool.give(buffer, class).is_some());
+    assert_eq!(pool.stats().misses, 0);
+    assert_eq!(pool.stats().bytes_cached, 0);
+}
+
+#[test]
+fn only_keeps_buffers_that_still_match_their_class() {
+    let mut pool = pool(1 << 20);
+
+    // Buffers not drawn from the pool are never taken in, whatever their capacity
+    assert!(pool.give(Vec::with_capacity(128), 0).is_some());
+    assert_eq!(pool.stats().bytes_cached, 0);
+
+    // A buffer pushed past its class is freed, but no longer counted as live
+    let (mut grown, class) = pool.take(64);
+    grown.resize(65, 0.0);
+    assert!(pool.give(grown, class).is_some());
+    assert_eq!(pool.stats().bytes_cached, 0);
+    assert_eq!(pool.stats().bytes_live, 0);
+}
+
+#[test]
+fn respects_cache_limit() {
+    let mut pool = pool(200 * 8);
+
+    let a = pool.take(128);
+    let b = pool.take(128);
+    assert!(pool.give(a.0, a.1).is_none());
+    assert!(pool.give(b.0, b.1).is_some());
+    assert_eq!(pool.stats().bytes_cached, 128 * 8);
+    assert_eq!(pool.stats().bytes_live, 0);
+}
+
+#[test]
+fn trims_largest_first() {
+    let mut pool = pool(1 << 20);
+
+    let buffers: Vec<(Vec<f64>, usize)> =
+        [64, 128, 256, 512].iter().map(|&len| pool.take(len)).collect();
+    for (buffer, class) in buffers {
+        pool.give(buffer, class);
+    }
+    assert_eq!(pool.stats().bytes_cached, 960 * 8);
+
+    assert_eq!(pool.trim_to(200 * 8), 768 * 8);
+    assert_eq!(pool.stats().bytes_cached, 192 * 8);
+    assert_eq!(pool.take(200).0.capacity(), 224);
+    assert_eq!(pool.stats().hits, 0);
+    assert_eq!(pool.take(128).0.capacity(), 128);
+    assert_eq!(pool.stats().hits, 1);
+
+    assert_eq!(pool.trim_to(0), 64 * 8);
+    assert_eq!(pool.stats().bytes_cached, 0);
+}
+
+#[test]
+fn disabling_releases_the_cache() {
+    let mut pool = pool(1 << 20);
+
+    let a = pool.take(256);
+    let b = pool.take(256);
+    pool.give(a.0, a.1);
+    pool.set_config(PoolConfig { enabled: false, max_cached_bytes: 1 << 20 });
+    assert_eq!(pool.stats().bytes_cached, 0);
+
+    // Buffers drawn before disabling are still counted when they come back
+    assert!(pool.give(b.0, b.1).is_some());
+    assert_eq!(pool.stats().bytes_live, 0);
+
+    let (c, class) = pool.take(100);
+    assert_eq!((c.capacity(), class), (100, 0));
+    assert_eq!(pool.stats().bytes_live, 0);
+}
+
+#[test]
+fn pool_buffers_hold_values() {
+    let mut buffer = PoolBuffer::with_capacity(1000);
+    assert!(buffer.capacity() >= 1000 && buffer.is_empty());
+    buffer.extend((0..1000).map(f64::from));
+    assert_eq!(buffer[999], 999.0);
+    drop(buffer);
+
+    assert_eq!(*PoolBuffer::zeros(300), vec![0.0; 300]);
+    assert_eq!(*PoolBuffer::zeros(3), vec![0.0; 3]);
+}
+
+#[test]
+fn tensors_do_not_hold_pool_buffers() {
+    let values: Vec<f64> = (0..4096).map(f64::from).collect();
+    let t = build_tensor(&values, &[4096]);
+    let copy = t.clone();
+    assert_eq!(copy, t);
+    drop(t);
+
+    // Values moved out of a Tensor are the caller's, not the pool's
+    let taken = copy.into_values();
+    assert_eq!(taken, values);
+    assert_eq!(build_tensor(&values, &[64, 64]).into_values(), values);
+}
+
+#[test]
+fn operations_draw_temporaries_from_global_pool() {
+    let input = build_tensor(&vec![1.0; 2 * 32 * 32], &[1, 2, 32, 32]);
+    let weight = build_tensor(&vec![0.5; 3 * 2 * 3 * 3], &[3, 2, 3, 3]);
+    let output = conv2d(&input, &weight, None, &Conv2dOptions::default());
+    assert!(pool_stats().peak_bytes_live >= 2 * 9 * 30 * 30 * 8);
+    assert_eq!(flatten_tensor(&output)[0], 9.0);
+
+    let x = Tensor::Element((0..4096).map(f64::from).collect()).lazy();
+    let y = ((&x * 2.0).sum(None) + 1.0).eval();
+    assert_eq!(flatten_tensor(&y), [4096.0 * 4095.0 + 1.0]);
+}
@@ -0,0 +1,139 @@
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+use crate::{MmapTensor, Tensor};
+use crate::tensor_io::DType;
+use crate::tensor_ops::{build_tensor, flatten_tensor};
+use super::{fixture, temp_path, two_by_three};
+
+/// Maps a `.npy` file that nothing changes while the test runs.
+fn open_npy(path: impl AsRef<Path>) -> io::Result<MmapTensor> {
+    // SAFETY: the fixtures and the files these tests write are never changed while mapped.
+    unsafe { MmapTensor::open_npy(path) }
+}
+
+/// Maps a raw file that nothing changes while the test runs.
+fn open_raw(
+    path: impl AsRef<Path>,
+    dtype: DType,
+    shape: &[usize],
+    offset: usize
+) -> io::Result<MmapTensor> {
+    // SAFETY: as in open_npy().
+    unsafe { MmapTensor::open_raw(path, dtype, shape, offset) }
+}
+
+#[test]
+fn open_npy_c_order() {
+    let t = open_npy(fixture("f8_c.npy")).unwrap();
+    assert_eq!(t.shape(), &[2, 3]);
+    assert_eq!(t.strides(), &[3, 1]);
+    assert_eq!(t.dtype(), DType::F64);
+    assert!(t.is_contiguous());
+    assert_eq!(t.to_tensor(), two_by_three());
+}
+
+#[test]
+fn open_npy_fortran_order() {
+    let t = open_npy(fixture("i4_fortran.npy")).unwrap();
+    assert_eq!(t.strides(), &[1, 2]);
+    assert!(!t.is_contiguous());
+    assert_eq!(t.get(&[1, 0]), Some(4.0));
+    assert_eq!(t.to_tensor(), two_by_three());
+}
+
+#[test]
+fn open_npy_big_endian() {
+    let t = open_npy(fixture("f4_big_v2.npy")).unwrap();
+    assert_eq!(t.to_tensor(), Tensor::Element(vec![0.5, -1.5, 2.25, 1000.0]));
+}
+
+#[test]
+fn get_out_of_range() {
+    let t = open_npy(fixture("f8_c.npy")).unwrap();
+    assert_eq!(t.get(&[2, 0]), None);
+    assert_eq!(t.get(&[0, 3]), None);
+    assert_eq!(t.get(&[0]), None);
+}
+
+#[test]
+fn slice_and_transpose_views() {
+    let t = open_npy(fixture("f8_c.npy")).unwrap();
+
+    assert_eq!(t.slice(1..2).to_tensor(), build_tensor(&[4.0, 5.0, 6.0], &[1, 3]));
+    assert_eq!(t.slice_axis(1, 1..3).to_tensor(), build_tensor(&[2.0, 3.0, 5.0, 6.0], &[2, 2]));
+
+    let transposed = t.transpose(0, 1);
+    assert_eq!(transposed.shape(), &[3, 2]);
+    assert_eq!(transposed.to_tensor(), build_tensor(&[1.0, 4.0, 2.0, 5.0, 3.0, 6.0], &[3, 2]));
+    assert_eq!(transposed.slice(2..3).to_tensor(), build_tensor(&[3.0, 6.0], &[1, 2]));
+}
+
+#[test]
+fn open_raw_with_offset() {
+    let path = temp_path("raw.bin");
+    let mut bytes = vec![0xFF; 4];
+    for v in 0..24u16 {
+        bytes.extend_from_slice(&v.to_le_bytes());
+    }
+    fs::write(&path, &bytes).unwrap();
+
+    let t = open_raw(&path, DType::U16, &[2, 3, 4], 4).unwrap();
+    assert_eq!(t.len(), 24);
+    assert_eq!(t.get(&[1, 2, 3]), Some(23.0));
+
+    let expected: Vec<f64> = (12..24).map(|x| x as f64).collect();
+    assert_eq!(t.slice(1..2).to_tensor(), build_tensor(&expected, &[1, 3, 4]));
+
+    drop(t);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn open_raw_half_dtypes() {
+    let path = temp_path("half.bin");
+    let values = [1.0, -0.5, 65504.0, 0.1];
+
+    let encodings = [
+        (DType::F16, [0x3C00u16, 0xB800, 0x7BFF, 0x2E66]),
+        (DType::BF16, [0x3F80, 0xBF00, 0x477F, 0x3DCD]),
+    ];
+
+    for (dtype, bits) in encodings {
+        let bytes: Vec<u8> = bits.iter().flat_map(|b| b.to_le_bytes()).collect();
+        fs::write(&path, &bytes).unwrap();
+
+        let t = open_raw(&path, dtype, &[2, 2], 0).unwrap();
+        assert_eq!(t.get(&[0, 1]), Some(-0.5));
+        let loaded = flatten_tensor(&t.to_tensor());
+        for (value, expected) in loaded.iter().zip(values) {
+            assert!((value - expected).abs() <= expected.abs() / 128.0, "{dtype:?} {value}");
+        }
+    }
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn open_raw_too_small() {
+    let path = temp_path("small.bin");
+    fs::write(&path, [0u8; 20]).unwrap();
+
+    let error = open_raw(&path, DType::F32, &[2, 3], 0).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(error.to_string().contains("too few"));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn open_raw_empty_shape() {
+    let error = open_raw(fixture("f8_c.npy"), DType::F64, &[], 0).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn slice_out_of_bounds() {
+    open_npy(fixture("f8_c.npy")).unwrap().slice(1..3);
+}
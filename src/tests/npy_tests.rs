@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use crate::Tensor;
+use crate::tensor_io::{DType, load_npy, load_npz, save_npy, save_npy_as, save_npz};
+use crate::tensor_ops::build_tensor;
+use super::{fixture, temp_path, two_by_three};
+
+#[test]
+fn load_f8_c_order() {
+    assert_eq!(load_npy(fixture("f8_c.npy")).unwrap(), two_by_three());
+}
+
+#[test]
+fn load_i4_fortran_order() {
+    assert_eq!(load_npy(fixture("i4_fortran.npy")).unwrap(), two_by_three());
+}
+
+#[test]
+fn load_big_endian_f4_version_2() {
+    let expected = Tensor::Element(vec![0.5, -1.5, 2.25, 1000.0]);
+    assert_eq!(load_npy(fixture("f4_big_v2.npy")).unwrap(), expected);
+}
+
+#[test]
+fn load_u1_version_3() {
+    let expected = build_tensor(&[0.0, 127.0, 128.0, 255.0], &[2, 2]);
+    assert_eq!(load_npy(fixture("u1_v3.npy")).unwrap(), expected);
+}
+
+#[test]
+fn load_bool() {
+    assert_eq!(load_npy(fixture("b1.npy")).unwrap(), Tensor::Element(vec![1.0, 0.0, 1.0]));
+}
+
+#[test]
+fn load_f2() {
+    let expected = build_tensor(&[0.0999755859375, -2.5, 65504.0, 2f64.powi(-24), f64::INFINITY, 2048.0], &[2, 3]);
+    assert_eq!(load_npy(fixture("f2.npy")).unwrap(), expected);
+}
+
+#[test]
+fn save_f2_matches_numpy_bytes() {
+    let t = build_tensor(&[0.1, -2.5, 65504.0, 6e-8, f64::INFINITY, 2049.0], &[2, 3]);
+
+    let path = temp_path("f2.npy");
+    save_npy_as(&t, &path, DType::F16).unwrap();
+    assert_eq!(fs::read(&path).unwrap(), fs::read(fixture("f2.npy")).unwrap());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn save_as_every_numpy_dtype() {
+    let t = build_tensor(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0], &[3, 2]);
+    let dtypes = [
+        DType::Bool, DType::I8, DType::I16, DType::I32, DType::I64, DType::U8, DType::U16, DType::U32,
+        DType::U64, DType::F16, DType::F32, DType::F64
+    ];
+
+    let path = temp_path("save_as.npy");
+    for dtype in dtypes {
+        save_npy_as(&t, &path, dtype).unwrap();
+        let expected = if dtype == DType::Bool {
+            build_tensor(&[0.0, 1.0, 1.0, 1.0, 1.0, 1.0], &[3, 2])
+        } else {
+            t.clone()
+        };
+        assert_eq!(load_npy(&path).unwrap(), expected, "{dtype:?}");
+    }
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn save_rejects_bf16() {
+    let error = save_npy_as(&Tensor::Element(vec![1.0]), temp_path("bf16.npy"), DType::BF16).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn save_matches_numpy_bytes() {
+    let path = temp_path("matches.npy");
+    two_by_three().save_npy(&path).unwrap();
+
+    assert_eq!(fs::read(&path).unwrap(), fs::read(fixture("f8_c.npy")).unwrap());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn save_load_round_trip_3d() {
+    let values: Vec<f64> = (0..24).map(|x| x as f64 * 0.25 - 3.0).collect();
+    let t = build_tensor(&values, &[2, 3, 4]);
+
+    let path = temp_path("round_trip.npy");
+    save_npy(&t, &path).unwrap();
+    assert_eq!(Tensor::load_npy(&path).unwrap(), t);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn load_rejects_truncated_data() {
+    let bytes = fs::read(fixture("f8_c.npy")).unwrap();
+    let path = temp_path("truncated.npy");
+    fs::write(&path, &bytes[..bytes.len() - 8]).unwrap();
+
+    let error = load_npy(&path).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn load_rejects_overflowing_shapes() {
+    let mut header = "{'descr': '<f8', 'fortran_order': False, 'shape': (4611686018427387904, 4), }"
+        .to_string();
+    while !(10 + header.len() + 1).is_multiple_of(64) {
+        header.push(' ');
+    }
+    header.push('\n');
+
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend_from_slice(&[0; 10]);
+
+    let path = temp_path("overflow.npy");
+    fs::write(&path, &bytes).unwrap();
+    assert_eq!(load_npy(&path).unwrap_err().kind(), ErrorKind::InvalidData);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn load_rejects_missing_magic() {
+    let path = temp_path("not_npy.npy");
+    fs::write(&path, b"definitely not a numpy file").unwrap();
+
+    assert_eq!(load_npy(&path).unwrap_err().kind(), ErrorKind::InvalidData);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn load_stored_npz() {
+    let tensors = load_npz(fixture("stored.npz")).unwrap();
+
+    assert_eq!(tensors.len(), 2);
+    assert_eq!(tensors["a"], two_by_three());
+    assert_eq!(tensors["b"], Tensor::Element(vec![1.0, 0.0, 1.0]));
+}
+
+#[test]
+fn load_deflated_npz() {
+    let tensors = load_npz(fixture("deflated.npz")).unwrap();
+
+    assert_eq!(tensors.len(), 2);
+    assert_eq!(tensors["a"], two_by_three());
+    assert_eq!(tensors["b"], two_by_three());
+}
+
+#[test]
+fn npz_round_trip() {
+    let mut tensors = HashMap::new();
+    tensors.insert(String::from("weight"), two_by_three());
+    tensors.insert(String::from("bias"), Tensor::Element(vec![0.1, -0.2]));
+
+    let path = temp_path("round_trip.npz");
+    save_npz(&tensors, &path).unwrap();
+    assert_eq!(load_npz(&path).unwrap(), tensors);
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,175 @@
+use std::sync::atomic::{ AtomicUsize, Ordering };
+use std::thread;
+use std::time::Duration;
+use crate::Tensor;
+use crate::parallel::{
+    self,
+    parallel_config,
+    set_parallel_config,
+    with_parallel_config,
+    ParallelConfig
+};
+use crate::tensor_ops::{
+    add_tensors,
+    broadcast_tensor,
+    build_tensor,
+    expand_tensor,
+    flatten_tensor,
+    get_dimension,
+    matmul,
+    max,
+    mean,
+    min,
+    multiply_add_tensors,
+    relu,
+    sum,
+    tensor_op
+};
+
+fn config(threads: usize, grain_size: usize, deterministic: bool) -> ParallelConfig {
+    ParallelConfig { threads, grain_size, deterministic }
+}
+
+fn values(len: usize) -> Vec<f64> {
+    (0..len).map(|i| ((i * 7919) % 1000) as f64 / 7.0 - 50.0 + 1.0 / (i as f64 + 1.0)).collect()
+}
+
+/// Runs `f` with several thread counts for each of several small grains, checking every run
+/// matches the serial run with the same grain.
+fn assert_same_for_all_threads(f: impl Fn() -> Tensor) {
+    for grain_size in [1, 7, 64] {
+        let expected = with_parallel_config(config(1, grain_size, true), &f);
+        for threads in [2, 3, 8] {
+            let actual = with_parallel_config(config(threads, grain_size, true), &f);
+            assert_eq!(
+                format!("{actual:?}"),
+                format!("{expected:?}"),
+                "threads {threads}, grain {grain_size}"
+            );
+        }
+    }
+}
+
+#[test]
+fn default_config() {
+    let config = ParallelConfig::default();
+    assert!(config.threads >= 1);
+    assert!(config.grain_size > 0);
+    assert!(config.deterministic);
+}
+
+#[test]
+fn override_is_restored() {
+    let outer = parallel_config();
+    let inner = config(3, 5, false);
+    assert_eq!(with_parallel_config(inner.clone(), parallel_config), inner);
+    assert_eq!(parallel_config(), outer);
+
+    let result = std::panic::catch_unwind(|| with_parallel_config(inner, || panic!("inside")));
+    assert!(result.is_err());
+    assert_eq!(parallel_config(), outer);
+}
+
+#[test]
+fn elementwise_ops_do_not_depend_on_threads() {
+    let a = build_tensor(&values(1000), &[1000]);
+    let b = build_tensor(&values(1000).iter().rev().copied().collect::<Vec<_>>(), &[1000]);
+    let a3 = build_tensor(&values(960), &[4, 15, 16]);
+    let b3 = build_tensor(&values(960).iter().map(|x| x * 0.5).collect::<Vec<_>>(), &[4, 15, 16]);
+
+    assert_same_for_all_threads(|| add_tensors(&a, &b));
+    assert_same_for_all_threads(|| add_tensors(&a3, &b3));
+    assert_same_for_all_threads(|| tensor_op(&a3, &b3, f64::powf));
+    assert_same_for_all_threads(|| multiply_add_tensors(&a3, &b3, &a3));
+    assert_same_for_all_threads(|| relu(&a3));
+}
+
+#[test]
+fn reductions_do_not_depend_on_threads() {
+    let t = build_tensor(&values(3 * 50 * 40), &[3, 50, 40]);
+
+    assert_same_for_all_threads(|| sum(&t, None));
+    assert_same_for_all_threads(|| mean(&t, None));
+    assert_same_for_all_threads(|| max(&t, None));
+    for axis in 0..3 {
+        assert_same_for_all_threads(|| sum(&t, Some(axis)));
+        assert_same_for_all_threads(|| min(&t, Some(axis)));
+    }
+}
+
+#[test]
+fn non_deterministic_sum_is_close() {
+    let t = build_tensor(&values(10_000), &[10_000]);
+    let exact: f64 = values(10_000).iter().sum();
+
+    for threads in [1, 2, 5] {
+        let total = with_parallel_config(config(threads, 16, false), || sum(&t, None));
+        assert!((flatten_tensor(&total)[0] - exact).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn matmul_does_not_depend_on_threads() {
+    let a = build_tensor(&values(3 * 17 * 9), &[3, 17, 9]);
+    let b = build_tensor(&values(9 * 11), &[9, 11]);
+    let v = build_tensor(&values(9), &[9]);
+
+    assert_same_for_all_threads(|| matmul(&a, &b));
+    assert_same_for_all_threads(|| matmul(&a, &v));
+    assert_same_for_all_threads(|| matmul(&v, &b));
+}
+
+#[test]
+fn broadcasting_does_not_depend_on_threads() {
+    let row = build_tensor(&values(40), &[1, 1, 40]);
+    let column = build_tensor(&values(6 * 30), &[6, 30, 1]);
+
+    assert_same_for_all_threads(|| broadcast_tensor(&row, &vec![6, 30, 40]));
+    assert_same_for_all_threads(|| broadcast_tensor(&column, &vec![6, 30, 40]));
+    assert_same_for_all_threads(|| expand_tensor(&column, 5));
+}
+
+#[test]
+fn smaller_thread_counts_reuse_the_pool() {
+    let active = AtomicUsize::new(0);
+    let most = AtomicUsize::new(0);
+    let run = || parallel::map_collect(32, usize::MAX, |_| {
+        let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+        most.fetch_max(now, Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(1));
+        active.fetch_sub(1, Ordering::SeqCst);
+    });
+
+    // The pool grown for 6 threads stays, but only 2 threads work on each task afterwards
+    with_parallel_config(config(6, 1, true), run);
+    for threads in [2, 3, 2] {
+        most.store(0, Ordering::SeqCst);
+        with_parallel_config(config(threads, 1, true), run);
+        assert!(most.load(Ordering::SeqCst) <= threads);
+    }
+}
+
+#[test]
+fn nested_parallel_work() {
+    // Element-wise ops inside a batched matmul chunk must not deadlock the pool
+    let t = build_tensor(&values(64 * 64), &[64, 64]);
+    let result = with_parallel_config(config(4, 1, true), || {
+        let squared = matmul(&t, &t);
+        add_tensors(&squared, &squared)
+    });
+    assert_eq!(get_dimension(&result), vec![64, 64]);
+}
+
+#[test]
+#[should_panic(expected = "Element Tensors are different lengths!")]
+fn panics_reach_the_caller() {
+    let a = build_tensor(&values(8 * 3), &[8, 3]);
+    let b = Tensor::Array((0..8).map(|i| Tensor::Element(vec![1.0; if i == 5 { 2 } else { 3 }])).collect());
+    with_parallel_config(config(4, 1, true), || add_tensors(&a, &b));
+}
+
+#[test]
+#[should_panic(expected = "at least one thread")]
+fn zero_threads() {
+    set_parallel_config(config(0, 1, true));
+}
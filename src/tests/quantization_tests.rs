@@ -0,0 +1,245 @@
+use crate::Tensor;
+use crate::quantization::{
+    calibrate,
+    dequantize,
+    quantize,
+    CalibrationMethod,
+    Calibrator,
+    QuantInt,
+    QuantParams,
+    QuantScheme,
+    QuantizedTensor
+};
+use crate::quantization::QuantScheme::{ Affine, Symmetric };
+use crate::random::Rng;
+use crate::tensor_ops::{ build_tensor, flatten_tensor, matmul };
+
+fn random_tensor(shape: &[usize], low: f64, high: f64, seed: u64) -> Tensor {
+    let mut rng = Rng::new(seed);
+    let values: Vec<f64> = (0..shape.iter().product())
+        .map(|_| rng.uniform(low, high))
+        .collect();
+    build_tensor(&values, shape)
+}
+
+/// Calibrates parameters from the minimum and maximum of a single Tensor.
+fn min_max<T: QuantInt>(t: &Tensor, axis: Option<usize>, scheme: QuantScheme) -> QuantParams {
+    calibrate::<T>(std::slice::from_ref(t), CalibrationMethod::MinMax, axis, scheme)
+}
+
+fn max_difference(a: &Tensor, b: &Tensor) -> f64 {
+    flatten_tensor(a).iter()
+        .zip(flatten_tensor(b))
+        .map(|(x, y)| (x - y).abs())
+        .fold(0.0, f64::max)
+}
+
+#[test]
+fn round_trip_error_is_at_most_half_a_step() {
+    let t = random_tensor(&[4, 50], -3.0, 5.0, 1);
+    for scheme in [Affine, Symmetric] {
+        let params = min_max::<u8>(&t, None, scheme);
+        let back = dequantize(&quantize::<u8>(&t, &params));
+        assert!(max_difference(&t, &back) <= params.scales()[0] / 2.0 + 1e-12, "{scheme:?}");
+
+        let params = min_max::<i8>(&t, None, scheme);
+        let back = quantize::<i8>(&t, &params).dequantize();
+        assert!(max_difference(&t, &back) <= params.scales()[0] / 2.0 + 1e-12, "{scheme:?}");
+    }
+}
+
+#[test]
+fn zero_is_exact() {
+    let t = Tensor::Element(vec![0.3, 0.0, 1.7, 0.9]);
+    for scheme in [Affine, Symmetric] {
+        let params = QuantParams::from_range::<i8>(0.3, 1.7, scheme);
+        assert_eq!(flatten_tensor(&quantize::<i8>(&t, &params).dequantize())[1], 0.0);
+    }
+}
+
+#[test]
+fn range_parameters() {
+    let params = QuantParams::from_range::<i8>(-1.0, 1.0, Affine);
+    assert_eq!(params.scales(), &[2.0 / 255.0]);
+    assert_eq!(params.zero_points(), &[0]);
+
+    // A positive range is widened to include 0
+    let params = QuantParams::from_range::<u8>(2.0, 4.0, Affine);
+    assert_eq!(params.scales(), &[4.0 / 255.0]);
+    assert_eq!(params.zero_points(), &[0]);
+
+    let params = QuantParams::from_range::<i8>(-4.0, -2.0, Affine);
+    assert_eq!(params.zero_points(), &[127]);
+
+    let params = QuantParams::from_range::<u8>(-2.0, 1.0, Symmetric);
+    assert_eq!(params.scales(), &[2.0 / 127.0]);
+    assert_eq!(params.zero_points(), &[128]);
+
+    // An empty range keeps a usable scale
+    let params = QuantParams::from_range::<u8>(0.0, 0.0, Affine);
+    assert_eq!(params.scales(), &[1.0]);
+    assert_eq!(params.axis(), None);
+}
+
+#[test]
+fn quantize_rounds_ties_to_even_and_clamps() {
+    let params = QuantParams::per_tensor(1.0, 0);
+    let (nan, infinity) = (f64::NAN, f64::INFINITY);
+    let t = Tensor::Element(vec![0.5, 1.5, 2.5, -0.5, -1.5, 300.0, -300.0, nan, infinity]);
+
+    let q = quantize::<i8>(&t, &params);
+    assert_eq!(q.values(), &[0, 2, 2, 0, -2, 127, -128, 0, 127]);
+
+    let q = quantize::<u8>(&t, &QuantParams::per_tensor(1.0, 3));
+    assert_eq!(q.values(), &[3, 5, 5, 3, 1, 255, 0, 3, 255]);
+}
+
+#[test]
+fn per_channel_along_each_axis() {
+    // Rows with very different magnitudes
+    let t = build_tensor(&[0.01, -0.02, 0.03, 10.0, 20.0, -30.0], &[2, 3]);
+
+    let params = min_max::<i8>(&t, Some(0), Symmetric);
+    assert_eq!(params.axis(), Some(0));
+    assert_eq!(params.scales(), &[0.03 / 127.0, 30.0 / 127.0]);
+
+    let q = quantize::<i8>(&t, &params);
+    assert_eq!(q.values(), &[42, -85, 127, 42, 85, -127]);
+    assert!(max_difference(&t, &q.dequantize()) < 30.0 / 127.0 / 2.0 + 1e-12);
+
+    // One scale per tensor loses the small row entirely
+    let per_tensor = min_max::<i8>(&t, None, Symmetric);
+    assert_eq!(&quantize::<i8>(&t, &per_tensor).values()[..3], &[0, 0, 0]);
+
+    let params = QuantParams::per_channel(vec![1.0, 0.5, 0.25], vec![0, 1, 2], 1);
+    let q = quantize::<u8>(&build_tensor(&[1.0, 1.0, 1.0, 2.0, 2.0, 2.0], &[2, 3]), &params);
+    assert_eq!(q.values(), &[1, 3, 6, 2, 5, 10]);
+    assert_eq!(q.dequantize(), build_tensor(&[1.0, 1.0, 1.0, 2.0, 2.0, 2.0], &[2, 3]));
+}
+
+#[test]
+fn calibrates_across_a_sample() {
+    let sample = [
+        build_tensor(&[1.0, -5.0, 2.0, 0.0], &[2, 2]),
+        build_tensor(&[3.0, 7.0, f64::NAN, -1.0], &[2, 2]),
+    ];
+
+    let mut calibrator = Calibrator::new(CalibrationMethod::MinMax, Some(1));
+    for tensor in &sample {
+        calibrator.observe(tensor);
+    }
+    assert_eq!(calibrator.ranges(), [(1.0, 3.0), (-5.0, 7.0)]);
+
+    let mut calibrator = Calibrator::new(CalibrationMethod::MinMax, None);
+    calibrator.observe(&sample[0]);
+    calibrator.observe(&sample[1]);
+    assert_eq!(calibrator.ranges(), [(-5.0, 7.0)]);
+}
+
+#[test]
+fn percentile_calibration_clips_outliers() {
+    let mut values: Vec<f64> = (0..=1000).map(|x| x as f64 / 1000.0).collect();
+    values[500] = 1e6;
+
+    let t = Tensor::Element(values);
+    let mut calibrator = Calibrator::new(CalibrationMethod::Percentile(99.0), None);
+    calibrator.observe(&t);
+    let (low, high) = calibrator.ranges()[0];
+    // Without 0.5, the values above it move down one place
+    assert!((low - 0.01).abs() < 1e-12, "{low}");
+    assert!((high - 0.991).abs() < 1e-12, "{high}");
+
+    let clipped = calibrator.params::<u8>(Affine);
+    let full = min_max::<u8>(&t, None, Affine);
+    assert!(clipped.scales()[0] * 1000.0 < full.scales()[0]);
+
+    let mut all = Calibrator::new(CalibrationMethod::Percentile(100.0), None);
+    all.observe(&Tensor::Element(vec![3.0, -1.0, 2.0]));
+    assert_eq!(all.ranges(), [(-1.0, 3.0)]);
+}
+
+#[test]
+fn int8_matmul_matches_dequantized_matmul() {
+    let a = random_tensor(&[7, 40], 0.0, 2.0, 2);
+    let b = random_tensor(&[40, 5], -1.0, 1.0, 3);
+
+    let qa = quantize::<u8>(&a, &min_max::<u8>(&a, None, Affine));
+    let qb = quantize::<i8>(&b, &min_max::<i8>(&b, Some(1), Symmetric));
+
+    let product = qa.matmul(&qb);
+    let reference = matmul(&qa.dequantize(), &qb.dequantize());
+    assert!(max_difference(&product, &reference) < 1e-12);
+
+    // And approximates the float product within the rounding of the inputs
+    assert!(max_difference(&product, &matmul(&a, &b)) < 0.1);
+
+    // Affine weights and per-row activations
+    let qa = quantize::<i8>(&a, &min_max::<i8>(&a, Some(0), Affine));
+    let qb = quantize::<u8>(&b, &min_max::<u8>(&b, None, Affine));
+    let reference = matmul(&qa.dequantize(), &qb.dequantize());
+    assert!(max_difference(&qa.matmul(&qb), &reference) < 1e-12);
+}
+
+#[test]
+fn from_values_keeps_the_integers() {
+    let params = QuantParams::per_tensor(0.25, 0);
+    let q = QuantizedTensor::from_values(vec![-3i8, 0, 4, 8], &[2, 2], params);
+    assert_eq!(q.shape(), &[2, 2]);
+    assert_eq!(q.params().scales(), &[0.25]);
+    assert_eq!(q.dequantize(), build_tensor(&[-0.75, 0.0, 1.0, 2.0], &[2, 2]));
+}
+
+#[test]
+#[should_panic(expected = "Zero point 200 is out of range for i8!")]
+fn rejects_zero_point_out_of_range() {
+    quantize::<i8>(&Tensor::Element(vec![1.0]), &QuantParams::per_tensor(1.0, 200));
+}
+
+#[test]
+#[should_panic(expected = "Quantization parameters have 2 channels but axis 1 has length 3!")]
+fn rejects_wrong_channel_count() {
+    let params = QuantParams::per_channel(vec![1.0, 1.0], vec![0, 0], 1);
+    quantize::<u8>(&build_tensor(&[1.0; 6], &[2, 3]), &params);
+}
+
+#[test]
+#[should_panic(expected = "Quantization scale 0 must be positive and finite!")]
+fn rejects_zero_scale() {
+    QuantParams::per_tensor(0.0, 0);
+}
+
+#[test]
+#[should_panic(expected = "Calibration percentile 40 must be between 50 and 100!")]
+fn rejects_low_percentile() {
+    Calibrator::new(CalibrationMethod::Percentile(40.0), None);
+}
+
+#[test]
+#[should_panic(expected = "The calibrator has not observed any Tensors!")]
+fn rejects_empty_calibration() {
+    calibrate::<u8>(&[], CalibrationMethod::MinMax, None, Affine);
+}
+
+#[test]
+#[should_panic(expected = "needs shapes [m, k] and [k, n], got [2, 3] and [2, 3]!")]
+fn matmul_checks_shapes() {
+    let q = quantize::<i8>(&build_tensor(&[1.0; 6], &[2, 3]), &QuantParams::per_tensor(1.0, 0));
+    q.matmul(&q);
+}
+
+#[test]
+#[should_panic(expected = "per-channel parameters along axis 0 of the left matrix and axis 1")]
+fn matmul_checks_channel_axes() {
+    let params = QuantParams::per_channel(vec![1.0; 2], vec![0; 2], 1);
+    let a = quantize::<i8>(&build_tensor(&[1.0; 4], &[2, 2]), &params);
+    a.matmul(&a);
+}
+
+#[test]
+#[should_panic(expected = "An inner dimension of 40000 could overflow the i32 sums")]
+fn matmul_checks_for_overflow() {
+    let params = QuantParams::per_tensor(1.0, 0);
+    let a = quantize::<u8>(&build_tensor(&vec![1.0; 40000], &[1, 40000]), &params);
+    let b = quantize::<u8>(&build_tensor(&vec![1.0; 40000], &[40000, 1]), &params);
+    a.matmul(&b);
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use crate::Tensor;
+use crate::tensor_io::{DType, load_safetensors, load_safetensors_metadata, save_safetensors, save_safetensors_as};
+use crate::tensor_ops::build_tensor;
+use super::{fixture, temp_path};
+
+/// Writes a file with the given raw header and data, loads it, and returns the error message.
+fn load_error(name: &str, header: &str, data: &[u8]) -> String {
+    let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend_from_slice(data);
+
+    let path = temp_path(name);
+    fs::write(&path, bytes).unwrap();
+    let error = load_safetensors(&path).unwrap_err();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    error.to_string()
+}
+
+#[test]
+fn load_mixed_dtypes() {
+    let tensors = load_safetensors(fixture("mixed.safetensors")).unwrap();
+
+    assert_eq!(tensors.len(), 5);
+    assert_eq!(tensors["embedding"], build_tensor(&[0.5, -1.0, 2.0, 0.25], &[2, 2]));
+    assert_eq!(tensors["ids"], Tensor::Element(vec![-7.0, 0.0, 1099511627776.0]));
+    assert_eq!(tensors["mask"], Tensor::Element(vec![1.0, 0.0, 0.0, 1.0]));
+    assert_eq!(tensors["scale"], Tensor::Element(vec![3.5]));
+    assert_eq!(tensors["pixels"], build_tensor(&[0.0, 255.0], &[1, 2]));
+}
+
+#[test]
+fn load_half_dtypes() {
+    let tensors = load_safetensors(fixture("half.safetensors")).unwrap();
+
+    let weight = build_tensor(&[0.0999755859375, -1.0, 1.0132789611816406e-6, 65504.0], &[2, 2]);
+    assert_eq!(tensors["weight"], weight);
+    assert_eq!(tensors["bias"], Tensor::Element(vec![0.333984375, -300.0, 1.0002555517425873e30]));
+}
+
+#[test]
+fn round_trip_half_dtypes() {
+    let mut tensors = HashMap::new();
+    tensors.insert(String::from("weight"), build_tensor(&[0.1, -1.0, 1e-6, 65504.0], &[2, 2]));
+    tensors.insert(String::from("bias"), Tensor::Element(vec![1.0 / 3.0, -300.5, 1e30]));
+
+    for (dtype, name) in [(DType::F16, "\"F16\""), (DType::BF16, "\"BF16\"")] {
+        let path = temp_path("half.safetensors");
+        save_safetensors_as(&tensors, &HashMap::new(), &path, dtype).unwrap();
+
+        let bytes = fs::read(&path).unwrap();
+        let header_len = u64::from_le_bytes(bytes[..8].try_into().unwrap()) as usize;
+        assert_eq!(bytes.len(), 8 + header_len + 7 * 2);
+        assert_eq!(String::from_utf8_lossy(&bytes[8..8 + header_len]).matches(name).count(), 2);
+
+        // Every value is rounded once, so writing what was read gives the same values again
+        let loaded = load_safetensors(&path).unwrap();
+        save_safetensors_as(&loaded, &HashMap::new(), &path, dtype).unwrap();
+        assert_eq!(load_safetensors(&path).unwrap(), loaded);
+        fs::remove_file(&path).unwrap();
+
+        if dtype == DType::F16 {
+            assert_eq!(loaded["weight"], build_tensor(&[0.0999755859375, -1.0, 1.0132789611816406e-6, 65504.0], &[2, 2]));
+            assert_eq!(loaded["bias"], Tensor::Element(vec![0.333251953125, -300.5, f64::INFINITY]));
+        } else {
+            assert_eq!(loaded["bias"], Tensor::Element(vec![0.333984375, -300.0, 1.0002555517425873e30]));
+        }
+    }
+}
+
+#[test]
+fn load_metadata() {
+    let metadata = load_safetensors_metadata(fixture("mixed.safetensors")).unwrap();
+
+    assert_eq!(metadata.len(), 2);
+    assert_eq!(metadata["format"], "pt");
+    assert_eq!(metadata["note"], "quote \" and é");
+}
+
+#[test]
+fn round_trip_with_metadata() {
+    let mut tensors = HashMap::new();
+    tensors.insert(String::from("layer.weight"), build_tensor(&[1.0, -2.0, 3.0, -4.0, 5.0, -6.0], &[3, 2]));
+    tensors.insert(String::from("layer.bias"), Tensor::Element(vec![0.1, 0.2, 0.3]));
+
+    let mut metadata = HashMap::new();
+    metadata.insert(String::from("step"), String::from("100"));
+    metadata.insert(String::from("comment"), String::from("line\nbreak"));
+
+    let path = temp_path("round_trip.safetensors");
+    save_safetensors(&tensors, &metadata, &path).unwrap();
+
+    assert_eq!(load_safetensors(&path).unwrap(), tensors);
+    assert_eq!(load_safetensors_metadata(&path).unwrap(), metadata);
+
+    // The data starts 8-byte aligned
+    let bytes = fs::read(&path).unwrap();
+    assert_eq!(u64::from_le_bytes(bytes[..8].try_into().unwrap()) % 8, 0);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn metadata_passthrough() {
+    let tensors = load_safetensors(fixture("mixed.safetensors")).unwrap();
+    let metadata = load_safetensors_metadata(fixture("mixed.safetensors")).unwrap();
+
+    let path = temp_path("passthrough.safetensors");
+    save_safetensors(&tensors, &metadata, &path).unwrap();
+
+    assert_eq!(load_safetensors(&path).unwrap(), tensors);
+    assert_eq!(load_safetensors_metadata(&path).unwrap(), metadata);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn empty_metadata_is_omitted() {
+    let path = temp_path("no_metadata.safetensors");
+    save_safetensors(&HashMap::new(), &HashMap::new(), &path).unwrap();
+
+    assert!(!String::from_utf8_lossy(&fs::read(&path).unwrap()).contains("__metadata__"));
+    assert!(load_safetensors_metadata(&path).unwrap().is_empty());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn rejects_wrong_byte_count() {
+    let message = load_error(
+        "wrong_count.safetensors",
+        r#"{"a":{"dtype":"F32","shape":[3],"data_offsets":[0,8]}}"#,
+        &[0; 8]
+    );
+    assert!(message.contains("Tensor 'a' has 8 bytes of data but its shape [3] and dtype need 12"), "{message}");
+}
+
+#[test]
+fn rejects_gap_between_tensors() {
+    let message = load_error(
+        "gap.safetensors",
+        r#"{"a":{"dtype":"U8","shape":[2],"data_offsets":[0,2]},"b":{"dtype":"U8","shape":[2],"data_offsets":[4,6]}}"#,
+        &[0; 6]
+    );
+    assert!(message.contains("leaving a gap"), "{message}");
+}
+
+#[test]
+fn rejects_overlapping_tensors() {
+    let message = load_error(
+        "overlap.safetensors",
+        r#"{"a":{"dtype":"U8","shape":[4],"data_offsets":[0,4]},"b":{"dtype":"U8","shape":[2],"data_offsets":[2,4]}}"#,
+        &[0; 4]
+    );
+    assert!(message.contains("overlaps"), "{message}");
+}
+
+#[test]
+fn rejects_trailing_data() {
+    let message = load_error(
+        "trailing.safetensors",
+        r#"{"a":{"dtype":"U8","shape":[2],"data_offsets":[0,2]}}"#,
+        &[0; 5]
+    );
+    assert!(message.contains("cover 2 bytes but the data buffer holds 5"), "{message}");
+}
+
+#[test]
+fn rejects_f8_dtype() {
+    let message = load_error(
+        "f8.safetensors",
+        r#"{"a":{"dtype":"F8_E4M3","shape":[1],"data_offsets":[0,1]}}"#,
+        &[0; 1]
+    );
+    assert!(message.contains("dtype F8_E4M3, which is not supported yet"), "{message}");
+}
+
+#[test]
+fn rejects_unknown_dtype() {
+    let message = load_error(
+        "dtype.safetensors",
+        r#"{"a":{"dtype":"C64","shape":[1],"data_offsets":[0,8]}}"#,
+        &[0; 8]
+    );
+    assert!(message.contains("unknown dtype 'C64'"), "{message}");
+}
+
+#[test]
+fn rejects_malformed_json() {
+    let message = load_error("json.safetensors", r#"{"a": [1, 2"#, &[]);
+    assert!(message.contains("malformed"), "{message}");
+}
+
+#[test]
+fn rejects_header_longer_than_file() {
+    let path = temp_path("short.safetensors");
+    let mut bytes = 1000u64.to_le_bytes().to_vec();
+    bytes.extend_from_slice(b"{}");
+    fs::write(&path, bytes).unwrap();
+
+    let error = load_safetensors(&path).unwrap_err();
+    assert!(error.to_string().contains("header length is 1000 bytes"), "{error}");
+    fs::remove_file(&path).unwrap();
+}
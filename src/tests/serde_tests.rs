@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use serde_test::{Configure, Token, assert_tokens};
+use crate::Tensor;
+use crate::tensor_ops::build_tensor;
+
+fn two_by_two() -> Tensor {
+    build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2])
+}
+
+#[test]
+fn serialize_json() {
+    let json = serde_json::to_string(&two_by_two()).unwrap();
+    assert_eq!(json, r#"{"shape":[2,2],"dtype":"f64","data":[1.0,2.0,3.0,4.0]}"#);
+}
+
+#[test]
+fn json_round_trip() {
+    let values: Vec<f64> = (0..24).map(|x| x as f64 / 3.0).collect();
+    let t = build_tensor(&values, &[2, 3, 4]);
+
+    let json = serde_json::to_string(&t).unwrap();
+    assert_eq!(serde_json::from_str::<Tensor>(&json).unwrap(), t);
+}
+
+#[test]
+fn deserialize_struct_without_dtype() {
+    let t: Tensor = serde_json::from_str(r#"{"data": [1, 2, 3, 4], "shape": [2, 2]}"#).unwrap();
+    assert_eq!(t, two_by_two());
+}
+
+#[test]
+fn deserialize_nested_array() {
+    let t: Tensor = serde_json::from_str("[[1, 2], [3, 4.0]]").unwrap();
+    assert_eq!(t, two_by_two());
+
+    let v: Tensor = serde_json::from_str("[-1.5, 2e3]").unwrap();
+    assert_eq!(v, Tensor::Element(vec![-1.5, 2000.0]));
+}
+
+#[test]
+fn deserialize_nested_array_in_config() {
+    let config: HashMap<String, Tensor> = serde_json::from_str(
+        r#"{"weights": [[1, 2], [3, 4]], "bias": {"shape": [1], "dtype": "f64", "data": [0.5]}}"#
+    ).unwrap();
+
+    assert_eq!(config["weights"], two_by_two());
+    assert_eq!(config["bias"], Tensor::Element(vec![0.5]));
+}
+
+#[test]
+fn ragged_nested_array_is_rejected() {
+    let error = serde_json::from_str::<Tensor>("[[1, 2], [3]]").unwrap_err();
+    assert!(error.to_string().contains("ragged nested array"), "{error}");
+
+    let error = serde_json::from_str::<Tensor>("[[1, 2], 3]").unwrap_err();
+    assert!(error.to_string().contains("ragged nested array"), "{error}");
+
+    let error = serde_json::from_str::<Tensor>("[1, [2]]").unwrap_err();
+    assert!(error.to_string().contains("ragged nested array"), "{error}");
+}
+
+#[test]
+fn mismatched_shape_is_rejected() {
+    let error = serde_json::from_str::<Tensor>(r#"{"shape": [2, 2], "dtype": "f64", "data": [1, 2, 3]}"#).unwrap_err();
+    assert!(error.to_string().contains("does not match the 3 data values"), "{error}");
+}
+
+#[test]
+fn unsupported_dtype_is_rejected() {
+    let error = serde_json::from_str::<Tensor>(r#"{"shape": [1], "dtype": "c64", "data": [1]}"#).unwrap_err();
+    assert!(error.to_string().contains("unsupported dtype"), "{error}");
+}
+
+#[test]
+fn compact_tokens() {
+    // Non-human-readable formats such as bincode only see the struct form
+    assert_tokens(&Tensor::Element(vec![0.5, 1.0]).compact(), &[
+        Token::Struct { name: "Tensor", len: 3 },
+        Token::Str("shape"),
+        Token::Seq { len: Some(1) },
+        Token::U64(2),
+        Token::SeqEnd,
+        Token::Str("dtype"),
+        Token::Str("f64"),
+        Token::Str("data"),
+        Token::Seq { len: Some(2) },
+        Token::F64(0.5),
+        Token::F64(1.0),
+        Token::SeqEnd,
+        Token::StructEnd,
+    ]);
+}
@@ -0,0 +1,145 @@
+use crate::Tensor;
+use crate::tensor_ops::{
+    abs_tensor,
+    add_tensors,
+    build_tensor,
+    divide_tensors,
+    multiply_add_tensors,
+    negate_tensor,
+    relu,
+    sqrt_tensor,
+    square_tensor,
+    tensor_op
+};
+use crate::tensor_ops::simd::{ binary_with, filled, fma_with, supported_levels, unary_with, BinaryOp, SimdLevel, UnaryOp };
+
+/// Values covering signs, zeros, NaN, infinities and subnormals, in a length that leaves a
+/// remainder for every vector width.
+fn inputs(len: usize, seed: u64) -> Vec<f64> {
+    let special = [0.0, -0.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY, f64::MIN_POSITIVE / 4.0, -1.5];
+    let mut state = seed;
+    (0..len).map(|i| {
+        if i % 7 == 3 {
+            return special[(i / 7) % special.len()];
+        }
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((state >> 11) as f64 / (1u64 << 53) as f64 - 0.5) * 200.0
+    }).collect()
+}
+
+fn assert_bits_eq(actual: &[f64], expected: &[f64], context: &str) {
+    assert_eq!(actual.len(), expected.len());
+    for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
+        assert!(
+            a.to_bits() == e.to_bits() || (a.is_nan() && e.is_nan()),
+            "{context}: element {i} is {a:?}, expected {e:?}"
+        );
+    }
+}
+
+#[test]
+fn levels_include_scalar() {
+    let levels = supported_levels();
+    assert_eq!(levels[0], SimdLevel::Scalar);
+    if cfg!(target_arch = "x86_64") {
+        assert!(levels.contains(&SimdLevel::Sse2));
+    }
+}
+
+#[test]
+fn binary_kernels_match_scalar() {
+    for len in [0, 1, 7, 8, 17, 100] {
+        let a = inputs(len, 1);
+        let b = inputs(len, 2);
+        for op in [BinaryOp::Add, BinaryOp::Sub, BinaryOp::Mul, BinaryOp::Div] {
+            let expected = filled(len, |out| binary_with(SimdLevel::Scalar, op, &a, &b, out));
+
+            for level in supported_levels() {
+                let actual = filled(len, |out| binary_with(level, op, &a, &b, out));
+                assert_bits_eq(&actual, &expected, &format!("{op:?} at {level:?}, length {len}"));
+            }
+        }
+    }
+}
+
+#[test]
+fn unary_kernels_match_scalar() {
+    for len in [0, 1, 7, 8, 17, 100] {
+        let a = inputs(len, 3);
+        for op in [UnaryOp::Neg, UnaryOp::Abs, UnaryOp::Sqrt, UnaryOp::Square, UnaryOp::Relu] {
+            let expected = filled(len, |out| unary_with(SimdLevel::Scalar, op, &a, out));
+
+            for level in supported_levels() {
+                let actual = filled(len, |out| unary_with(level, op, &a, out));
+                assert_bits_eq(&actual, &expected, &format!("{op:?} at {level:?}, length {len}"));
+            }
+        }
+    }
+}
+
+#[test]
+fn fma_kernels_round_once() {
+    for len in [0, 1, 7, 8, 17, 100] {
+        let a = inputs(len, 4);
+        let b = inputs(len, 5);
+        let c = inputs(len, 6);
+        let expected: Vec<f64> = (0..len).map(|i| a[i].mul_add(b[i], c[i])).collect();
+
+        for level in supported_levels() {
+            let actual = filled(len, |out| fma_with(level, &a, &b, &c, out));
+            assert_bits_eq(&actual, &expected, &format!("fma at {level:?}, length {len}"));
+        }
+    }
+
+    // 0.1 * 10 - 1 is not zero when rounded once
+    assert_ne!(filled(1, |out| fma_with(SimdLevel::Scalar, &[0.1], &[10.0], &[-1.0], out)), vec![0.0]);
+}
+
+#[test]
+fn tensor_functions_match_tensor_op() {
+    let values = inputs(60, 7);
+    let others = inputs(60, 8);
+    let t1 = build_tensor(&values, &[3, 4, 5]);
+    let t2 = build_tensor(&others, &[3, 4, 5]);
+
+    assert_eq!(format!("{:?}", add_tensors(&t1, &t2)), format!("{:?}", tensor_op(&t1, &t2, |x, y| x + y)));
+    assert_eq!(format!("{:?}", divide_tensors(&t1, &t2)), format!("{:?}", tensor_op(&t1, &t2, |x, y| x / y)));
+}
+
+#[test]
+fn unary_tensor_functions() {
+    let t = build_tensor(&[4.0, -9.0, 0.25, -0.0], &[2, 2]);
+
+    assert_eq!(negate_tensor(&t), build_tensor(&[-4.0, 9.0, -0.25, 0.0], &[2, 2]));
+    assert_eq!(abs_tensor(&t), build_tensor(&[4.0, 9.0, 0.25, 0.0], &[2, 2]));
+    assert_eq!(square_tensor(&t), build_tensor(&[16.0, 81.0, 0.0625, 0.0], &[2, 2]));
+    assert_eq!(relu(&t), build_tensor(&[4.0, 0.0, 0.25, 0.0], &[2, 2]));
+
+    let roots = sqrt_tensor(&Tensor::Element(vec![16.0, -1.0]));
+    match &roots {
+        Tensor::Element(v) => assert!(v[0] == 4.0 && v[1].is_nan()),
+        Tensor::Array(_) => panic!("expected an Element Tensor"),
+    }
+}
+
+#[test]
+fn multiply_add_nested() {
+    let a = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]);
+    let b = build_tensor(&[2.0, 2.0, 2.0, 2.0], &[2, 2]);
+    let c = build_tensor(&[1.0, 1.0, 1.0, 1.0], &[2, 2]);
+    assert_eq!(multiply_add_tensors(&a, &b, &c), build_tensor(&[3.0, 5.0, 7.0, 9.0], &[2, 2]));
+}
+
+#[test]
+#[should_panic(expected = "Dimensionality Mismatch!")]
+fn multiply_add_shape_mismatch() {
+    let a = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]);
+    let b = Tensor::Element(vec![1.0, 2.0, 3.0, 4.0]);
+    multiply_add_tensors(&a, &b, &a);
+}
+
+#[test]
+#[should_panic(expected = "Element Tensors are different lengths!")]
+fn add_length_mismatch() {
+    add_tensors(&Tensor::Element(vec![1.0, 2.0]), &Tensor::Element(vec![1.0, 2.0, 3.0]));
+}
@@ -0,0 +1,261 @@
+use crate::Tensor;
+use crate::random::Rng;
+use crate::tensor_ops::{
+    argpartition,
+    argsort,
+    build_tensor,
+    flatten_tensor,
+    get_dimension,
+    partition,
+    searchsorted,
+    sort,
+    topk,
+    unique,
+    SearchSide,
+    SortOptions
+};
+
+/// Random values with repeats, so that ties are common.
+fn random_values(len: usize, seed: u64) -> Vec<f64> {
+    let mut rng = Rng::new(seed);
+    (0..len).map(|_| rng.uniform(0.0, 8.0).floor()).collect()
+}
+
+/// The lanes of a Tensor of the given shape along an axis.
+fn lanes(values: &[f64], shape: &[usize], axis: usize) -> Vec<Vec<f64>> {
+    let len = shape[axis];
+    let inner: usize = shape[axis + 1..].iter().product();
+    (0..values.len() / len)
+        .map(|lane| {
+            let start = (lane / inner) * len * inner + lane % inner;
+            (0..len).map(|k| values[start + k * inner]).collect()
+        })
+        .collect()
+}
+
+fn same_bits(a: &[f64], b: &[f64]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.to_bits() == y.to_bits())
+}
+
+#[test]
+fn sort_orders_every_lane() {
+    let shape = [3, 4, 5];
+    let values = random_values(60, 1);
+    let t = build_tensor(&values, &shape);
+
+    for axis in 0..3 {
+        for descending in [false, true] {
+            let options = SortOptions { descending, ..Default::default() };
+            let sorted = sort(&t, Some(axis), &options);
+            assert_eq!(get_dimension(&sorted), shape);
+
+            let actual = lanes(&flatten_tensor(&sorted), &shape, axis);
+            for (lane, mut expected) in actual.into_iter().zip(lanes(&values, &shape, axis)) {
+                expected.sort_by(f64::total_cmp);
+                if descending {
+                    expected.reverse();
+                }
+                assert_eq!(lane, expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn stable_argsort_keeps_ties_in_order() {
+    let values = random_values(200, 2);
+    let t = Tensor::Element(values.clone());
+
+    for descending in [false, true] {
+        let options = SortOptions { descending, stable: true };
+        let indices: Vec<usize> = flatten_tensor(&argsort(&t, None, &options)).into_iter()
+            .map(|i| i as usize)
+            .collect();
+        for pair in indices.windows(2) {
+            let (a, b) = (values[pair[0]], values[pair[1]]);
+            assert!(if descending { a >= b } else { a <= b });
+            if a == b {
+                assert!(pair[0] < pair[1]);
+            }
+        }
+    }
+}
+
+#[test]
+fn argsort_indexes_the_sorted_values() {
+    let shape = [4, 6];
+    let values = random_values(24, 3);
+    let t = build_tensor(&values, &shape);
+
+    for axis in 0..2 {
+        let options = SortOptions::default();
+        let sorted = lanes(&flatten_tensor(&sort(&t, Some(axis), &options)), &shape, axis);
+        let indices = lanes(&flatten_tensor(&argsort(&t, Some(axis), &options)), &shape, axis);
+        let original = lanes(&values, &shape, axis);
+        for ((lane, indices), sorted) in original.iter().zip(indices).zip(sorted) {
+            let gathered: Vec<f64> = indices.iter().map(|&i| lane[i as usize]).collect();
+            assert_eq!(gathered, sorted);
+        }
+    }
+}
+
+#[test]
+fn nan_sorts_after_infinity() {
+    let nan = f64::NAN;
+    let t = Tensor::Element(vec![1.0, nan, f64::INFINITY, -1.0, nan, f64::NEG_INFINITY]);
+
+    let ascending = flatten_tensor(&sort(&t, None, &SortOptions::default()));
+    let expected = [f64::NEG_INFINITY, -1.0, 1.0, f64::INFINITY, nan, nan];
+    assert!(same_bits(&ascending, &expected));
+
+    let options = SortOptions { descending: true, stable: true };
+    let descending = flatten_tensor(&argsort(&t, Some(0), &options));
+    assert_eq!(descending, [1.0, 4.0, 2.0, 0.0, 3.0, 5.0]);
+
+    let (values, indices) = topk(&t, 2, 0, true);
+    assert!(flatten_tensor(&values).iter().all(|x| x.is_nan()));
+    assert_eq!(indices, Tensor::Element(vec![1.0, 4.0]));
+    assert_eq!(topk(&t, 2, 0, false).1, Tensor::Element(vec![5.0, 3.0]));
+}
+
+#[test]
+fn topk_matches_a_full_sort() {
+    let shape = [5, 7];
+    let values = random_values(35, 4);
+    let t = build_tensor(&values, &shape);
+
+    for axis in 0..2 {
+        for largest in [true, false] {
+            for k in 1..=shape[axis] {
+                let (top, indices) = topk(&t, k, axis, largest);
+                let mut expected_shape = shape.to_vec();
+                expected_shape[axis] = k;
+                assert_eq!(get_dimension(&top), expected_shape);
+
+                let options = SortOptions { descending: largest, stable: true };
+                let sorted = argsort(&t, Some(axis), &options);
+                let expected = lanes(&flatten_tensor(&sorted), &shape, axis);
+                let actual = lanes(&flatten_tensor(&indices), &expected_shape, axis);
+                for (actual, expected) in actual.iter().zip(&expected) {
+                    assert_eq!(actual[..], expected[..k]);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "Cannot take the top 4 of an axis of length 3!")]
+fn topk_rejects_k_longer_than_the_axis() {
+    topk(&Tensor::Element(vec![1.0, 2.0, 3.0]), 4, 0, true);
+}
+
+#[test]
+fn unique_rebuilds_the_tensor() {
+    let values = random_values(40, 5);
+    let t = build_tensor(&values, &[5, 8]);
+    let result = unique(&t);
+
+    let distinct = flatten_tensor(&result.values);
+    assert!(distinct.windows(2).all(|pair| pair[0] < pair[1]));
+
+    let rebuilt: Vec<f64> = flatten_tensor(&result.inverse).iter()
+        .map(|&i| distinct[i as usize])
+        .collect();
+    assert_eq!(build_tensor(&rebuilt, &[5, 8]), t);
+
+    let counts = flatten_tensor(&result.counts);
+    assert_eq!(counts.iter().sum::<f64>(), 40.0);
+    let firsts = flatten_tensor(&result.indices);
+    for ((value, count), first) in distinct.iter().zip(&counts).zip(firsts) {
+        assert_eq!(values.iter().filter(|&x| x == value).count() as f64, *count);
+        assert_eq!(values.iter().position(|x| x == value), Some(first as usize));
+    }
+}
+
+#[test]
+fn unique_merges_nan_and_signed_zeros() {
+    let nan = f64::NAN;
+    let result = unique(&Tensor::Element(vec![nan, 0.0, -0.0, nan, 1.0]));
+
+    let values = flatten_tensor(&result.values);
+    assert_eq!(values.len(), 3);
+    assert_eq!(values[..2], [0.0, 1.0]);
+    assert!(values[2].is_nan());
+    assert_eq!(result.indices, Tensor::Element(vec![1.0, 4.0, 0.0]));
+    assert_eq!(result.inverse, Tensor::Element(vec![2.0, 0.0, 0.0, 2.0, 1.0]));
+    assert_eq!(result.counts, Tensor::Element(vec![2.0, 1.0, 2.0]));
+
+    let empty = unique(&Tensor::Element(vec![]));
+    assert_eq!(empty.values, Tensor::Element(vec![]));
+}
+
+#[test]
+fn searchsorted_keeps_the_order() {
+    let mut sorted = random_values(30, 6);
+    sorted.sort_by(f64::total_cmp);
+    let queries = build_tensor(&[-1.0, 0.0, 2.5, 3.0, 7.0, 9.0], &[2, 3]);
+
+    for side in [SearchSide::Left, SearchSide::Right] {
+        let positions = searchsorted(&Tensor::Element(sorted.clone()), &queries, side);
+        assert_eq!(get_dimension(&positions), [2, 3]);
+
+        for (x, position) in flatten_tensor(&queries).into_iter().zip(flatten_tensor(&positions)) {
+            let position = position as usize;
+            let (before, after) = sorted.split_at(position);
+            match side {
+                SearchSide::Left => {
+                    assert!(before.iter().all(|&y| y < x) && after.iter().all(|&y| y >= x));
+                },
+                SearchSide::Right => {
+                    assert!(before.iter().all(|&y| y <= x) && after.iter().all(|&y| y > x));
+                },
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "searchsorted needs a 1-D sorted Tensor, got shape [2, 2]!")]
+fn searchsorted_rejects_matrices() {
+    let sorted = build_tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]);
+    searchsorted(&sorted, &Tensor::Element(vec![1.0]), SearchSide::Left);
+}
+
+#[test]
+fn partition_splits_around_kth() {
+    let shape = [6, 9];
+    let mut values = random_values(54, 7);
+    values[10] = f64::NAN;
+    let t = build_tensor(&values, &shape);
+    let options = SortOptions { stable: true, ..Default::default() };
+
+    for axis in 0..2 {
+        for kth in 0..shape[axis] {
+            let sorted = lanes(&flatten_tensor(&sort(&t, Some(axis), &options)), &shape, axis);
+            let parted = lanes(&flatten_tensor(&partition(&t, kth, axis)), &shape, axis);
+            let indices = lanes(&flatten_tensor(&argpartition(&t, kth, axis)), &shape, axis);
+
+            let original = lanes(&values, &shape, axis);
+            let all = original.iter().zip(parted).zip(indices).zip(sorted);
+            for (((lane, parted), indices), sorted) in all {
+                let pivot = sorted[kth];
+                assert!(same_bits(&[parted[kth]], &[pivot]));
+                let gathered: Vec<f64> = indices.iter().map(|&i| lane[i as usize]).collect();
+                assert!(same_bits(&gathered[kth..=kth], &[pivot]));
+
+                // NaN counts as the largest value on either side
+                for side in [&parted, &gathered] {
+                    assert!(side[..kth].iter().all(|&x| x <= pivot || pivot.is_nan()));
+                    assert!(side[kth + 1..].iter().all(|&x| x >= pivot || x.is_nan()));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "Partition index 3 is out of range for an axis of length 3!")]
+fn partition_rejects_kth_past_the_end() {
+    partition(&Tensor::Element(vec![1.0, 2.0, 3.0]), 3, 0);
+}
@@ -0,0 +1,248 @@
+use crate::Tensor;
+use crate::sparse::{ CooMatrix, CscMatrix, CsrMatrix };
+use crate::tensor_ops::{
+    add_tensors,
+    build_tensor,
+    flatten_tensor,
+    map_tensor,
+    matmul,
+    multiply_tensors,
+    subtract_tensors,
+    sum
+};
+
+/// A dense matrix where roughly one entry in `every` is non-zero, with small integer values so
+/// sums are exact.
+fn sparse_values(rows: usize, cols: usize, every: usize, seed: usize) -> Tensor {
+    let values: Vec<f64> = (0..rows * cols)
+        .map(|i| {
+            let hash = (i * 7919 + seed * 104729) % 997;
+            if hash.is_multiple_of(every) { (hash % 9) as f64 - 4.0 } else { 0.0 }
+        })
+        .collect();
+    build_tensor(&values, &[rows, cols])
+}
+
+fn transpose(tensor: &Tensor) -> Tensor {
+    CsrMatrix::from_dense(tensor).transpose().to_dense()
+}
+
+#[test]
+fn dense_round_trips() {
+    let dense = sparse_values(7, 5, 3, 1);
+    let nnz = CsrMatrix::from_dense(&dense).nnz();
+    assert!(nnz > 0 && nnz < 35);
+
+    assert_eq!(CooMatrix::from_dense(&dense).to_dense(), dense);
+    assert_eq!(CsrMatrix::from_dense(&dense).to_dense(), dense);
+    assert_eq!(CscMatrix::from_dense(&dense).to_dense(), dense);
+
+    let zeros = build_tensor(&[0.0; 6], &[2, 3]);
+    let csr = CsrMatrix::from_dense(&zeros);
+    assert_eq!(csr.nnz(), 0);
+    assert_eq!(csr.indptr(), &[0, 0, 0]);
+    assert_eq!(csr.to_dense(), zeros);
+}
+
+#[test]
+fn csr_layout() {
+    let dense = build_tensor(&[0.0, 1.0, 0.0, 2.0, 0.0, 0.0, 3.0, 0.0, 4.0], &[3, 3]);
+
+    let csr = CsrMatrix::from_dense(&dense);
+    assert_eq!(csr.shape(), [3, 3]);
+    assert_eq!(csr.indptr(), &[0, 1, 2, 4]);
+    assert_eq!(csr.indices(), &[1, 0, 0, 2]);
+    assert_eq!(csr.values(), &[1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(csr.get(2, 2), 4.0);
+    assert_eq!(csr.get(1, 1), 0.0);
+
+    let csc = CscMatrix::from_dense(&dense);
+    assert_eq!(csc.indptr(), &[0, 2, 3, 4]);
+    assert_eq!(csc.indices(), &[1, 2, 0, 2]);
+    assert_eq!(csc.values(), &[2.0, 3.0, 1.0, 4.0]);
+    assert_eq!(csc.get(1, 0), 2.0);
+}
+
+#[test]
+fn conversions_agree() {
+    let dense = sparse_values(6, 9, 4, 2);
+    let coo = CooMatrix::from_dense(&dense);
+    let csr = coo.to_csr();
+    let csc = coo.to_csc();
+
+    assert_eq!(csr, CsrMatrix::from_dense(&dense));
+    assert_eq!(csc, CscMatrix::from_dense(&dense));
+    assert_eq!(csr.to_csc(), csc);
+    assert_eq!(csc.to_csr(), csr);
+    assert_eq!(csr.to_coo().to_csr(), csr);
+    assert_eq!(csc.to_coo().to_dense(), dense);
+}
+
+#[test]
+fn coo_sums_repeated_entries() {
+    let coo = CooMatrix::from_triplets(
+        2,
+        2,
+        vec![1, 0, 1, 1],
+        vec![1, 0, 1, 0],
+        vec![1.0, 2.0, 3.0, 4.0]
+    );
+    assert_eq!(coo.nnz(), 4);
+    assert_eq!(coo.to_dense(), build_tensor(&[2.0, 0.0, 4.0, 4.0], &[2, 2]));
+
+    let csr = coo.to_csr();
+    assert_eq!(csr.indptr(), &[0, 1, 3]);
+    assert_eq!(csr.indices(), &[0, 0, 1]);
+    assert_eq!(csr.values(), &[2.0, 4.0, 4.0]);
+    assert_eq!(coo.to_csc().to_dense(), coo.to_dense());
+}
+
+#[test]
+fn transposes() {
+    let dense = sparse_values(4, 7, 3, 3);
+    let expected = build_tensor(
+        &(0..28).map(|i| flatten_tensor(&dense)[(i % 4) * 7 + i / 4]).collect::<Vec<f64>>(),
+        &[7, 4]
+    );
+
+    assert_eq!(CooMatrix::from_dense(&dense).transpose().to_dense(), expected);
+    assert_eq!(CsrMatrix::from_dense(&dense).transpose().to_dense(), expected);
+    assert_eq!(CscMatrix::from_dense(&dense).transpose().to_dense(), expected);
+    assert_eq!(CsrMatrix::from_dense(&dense).transpose().shape(), [7, 4]);
+}
+
+#[test]
+fn sparse_dense_matmul_matches_dense() {
+    let a = sparse_values(8, 6, 3, 4);
+    let b = sparse_values(6, 5, 1, 5);
+    let v = Tensor::Element(vec![1.0, -2.0, 0.5, 3.0, 0.0, 1.5]);
+
+    assert_eq!(CsrMatrix::from_dense(&a).matmul_dense(&b), matmul(&a, &b));
+    assert_eq!(CscMatrix::from_dense(&a).matmul_dense(&b), matmul(&a, &b));
+    assert_eq!(CsrMatrix::from_dense(&a).matmul_dense(&v), matmul(&a, &v));
+    assert_eq!(CscMatrix::from_dense(&a).matmul_dense(&v), matmul(&a, &v));
+}
+
+#[test]
+fn sparse_sparse_matmul_matches_dense() {
+    let a = sparse_values(9, 7, 3, 6);
+    let b = sparse_values(7, 8, 4, 7);
+    let expected = matmul(&a, &b);
+
+    let csr = CsrMatrix::from_dense(&a).matmul(&CsrMatrix::from_dense(&b));
+    assert_eq!(csr.shape(), [9, 8]);
+    assert_eq!(csr.to_dense(), expected);
+    assert!(csr.values().iter().all(|&x| x != 0.0));
+
+    let csc = CscMatrix::from_dense(&a).matmul(&CscMatrix::from_dense(&b));
+    assert_eq!(csc.shape(), [9, 8]);
+    assert_eq!(csc.to_dense(), expected);
+    assert_eq!(csc.to_csr(), csr);
+
+    let gram = CsrMatrix::from_dense(&a).matmul(&CsrMatrix::from_dense(&transpose(&a)));
+    assert_eq!(gram.to_dense(), matmul(&a, &transpose(&a)));
+}
+
+#[test]
+fn element_wise_ops_match_dense() {
+    let a = sparse_values(5, 6, 2, 8);
+    let b = sparse_values(5, 6, 3, 9);
+
+    let csr_a = CsrMatrix::from_dense(&a);
+    let csr_b = CsrMatrix::from_dense(&b);
+    assert_eq!(csr_a.add(&csr_b).to_dense(), add_tensors(&a, &b));
+    assert_eq!(csr_a.subtract(&csr_b).to_dense(), subtract_tensors(&a, &b));
+    assert_eq!(csr_a.multiply(&csr_b).to_dense(), multiply_tensors(&a, &b));
+    assert_eq!(csr_a.scale(2.5).to_dense(), map_tensor(&a, |x| x * 2.5));
+
+    let csc_a = CscMatrix::from_dense(&a);
+    let csc_b = CscMatrix::from_dense(&b);
+    assert_eq!(csc_a.add(&csc_b).to_dense(), add_tensors(&a, &b));
+    assert_eq!(csc_a.subtract(&csc_b).to_dense(), subtract_tensors(&a, &b));
+    assert_eq!(csc_a.multiply(&csc_b).to_dense(), multiply_tensors(&a, &b));
+    assert_eq!(csc_a.map_values(f64::abs).to_dense(), map_tensor(&a, f64::abs));
+}
+
+#[test]
+fn results_drop_zeros() {
+    let a = CsrMatrix::from_dense(&sparse_values(5, 5, 2, 10));
+
+    let difference = a.subtract(&a);
+    assert_eq!(difference.nnz(), 0);
+    assert_eq!(difference.indptr(), &[0; 6]);
+    assert_eq!(a.scale(0.0).nnz(), 0);
+
+    // The product only keeps positions stored in both
+    let b = CsrMatrix::new(5, 5, vec![0, 1, 1, 1, 1, 1], vec![0], vec![1.0]);
+    assert!(a.multiply(&b).nnz() <= 1);
+}
+
+#[test]
+fn sums_match_dense() {
+    let dense = sparse_values(6, 4, 2, 11);
+
+    let csr = CsrMatrix::from_dense(&dense);
+    let csc = CscMatrix::from_dense(&dense);
+    for axis in [None, Some(0), Some(1)] {
+        assert_eq!(csr.sum(axis), sum(&dense, axis));
+        assert_eq!(csc.sum(axis), sum(&dense, axis));
+    }
+}
+
+#[test]
+#[should_panic(expected = "Index [2, 0] is out of bounds for shape [2, 2]!")]
+fn coo_push_out_of_bounds_panics() {
+    CooMatrix::new(2, 2).push(2, 0, 1.0);
+}
+
+#[test]
+#[should_panic(expected = "The indices of each line of a CSR matrix must be strictly increasing!")]
+fn unsorted_csr_panics() {
+    CsrMatrix::new(1, 3, vec![0, 2], vec![2, 0], vec![1.0, 2.0]);
+}
+
+#[test]
+#[should_panic(expected = "Index out of bounds for a CSC matrix!")]
+fn csc_index_out_of_bounds_panics() {
+    CscMatrix::new(2, 1, vec![0, 1], vec![2], vec![1.0]);
+}
+
+#[test]
+#[should_panic(expected = "The index pointers of a CSR matrix must run from 0 to its entry count!")]
+fn bad_indptr_panics() {
+    CsrMatrix::new(2, 2, vec![0, 1], vec![0], vec![1.0]);
+}
+
+#[test]
+#[should_panic(expected = "The index pointers of a CSR matrix must not decrease!")]
+fn decreasing_indptr_panics() {
+    // The first row would reach past the indices before the second row's pointer is checked
+    CsrMatrix::new(2, 3, vec![0, 5, 3], vec![0, 1, 2], vec![1.0, 2.0, 3.0]);
+}
+
+#[test]
+#[should_panic(expected = "Inner dimensions do not match for matmul! (3 vs 2)")]
+fn mismatched_sparse_matmul_panics() {
+    let a = CsrMatrix::from_dense(&sparse_values(2, 3, 1, 1));
+    a.matmul(&a);
+}
+
+#[test]
+#[should_panic(expected = "Inner dimensions do not match for matmul! (3 vs 2)")]
+fn mismatched_csc_matmul_panics() {
+    let a = CscMatrix::from_dense(&sparse_values(2, 3, 1, 1));
+    a.matmul(&a);
+}
+
+#[test]
+#[should_panic(expected = "Sparse matrix shapes [2, 3] and [3, 2] do not match!")]
+fn mismatched_element_wise_panics() {
+    let a = CsrMatrix::from_dense(&sparse_values(2, 3, 1, 1));
+    a.add(&a.transpose());
+}
+
+#[test]
+#[should_panic(expected = "A CSR matrix can only be built from a 2-D Tensor!")]
+fn from_dense_needs_2d() {
+    CsrMatrix::from_dense(&Tensor::Element(vec![1.0, 0.0]));
+}
@@ -0,0 +1,394 @@
+use crate::Tensor;
+use crate::random::Rng;
+use crate::stats::{
+    bincount,
+    corrcoef,
+    cov,
+    cumprod,
+    cumsum,
+    diff,
+    histogram,
+    histogram_bin_edges,
+    median,
+    nanmax,
+    nanmean,
+    nanmin,
+    nansum,
+    percentile,
+    quantile,
+    Bins,
+    HistogramOptions,
+    QuantileMethod
+};
+use crate::tensor_ops::{ build_tensor, flatten_tensor, get_dimension, max, mean, min, sum };
+
+fn random_values(len: usize, seed: u64) -> Vec<f64> {
+    let mut rng = Rng::new(seed);
+    (0..len).map(|_| rng.uniform(-5.0, 5.0)).collect()
+}
+
+fn assert_close(actual: &Tensor, expected: &[f64]) {
+    let actual = flatten_tensor(actual);
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 1e-9, "{actual:?} != {expected:?}");
+    }
+}
+
+/// Transposes a matrix of the given shape.
+fn transpose(values: &[f64], rows: usize, columns: usize) -> Tensor {
+    let transposed: Vec<f64> = (0..rows * columns)
+        .map(|i| values[(i % rows) * columns + i / rows])
+        .collect();
+    build_tensor(&transposed, &[columns, rows])
+}
+
+#[test]
+fn quantile_methods_match_numpy() {
+    let t = Tensor::Element(vec![4.0, 1.0, 3.0, 2.0]);
+    let cases = [
+        (QuantileMethod::Linear, [2.2, 2.5, 3.0]),
+        (QuantileMethod::Lower, [2.0, 2.0, 3.0]),
+        (QuantileMethod::Higher, [3.0, 3.0, 3.0]),
+        (QuantileMethod::Nearest, [2.0, 3.0, 3.0]),
+        (QuantileMethod::Midpoint, [2.5, 2.5, 3.0]),
+    ];
+    for (method, expected) in cases {
+        for (q, expected) in [0.4, 0.5, 2.0 / 3.0].into_iter().zip(expected) {
+            assert_close(&quantile(&t, q, None, method), &[expected]);
+        }
+    }
+
+    // Position 2.5 lies between indices 2 and 3, and the tie goes to the even one
+    let t = Tensor::Element(vec![0.0, 10.0, 20.0, 30.0, 40.0, 50.0]);
+    assert_eq!(quantile(&t, 0.5, None, QuantileMethod::Nearest), Tensor::Element(vec![20.0]));
+    assert_eq!(quantile(&t, 0.0, None, QuantileMethod::Higher), Tensor::Element(vec![0.0]));
+    assert_eq!(quantile(&t, 1.0, None, QuantileMethod::Lower), Tensor::Element(vec![50.0]));
+}
+
+#[test]
+fn quantiles_along_every_axis_match_a_full_sort() {
+    let shape = [3, 4, 5];
+    let values = random_values(60, 3);
+    let t = build_tensor(&values, &shape);
+
+    for axis in 0..3 {
+        for q in [0.0, 0.1, 0.37, 0.5, 0.9, 1.0] {
+            let actual = flatten_tensor(&quantile(&t, q, Some(axis), QuantileMethod::Linear));
+
+            let len = shape[axis];
+            let inner: usize = shape[axis + 1..].iter().product();
+            let expected: Vec<f64> = (0..60 / len)
+                .map(|lane| {
+                    let start = (lane / inner) * len * inner + lane % inner;
+                    let mut sorted: Vec<f64> = (0..len)
+                        .map(|k| values[start + k * inner])
+                        .collect();
+                    sorted.sort_by(f64::total_cmp);
+                    let position = q * (len - 1) as f64;
+                    let below = position.floor() as usize;
+                    let above = position.ceil() as usize;
+                    sorted[below] + (sorted[above] - sorted[below]) * (position - below as f64)
+                })
+                .collect();
+            assert_close(&Tensor::Element(actual), &expected);
+        }
+    }
+}
+
+#[test]
+fn median_and_percentile_agree_with_quantile() {
+    let t = build_tensor(&random_values(24, 5), &[4, 6]);
+    for axis in [None, Some(0), Some(1)] {
+        let halfway = quantile(&t, 0.5, axis, QuantileMethod::Linear);
+        assert_eq!(median(&t, axis), halfway);
+        assert_eq!(percentile(&t, 50.0, axis, QuantileMethod::Linear), halfway);
+    }
+    assert_eq!(get_dimension(&median(&t, Some(1))), [4]);
+}
+
+#[test]
+fn quantiles_propagate_nan() {
+    let t = build_tensor(&[1.0, f64::NAN, 3.0, 4.0, 5.0, 6.0], &[2, 3]);
+    let medians = flatten_tensor(&median(&t, Some(1)));
+    assert!(medians[0].is_nan());
+    assert_eq!(medians[1], 5.0);
+    assert!(flatten_tensor(&quantile(&t, 0.0, None, QuantileMethod::Lower))[0].is_nan());
+}
+
+#[test]
+#[should_panic(expected = "Quantile 1.5 must be between 0 and 1!")]
+fn quantile_rejects_out_of_range_q() {
+    quantile(&Tensor::Element(vec![1.0]), 1.5, None, QuantileMethod::Linear);
+}
+
+#[test]
+#[should_panic(expected = "Percentile -1 must be between 0 and 100!")]
+fn percentile_rejects_out_of_range_p() {
+    percentile(&Tensor::Element(vec![1.0]), -1.0, None, QuantileMethod::Linear);
+}
+
+#[test]
+#[should_panic(expected = "Cannot take the median of an empty Tensor!")]
+fn median_of_nothing_panics() {
+    median(&Tensor::Element(vec![]), None);
+}
+
+#[test]
+fn histogram_counts_every_value_once() {
+    let values = random_values(1000, 7);
+    let t = build_tensor(&values, &[10, 100]);
+    let (counts, edges) = histogram(&t, None, &HistogramOptions::default());
+
+    let edges = flatten_tensor(&edges);
+    assert_eq!(edges.len(), 11);
+    assert_eq!(edges[0], values.iter().copied().fold(f64::INFINITY, f64::min));
+    assert_eq!(edges[10], values.iter().copied().fold(f64::NEG_INFINITY, f64::max));
+    assert_eq!(flatten_tensor(&counts).iter().sum::<f64>(), 1000.0);
+
+    // Each bin holds the values between its edges, and the last also its upper edge
+    for (bin, count) in flatten_tensor(&counts).into_iter().enumerate() {
+        let inside = values.iter()
+            .filter(|&&x| x >= edges[bin] && (x < edges[bin + 1] || (bin == 9 && x == edges[10])))
+            .count();
+        assert_eq!(count, inside as f64);
+    }
+}
+
+#[test]
+fn histogram_skips_nan_and_values_outside_the_range() {
+    let t = Tensor::Element(vec![-1.0, 0.0, 0.5, f64::NAN, 1.0, 2.0, 2.0, 3.0]);
+    let options = HistogramOptions {
+        bins: Bins::Count(4),
+        range: Some((0.0, 2.0)),
+        ..Default::default()
+    };
+    let (counts, edges) = histogram(&t, None, &options);
+    assert_eq!(counts, Tensor::Element(vec![1.0, 1.0, 1.0, 2.0]));
+    assert_eq!(edges, Tensor::Element(vec![0.0, 0.5, 1.0, 1.5, 2.0]));
+}
+
+#[test]
+fn histogram_widens_a_single_value() {
+    let t = Tensor::Element(vec![3.0, 3.0]);
+    let options = HistogramOptions { bins: Bins::Count(2), ..Default::default() };
+    let (counts, edges) = histogram(&t, None, &options);
+    assert_eq!(counts, Tensor::Element(vec![0.0, 2.0]));
+    assert_eq!(edges, Tensor::Element(vec![2.5, 3.0, 3.5]));
+}
+
+#[test]
+fn histogram_density_integrates_to_one() {
+    let t = Tensor::Element(random_values(500, 11));
+    let options = HistogramOptions {
+        bins: Bins::Edges(vec![-5.0, -1.0, 0.0, 0.5, 5.0]),
+        density: true,
+        ..Default::default()
+    };
+    let (density, edges) = histogram(&t, None, &options);
+    let edges = flatten_tensor(&edges);
+    let area: f64 = flatten_tensor(&density).iter()
+        .zip(edges.windows(2))
+        .map(|(d, edge)| d * (edge[1] - edge[0]))
+        .sum();
+    assert!((area - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn estimated_bins_match_numpy() {
+    let t = Tensor::Element((0..100).map(f64::from).collect());
+    let bin_count = |bins| {
+        let options = HistogramOptions { bins, ..Default::default() };
+        flatten_tensor(&histogram_bin_edges(&t, &options)).len() - 1
+    };
+    // Widths 99 / (log2(100) + 1) = 12.95 and 2 * 49.5 / cbrt(100) = 21.33
+    assert_eq!(bin_count(Bins::Sturges), 8);
+    assert_eq!(bin_count(Bins::FreedmanDiaconis), 5);
+    assert_eq!(bin_count(Bins::Auto), 8);
+
+    // No spread between the quartiles falls back to Sturges
+    let t = Tensor::Element(vec![0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 2.0]);
+    let options = HistogramOptions { bins: Bins::Auto, ..Default::default() };
+    assert_eq!(flatten_tensor(&histogram_bin_edges(&t, &options)).len() - 1, 4);
+}
+
+#[test]
+#[should_panic(expected = "Histogram bin edges must be increasing!")]
+fn histogram_rejects_decreasing_edges() {
+    let options = HistogramOptions { bins: Bins::Edges(vec![0.0, 2.0, 1.0]), ..Default::default() };
+    histogram(&Tensor::Element(vec![1.0]), None, &options);
+}
+
+#[test]
+#[should_panic(expected = "Histogram range (1, 0) must be finite with min <= max!")]
+fn histogram_rejects_reversed_range() {
+    let options = HistogramOptions { range: Some((1.0, 0.0)), ..Default::default() };
+    histogram(&Tensor::Element(vec![1.0]), None, &options);
+}
+
+#[test]
+#[should_panic(expected = "Weights of shape [3] do not match a Tensor of shape [2]!")]
+fn histogram_rejects_mismatched_weights() {
+    let weights = Tensor::Element(vec![1.0, 1.0, 1.0]);
+    histogram(&Tensor::Element(vec![1.0, 2.0]), Some(&weights), &HistogramOptions::default());
+}
+
+#[test]
+fn bincount_counts_labels_of_any_shape() {
+    let labels = build_tensor(&[2.0, 0.0, 2.0, 5.0], &[2, 2]);
+    assert_eq!(bincount(&labels, None, 3), Tensor::Element(vec![1.0, 0.0, 2.0, 0.0, 0.0, 1.0]));
+    assert_eq!(bincount(&Tensor::Element(vec![]), None, 2), Tensor::Element(vec![0.0, 0.0]));
+}
+
+#[test]
+#[should_panic(expected = "bincount needs non-negative integers, got 1.5!")]
+fn bincount_rejects_fractions() {
+    bincount(&Tensor::Element(vec![1.0, 1.5]), None, 0);
+}
+
+#[test]
+#[should_panic(expected = "bincount needs non-negative integers, got -1!")]
+fn bincount_rejects_negative_values() {
+    bincount(&Tensor::Element(vec![-1.0]), None, 0);
+}
+
+#[test]
+fn cov_matches_the_definition() {
+    let (rows, columns) = (3, 40);
+    let values = random_values(rows * columns, 13);
+    let t = build_tensor(&values, &[rows, columns]);
+
+    let means: Vec<f64> = values.chunks(columns)
+        .map(|row| row.iter().sum::<f64>() / columns as f64)
+        .collect();
+    let expected: Vec<f64> = (0..rows * rows)
+        .map(|index| {
+            let (x, y) = (index / rows, index % rows);
+            let deviation = |row: usize, k: usize| values[row * columns + k] - means[row];
+            (0..columns).map(|k| deviation(x, k) * deviation(y, k)).sum::<f64>()
+                / (columns - 1) as f64
+        })
+        .collect();
+
+    assert_close(&cov(&t, 1, 1), &expected);
+    // Observations down the columns of the transpose give the same matrix
+    assert_close(&cov(&transpose(&values, rows, columns), 0, 1), &expected);
+
+    let biased: Vec<f64> = expected.iter()
+        .map(|c| c * (columns - 1) as f64 / columns as f64)
+        .collect();
+    assert_close(&cov(&t, 1, 0), &biased);
+}
+
+#[test]
+fn cov_of_a_vector_is_its_variance() {
+    let t = Tensor::Element(vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+    assert_eq!(cov(&t, 0, 0), Tensor::Element(vec![4.0]));
+    assert_close(&cov(&t, 0, 1), &[32.0 / 7.0]);
+}
+
+#[test]
+fn corrcoef_is_bounded_with_a_unit_diagonal() {
+    let t = build_tensor(&random_values(4 * 30, 17), &[4, 30]);
+    let r = flatten_tensor(&corrcoef(&t, 1));
+    for i in 0..4 {
+        assert!((r[i * 4 + i] - 1.0).abs() < 1e-12);
+        for j in 0..4 {
+            assert!((-1.0..=1.0).contains(&r[i * 4 + j]));
+            assert_eq!(r[i * 4 + j], r[j * 4 + i]);
+        }
+    }
+
+    // A variable that never changes has no correlation to speak of
+    let t = build_tensor(&[1.0, 2.0, 3.0, 5.0, 5.0, 5.0], &[2, 3]);
+    let r = flatten_tensor(&corrcoef(&t, 1));
+    assert_eq!(r[0], 1.0);
+    assert!(r[1].is_nan() && r[3].is_nan());
+}
+
+#[test]
+#[should_panic(expected = "Covariance needs a 1-D or 2-D Tensor, got shape [2, 2, 2]!")]
+fn cov_rejects_three_dimensions() {
+    cov(&build_tensor(&[0.0; 8], &[2, 2, 2]), 0, 1);
+}
+
+#[test]
+fn cumulative_ends_match_reductions() {
+    let shape = [2, 3, 4];
+    let t = build_tensor(&random_values(24, 19), &shape);
+    for axis in 0..3 {
+        let running = cumsum(&t, Some(axis));
+        assert_eq!(get_dimension(&running), shape);
+        assert_eq!(diff(&running, 0, axis), running);
+
+        // The final element along the axis is the sum of the lane
+        let values = flatten_tensor(&running);
+        let inner: usize = shape[axis + 1..].iter().product();
+        let totals = flatten_tensor(&sum(&t, Some(axis)));
+        for (lane, total) in totals.iter().enumerate() {
+            let start = (lane / inner) * shape[axis] * inner + lane % inner;
+            assert!((values[start + (shape[axis] - 1) * inner] - total).abs() < 1e-12);
+        }
+    }
+
+    let flat = cumsum(&t, None);
+    assert_eq!(get_dimension(&flat), [24]);
+    assert!((flatten_tensor(&flat)[23] - flatten_tensor(&sum(&t, None))[0]).abs() < 1e-12);
+
+    let products = cumprod(&Tensor::Element(vec![2.0, -1.0, 0.5, 4.0]), Some(0));
+    assert_eq!(products, Tensor::Element(vec![2.0, -2.0, -1.0, -4.0]));
+}
+
+#[test]
+fn diff_undoes_cumsum() {
+    let values = vec![3.0, 1.0, 4.0, 1.0, 5.0, 9.0];
+    let t = build_tensor(&values, &[2, 3]);
+    let differences = diff(&cumsum(&t, Some(1)), 1, 1);
+    assert_eq!(differences, build_tensor(&[1.0, 4.0, 5.0, 9.0], &[2, 2]));
+
+    // The second difference of a quadratic is constant
+    let squares = Tensor::Element((0..6).map(|x| f64::from(x * x)).collect());
+    assert_eq!(diff(&squares, 2, 0), Tensor::Element(vec![2.0; 4]));
+}
+
+#[test]
+#[should_panic(expected = "Cannot take 3 differences along an axis of length 3!")]
+fn diff_rejects_too_many_differences() {
+    diff(&Tensor::Element(vec![1.0, 2.0, 3.0]), 3, 0);
+}
+
+#[test]
+fn nan_reductions_match_reductions_without_nan() {
+    let t = build_tensor(&random_values(24, 23), &[4, 6]);
+    for axis in [None, Some(0), Some(1)] {
+        assert_eq!(nansum(&t, axis), sum(&t, axis));
+        assert_eq!(nanmax(&t, axis), max(&t, axis));
+        assert_eq!(nanmin(&t, axis), min(&t, axis));
+        assert_close(&nanmean(&t, axis), &flatten_tensor(&mean(&t, axis)));
+    }
+}
+
+#[test]
+fn nan_reductions_skip_nan() {
+    let nan = f64::NAN;
+    let t = build_tensor(&[nan, 2.0, nan, nan, 4.0, nan], &[3, 2]);
+
+    assert_eq!(nansum(&t, Some(0)), Tensor::Element(vec![4.0, 2.0]));
+    assert_eq!(nansum(&t, Some(1)), Tensor::Element(vec![2.0, 0.0, 4.0]));
+
+    let means = flatten_tensor(&nanmean(&t, Some(1)));
+    assert_eq!(means[0], 2.0);
+    assert!(means[1].is_nan());
+    assert_eq!(means[2], 4.0);
+
+    let maxima = flatten_tensor(&nanmax(&t, Some(1)));
+    assert!(maxima[1].is_nan());
+    assert_eq!((maxima[0], maxima[2]), (2.0, 4.0));
+    assert_eq!(nanmin(&t, None), Tensor::Element(vec![2.0]));
+}
+
+#[test]
+#[should_panic(expected = "Cannot take the nanmax of an empty Tensor!")]
+fn nanmax_of_nothing_panics() {
+    nanmax(&Tensor::Element(vec![]), None);
+}
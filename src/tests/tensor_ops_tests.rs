@@ -164,4 +164,64 @@ fn expand_test() {
         tensor_ops::expand_tensor(&tensor, copy_num),
         truth_tensor
     );
-}
\ No newline at end of file
+}
+
+#[test]
+fn flatten_build_round_trip() {
+    let tensor: Tensor = Tensor::Array(Vec::from([
+        Tensor::Array(Vec::from([
+            Tensor::Element(Vec::from([1.0, 2.0])),
+            Tensor::Element(Vec::from([3.0, 4.0]))
+        ])),
+        Tensor::Array(Vec::from([
+            Tensor::Element(Vec::from([5.0, 6.0])),
+            Tensor::Element(Vec::from([7.0, 8.0]))
+        ]))
+    ]));
+
+    let values = tensor_ops::flatten_tensor(&tensor);
+    assert_eq!(values, Vec::from([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]));
+
+    let shape = tensor_ops::get_dimension(&tensor);
+    assert_eq!(tensor_ops::build_tensor(&values, &shape), tensor);
+}
+
+#[test]
+fn test_reductions_over_axes() {
+    let tensor = tensor_ops::build_tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0], &[2, 2, 2]);
+
+    assert_eq!(
+        tensor_ops::sum(&tensor, Some(0)),
+        tensor_ops::build_tensor(&[6.0, 8.0, 10.0, 12.0], &[2, 2])
+    );
+    assert_eq!(
+        tensor_ops::mean(&tensor, Some(2)),
+        tensor_ops::build_tensor(&[1.5, 3.5, 5.5, 7.5], &[2, 2])
+    );
+    assert_eq!(
+        tensor_ops::max(&tensor, Some(1)),
+        tensor_ops::build_tensor(&[3.0, 4.0, 7.0, 8.0], &[2, 2])
+    );
+    assert_eq!(tensor_ops::min(&tensor, None), Tensor::Element(vec![1.0]));
+    assert_eq!(tensor_ops::sum(&Tensor::Element(vec![1.0, 2.0]), Some(0)), Tensor::Element(vec![3.0]));
+}
+
+#[test]
+fn test_reductions_propagate_nan() {
+    let tensor = Tensor::Element(vec![1.0, f64::NAN, 3.0]);
+    assert!(tensor_ops::flatten_tensor(&tensor_ops::max(&tensor, None))[0].is_nan());
+    assert!(tensor_ops::flatten_tensor(&tensor_ops::min(&tensor, Some(0)))[0].is_nan());
+    assert!(tensor_ops::flatten_tensor(&tensor_ops::mean(&Tensor::Element(vec![]), None))[0].is_nan());
+}
+
+#[test]
+#[should_panic(expected = "Cannot take the max of an empty Tensor!")]
+fn test_max_of_empty() {
+    tensor_ops::max(&Tensor::Element(vec![]), None);
+}
+
+#[test]
+#[should_panic(expected = "Axis 2 is out of range")]
+fn test_sum_axis_out_of_range() {
+    tensor_ops::sum(&Tensor::Element(vec![1.0, 2.0]), Some(2));
+}